@@ -0,0 +1,116 @@
+//! Statistical micro-benchmarks of the core kernels on fixed synthetic graphs
+//! (`utils::benchmark::SyntheticGraph`), for catching kernel-level regressions that the
+//! whole-algorithm `benchmark` command averages away.
+//!
+//! cargo bench --bench kernels                          # everything
+//! cargo bench --bench kernels -- union_find            # one group
+//! cargo bench --bench kernels -- --save-baseline main  # then --baseline main on a branch
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use fast_transit_network::algorithms::atomic_union_find::AtomicUnionFind;
+use fast_transit_network::algorithms::bfs::{bfs_parallel_with_policy, bfs_sequential};
+use fast_transit_network::algorithms::chunking::hybrid_frontier_chunks;
+use fast_transit_network::algorithms::pagerank::{pagerank_parallel_with_policy, pagerank_sequential, PageRankConfig};
+use fast_transit_network::algorithms::union_find::UnionFind;
+use fast_transit_network::config::ParallelismPolicy;
+use fast_transit_network::graph::graph::Graph;
+use fast_transit_network::utils::benchmark::SyntheticGraph;
+
+const GRAPHS: [SyntheticGraph; 3] = [
+    SyntheticGraph::Grid { side: 300 },
+    SyntheticGraph::Uniform { nodes: 100_000, degree: 8 },
+    SyntheticGraph::PowerLaw { nodes: 100_000, degree: 8 },
+];
+
+const THREADS: usize = 4;
+
+fn graphs() -> Vec<(String, Graph)> {
+    GRAPHS.iter().map(|g| (g.name(), g.build())).collect()
+}
+
+fn edges(graph: &Graph) -> impl Iterator<Item = (usize, usize)> + '_ {
+    (0..graph.num_nodes).flat_map(move |u| graph.neighbors(u).iter().map(move |&v| (u, v)))
+}
+
+/// Union of every edge, then a find per node: the WCC inner loop.
+fn union_find(c: &mut Criterion) {
+    let mut group = c.benchmark_group("union_find");
+    for (name, graph) in graphs() {
+        group.throughput(Throughput::Elements(graph.num_edges as u64));
+        group.bench_with_input(BenchmarkId::new("sequential", &name), &graph, |b, graph| {
+            b.iter(|| {
+                let mut uf = UnionFind::new(graph.num_nodes);
+                for (u, v) in edges(graph) {
+                    uf.union(u, v);
+                }
+                (0..graph.num_nodes).map(|v| uf.find(v)).sum::<usize>()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("atomic", &name), &graph, |b, graph| {
+            b.iter(|| {
+                let uf = AtomicUnionFind::new(graph.num_nodes);
+                for (u, v) in edges(graph) {
+                    uf.union(u, v);
+                }
+                (0..graph.num_nodes).map(|v| uf.find(v)).sum::<usize>()
+            })
+        });
+    }
+    group.finish();
+}
+
+/// A pass over every adjacency list, the memory-bound floor of every traversal.
+fn csr_neighbors(c: &mut Criterion) {
+    let mut group = c.benchmark_group("csr_neighbors");
+    for (name, graph) in graphs() {
+        group.throughput(Throughput::Elements(graph.num_edges as u64));
+        group.bench_with_input(BenchmarkId::new("sum", &name), &graph, |b, graph| {
+            b.iter(|| (0..graph.num_nodes).map(|u| graph.neighbors(u).iter().sum::<usize>()).sum::<usize>())
+        });
+    }
+    group.finish();
+}
+
+/// Splitting the widest BFS level into work chunks, and whole level-synchronous BFS runs where
+/// frontier expansion is the per-level cost.
+fn frontier_expansion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("frontier_expansion");
+    for (name, graph) in graphs() {
+        let dist = bfs_sequential(&graph, 0).expect("node 0 exists").distances;
+        let widest = (0..=dist.iter().copied().max().unwrap_or(0))
+            .max_by_key(|&level| dist.iter().filter(|&&d| d == level).count())
+            .unwrap_or(0);
+        let frontier: Vec<usize> = (0..graph.num_nodes).filter(|&v| dist[v] == widest).collect();
+        group.throughput(Throughput::Elements(frontier.len() as u64));
+        group.bench_with_input(BenchmarkId::new("chunks", &name), &frontier, |b, frontier| {
+            b.iter(|| hybrid_frontier_chunks(&graph, frontier, THREADS * 8).len())
+        });
+        group.throughput(Throughput::Elements(graph.num_edges as u64));
+        group.bench_with_input(BenchmarkId::new("bfs_seq", &name), &graph, |b, graph| {
+            b.iter(|| bfs_sequential(graph, black_box(0)).map(|r| r.distances.len()))
+        });
+        group.bench_with_input(BenchmarkId::new("bfs_par", &name), &graph, |b, graph| {
+            b.iter(|| bfs_parallel_with_policy(graph, black_box(0), THREADS, ParallelismPolicy::ForceParallel).map(|r| r.distances.len()))
+        });
+    }
+    group.finish();
+}
+
+/// One power iteration (`max_iterations = 1`), including the rank vector setup.
+fn pagerank_step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pagerank_step");
+    let config = PageRankConfig { max_iterations: 1, tolerance: 0.0, ..PageRankConfig::default() };
+    for (name, graph) in graphs() {
+        group.throughput(Throughput::Elements(graph.num_edges as u64));
+        group.bench_with_input(BenchmarkId::new("sequential", &name), &graph, |b, graph| {
+            b.iter(|| pagerank_sequential(graph, &config).final_delta)
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", &name), &graph, |b, graph| {
+            b.iter(|| pagerank_parallel_with_policy(graph, &config, THREADS, ParallelismPolicy::ForceParallel).final_delta)
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, union_find, csr_neighbors, frontier_expansion, pagerank_step);
+criterion_main!(benches);
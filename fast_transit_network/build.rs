@@ -0,0 +1,36 @@
+fn main() {
+    // Recorded in benchmark reports (`utils::benchmark::EnvironmentInfo`)
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    println!("cargo:rustc-env=FTN_RUSTC_VERSION={}", command_output(&rustc, &["--version"]).unwrap_or_default());
+    println!("cargo:rustc-env=FTN_GIT_COMMIT={}", command_output("git", &["rev-parse", "--short=12", "HEAD"]).unwrap_or_default());
+    if let Some(git_dir) = command_output("git", &["rev-parse", "--git-dir"]) {
+        // Rebuild on a new commit or checkout as well as on source changes
+        println!("cargo:rerun-if-changed=src");
+        println!("cargo:rerun-if-changed=build.rs");
+        println!("cargo:rerun-if-changed={}/HEAD", git_dir);
+        if let Some(branch) = command_output("git", &["symbolic-ref", "-q", "HEAD"]) {
+            println!("cargo:rerun-if-changed={}/{}", git_dir, branch);
+        }
+    }
+
+    #[cfg(feature = "ffi")]
+    {
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+            .expect("cbindgen.toml");
+        cbindgen::Builder::new()
+            .with_config(config)
+            .with_src(format!("{}/src/ffi.rs", crate_dir))
+            .generate()
+            .expect("Unable to generate C header")
+            .write_to_file(format!("{}/include/fast_transit_network.h", crate_dir));
+    }
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program).args(args).output().ok()?;
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !text.is_empty()).then_some(text)
+}
@@ -0,0 +1,144 @@
+#ifndef FAST_TRANSIT_NETWORK_H
+#define FAST_TRANSIT_NETWORK_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit. Rebuild with --features ffi. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Opaque graph handle.
+ */
+typedef struct FtnGraph FtnGraph;
+
+/**
+ * Hop distances from a BFS; `distances[v]` is -1 if `v` is unreachable.
+ */
+typedef struct FtnBfsResult {
+  int32_t *distances;
+  size_t len;
+  size_t reachable;
+  size_t levels;
+} FtnBfsResult;
+
+/**
+ * Component label per node.
+ */
+typedef struct FtnWccResult {
+  size_t *labels;
+  size_t len;
+  size_t num_components;
+} FtnWccResult;
+
+/**
+ * PageRank score per node.
+ */
+typedef struct FtnPageRankResult {
+  double *ranks;
+  size_t len;
+  size_t iterations;
+  bool converged;
+} FtnPageRankResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Message of the last failed call on this thread, or null. Valid until the next failing call.
+ */
+const char *ftn_last_error(void);
+
+/**
+ * Loads an edge-list file. Returns null on failure.
+ *
+ * # Safety
+ * `path` must be a valid NUL-terminated string.
+ */
+struct FtnGraph *ftn_load_graph(const char *path);
+
+/**
+ * Builds a graph from `num_edges` edges `src[i] -> dst[i]`. Returns null on failure.
+ *
+ * # Safety
+ * `src` and `dst` must each point to `num_edges` readable values.
+ */
+struct FtnGraph *ftn_graph_from_edges(size_t num_nodes,
+                                      const size_t *src,
+                                      const size_t *dst,
+                                      size_t num_edges);
+
+/**
+ * # Safety
+ * `graph` must be a live handle or null.
+ */
+size_t ftn_graph_num_nodes(const struct FtnGraph *graph);
+
+/**
+ * # Safety
+ * `graph` must be a live handle or null.
+ */
+size_t ftn_graph_num_edges(const struct FtnGraph *graph);
+
+/**
+ * # Safety
+ * `graph` must be a handle from this library (or null) and not used afterwards.
+ */
+void ftn_free_graph(struct FtnGraph *graph);
+
+/**
+ * BFS from `source`; `threads == 0` runs the sequential version.
+ * On failure `distances` is null.
+ *
+ * # Safety
+ * `graph` must be a live handle.
+ */
+struct FtnBfsResult ftn_bfs(const struct FtnGraph *graph, size_t source, size_t threads);
+
+/**
+ * # Safety
+ * `result` must come from [`ftn_bfs`] and not have been freed.
+ */
+void ftn_free_bfs_result(struct FtnBfsResult result);
+
+/**
+ * Weakly connected components; `threads == 0` runs the sequential version.
+ *
+ * # Safety
+ * `graph` must be a live handle.
+ */
+struct FtnWccResult ftn_wcc(const struct FtnGraph *graph, size_t threads);
+
+/**
+ * # Safety
+ * `result` must come from [`ftn_wcc`] and not have been freed.
+ */
+void ftn_free_wcc_result(struct FtnWccResult result);
+
+/**
+ * PageRank with the given damping factor, iteration cap and L1 tolerance;
+ * `threads == 0` runs the sequential version.
+ *
+ * # Safety
+ * `graph` must be a live handle.
+ */
+struct FtnPageRankResult ftn_pagerank(const struct FtnGraph *graph,
+                                      double alpha,
+                                      size_t max_iterations,
+                                      double tolerance,
+                                      size_t threads);
+
+/**
+ * # Safety
+ * `result` must come from [`ftn_pagerank`] and not have been freed.
+ */
+void ftn_free_pagerank_result(struct FtnPageRankResult result);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* FAST_TRANSIT_NETWORK_H */
@@ -265,7 +265,8 @@ def main():
     print(f"\nReading data from: {csv_file}")
     
     # Load data
-    df = pd.read_csv(csv_file)
+    # Lines starting with '#' record the machine and build (see EnvironmentInfo)
+    df = pd.read_csv(csv_file, comment='#')
     
     print(f"Loaded {len(df)} benchmark results")
     print(f"\nAlgorithms: {', '.join(df['algorithm'].unique())}")
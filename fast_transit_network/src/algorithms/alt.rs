@@ -0,0 +1,247 @@
+use crate::algorithms::sssp::{dijkstra_unchecked, find_negative_edge};
+use crate::error::{Error, Result};
+use crate::graph::graph::Graph;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+const LANDMARKS_MAGIC: &[u8; 8] = b"FTNALT01";
+
+/// Landmarks with exact distances to and from every node, for ALT (A*, landmarks, triangle inequality).
+///
+/// Distances are kept as `f64` (unlike [`crate::algorithms::apsp::DistanceMatrix`]) because rounding
+/// could push a lower bound above the true distance and make A* return a longer path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Landmarks {
+    pub landmarks: Vec<usize>,
+    num_nodes: usize,
+    /// `from[i * n + v]` = distance from landmark `i` to `v`.
+    from: Vec<f64>,
+    /// `to[i * n + v]` = distance from `v` to landmark `i`.
+    to: Vec<f64>,
+}
+
+impl Landmarks {
+    pub fn num_nodes(&self) -> usize {
+        self.num_nodes
+    }
+
+    /// Lower bound on the distance from `u` to `v` by the triangle inequality over every landmark:
+    /// `d(u, v) >= d(L, v) - d(L, u)` and `d(u, v) >= d(u, L) - d(v, L)`. Terms with an unreachable
+    /// side are skipped, so the bound is 0 when no landmark is informative.
+    pub fn lower_bound(&self, u: usize, v: usize) -> f64 {
+        let n = self.num_nodes;
+        let mut bound: f64 = 0.0;
+        for i in 0..self.landmarks.len() {
+            let (from_u, from_v) = (self.from[i * n + u], self.from[i * n + v]);
+            if from_u.is_finite() && from_v.is_finite() {
+                bound = bound.max(from_v - from_u);
+            }
+            let (u_to, v_to) = (self.to[i * n + u], self.to[i * n + v]);
+            if u_to.is_finite() && v_to.is_finite() {
+                bound = bound.max(u_to - v_to);
+            }
+        }
+        bound
+    }
+
+    /// Approximate heap size in bytes.
+    pub fn memory_bytes(&self) -> usize {
+        (self.from.len() + self.to.len()) * std::mem::size_of::<f64>()
+    }
+
+    /// Writes the landmarks as `FTNALT01`, node count and landmark count (u64 LE), the landmark ids
+    /// (u64 LE), then the `from` and `to` tables (f64 LE, landmark-major).
+    #[cfg(feature = "fs")]
+    pub fn write_binary(&self, path: &str) -> std::io::Result<()> {
+        use std::io::Write;
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        writer.write_all(LANDMARKS_MAGIC)?;
+        writer.write_all(&(self.num_nodes as u64).to_le_bytes())?;
+        writer.write_all(&(self.landmarks.len() as u64).to_le_bytes())?;
+        for &l in &self.landmarks {
+            writer.write_all(&(l as u64).to_le_bytes())?;
+        }
+        for value in self.from.iter().chain(&self.to) {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        writer.flush()
+    }
+
+    /// Reads landmarks written by [`Landmarks::write_binary`].
+    #[cfg(feature = "fs")]
+    pub fn read_binary(path: &str) -> Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Parses the binary landmark format from memory.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let header = LANDMARKS_MAGIC.len() + 16;
+        if bytes.len() < header || &bytes[..LANDMARKS_MAGIC.len()] != LANDMARKS_MAGIC {
+            return Err(Error::parse(1, "not a landmarks file"));
+        }
+        let word = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap()) as usize;
+        let (n, k) = (word(LANDMARKS_MAGIC.len()), word(LANDMARKS_MAGIC.len() + 8));
+        let body = &bytes[header..];
+        let size = k.checked_mul(n).and_then(|kn| kn.checked_mul(2)).and_then(|v| v.checked_add(k)).and_then(|v| v.checked_mul(8));
+        if size != Some(body.len()) {
+            return Err(Error::parse(1, format!("expected {} landmarks over {} nodes, file holds {} bytes", k, n, body.len())));
+        }
+        let landmarks: Vec<usize> = (0..k).map(|i| word(header + i * 8)).collect();
+        if let Some(&bad) = landmarks.iter().find(|&&l| l >= n) {
+            return Err(Error::InvalidNode { node: bad, num_nodes: n });
+        }
+        let values: Vec<f64> = body[k * 8..]
+            .chunks_exact(8)
+            .map(|c| f64::from_le_bytes(c.try_into().unwrap()))
+            .collect();
+        let (from, to) = values.split_at(k * n);
+        Ok(Self { landmarks, num_nodes: n, from: from.to_vec(), to: to.to_vec() })
+    }
+}
+
+/// Picks `k` landmarks by farthest-point selection and stores exact distances to and from each.
+///
+/// The first landmark is the node farthest from the highest-degree node; every next one maximizes the
+/// distance to the closest landmark picked so far, preferring nodes no landmark reaches yet (so each
+/// weakly disconnected piece gets one). Unweighted graphs count hops. Returns
+/// [`Error::NegativeWeight`] for negative weights.
+#[tracing::instrument(level = "debug", skip(graph))]
+pub fn build_landmarks(graph: &Graph, k: usize) -> Result<Landmarks> {
+    if let Some((from, to)) = find_negative_edge(graph) {
+        return Err(Error::NegativeWeight { from, to });
+    }
+    let n = graph.num_nodes;
+    let k = k.min(n);
+    let mut landmarks = Vec::with_capacity(k);
+    let mut from = Vec::with_capacity(k * n);
+    // Distance from the closest landmark so far; infinite until some landmark reaches the node
+    let mut closest = vec![f64::INFINITY; n];
+
+    if k > 0 {
+        let hub = (0..n).max_by_key(|&v| (graph.out_degree[v], std::cmp::Reverse(v))).unwrap();
+        let seed = dijkstra_unchecked(graph, hub).distances;
+        let mut next = farthest(&seed, |_| 0.0);
+        for _ in 0..k {
+            let distances = dijkstra_unchecked(graph, next).distances;
+            for (c, &d) in closest.iter_mut().zip(&distances) {
+                *c = c.min(d);
+            }
+            landmarks.push(next);
+            from.extend_from_slice(&distances);
+            next = farthest(&closest, |v| if landmarks.contains(&v) { f64::NEG_INFINITY } else { 0.0 });
+        }
+    }
+
+    let transpose = graph.transpose();
+    let backward = |&l: &usize| dijkstra_unchecked(&transpose, l).distances;
+    #[cfg(feature = "parallel")]
+    let to: Vec<f64> = landmarks.par_iter().flat_map_iter(backward).collect();
+    #[cfg(not(feature = "parallel"))]
+    let to: Vec<f64> = landmarks.iter().flat_map(backward).collect();
+
+    Ok(Landmarks { landmarks, num_nodes: n, from, to })
+}
+
+/// Node with the largest `distances[v] + adjust(v)`, counting unreached nodes as farthest; ties go to the smaller id.
+fn farthest(distances: &[f64], adjust: impl Fn(usize) -> f64) -> usize {
+    let key = |v: usize| {
+        let d = if distances[v].is_infinite() { f64::MAX } else { distances[v] };
+        d + adjust(v)
+    };
+    (0..distances.len())
+        .max_by(|&a, &b| key(a).total_cmp(&key(b)).then(b.cmp(&a)))
+        .unwrap_or(0)
+}
+
+/// Result of an ALT query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AltPath {
+    pub cost: f64,
+    /// Nodes from source to target, both included.
+    pub path: Vec<usize>,
+    /// Nodes taken off the queue; plain Dijkstra settles every node closer than the target.
+    pub settled: usize,
+}
+
+/// Min-heap entry (priority, node).
+struct Entry(f64, usize);
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Entry {}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0)
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A* from `source` to `target` guided by the landmark lower bounds; `None` if `target` is unreachable.
+///
+/// The bounds are consistent, so every node is settled at most once and the result is exact.
+/// `landmarks` must have been built for this graph; a node count mismatch returns
+/// [`Error::AttributeLength`].
+#[tracing::instrument(level = "debug", skip(graph, landmarks))]
+pub fn alt_shortest_path(graph: &Graph, landmarks: &Landmarks, source: usize, target: usize) -> Result<Option<AltPath>> {
+    for node in [source, target] {
+        if !graph.is_valid_node(node) {
+            return Err(Error::InvalidNode { node, num_nodes: graph.num_nodes });
+        }
+    }
+    if landmarks.num_nodes != graph.num_nodes {
+        return Err(Error::AttributeLength {
+            name: "landmarks".to_string(),
+            expected: graph.num_nodes,
+            actual: landmarks.num_nodes,
+        });
+    }
+    if let Some((from, to)) = find_negative_edge(graph) {
+        return Err(Error::NegativeWeight { from, to });
+    }
+
+    let n = graph.num_nodes;
+    let mut distances = vec![f64::INFINITY; n];
+    let mut predecessors = vec![usize::MAX; n];
+    let mut settled = vec![false; n];
+    let mut settled_count = 0;
+    let mut heap = BinaryHeap::new();
+    distances[source] = 0.0;
+    heap.push(Entry(landmarks.lower_bound(source, target), source));
+
+    while let Some(Entry(_, u)) = heap.pop() {
+        if settled[u] {
+            continue;
+        }
+        settled[u] = true;
+        settled_count += 1;
+        if u == target {
+            let mut path = vec![target];
+            while let Some(&v) = path.last().filter(|&&v| v != source) {
+                path.push(predecessors[v]);
+            }
+            path.reverse();
+            return Ok(Some(AltPath { cost: distances[target], path, settled: settled_count }));
+        }
+        for (v, w) in graph.weighted_neighbors(u) {
+            let candidate = distances[u] + w;
+            if candidate < distances[v] {
+                distances[v] = candidate;
+                predecessors[v] = u;
+                heap.push(Entry(candidate + landmarks.lower_bound(v, target), v));
+            }
+        }
+    }
+    Ok(None)
+}
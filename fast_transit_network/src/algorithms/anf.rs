@@ -0,0 +1,175 @@
+use crate::graph::graph::Graph;
+#[cfg(feature = "parallel")]
+use super::threadpool::get_thread_pool;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Settings for [`approximate_neighborhood_function`].
+#[derive(Debug, Clone)]
+pub struct AnfConfig {
+    /// Each node's HyperLogLog counter has `2^log2_registers` one-byte registers; the relative
+    /// error of one counter is about `1.04 / sqrt(2^log2_registers)`. Clamped to `4..=16`.
+    pub log2_registers: u32,
+    /// Stop after this many hops even if the counters are still growing.
+    pub max_distance: usize,
+    /// Seed of the node hash; different seeds give independent estimates.
+    pub seed: u64,
+    pub num_threads: usize,
+}
+
+impl Default for AnfConfig {
+    fn default() -> Self {
+        Self {
+            log2_registers: 6,
+            max_distance: 1000,
+            seed: 42,
+            num_threads: 4,
+        }
+    }
+}
+
+/// Estimated neighbourhood function of a graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NeighborhoodFunction {
+    /// `pairs[t]`: estimated number of ordered pairs `(u, v)` with `v` reachable from `u` in at most
+    /// `t` hops (`pairs[0]` counts every node with itself).
+    pub pairs: Vec<f64>,
+    /// Whether no counter changed in the last iteration, i.e. `pairs` covers every reachable pair.
+    pub converged: bool,
+}
+
+impl NeighborhoodFunction {
+    /// Estimated number of pairs at distance exactly `t`, for `t` in `0..pairs.len()`.
+    pub fn distance_distribution(&self) -> Vec<f64> {
+        let mut previous = 0.0;
+        self.pairs
+            .iter()
+            .map(|&p| {
+                // Counters only grow, but the estimates of sums can wobble; keep the distribution non-negative
+                let at = (p - previous).max(0.0);
+                previous = previous.max(p);
+                at
+            })
+            .collect()
+    }
+
+    /// Smallest (linearly interpolated) hop count within which a `quantile` fraction of the
+    /// connected pairs `(u, v)`, `u != v`, lie; `quantile = 0.9` gives the usual effective diameter.
+    pub fn effective_diameter(&self, quantile: f64) -> f64 {
+        let Some((&self_pairs, &total)) = self.pairs.first().zip(self.pairs.last()) else {
+            return 0.0;
+        };
+        let target = self_pairs + quantile * (total - self_pairs);
+        for t in 1..self.pairs.len() {
+            let (before, at) = (self.pairs[t - 1], self.pairs[t]);
+            if at >= target {
+                let step = at - before;
+                return if step > 0.0 { (t - 1) as f64 + (target - before) / step } else { t as f64 };
+            }
+        }
+        (self.pairs.len() - 1) as f64
+    }
+
+    /// Mean hop distance over connected pairs `(u, v)`, `u != v`.
+    pub fn average_distance(&self) -> f64 {
+        let distribution = self.distance_distribution();
+        let connected: f64 = distribution.iter().skip(1).sum();
+        if connected <= 0.0 {
+            return 0.0;
+        }
+        distribution.iter().enumerate().skip(1).map(|(t, &p)| t as f64 * p).sum::<f64>() / connected
+    }
+}
+
+/// Estimates the neighbourhood function with HyperANF: every node keeps a HyperLogLog counter of the
+/// nodes it reaches, and hop `t + 1` merges each counter with those of its out-neighbors.
+///
+/// Needs `n * (2 * 2^log2_registers + 8)` bytes (two generations of counters and the per-node
+/// estimates) and one pass over the edges per hop, instead of a BFS from every node. Counts follow
+/// edge direction; load with `--undirected` for the undirected distribution.
+#[tracing::instrument(level = "debug", skip_all, fields(nodes = graph.num_nodes))]
+pub fn approximate_neighborhood_function(graph: &Graph, config: &AnfConfig) -> NeighborhoodFunction {
+    let b = config.log2_registers.clamp(4, 16);
+    let m = 1usize << b;
+    let n = graph.num_nodes;
+    if n == 0 {
+        return NeighborhoodFunction { pairs: vec![0.0], converged: true };
+    }
+
+    let mut current = vec![0u8; n * m];
+    for (v, registers) in current.chunks_mut(m).enumerate() {
+        let h = hash(v as u64, config.seed);
+        let index = (h >> (64 - b)) as usize;
+        // Rank of the first set bit in the remaining 64 - b bits
+        registers[index] = ((h << b).leading_zeros().min(64 - b) + 1) as u8;
+    }
+    let mut next = vec![0u8; n * m];
+    // Per-node estimates, summed in node order so the result does not depend on the thread count
+    let mut sizes = vec![0.0f64; n];
+    let mut pairs = vec![current.chunks(m).map(estimate).sum::<f64>()];
+    let mut converged = false;
+
+    let merge = |current: &[u8], v: usize, registers: &mut [u8], size: &mut f64| -> bool {
+        registers.copy_from_slice(&current[v * m..(v + 1) * m]);
+        for &u in graph.neighbors(v) {
+            for (r, &other) in registers.iter_mut().zip(&current[u * m..(u + 1) * m]) {
+                *r = (*r).max(other);
+            }
+        }
+        *size = estimate(registers);
+        registers != &current[v * m..(v + 1) * m]
+    };
+    #[cfg(feature = "parallel")]
+    let pool = get_thread_pool(config.num_threads);
+    while pairs.len() <= config.max_distance {
+        #[cfg(feature = "parallel")]
+        let changed = pool.install(|| {
+            next.par_chunks_mut(m)
+                .zip(sizes.par_iter_mut())
+                .enumerate()
+                .map(|(v, (registers, size))| merge(&current, v, registers, size))
+                .reduce(|| false, |a, b| a || b)
+        });
+        #[cfg(not(feature = "parallel"))]
+        let changed = next
+            .chunks_mut(m)
+            .zip(sizes.iter_mut())
+            .enumerate()
+            .fold(false, |changed, (v, (registers, size))| merge(&current, v, registers, size) || changed);
+        if !changed {
+            converged = true;
+            break;
+        }
+        pairs.push(sizes.iter().sum());
+        std::mem::swap(&mut current, &mut next);
+    }
+    NeighborhoodFunction { pairs, converged }
+}
+
+/// Seeded 64-bit hash of a node id (splitmix64 finalizer).
+fn hash(v: u64, seed: u64) -> u64 {
+    let mut z = v.wrapping_add(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15)).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// HyperLogLog cardinality estimate, with linear counting for small sets.
+fn estimate(registers: &[u8]) -> f64 {
+    let m = registers.len() as f64;
+    let alpha = match registers.len() {
+        16 => 0.673,
+        32 => 0.697,
+        64 => 0.709,
+        _ => 0.7213 / (1.0 + 1.079 / m),
+    };
+    let (sum, zeros) = registers
+        .iter()
+        .fold((0.0, 0usize), |(sum, zeros), &r| (sum + (-f64::from(r)).exp2(), zeros + usize::from(r == 0)));
+    let raw = alpha * m * m / sum;
+    if raw <= 2.5 * m && zeros > 0 {
+        m * (m / zeros as f64).ln()
+    } else {
+        raw
+    }
+}
@@ -0,0 +1,236 @@
+use crate::error::{Error, Result};
+use crate::graph::graph::Graph;
+use super::bfs::bfs_sequential;
+use super::sssp::{dijkstra_unchecked, find_negative_edge};
+#[cfg(feature = "parallel")]
+use super::threadpool::get_thread_pool;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::str::FromStr;
+
+/// Rows per block in [`floyd_warshall`]; one block-row of the matrix is copied per outer step.
+const FW_BLOCK: usize = 64;
+
+/// Magic bytes at the start of a binary distance matrix file.
+const MATRIX_MAGIC: &[u8; 8] = b"FTNAPSP1";
+
+/// How [`apsp`] computes the matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApspMethod {
+    /// Blocked Floyd-Warshall, O(n^3); handles negative weights.
+    FloydWarshall,
+    /// One BFS (unweighted) or Dijkstra (weighted) per source, run in parallel; non-negative weights only.
+    RepeatedSssp,
+}
+
+impl FromStr for ApspMethod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "fw" | "floyd-warshall" => Ok(ApspMethod::FloydWarshall),
+            "sssp" | "bfs" | "dijkstra" => Ok(ApspMethod::RepeatedSssp),
+            _ => Err(anyhow::anyhow!("Invalid APSP method: {}. Use 'fw' or 'sssp'", s)),
+        }
+    }
+}
+
+/// Dense row-major `n x n` matrix of path costs; `f32::INFINITY` marks unreachable pairs.
+///
+/// Stored as `f32` to halve memory: 20k nodes take 1.6 GB.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceMatrix {
+    n: usize,
+    data: Vec<f32>,
+}
+
+impl DistanceMatrix {
+    fn unreachable(n: usize) -> Self {
+        Self { n, data: vec![f32::INFINITY; n * n] }
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.n
+    }
+
+    /// Cost from `u` to `v`.
+    pub fn get(&self, u: usize, v: usize) -> f32 {
+        self.data[u * self.n + v]
+    }
+
+    /// Costs from `u` to every node.
+    pub fn row(&self, u: usize) -> &[f32] {
+        &self.data[u * self.n..(u + 1) * self.n]
+    }
+
+    /// Writes the matrix as `FTNAPSP1`, the node count as little-endian u64, then `n * n` little-endian f32 values.
+    #[cfg(feature = "fs")]
+    pub fn write_binary(&self, path: &str) -> std::io::Result<()> {
+        use std::io::Write;
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        writer.write_all(MATRIX_MAGIC)?;
+        writer.write_all(&(self.n as u64).to_le_bytes())?;
+        for value in &self.data {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        writer.flush()
+    }
+
+    /// Reads a matrix written by [`DistanceMatrix::write_binary`].
+    #[cfg(feature = "fs")]
+    pub fn read_binary(path: &str) -> Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Parses the binary matrix format from memory.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let header = MATRIX_MAGIC.len() + 8;
+        if bytes.len() < header || &bytes[..MATRIX_MAGIC.len()] != MATRIX_MAGIC {
+            return Err(Error::parse(1, "not a distance matrix file"));
+        }
+        let n = u64::from_le_bytes(bytes[MATRIX_MAGIC.len()..header].try_into().unwrap()) as usize;
+        let body = &bytes[header..];
+        if n.checked_mul(n).and_then(|v| v.checked_mul(4)) != Some(body.len()) {
+            return Err(Error::parse(1, format!("expected {} x {} values, file holds {} bytes", n, n, body.len())));
+        }
+        let data = body
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes(c.try_into().unwrap()))
+            .collect();
+        Ok(Self { n, data })
+    }
+}
+
+/// All-pairs shortest paths with the chosen method.
+pub fn apsp(graph: &Graph, method: ApspMethod, num_threads: usize) -> Result<DistanceMatrix> {
+    if graph.num_nodes > 50_000 {
+        tracing::warn!(
+            nodes = graph.num_nodes,
+            "APSP matrix needs {:.1} GB",
+            (graph.num_nodes * graph.num_nodes * 4) as f64 / 1e9
+        );
+    }
+    match method {
+        ApspMethod::FloydWarshall => floyd_warshall(graph, num_threads),
+        ApspMethod::RepeatedSssp => apsp_repeated_sssp(graph, num_threads),
+    }
+}
+
+/// Runs `update` on every block of `rows_per_block` matrix rows, in parallel when available.
+fn for_each_block_row<F>(data: &mut [f32], rows_per_block: usize, n: usize, num_threads: usize, update: F)
+where
+    F: Fn(usize, &mut [f32]) + Send + Sync,
+{
+    if n == 0 {
+        return;
+    }
+    #[cfg(feature = "parallel")]
+    get_thread_pool(num_threads).install(|| {
+        data.par_chunks_mut(rows_per_block * n)
+            .enumerate()
+            .for_each(|(block, rows)| update(block, rows));
+    });
+    #[cfg(not(feature = "parallel"))]
+    {
+        let _ = num_threads;
+        data.chunks_mut(rows_per_block * n)
+            .enumerate()
+            .for_each(|(block, rows)| update(block, rows));
+    }
+}
+
+/// Blocked Floyd-Warshall.
+///
+/// For each block of `FW_BLOCK` intermediate nodes, the block's own rows are relaxed first
+/// (the diagonal and row blocks), then every other block-row is relaxed in parallel against a copy
+/// of those finished rows. Returns [`Error::NegativeCycle`] if any node ends up with a negative
+/// distance to itself.
+#[tracing::instrument(level = "debug", skip(graph), fields(nodes = graph.num_nodes))]
+pub fn floyd_warshall(graph: &Graph, num_threads: usize) -> Result<DistanceMatrix> {
+    let n = graph.num_nodes;
+    let mut matrix = DistanceMatrix::unreachable(n);
+    for u in 0..n {
+        matrix.data[u * n + u] = 0.0;
+        for (v, w) in graph.weighted_neighbors(u) {
+            let cell = &mut matrix.data[u * n + v];
+            *cell = cell.min(w as f32);
+        }
+    }
+
+    let data = &mut matrix.data;
+    for k_start in (0..n).step_by(FW_BLOCK) {
+        let k_end = (k_start + FW_BLOCK).min(n);
+
+        // Diagonal and row blocks: plain Floyd-Warshall restricted to rows and intermediates in the block
+        for k in k_start..k_end {
+            let (before, rest) = data.split_at_mut(k * n);
+            let (k_row, after) = rest.split_at_mut(n);
+            for r in (k_start..k_end).filter(|&r| r != k) {
+                let row = if r < k {
+                    &mut before[r * n..(r + 1) * n]
+                } else {
+                    &mut after[(r - k - 1) * n..(r - k) * n]
+                };
+                relax_row(row, row[k], k_row);
+            }
+        }
+
+        let k_rows = data[k_start * n..k_end * n].to_vec();
+        let k_block = k_start / FW_BLOCK;
+        for_each_block_row(data, FW_BLOCK, n, num_threads, |block, rows| {
+            if block == k_block {
+                return;
+            }
+            for row in rows.chunks_mut(n) {
+                for k in k_start..k_end {
+                    let via = row[k];
+                    relax_row(row, via, &k_rows[(k - k_start) * n..(k - k_start + 1) * n]);
+                }
+            }
+        });
+    }
+
+    if let Some(node) = (0..n).find(|&v| matrix.data[v * n + v] < 0.0) {
+        return Err(Error::NegativeCycle { node });
+    }
+    Ok(matrix)
+}
+
+/// `row[j] = min(row[j], via + k_row[j])` for all `j`, skipping unreachable intermediates.
+#[inline]
+fn relax_row(row: &mut [f32], via: f32, k_row: &[f32]) {
+    if via == f32::INFINITY {
+        return;
+    }
+    for (d, &dk) in row.iter_mut().zip(k_row) {
+        let candidate = via + dk;
+        if candidate < *d {
+            *d = candidate;
+        }
+    }
+}
+
+/// One BFS (unweighted graphs) or Dijkstra (weighted) per source, sources split across threads.
+///
+/// Returns [`Error::NegativeWeight`] for graphs with negative weights; use [`floyd_warshall`] there.
+#[tracing::instrument(level = "debug", skip(graph), fields(nodes = graph.num_nodes))]
+pub fn apsp_repeated_sssp(graph: &Graph, num_threads: usize) -> Result<DistanceMatrix> {
+    if let Some((from, to)) = find_negative_edge(graph) {
+        return Err(Error::NegativeWeight { from, to });
+    }
+    let n = graph.num_nodes;
+    let mut matrix = DistanceMatrix::unreachable(n);
+    for_each_block_row(&mut matrix.data, 1, n, num_threads, |source, row| {
+        if graph.is_weighted() {
+            let result = dijkstra_unchecked(graph, source);
+            for (cell, d) in row.iter_mut().zip(result.distances) {
+                *cell = d as f32;
+            }
+        } else if let Ok(result) = bfs_sequential(graph, source) {
+            for (cell, d) in row.iter_mut().zip(result.distances) {
+                *cell = if d < 0 { f32::INFINITY } else { d as f32 };
+            }
+        }
+    });
+    Ok(matrix)
+}
@@ -1,6 +1,13 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+/// Set in a slot that holds a root's component size instead of a parent index.
+const ROOT: usize = 1 << (usize::BITS - 1);
+
 /// Thread-safe Union-Find for parallel WCC using atomics (no locks).
+///
+/// Union by size with path splitting. A root's slot holds its size (tagged with [`ROOT`]) rather
+/// than itself, so a single compare-exchange both links a root and checks that its size has not
+/// changed since it was compared, which keeps the parent links acyclic.
 pub struct AtomicUnionFind {
     parent: Vec<AtomicUsize>,
 }
@@ -8,59 +15,91 @@ pub struct AtomicUnionFind {
 impl AtomicUnionFind {
     pub fn new(n: usize) -> Self {
         Self {
-            parent: (0..n).map(|i| AtomicUsize::new(i)).collect(),
+            parent: (0..n).map(|_| AtomicUsize::new(ROOT | 1)).collect(),
         }
     }
 
-    /// Thread-safe find with path compression (try to skip to grandparent).
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Thread-safe find with path splitting: every node on the path is pointed at its grandparent.
     pub fn find(&self, mut x: usize) -> usize {
         loop {
             let parent = self.parent[x].load(Ordering::Relaxed);
-            if parent == x {
+            if parent & ROOT != 0 {
                 return x;
             }
 
             let grandparent = self.parent[parent].load(Ordering::Relaxed);
-            if grandparent == parent {
-                return parent;
+            if grandparent & ROOT == 0 {
+                self.parent[x]
+                    .compare_exchange(parent, grandparent, Ordering::Relaxed, Ordering::Relaxed)
+                    .ok();
             }
 
-            self.parent[x]
-                .compare_exchange(parent, grandparent, Ordering::Relaxed, Ordering::Relaxed)
-                .ok();
-
             x = parent;
         }
     }
 
-    /// Thread-safe union: always link smaller root to larger for consistency.
-    pub fn union(&self, x: usize, y: usize) {
+    /// Thread-safe union: links the root of the smaller component under the larger one (ties by
+    /// index). Returns `true` if `x` and `y` were in different components.
+    pub fn union(&self, x: usize, y: usize) -> bool {
+        let (mut x, mut y) = (x, y);
         loop {
-            let root_x = self.find(x);
-            let root_y = self.find(y);
+            x = self.find(x);
+            y = self.find(y);
+            if x == y {
+                return false;
+            }
 
-            if root_x == root_y {
-                return;
+            let slot_x = self.parent[x].load(Ordering::Relaxed);
+            let slot_y = self.parent[y].load(Ordering::Relaxed);
+            if slot_x & ROOT == 0 || slot_y & ROOT == 0 {
+                continue;
             }
 
-            let (small, large) = if root_x < root_y {
-                (root_x, root_y)
-            } else {
-                (root_y, root_x)
-            };
+            let (small, slot, large) = if (slot_x, x) < (slot_y, y) { (x, slot_x, y) } else { (y, slot_y, x) };
+            if self.parent[small]
+                .compare_exchange(slot, large, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+            {
+                self.add_size(large, slot & !ROOT);
+                return true;
+            }
+        }
+    }
 
-            match self.parent[small].compare_exchange(
-                small,
-                large,
-                Ordering::Relaxed,
-                Ordering::Relaxed,
-            ) {
-                Ok(_) => return,
-                Err(_) => continue,
+    /// Adds `size` to the component of `x`, following `x` to its root if it was linked meanwhile.
+    fn add_size(&self, mut x: usize, size: usize) {
+        loop {
+            x = self.find(x);
+            let slot = self.parent[x].load(Ordering::Relaxed);
+            if slot & ROOT != 0
+                && self.parent[x]
+                    .compare_exchange(slot, slot + size, Ordering::Relaxed, Ordering::Relaxed)
+                    .is_ok()
+            {
+                return;
             }
         }
     }
 
+    /// Number of elements in the component containing `x`.
+    pub fn component_size(&self, x: usize) -> usize {
+        self.parent[self.find(x)].load(Ordering::Relaxed) & !ROOT
+    }
+
+    /// Returns the number of distinct components, without building the labels vector.
+    pub fn count_components(&self) -> usize {
+        self.parent.iter().filter(|p| p.load(Ordering::Relaxed) & ROOT != 0).count()
+    }
+
     /// Returns the final component id (root) for each element.
     pub fn get_components(&self) -> Vec<usize> {
         (0..self.parent.len()).map(|i| self.find(i)).collect()
@@ -1,56 +1,253 @@
-use crate::graph::graph::Graph;
+use crate::config::{Config, ParallelismPolicy};
+use crate::error::{Error, Result};
+use crate::graph::graph::{Graph, GraphLike};
+use crate::progress::{NoProgress, ProgressEvent, ProgressSink};
 use std::collections::VecDeque;
+use std::str::FromStr;
+#[cfg(feature = "parallel")]
+use super::threadpool::get_thread_pool;
+#[cfg(feature = "parallel")]
+use super::chunking::{hybrid_frontier_chunks, FrontierChunk, CHUNKS_PER_THREAD};
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
+#[cfg(feature = "parallel")]
 use std::sync::atomic::{AtomicI32, Ordering};
+use std::time::Duration;
+#[cfg(not(target_family = "wasm"))]
+use std::time::Instant;
+#[cfg(target_family = "wasm")]
+use web_time::Instant;
 
-/// Below this many nodes, use sequential BFS to avoid thread-pool and atomic overhead.
-const PAR_MIN_NODES: usize = 50_000;
-/// Minimum frontier size to use parallel iteration; below this we process the level sequentially.
-const PAR_MIN_FRONTIER: usize = 1024;
+/// Output of a BFS run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BfsResult {
+    /// Hop distance from the source per node; -1 if unreachable.
+    pub distances: Vec<i32>,
+    /// Number of nodes reached, including the source.
+    pub reachable: usize,
+    /// Number of BFS levels (max distance + 1).
+    pub levels: usize,
+    /// Wall-clock time of the traversal.
+    pub elapsed: Duration,
+    /// One entry per level if the run recorded them (the `_with_level_stats` functions); empty otherwise.
+    pub level_stats: Vec<BfsLevelStats>,
+}
+
+/// How one BFS level was expanded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BfsLevelStats {
+    pub level: usize,
+    /// Nodes at this distance from the source.
+    pub frontier: usize,
+    /// Out-edges of the frontier scanned to find the next level.
+    pub edges: usize,
+    /// Whether the level was expanded by several threads (only the parallel BFS does).
+    pub parallel: bool,
+    /// Frontier hubs whose out-edges were split across threads (only the parallel BFS does).
+    pub split_hubs: usize,
+    /// Time to expand the level into the next frontier.
+    pub elapsed: Duration,
+}
+
+impl BfsResult {
+    /// Builds a result from a distance vector, deriving `reachable` and `levels`.
+    pub fn from_distances(distances: Vec<i32>, elapsed: Duration) -> Self {
+        let reachable = distances.iter().filter(|&&d| d >= 0).count();
+        let levels = distances.iter().copied().max().map_or(0, |d| (d + 1).max(0) as usize);
+        Self {
+            distances,
+            reachable,
+            levels,
+            elapsed,
+            level_stats: Vec::new(),
+        }
+    }
+}
 
 /// Sequential BFS: returns distance from source for each node (-1 if unreachable).
-pub fn bfs_sequential(graph: &Graph, source: usize) -> Vec<i32> {
-    let mut dist = vec![-1; graph.num_nodes];
-    
-    if !graph.is_valid_node(source) {
-        eprintln!("Invalid source node: {}", source);
-        return dist;
+///
+/// Works on any [`GraphLike`], including filtered views. Returns [`Error::InvalidNode`] if `source`
+/// is out of range (or hidden by the view).
+pub fn bfs_sequential<G: GraphLike>(graph: &G, source: usize) -> Result<BfsResult> {
+    bfs_sequential_with_progress(graph, source, &NoProgress)
+}
+
+/// Sequential BFS that reports the size of every level to `progress` once the level is complete.
+#[tracing::instrument(level = "debug", skip_all, fields(source))]
+pub fn bfs_sequential_with_progress<G: GraphLike>(
+    graph: &G,
+    source: usize,
+    progress: &dyn ProgressSink,
+) -> Result<BfsResult> {
+    check_source(graph, source)?;
+    let start = Instant::now();
+    let dist = bfs_sequential_impl(graph, source, progress, None);
+    Ok(BfsResult::from_distances(dist, start.elapsed()))
+}
+
+/// [`bfs_sequential_with_progress`] that also fills [`BfsResult::level_stats`].
+pub fn bfs_sequential_with_level_stats<G: GraphLike>(
+    graph: &G,
+    source: usize,
+    progress: &dyn ProgressSink,
+) -> Result<BfsResult> {
+    check_source(graph, source)?;
+    let start = Instant::now();
+    let mut levels = Vec::new();
+    let dist = bfs_sequential_impl(graph, source, progress, Some(&mut levels));
+    let mut result = BfsResult::from_distances(dist, start.elapsed());
+    result.level_stats = levels;
+    Ok(result)
+}
+
+fn check_source<G: GraphLike>(graph: &G, source: usize) -> Result<()> {
+    if graph.contains_node(source) {
+        Ok(())
+    } else {
+        Err(Error::InvalidNode {
+            node: source,
+            num_nodes: graph.node_count(),
+        })
     }
+}
+
+fn bfs_sequential_impl<G: GraphLike>(
+    graph: &G,
+    source: usize,
+    progress: &dyn ProgressSink,
+    mut level_stats: Option<&mut Vec<BfsLevelStats>>,
+) -> Vec<i32> {
+    let mut dist = vec![-1; graph.node_count()];
     
     let mut queue = VecDeque::new();
     dist[source] = 0;
     queue.push_back(source);
     
+    let mut level = 0;
+    let mut level_size = 0;
+    let mut level_edges = 0;
+    let mut level_start = Instant::now();
+    let mut finish_level = |level: usize, frontier: usize, edges: usize, level_start: Instant| {
+        progress.event(&ProgressEvent::BfsLevel { level, frontier });
+        if let Some(stats) = level_stats.as_deref_mut() {
+            stats.push(BfsLevelStats { level, frontier, edges, parallel: false, split_hubs: 0, elapsed: level_start.elapsed() });
+        }
+    };
+    
     while let Some(u) = queue.pop_front() {
-        for &v in graph.neighbors(u) {
+        let d = dist[u] as usize;
+        if d != level {
+            finish_level(level, level_size, level_edges, level_start);
+            level = d;
+            level_size = 0;
+            level_edges = 0;
+            level_start = Instant::now();
+        }
+        level_size += 1;
+        
+        for v in graph.neighbor_iter(u) {
+            level_edges += 1;
             if dist[v] == -1 {
                 dist[v] = dist[u] + 1;
                 queue.push_back(v);
             }
         }
     }
+    finish_level(level, level_size, level_edges, level_start);
     
     dist
 }
 
 /// Parallel level-synchronous BFS. Falls back to sequential for small graphs; uses threads only when the current frontier is large.
-pub fn bfs_parallel(graph: &Graph, source: usize, num_threads: usize) -> Vec<i32> {
-    if graph.num_nodes < PAR_MIN_NODES {
-        return bfs_sequential(graph, source);
-    }
-    rayon::ThreadPoolBuilder::new()
-        .num_threads(num_threads)
-        .build()
-        .expect("rayon thread pool")
-        .install(|| bfs_parallel_impl(graph, source))
+///
+/// Both thresholds come from [`Config::global`].
+pub fn bfs_parallel(graph: &Graph, source: usize, num_threads: usize) -> Result<BfsResult> {
+    bfs_parallel_with_policy(graph, source, num_threads, ParallelismPolicy::Auto)
+}
+
+/// Parallel BFS with an explicit fallback policy; `ForceParallel` also expands every level in parallel.
+pub fn bfs_parallel_with_policy(
+    graph: &Graph,
+    source: usize,
+    num_threads: usize,
+    policy: ParallelismPolicy,
+) -> Result<BfsResult> {
+    bfs_parallel_with_progress(graph, source, num_threads, policy, &NoProgress)
 }
 
-fn bfs_parallel_impl(graph: &Graph, source: usize) -> Vec<i32> {
-    if !graph.is_valid_node(source) {
-        eprintln!("Invalid source node: {}", source);
-        return vec![-1; graph.num_nodes];
+/// Parallel BFS that reports the size of every level to `progress`.
+#[tracing::instrument(level = "debug", skip_all, fields(source, num_threads))]
+pub fn bfs_parallel_with_progress(
+    graph: &Graph,
+    source: usize,
+    num_threads: usize,
+    policy: ParallelismPolicy,
+    progress: &dyn ProgressSink,
+) -> Result<BfsResult> {
+    bfs_parallel_run(graph, source, num_threads, policy, progress, false)
+}
+
+/// [`bfs_parallel_with_progress`] that also fills [`BfsResult::level_stats`], e.g. to see which
+/// levels were too small to expand in parallel.
+pub fn bfs_parallel_with_level_stats(
+    graph: &Graph,
+    source: usize,
+    num_threads: usize,
+    policy: ParallelismPolicy,
+    progress: &dyn ProgressSink,
+) -> Result<BfsResult> {
+    bfs_parallel_run(graph, source, num_threads, policy, progress, true)
+}
+
+fn bfs_parallel_run(
+    graph: &Graph,
+    source: usize,
+    num_threads: usize,
+    policy: ParallelismPolicy,
+    progress: &dyn ProgressSink,
+    record_levels: bool,
+) -> Result<BfsResult> {
+    check_source(graph, source)?;
+    let config = Config::global();
+    let sequential = || {
+        if record_levels {
+            bfs_sequential_with_level_stats(graph, source, progress)
+        } else {
+            bfs_sequential_with_progress(graph, source, progress)
+        }
+    };
+    if !policy.use_parallel(graph.num_nodes, config.bfs_min_nodes) {
+        return sequential();
+    }
+    let min_frontier = match policy {
+        ParallelismPolicy::ForceParallel => 1,
+        _ => config.bfs_min_frontier,
+    };
+    #[cfg(feature = "parallel")]
+    {
+        let start = Instant::now();
+        let mut levels = Vec::new();
+        let level_stats = record_levels.then_some(&mut levels);
+        let dist = get_thread_pool(num_threads).install(|| bfs_parallel_impl(graph, source, min_frontier, progress, level_stats));
+        let mut result = BfsResult::from_distances(dist, start.elapsed());
+        result.level_stats = levels;
+        Ok(result)
     }
+    #[cfg(not(feature = "parallel"))]
+    {
+        let _ = (num_threads, min_frontier);
+        sequential()
+    }
+}
 
+#[cfg(feature = "parallel")]
+fn bfs_parallel_impl(
+    graph: &Graph,
+    source: usize,
+    min_frontier: usize,
+    progress: &dyn ProgressSink,
+    mut level_stats: Option<&mut Vec<BfsLevelStats>>,
+) -> Vec<i32> {
     let dist: Vec<AtomicI32> = (0..graph.num_nodes)
         .map(|_| AtomicI32::new(-1))
         .collect();
@@ -59,31 +256,55 @@ fn bfs_parallel_impl(graph: &Graph, source: usize) -> Vec<i32> {
 
     let mut current_frontier = vec![source];
     let mut next_frontier = Vec::new();
+    // One discovery buffer per frontier chunk, kept across levels so deep graphs do not
+    // allocate fresh vectors every level; capacity stays at the largest level seen.
+    let mut chunk_buffers: Vec<Vec<usize>> = Vec::new();
     let mut level = 0;
 
     while !current_frontier.is_empty() {
-        let use_parallel = current_frontier.len() >= PAR_MIN_FRONTIER;
+        progress.event(&ProgressEvent::BfsLevel {
+            level: level as usize,
+            frontier: current_frontier.len(),
+        });
+        let use_parallel = current_frontier.len() >= min_frontier;
+        let level_start = Instant::now();
+        let mut split_hubs = 0;
 
         if use_parallel {
-            let local_next: Vec<Vec<usize>> = current_frontier
-                .par_iter()
-                .map(|&u| {
-                    let mut local_neighbors = Vec::new();
-                    for &v in graph.neighbors(u) {
-                        if dist[v]
-                            .compare_exchange(-1, level + 1, Ordering::Relaxed, Ordering::Relaxed)
-                            .is_ok()
-                        {
-                            local_neighbors.push(v);
+            // Split the frontier by out-degree, and the adjacency of hubs by edges, so neither many
+            // hubs nor one huge hub serialize a level
+            let chunks = hybrid_frontier_chunks(graph, &current_frontier, rayon::current_num_threads() * CHUNKS_PER_THREAD);
+            split_hubs = chunks.iter().filter(|c| matches!(c, FrontierChunk::Edges { edges, .. } if edges.start == 0)).count();
+            if chunk_buffers.len() < chunks.len() {
+                chunk_buffers.resize_with(chunks.len(), Vec::new);
+            }
+            let buffers = &mut chunk_buffers[..chunks.len()];
+            let visit = |v: usize, local_neighbors: &mut Vec<usize>| {
+                if dist[v]
+                    .compare_exchange(-1, level + 1, Ordering::Relaxed, Ordering::Relaxed)
+                    .is_ok()
+                {
+                    local_neighbors.push(v);
+                }
+            };
+            buffers.par_iter_mut().zip(chunks).for_each(|(local_neighbors, chunk)| {
+                local_neighbors.clear();
+                match chunk {
+                    FrontierChunk::Nodes(range) => {
+                        for &u in &current_frontier[range] {
+                            for &v in graph.neighbors(u) {
+                                visit(v, local_neighbors);
+                            }
                         }
                     }
-                    local_neighbors
-                })
-                .collect();
-            next_frontier.clear();
-            for local in local_next {
-                next_frontier.extend(local);
-            }
+                    FrontierChunk::Edges { node, edges } => {
+                        for &v in &graph.neighbors(node)[edges] {
+                            visit(v, local_neighbors);
+                        }
+                    }
+                }
+            });
+            concat_parallel(buffers, &mut next_frontier);
         } else {
             next_frontier.clear();
             for &u in &current_frontier {
@@ -100,6 +321,16 @@ fn bfs_parallel_impl(graph: &Graph, source: usize) -> Vec<i32> {
 
         next_frontier.sort_unstable();
         next_frontier.dedup();
+        if let Some(stats) = level_stats.as_deref_mut() {
+            stats.push(BfsLevelStats {
+                level: level as usize,
+                frontier: current_frontier.len(),
+                edges: current_frontier.iter().map(|&u| graph.out_degree[u]).sum(),
+                parallel: use_parallel,
+                split_hubs,
+                elapsed: level_start.elapsed(),
+            });
+        }
         std::mem::swap(&mut current_frontier, &mut next_frontier);
         level += 1;
     }
@@ -107,6 +338,198 @@ fn bfs_parallel_impl(graph: &Graph, source: usize) -> Vec<i32> {
     dist.into_iter().map(|d| d.into_inner()).collect()
 }
 
+/// Concatenates `parts` into `out`, copying in parallel at offsets from a prefix sum of the part lengths.
+#[cfg(feature = "parallel")]
+fn concat_parallel(parts: &[Vec<usize>], out: &mut Vec<usize>) {
+    let total = parts.iter().map(Vec::len).sum();
+    out.clear();
+    out.resize(total, 0);
+    let mut rest = out.as_mut_slice();
+    let mut slices = Vec::with_capacity(parts.len());
+    for part in parts {
+        let (head, tail) = rest.split_at_mut(part.len());
+        slices.push(head);
+        rest = tail;
+    }
+    slices
+        .into_par_iter()
+        .zip(parts.par_iter())
+        .for_each(|(dst, src)| dst.copy_from_slice(src));
+}
+
+/// When a search towards a set of targets ([`bfs_to_targets`], [`dijkstra_to_targets`]) stops.
+///
+/// [`dijkstra_to_targets`]: super::sssp::dijkstra_to_targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StopAt {
+    /// Once every target has its final distance.
+    #[default]
+    All,
+    /// Once the first target has its final distance: the nearest one.
+    Any,
+}
+
+impl FromStr for StopAt {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "all" => Ok(StopAt::All),
+            "any" => Ok(StopAt::Any),
+            _ => Err(anyhow::anyhow!("Invalid stop condition: {}. Use 'all' or 'any'", s)),
+        }
+    }
+}
+
+/// Distances from a source to a set of targets, from a search that stopped once it had them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetDistances<D> {
+    pub source: usize,
+    /// The targets as given, duplicates included.
+    pub targets: Vec<usize>,
+    /// Distance of each target, in the order of `targets`: -1 (hops) or `f64::INFINITY` (costs) if
+    /// it is unreachable or, with [`StopAt::Any`], was not reached before the search stopped.
+    pub distances: Vec<D>,
+    /// Nodes whose distance was final when the search stopped, including the source.
+    pub settled: usize,
+}
+
+impl TargetDistances<i32> {
+    /// The reached target with the fewest hops and its distance (the first given on ties).
+    pub fn nearest(&self) -> Option<(usize, i32)> {
+        self.targets
+            .iter()
+            .copied()
+            .zip(self.distances.iter().copied())
+            .filter(|&(_, d)| d >= 0)
+            .min_by_key(|&(_, d)| d)
+    }
+}
+
+impl TargetDistances<f64> {
+    /// The reached target with the lowest cost and its distance (the first given on ties).
+    pub fn nearest(&self) -> Option<(usize, f64)> {
+        self.targets
+            .iter()
+            .copied()
+            .zip(self.distances.iter().copied())
+            .filter(|&(_, d)| d.is_finite())
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+/// Tracks which targets are still open during a search; see [`StopAt`].
+pub(crate) struct TargetSet {
+    is_target: Vec<bool>,
+    remaining: usize,
+    found: usize,
+    stop: StopAt,
+}
+
+impl TargetSet {
+    pub(crate) fn new(num_nodes: usize, targets: &[usize], stop: StopAt) -> Self {
+        let mut is_target = vec![false; num_nodes];
+        let mut remaining = 0;
+        for &t in targets {
+            if !is_target[t] {
+                is_target[t] = true;
+                remaining += 1;
+            }
+        }
+        Self { is_target, remaining, found: 0, stop }
+    }
+
+    /// Records that `v` has its final distance; true once the search can stop.
+    pub(crate) fn settle(&mut self, v: usize) -> bool {
+        if self.is_target[v] {
+            self.is_target[v] = false;
+            self.remaining -= 1;
+            self.found += 1;
+        }
+        self.done()
+    }
+
+    /// Whether `v` is a target that has not been settled yet.
+    pub(crate) fn is_open(&self, v: usize) -> bool {
+        self.is_target[v]
+    }
+
+    pub(crate) fn done(&self) -> bool {
+        self.remaining == 0 || (self.stop == StopAt::Any && self.found > 0)
+    }
+}
+
+/// BFS from `source` that stops as soon as the `targets` have their hop distance (all of them, or
+/// the first one with [`StopAt::Any`]) instead of visiting every reachable node. A node's distance
+/// is final when it is discovered, so the search can stop in the middle of a level.
+///
+/// Returns [`Error::InvalidNode`] if the source or a target is out of range (or hidden by the view).
+#[tracing::instrument(level = "debug", skip_all, fields(source, targets = targets.len()))]
+pub fn bfs_to_targets<G: GraphLike>(graph: &G, source: usize, targets: &[usize], stop: StopAt) -> Result<TargetDistances<i32>> {
+    check_source(graph, source)?;
+    for &t in targets {
+        check_source(graph, t)?;
+    }
+    let mut dist = vec![-1; graph.node_count()];
+    let mut open = TargetSet::new(graph.node_count(), targets, stop);
+    let mut queue = VecDeque::new();
+    dist[source] = 0;
+    queue.push_back(source);
+    let mut settled = 1;
+    let mut done = open.settle(source);
+
+    while !done && let Some(u) = queue.pop_front() {
+        for v in graph.neighbor_iter(u) {
+            if dist[v] == -1 {
+                dist[v] = dist[u] + 1;
+                settled += 1;
+                if open.settle(v) {
+                    done = true;
+                    break;
+                }
+                queue.push_back(v);
+            }
+        }
+    }
+
+    let distances = targets.iter().map(|&t| dist[t]).collect();
+    Ok(TargetDistances { source, targets: targets.to_vec(), distances, settled })
+}
+
+/// Fewest-hop path from `source` to `target` (both included), or `None` if `target` is unreachable.
+///
+/// Stops as soon as `target` is dequeued instead of exploring the whole graph.
+pub fn shortest_path<G: GraphLike>(graph: &G, source: usize, target: usize) -> Result<Option<Vec<usize>>> {
+    check_source(graph, source)?;
+    check_source(graph, target)?;
+    
+    let mut parent = vec![usize::MAX; graph.node_count()];
+    let mut queue = VecDeque::new();
+    parent[source] = source;
+    queue.push_back(source);
+    
+    while let Some(u) = queue.pop_front() {
+        if u == target {
+            let mut path = vec![target];
+            let mut v = target;
+            while v != source {
+                v = parent[v];
+                path.push(v);
+            }
+            path.reverse();
+            return Ok(Some(path));
+        }
+        for v in graph.neighbor_iter(u) {
+            if parent[v] == usize::MAX {
+                parent[v] = u;
+                queue.push_back(v);
+            }
+        }
+    }
+    
+    Ok(None)
+}
+
 /// Prints BFS result: levels and reachable node count.
 pub fn print_bfs_result(dist: &[i32], source: usize) {
     println!("\nBFS from node {}:", source);
@@ -0,0 +1,135 @@
+use crate::graph::graph::{build_csr, Graph, GraphLike};
+use serde::Serialize;
+
+/// An edge whose removal disconnects its component of the undirected view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Bridge {
+    /// Endpoints with `u < v`.
+    pub u: usize,
+    pub v: usize,
+    /// Nodes on the smaller side once the edge is gone.
+    pub stranded: usize,
+}
+
+/// A node (articulation point) whose removal disconnects its component of the undirected view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CutVertex {
+    pub node: usize,
+    /// Pieces its component falls into without it (at least 2).
+    pub pieces: usize,
+    /// Nodes cut off from the largest remaining piece.
+    pub stranded: usize,
+}
+
+/// Output of [`find_bridges`], most critical (largest `stranded`) first, ties by id.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct BridgeResult {
+    pub bridges: Vec<Bridge>,
+    pub cut_vertices: Vec<CutVertex>,
+}
+
+/// Bridges and cut vertices of the undirected view of `graph` (Tarjan's lowlink, iterative, O(n + m)).
+///
+/// `u -> v` and `v -> u` count as one undirected link and self-loops are ignored, so a two-way street
+/// between two stations is still a bridge if nothing else connects them. Works on any [`GraphLike`],
+/// e.g. a view with closed stations hidden.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn find_bridges<G: GraphLike>(graph: &G) -> BridgeResult {
+    const UNVISITED: usize = usize::MAX;
+    let n = graph.node_count();
+    let undirected = undirected_simple(graph);
+
+    let mut disc = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut size = vec![1; n];
+    let mut root_of = vec![0; n];
+    // Child subtrees that would be cut off with the node: count, total and largest size
+    let mut piece_count = vec![0; n];
+    let mut piece_sum = vec![0; n];
+    let mut piece_max = vec![0; n];
+    let mut tree_bridges = Vec::new();
+    let mut next_index = 0;
+    // DFS path: node, its tree parent and its next undirected edge
+    let mut stack: Vec<(usize, usize, usize)> = Vec::new();
+
+    for root in 0..n {
+        if disc[root] != UNVISITED || !graph.contains_node(root) {
+            continue;
+        }
+        disc[root] = next_index;
+        low[root] = next_index;
+        next_index += 1;
+        root_of[root] = root;
+        stack.push((root, UNVISITED, undirected.offsets[root]));
+
+        while let Some(top) = stack.last_mut() {
+            let (v, parent) = (top.0, top.1);
+            if top.2 < undirected.offsets[v + 1] {
+                let w = undirected.neighbors[top.2];
+                top.2 += 1;
+                if disc[w] == UNVISITED {
+                    disc[w] = next_index;
+                    low[w] = next_index;
+                    next_index += 1;
+                    root_of[w] = root;
+                    stack.push((w, v, undirected.offsets[w]));
+                } else if w != parent {
+                    low[v] = low[v].min(disc[w]);
+                }
+                continue;
+            }
+            stack.pop();
+            if parent == UNVISITED {
+                continue;
+            }
+            size[parent] += size[v];
+            low[parent] = low[parent].min(low[v]);
+            if low[v] > disc[parent] {
+                tree_bridges.push((parent, v));
+            }
+            if low[v] >= disc[parent] {
+                piece_count[parent] += 1;
+                piece_sum[parent] += size[v];
+                piece_max[parent] = piece_max[parent].max(size[v]);
+            }
+        }
+    }
+
+    let mut bridges: Vec<Bridge> = tree_bridges
+        .into_iter()
+        .map(|(p, c)| {
+            let other = size[root_of[c]] - size[c];
+            Bridge { u: p.min(c), v: p.max(c), stranded: size[c].min(other) }
+        })
+        .collect();
+    bridges.sort_unstable_by_key(|b| (std::cmp::Reverse(b.stranded), b.u, b.v));
+
+    let mut cut_vertices: Vec<CutVertex> = (0..n)
+        .filter(|&v| disc[v] != UNVISITED)
+        .filter_map(|v| {
+            let is_root = root_of[v] == v;
+            // Everything in the component outside v's cut-off subtrees stays connected through v's parent
+            let rest = size[root_of[v]] - 1 - piece_sum[v];
+            let pieces = piece_count[v] + usize::from(!is_root);
+            (pieces >= 2).then(|| CutVertex {
+                node: v,
+                pieces,
+                stranded: size[root_of[v]] - 1 - piece_max[v].max(rest),
+            })
+        })
+        .collect();
+    cut_vertices.sort_unstable_by_key(|c| (std::cmp::Reverse(c.stranded), c.node));
+
+    BridgeResult { bridges, cut_vertices }
+}
+
+/// Undirected simple graph on the visible nodes: both directions of every link, no loops or duplicates.
+fn undirected_simple<G: GraphLike>(graph: &G) -> Graph {
+    let mut links: Vec<(usize, usize)> = (0..graph.node_count())
+        .flat_map(|u| graph.neighbor_iter(u).filter(move |&v| v != u).map(move |v| (u.min(v), u.max(v))))
+        .collect();
+    links.sort_unstable();
+    links.dedup();
+    let edges = links.iter().flat_map(|&(u, v)| [(u, v), (v, u)]).collect();
+    build_csr(graph.node_count(), edges)
+}
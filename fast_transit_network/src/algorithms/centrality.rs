@@ -0,0 +1,280 @@
+use crate::analysis::Component;
+use crate::graph::graph::Graph;
+use super::pagerank::{pagerank_parallel, PageRankConfig};
+#[cfg(feature = "parallel")]
+use super::threadpool::get_thread_pool;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::VecDeque;
+
+/// Settings for [`centrality_report`].
+#[derive(Clone)]
+pub struct CentralityConfig {
+    /// BFS sources sampled for betweenness and closeness; the graph's node count or more gives exact values.
+    pub samples: usize,
+    pub num_threads: usize,
+    pub pagerank: PageRankConfig,
+    /// Also score every edge ([`CentralityReport::edge_betweenness`]) in the same Brandes passes.
+    pub edge_betweenness: bool,
+    /// Scores the largest weakly connected component only, as its own graph; every other node and
+    /// edge gets 0 PageRank, betweenness and closeness (see
+    /// [`crate::algorithms::pagerank::pagerank_largest_component`]). Degrees stay those of the whole graph.
+    pub largest_component: bool,
+}
+
+impl Default for CentralityConfig {
+    fn default() -> Self {
+        Self {
+            samples: 256,
+            num_threads: 4,
+            pagerank: PageRankConfig::default(),
+            edge_betweenness: false,
+            largest_component: false,
+        }
+    }
+}
+
+/// Per-node centrality scores from one loaded graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CentralityReport {
+    pub in_degree: Vec<usize>,
+    pub out_degree: Vec<usize>,
+    pub pagerank: Vec<f64>,
+    /// Shortest-path betweenness (unnormalized, hop counts), estimated from the sampled sources.
+    pub betweenness: Vec<f64>,
+    /// Harmonic closeness in `[0, 1]`: mean of `1 / distance` from the node to every other node.
+    pub closeness: Vec<f64>,
+    /// Edge betweenness per CSR edge index, if [`CentralityConfig::edge_betweenness`] was set.
+    pub edge_betweenness: Option<Vec<f64>>,
+    /// Sources actually used; equal to the node count when `exact`.
+    pub samples: usize,
+    pub exact: bool,
+}
+
+impl CentralityReport {
+    pub fn num_nodes(&self) -> usize {
+        self.pagerank.len()
+    }
+}
+
+/// In- and out-degree of every node.
+pub fn degree_centrality(graph: &Graph) -> (Vec<usize>, Vec<usize>) {
+    let mut in_degree = vec![0; graph.num_nodes];
+    for &v in &graph.neighbors {
+        in_degree[v] += 1;
+    }
+    (in_degree, graph.out_degree.clone())
+}
+
+/// Betweenness from Brandes' algorithm over `samples` evenly spaced BFS sources, scaled up by
+/// `n / samples` (exact when `samples >= n`).
+pub fn approximate_betweenness(graph: &Graph, samples: usize, num_threads: usize) -> Vec<f64> {
+    sampled_centrality(graph.num_nodes, Some(graph), None, samples, num_threads, false).betweenness
+}
+
+/// Edge betweenness, indexed like [`Graph::neighbors`]: the (estimated) number of shortest paths
+/// through each edge, sampled and scaled as in [`approximate_betweenness`]. Parallel edges share
+/// their paths evenly, and the two directions of an undirected link are scored separately.
+pub fn approximate_edge_betweenness(graph: &Graph, samples: usize, num_threads: usize) -> Vec<f64> {
+    sampled_centrality(graph.num_nodes, Some(graph), None, samples, num_threads, true).edges
+}
+
+/// Harmonic closeness estimated from `samples` evenly spaced BFS targets (exact when `samples >= n`).
+pub fn approximate_closeness(graph: &Graph, samples: usize, num_threads: usize) -> Vec<f64> {
+    closeness_from_transpose(&graph.transpose(), samples, num_threads)
+}
+
+/// [`approximate_closeness`] for callers that already hold the transposed graph.
+pub(crate) fn closeness_from_transpose(transposed: &Graph, samples: usize, num_threads: usize) -> Vec<f64> {
+    sampled_centrality(transposed.num_nodes, None, Some(transposed), samples, num_threads, false).harmonic
+}
+
+/// Degree, PageRank, betweenness and closeness for every node.
+///
+/// Betweenness and closeness share the sampled sources, so each sample costs one BFS over the
+/// graph and one over its transpose.
+#[tracing::instrument(level = "debug", skip_all, fields(nodes = graph.num_nodes, samples = config.samples))]
+pub fn centrality_report(graph: &Graph, config: &CentralityConfig) -> CentralityReport {
+    if config.largest_component {
+        return largest_component_report(graph, config);
+    }
+    centrality_report_with_transpose(graph, &graph.transpose(), config)
+}
+
+/// [`centrality_report`] for callers that already hold the transposed graph. Ignores
+/// [`CentralityConfig::largest_component`]: callers check it first, as the component needs its own transpose.
+pub(crate) fn centrality_report_with_transpose(graph: &Graph, transposed: &Graph, config: &CentralityConfig) -> CentralityReport {
+    let (in_degree, out_degree) = degree_centrality(graph);
+    let pagerank = pagerank_parallel(graph, &config.pagerank, config.num_threads).ranks;
+    let sums = sampled_centrality(graph.num_nodes, Some(graph), Some(transposed), config.samples, config.num_threads, config.edge_betweenness);
+    let samples = config.samples.min(graph.num_nodes);
+    CentralityReport {
+        in_degree,
+        out_degree,
+        pagerank,
+        betweenness: sums.betweenness,
+        closeness: sums.harmonic,
+        edge_betweenness: config.edge_betweenness.then_some(sums.edges),
+        samples,
+        exact: samples == graph.num_nodes,
+    }
+}
+
+/// [`centrality_report`] of the largest component, mapped back to `graph`'s nodes and edges.
+/// Degrees are local, so they are those of the whole graph.
+fn largest_component_report(graph: &Graph, config: &CentralityConfig) -> CentralityReport {
+    let component = Component::largest(graph, Some(config.num_threads));
+    let report = centrality_report(&component.graph, &CentralityConfig { largest_component: false, ..config.clone() });
+    let (in_degree, out_degree) = degree_centrality(graph);
+    CentralityReport {
+        in_degree,
+        out_degree,
+        pagerank: component.expand(&report.pagerank),
+        betweenness: component.expand(&report.betweenness),
+        closeness: component.expand(&report.closeness),
+        edge_betweenness: report.edge_betweenness.map(|scores| component.expand_edges(graph, &scores)),
+        samples: report.samples,
+        exact: report.exact,
+    }
+}
+
+/// Evenly spaced sample of `samples` nodes (all nodes if `samples >= n`).
+fn sample_sources(n: usize, samples: usize) -> Vec<usize> {
+    let k = samples.min(n);
+    (0..k).map(|i| i * n / k).collect()
+}
+
+/// Per-node (and optionally per-edge) sums over the sampled sources.
+struct Sums {
+    betweenness: Vec<f64>,
+    harmonic: Vec<f64>,
+    /// Empty unless edge betweenness was requested.
+    edges: Vec<f64>,
+}
+
+impl Sums {
+    fn zero(n: usize, m: usize) -> Self {
+        Self { betweenness: vec![0.0; n], harmonic: vec![0.0; n], edges: vec![0.0; m] }
+    }
+
+    #[cfg(feature = "parallel")]
+    fn add(mut self, other: Sums) -> Self {
+        self.betweenness.iter_mut().zip(other.betweenness).for_each(|(a, b)| *a += b);
+        self.harmonic.iter_mut().zip(other.harmonic).for_each(|(a, b)| *a += b);
+        self.edges.iter_mut().zip(other.edges).for_each(|(a, b)| *a += b);
+        self
+    }
+}
+
+/// Runs the sampled BFS passes: Brandes dependency accumulation on `forward` (per edge too if
+/// `edges`), and harmonic-distance sums from a BFS on `transposed` (distances *to* the source in
+/// the original graph).
+fn sampled_centrality(
+    n: usize,
+    forward: Option<&Graph>,
+    transposed: Option<&Graph>,
+    samples: usize,
+    num_threads: usize,
+    edges: bool,
+) -> Sums {
+    let m = match forward {
+        Some(graph) if edges => graph.num_edges,
+        _ => 0,
+    };
+    let sources = sample_sources(n, samples);
+    if sources.is_empty() {
+        return Sums::zero(n, m);
+    }
+
+    let visit = |mut sums: Sums, &s: &usize| {
+        if let Some(graph) = forward {
+            let edge_acc = if edges { Some(sums.edges.as_mut_slice()) } else { None };
+            brandes_from(graph, s, &mut sums.betweenness, edge_acc);
+        }
+        if let Some(transposed) = transposed {
+            for (v, d) in bfs_hops(transposed, s).into_iter().enumerate() {
+                if d > 0 {
+                    sums.harmonic[v] += 1.0 / d as f64;
+                }
+            }
+        }
+        sums
+    };
+    let zero = || Sums::zero(n, m);
+
+    #[cfg(feature = "parallel")]
+    let mut sums = get_thread_pool(num_threads).install(|| sources.par_iter().fold(zero, visit).reduce(zero, Sums::add));
+    #[cfg(not(feature = "parallel"))]
+    let mut sums = {
+        let _ = num_threads;
+        sources.iter().fold(zero(), visit)
+    };
+
+    let scale = n as f64 / sources.len() as f64;
+    sums.betweenness.iter_mut().for_each(|b| *b *= scale);
+    sums.edges.iter_mut().for_each(|b| *b *= scale);
+    let closeness_scale = if n > 1 { scale / (n - 1) as f64 } else { 0.0 };
+    sums.harmonic.iter_mut().for_each(|h| *h *= closeness_scale);
+    sums
+}
+
+/// Hop distance from `source` to every node; `usize::MAX` if unreachable.
+fn bfs_hops(graph: &Graph, source: usize) -> Vec<usize> {
+    let mut dist = vec![usize::MAX; graph.num_nodes];
+    let mut queue = VecDeque::from([source]);
+    dist[source] = 0;
+    while let Some(u) = queue.pop_front() {
+        for &v in graph.neighbors(u) {
+            if dist[v] == usize::MAX {
+                dist[v] = dist[u] + 1;
+                queue.push_back(v);
+            }
+        }
+    }
+    dist
+}
+
+/// Adds the dependencies of `source` (Brandes 2001) to `acc`, and each edge's share of them to
+/// `edge_acc` (by CSR edge index).
+fn brandes_from(graph: &Graph, source: usize, acc: &mut [f64], mut edge_acc: Option<&mut [f64]>) {
+    let n = graph.num_nodes;
+    let mut dist = vec![usize::MAX; n];
+    let mut sigma = vec![0.0f64; n];
+    let mut order = Vec::new();
+    let mut queue = VecDeque::from([source]);
+    dist[source] = 0;
+    sigma[source] = 1.0;
+
+    while let Some(u) = queue.pop_front() {
+        order.push(u);
+        for &v in graph.neighbors(u) {
+            if dist[v] == usize::MAX {
+                dist[v] = dist[u] + 1;
+                queue.push_back(v);
+            }
+            if dist[v] == dist[u] + 1 {
+                sigma[v] += sigma[u];
+            }
+        }
+    }
+
+    // Successors on shortest paths are the neighbors one level further out, so no predecessor lists are needed
+    let mut delta = vec![0.0f64; n];
+    for &u in order.iter().rev() {
+        let mut d = 0.0;
+        for e in graph.edge_range(u) {
+            let v = graph.neighbors[e];
+            if dist[v] == dist[u] + 1 {
+                let share = sigma[u] / sigma[v] * (1.0 + delta[v]);
+                d += share;
+                if let Some(edge_acc) = edge_acc.as_deref_mut() {
+                    edge_acc[e] += share;
+                }
+            }
+        }
+        delta[u] = d;
+        if u != source {
+            acc[u] += d;
+        }
+    }
+}
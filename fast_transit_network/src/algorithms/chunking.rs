@@ -0,0 +1,110 @@
+use crate::graph::graph::Graph;
+use std::ops::Range;
+
+/// Chunks per worker thread for loops without per-chunk allocations, so work stealing can even out
+/// whatever imbalance the degree-based split leaves.
+pub const CHUNKS_PER_THREAD: usize = 8;
+
+/// Splits the nodes of a CSR graph into at most `num_chunks` contiguous, non-empty ranges of
+/// roughly equal work, where a node costs `1 + out-degree`.
+///
+/// The prefix sums come straight from `offsets`, so this is O(num_chunks · log n). On power-law
+/// graphs a hub gets a range to itself instead of sharing one with thousands of other nodes.
+/// A single node is never split, so one huge hub can still bound the speedup.
+pub fn edge_balanced_ranges(offsets: &[usize], num_chunks: usize) -> Vec<Range<usize>> {
+    let n = offsets.len().saturating_sub(1);
+    balanced_ranges(n, num_chunks, |v| offsets[v] + v)
+}
+
+/// Like [`edge_balanced_ranges`] for an arbitrary node list (e.g. a BFS frontier): the ranges index
+/// into `nodes`. [`hybrid_frontier_chunks`] uses it for the nodes between hubs.
+pub fn frontier_ranges(graph: &Graph, nodes: &[usize], num_chunks: usize) -> Vec<Range<usize>> {
+    let mut prefix = Vec::with_capacity(nodes.len() + 1);
+    prefix.push(0);
+    let mut total = 0;
+    for &u in nodes {
+        total += 1 + graph.out_degree[u];
+        prefix.push(total);
+    }
+    balanced_ranges(nodes.len(), num_chunks, |i| prefix[i])
+}
+
+/// One unit of work from [`hybrid_frontier_chunks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrontierChunk {
+    /// Positions in the frontier whose nodes are expanded whole by one thread; never contains a hub.
+    Nodes(Range<usize>),
+    /// Part of one hub's adjacency: positions `edges` of `graph.neighbors(node)`.
+    Edges { node: usize, edges: Range<usize> },
+}
+
+/// Splits the expansion of a frontier into about `num_chunks` pieces of equal work, where a node
+/// costs `1 + out-degree`.
+///
+/// Unlike [`frontier_ranges`], a hub whose out-degree alone exceeds one piece's share has its
+/// adjacency cut into edge ranges for several threads, so a single hub on a scale-free graph no
+/// longer bounds the level. The runs of other nodes between the hubs are split by
+/// [`frontier_ranges`] into their share of the `num_chunks` pieces.
+pub fn hybrid_frontier_chunks(graph: &Graph, nodes: &[usize], num_chunks: usize) -> Vec<FrontierChunk> {
+    let total: usize = nodes.iter().map(|&u| 1 + graph.out_degree[u]).sum();
+    let grain = (total / num_chunks.max(1)).max(1);
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut work = 0;
+    let push_run = |chunks: &mut Vec<FrontierChunk>, run: Range<usize>, work: usize| {
+        let ranges = frontier_ranges(graph, &nodes[run.clone()], (work * num_chunks).div_ceil(total.max(1)));
+        chunks.extend(ranges.into_iter().map(|r| FrontierChunk::Nodes(run.start + r.start..run.start + r.end)));
+    };
+    for (i, &u) in nodes.iter().enumerate() {
+        let degree = graph.out_degree[u];
+        if degree > grain {
+            push_run(&mut chunks, start..i, work);
+            let size = degree.div_ceil(degree.div_ceil(grain));
+            chunks.extend((0..degree).step_by(size).map(|s| FrontierChunk::Edges { node: u, edges: s..(s + size).min(degree) }));
+            start = i + 1;
+            work = 0;
+        } else {
+            work += 1 + degree;
+        }
+    }
+    push_run(&mut chunks, start..nodes.len(), work);
+    chunks
+}
+
+/// Cuts `0..n` using the non-decreasing cost prefix `prefix(i)` (cost of items `0..i`).
+///
+/// Each cut spreads the cost still left evenly over the chunks still to make, so one oversized
+/// item early on does not leave the later chunks lopsided.
+fn balanced_ranges(n: usize, num_chunks: usize, prefix: impl Fn(usize) -> usize) -> Vec<Range<usize>> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let k = num_chunks.clamp(1, n);
+    let total = prefix(n);
+    let mut bounds = Vec::with_capacity(k + 1);
+    bounds.push(0);
+    for i in 1..k {
+        let done = prefix(*bounds.last().unwrap());
+        let target = done + (total - done) / (k - i + 1);
+        // First cut point whose prefix reaches the target
+        let (mut lo, mut hi) = (*bounds.last().unwrap(), n);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if prefix(mid) < target {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        // Cut before the item that overshoots if that lands closer, so a hub starts its own chunk
+        let last = *bounds.last().unwrap();
+        if lo > last + 1 && target - prefix(lo - 1) < prefix(lo) - target {
+            lo -= 1;
+        }
+        if lo > last && lo < n {
+            bounds.push(lo);
+        }
+    }
+    bounds.push(n);
+    bounds.windows(2).map(|w| w[0]..w[1]).collect()
+}
@@ -0,0 +1,165 @@
+use crate::graph::graph::{build_csr, Graph};
+#[cfg(feature = "parallel")]
+use super::threadpool::get_thread_pool;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+const UNCOLORED: usize = usize::MAX;
+
+/// Output of a coloring run: adjacent items never share a color.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColoringResult {
+    /// Color per node (or per edge for [`edge_coloring`]), in `0..num_colors`.
+    pub colors: Vec<usize>,
+    pub num_colors: usize,
+    /// Jones-Plassmann rounds; 1 for the sequential greedy coloring.
+    pub rounds: usize,
+}
+
+impl ColoringResult {
+    fn from_colors(colors: Vec<usize>, rounds: usize) -> Self {
+        let num_colors = colors.iter().map(|&c| c + 1).max().unwrap_or(0);
+        Self { colors, num_colors, rounds }
+    }
+
+    /// Nodes (or edges) grouped by color, e.g. one group per non-conflicting maintenance window.
+    pub fn classes(&self) -> Vec<Vec<usize>> {
+        let mut classes = vec![Vec::new(); self.num_colors];
+        for (v, &c) in self.colors.iter().enumerate() {
+            classes[c].push(v);
+        }
+        classes
+    }
+}
+
+/// Edges ignoring direction and self-loops, as a symmetric CSR graph.
+fn undirected(graph: &Graph) -> Graph {
+    let edges = graph
+        .edges()
+        .filter(|(u, v)| u != v)
+        .flat_map(|(u, v)| [(u, v), (v, u)])
+        .collect();
+    build_csr(graph.num_nodes, edges)
+}
+
+/// Line graph of the undirected `graph`: one node per edge (in `Graph::neighbors` order), adjacent
+/// when the edges share an endpoint. Has O(Σ degree²) edges.
+fn line_graph(graph: &Graph) -> Graph {
+    let mut incident = vec![Vec::new(); graph.num_nodes];
+    for (e, (u, v)) in graph.edges().enumerate() {
+        incident[u].push(e);
+        if v != u {
+            incident[v].push(e);
+        }
+    }
+    let mut pairs = Vec::new();
+    for edges in &incident {
+        for (i, &a) in edges.iter().enumerate() {
+            for &b in &edges[i + 1..] {
+                pairs.push((a, b));
+                pairs.push((b, a));
+            }
+        }
+    }
+    build_csr(graph.num_edges, pairs)
+}
+
+/// Smallest color not used by any already-colored neighbor of `v`.
+fn smallest_free_color(graph: &Graph, colors: &[usize], v: usize) -> usize {
+    let mut used: Vec<usize> = graph
+        .neighbors(v)
+        .iter()
+        .map(|&w| colors[w])
+        .filter(|&c| c != UNCOLORED)
+        .collect();
+    used.sort_unstable();
+    used.dedup();
+    used.iter().enumerate().find(|&(i, &c)| i != c).map_or(used.len(), |(i, _)| i)
+}
+
+/// Random but deterministic per-node priority (splitmix64), ties broken by node id.
+fn priority(v: usize) -> (u64, usize) {
+    let mut z = (v as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (z ^ (z >> 31), v)
+}
+
+/// Sequential greedy coloring in node-id order (edge direction ignored).
+///
+/// Uses at most `max degree + 1` colors.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn greedy_coloring(graph: &Graph) -> ColoringResult {
+    let sym = undirected(graph);
+    let mut colors = vec![UNCOLORED; sym.num_nodes];
+    for v in 0..sym.num_nodes {
+        colors[v] = smallest_free_color(&sym, &colors, v);
+    }
+    ColoringResult::from_colors(colors, 1)
+}
+
+/// Parallel Jones-Plassmann coloring (edge direction ignored).
+///
+/// Each round, every uncolored node whose random priority beats all its uncolored neighbors
+/// takes the smallest color free among its neighbors. Those nodes form an independent set, so
+/// a round colors them in parallel without conflicts. Deterministic for a given graph.
+pub fn jones_plassmann(graph: &Graph, num_threads: usize) -> ColoringResult {
+    color_symmetric(&undirected(graph), num_threads)
+}
+
+/// Colors the edges so that edges sharing a node (in either direction) get different colors.
+///
+/// `colors[e]` belongs to the edge at `Graph::neighbors` position `e`. Runs Jones-Plassmann on the
+/// line graph, which has O(Σ degree²) edges, so this is meant for sparse networks.
+pub fn edge_coloring(graph: &Graph, num_threads: usize) -> ColoringResult {
+    color_symmetric(&line_graph(graph), num_threads)
+}
+
+/// Maximal independent set: no two chosen nodes are adjacent, and every other node has a chosen neighbor.
+///
+/// These are exactly the nodes Jones-Plassmann gives color 0.
+pub fn maximal_independent_set(graph: &Graph, num_threads: usize) -> Vec<bool> {
+    jones_plassmann(graph, num_threads).colors.iter().map(|&c| c == 0).collect()
+}
+
+/// Returns true if no two adjacent nodes share a color (self-loops ignored).
+pub fn is_proper_coloring(graph: &Graph, colors: &[usize]) -> bool {
+    colors.len() == graph.num_nodes
+        && (0..graph.num_nodes).all(|u| graph.neighbors(u).iter().all(|&v| v == u || colors[u] != colors[v]))
+}
+
+#[tracing::instrument(level = "debug", skip(sym), fields(nodes = sym.num_nodes))]
+fn color_symmetric(sym: &Graph, num_threads: usize) -> ColoringResult {
+    let mut colors = vec![UNCOLORED; sym.num_nodes];
+    let mut remaining: Vec<usize> = (0..sym.num_nodes).collect();
+    let mut rounds = 0;
+
+    while !remaining.is_empty() {
+        let colors_ref = &colors;
+        let select = |&v: &usize| {
+            let p = priority(v);
+            let wins = sym
+                .neighbors(v)
+                .iter()
+                .all(|&w| w == v || colors_ref[w] != UNCOLORED || priority(w) < p);
+            wins.then(|| (v, smallest_free_color(sym, colors_ref, v)))
+        };
+        #[cfg(feature = "parallel")]
+        let selected: Vec<(usize, usize)> =
+            get_thread_pool(num_threads).install(|| remaining.par_iter().filter_map(select).collect());
+        #[cfg(not(feature = "parallel"))]
+        let selected: Vec<(usize, usize)> = {
+            let _ = num_threads;
+            remaining.iter().filter_map(select).collect()
+        };
+
+        for (v, c) in selected {
+            colors[v] = c;
+        }
+        remaining.retain(|&v| colors[v] == UNCOLORED);
+        rounds += 1;
+    }
+
+    tracing::debug!(rounds, "jones-plassmann finished");
+    ColoringResult::from_colors(colors, rounds)
+}
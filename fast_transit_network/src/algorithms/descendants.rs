@@ -0,0 +1,234 @@
+use crate::error::{Error, Result};
+use crate::graph::graph::Graph;
+use crate::utils::rng::Rng;
+use super::scc::strongly_connected_components;
+#[cfg(feature = "parallel")]
+use super::threadpool::get_thread_pool;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+/// [`DescendantMethod::Auto`] counts exactly in one pass up to this many nodes (32 MiB of bits).
+const EXACT_MAX_NODES: usize = 1 << 14;
+/// [`DescendantMethod::Auto`] counts exactly in blocks up to this many nodes, and samples above.
+const BLOCKED_MAX_NODES: usize = 1 << 18;
+
+/// How [`count_descendants`] counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DescendantMethod {
+    /// Exact, blocked or sampled by node count.
+    #[default]
+    Auto,
+    /// One bitset of all nodes per node: exact, `n^2 / 8` bytes.
+    Exact,
+    /// The nodes split into blocks of targets, one pass over the edges per block (blocks run in
+    /// parallel): exact, `O(m * n / 64)` time.
+    Blocked,
+    /// Counts only `samples` random targets and scales up: an unbiased estimate.
+    Sampled,
+}
+
+impl FromStr for DescendantMethod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "auto" => Ok(DescendantMethod::Auto),
+            "exact" => Ok(DescendantMethod::Exact),
+            "blocked" => Ok(DescendantMethod::Blocked),
+            "sampled" => Ok(DescendantMethod::Sampled),
+            _ => Err(anyhow::anyhow!(
+                "Invalid descendant method: {}. Use 'auto', 'exact', 'blocked' or 'sampled'",
+                s
+            )),
+        }
+    }
+}
+
+/// Parameters for [`count_descendants`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DescendantConfig {
+    pub method: DescendantMethod,
+    /// Target nodes for [`DescendantMethod::Sampled`].
+    pub samples: usize,
+    pub seed: u64,
+    /// Bitset memory of one block (per thread) for the blocked and sampled passes, which sets how
+    /// many targets one pass over the edges covers.
+    pub block_bytes: usize,
+    pub num_threads: usize,
+}
+
+impl Default for DescendantConfig {
+    fn default() -> Self {
+        Self {
+            method: DescendantMethod::Auto,
+            samples: 1024,
+            seed: 42,
+            block_bytes: 64 << 20,
+            num_threads: 4,
+        }
+    }
+}
+
+/// Output of [`count_descendants`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DescendantCounts {
+    /// Nodes reachable from each node, not counting itself (rounded estimates if sampled).
+    pub counts: Vec<u64>,
+    /// The method that ran (never [`DescendantMethod::Auto`]).
+    pub method: DescendantMethod,
+    /// Target nodes counted: every node unless sampled.
+    pub targets: usize,
+}
+
+impl DescendantCounts {
+    pub fn is_exact(&self) -> bool {
+        self.method != DescendantMethod::Sampled
+    }
+
+    /// The `k` nodes with the most descendants, most first (ties by id).
+    pub fn top(&self, k: usize) -> Vec<(usize, u64)> {
+        let mut nodes: Vec<(usize, u64)> = self.counts.iter().copied().enumerate().collect();
+        nodes.sort_unstable_by_key(|&(v, count)| (std::cmp::Reverse(count), v));
+        nodes.truncate(k);
+        nodes
+    }
+}
+
+/// Number of nodes reachable from every node of a DAG (its descendants), e.g. how many downstream
+/// events a delay at a timetable node can reach.
+///
+/// Each node's set of reachable targets is a bitset, filled in reverse topological order as the
+/// union of its successors' sets; only the counts are kept. Self-loops are ignored; any other cycle
+/// returns [`Error::Cycle`].
+#[tracing::instrument(level = "debug", skip_all, fields(nodes = graph.num_nodes, method = ?config.method))]
+pub fn count_descendants(graph: &Graph, config: &DescendantConfig) -> Result<DescendantCounts> {
+    let n = graph.num_nodes;
+    let order = topological_order(graph)?;
+    let method = match config.method {
+        DescendantMethod::Auto if n <= EXACT_MAX_NODES => DescendantMethod::Exact,
+        DescendantMethod::Auto if n <= BLOCKED_MAX_NODES => DescendantMethod::Blocked,
+        DescendantMethod::Auto => DescendantMethod::Sampled,
+        method => method,
+    };
+    let block_bits = |targets: usize| 64 * (config.block_bytes / (8 * n.max(1))).clamp(1, targets.div_ceil(64).max(1));
+
+    let counts = match method {
+        DescendantMethod::Exact => count_in_blocks(graph, &order, n, |v| v, n.max(1), 1),
+        DescendantMethod::Blocked => count_in_blocks(graph, &order, n, |v| v, block_bits(n), config.num_threads),
+        _ => {
+            let samples = config.samples.max(1).min(n);
+            // Target index of each sampled node; usize::MAX for the rest
+            let mut slot = vec![usize::MAX; n];
+            let mut rng = Rng::new(config.seed);
+            let mut chosen = 0;
+            while chosen < samples {
+                let v = rng.below(n);
+                if slot[v] == usize::MAX {
+                    slot[v] = chosen;
+                    chosen += 1;
+                }
+            }
+            let hits = count_in_blocks(graph, &order, chosen, |v| slot[v], block_bits(chosen), config.num_threads);
+            let scale = n as f64 / chosen.max(1) as f64;
+            let counts = hits.into_iter().map(|h| (h as f64 * scale).round() as u64).collect();
+            return Ok(DescendantCounts { counts, method: DescendantMethod::Sampled, targets: chosen });
+        }
+    };
+    Ok(DescendantCounts { counts, method, targets: n })
+}
+
+/// Topological order by Kahn's algorithm, ignoring self-loops.
+fn topological_order(graph: &Graph) -> Result<Vec<usize>> {
+    let n = graph.num_nodes;
+    let mut in_degree = vec![0usize; n];
+    for (_, v) in graph.edges().filter(|(u, v)| u != v) {
+        in_degree[v] += 1;
+    }
+    let mut queue: VecDeque<usize> = (0..n).filter(|&v| in_degree[v] == 0).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(u) = queue.pop_front() {
+        order.push(u);
+        for &v in graph.neighbors(u) {
+            if v != u {
+                in_degree[v] -= 1;
+                if in_degree[v] == 0 {
+                    queue.push_back(v);
+                }
+            }
+        }
+    }
+    if order.len() < n {
+        // Some strongly connected component has more than one node; name one of them
+        let scc = strongly_connected_components(graph);
+        let sizes = scc.sizes();
+        let node = (0..n).find(|&v| sizes[scc.component[v]] > 1).unwrap_or(0);
+        return Err(Error::Cycle { node });
+    }
+    Ok(order)
+}
+
+/// Per node, how many of the targets `0..num_targets` it reaches, where `index(v)` is the target
+/// index of node `v` (`usize::MAX` if it is not a target). Targets are taken `block_bits` at a time.
+fn count_in_blocks(
+    graph: &Graph,
+    order: &[usize],
+    num_targets: usize,
+    index: impl Fn(usize) -> usize + Sync,
+    block_bits: usize,
+    num_threads: usize,
+) -> Vec<u64> {
+    let n = graph.num_nodes;
+    let blocks: Vec<(usize, usize)> = (0..num_targets).step_by(block_bits).map(|s| (s, (s + block_bits).min(num_targets))).collect();
+    let run = |(counts, bits, row): &mut (Vec<u64>, Vec<u64>, Vec<u64>), &(start, end): &(usize, usize)| {
+        let words = (end - start).div_ceil(64);
+        bits.clear();
+        bits.resize(n * words, 0);
+        row.resize(words, 0);
+        for &u in order.iter().rev() {
+            row.fill(0);
+            for &v in graph.neighbors(u) {
+                if v == u {
+                    continue;
+                }
+                for (r, &b) in row.iter_mut().zip(&bits[v * words..(v + 1) * words]) {
+                    *r |= b;
+                }
+                let i = index(v);
+                if (start..end).contains(&i) {
+                    row[(i - start) / 64] |= 1 << ((i - start) % 64);
+                }
+            }
+            counts[u] += row.iter().map(|w| u64::from(w.count_ones())).sum::<u64>();
+            bits[u * words..(u + 1) * words].copy_from_slice(row);
+        }
+    };
+
+    #[cfg(feature = "parallel")]
+    if num_threads > 1 && blocks.len() > 1 {
+        return get_thread_pool(num_threads).install(|| {
+            blocks
+                .par_iter()
+                .fold(
+                    || (vec![0u64; n], Vec::new(), Vec::new()),
+                    |mut state, block| {
+                        run(&mut state, block);
+                        state
+                    },
+                )
+                .map(|(counts, _, _)| counts)
+                .reduce_with(|mut a, b| {
+                    a.iter_mut().zip(b).for_each(|(x, y)| *x += y);
+                    a
+                })
+                .unwrap_or_else(|| vec![0; n])
+        });
+    }
+    let _ = num_threads;
+    let mut state = (vec![0u64; n], Vec::new(), Vec::new());
+    for block in &blocks {
+        run(&mut state, block);
+    }
+    state.0
+}
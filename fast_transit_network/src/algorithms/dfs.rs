@@ -0,0 +1,177 @@
+use crate::error::{Error, Result};
+use crate::graph::graph::Graph;
+
+/// Kind of an edge `u -> v` relative to a DFS forest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeKind {
+    /// `v` was discovered through this edge.
+    Tree,
+    /// `v` is an ancestor of `u` (or `u` itself): the edge closes a cycle.
+    Back,
+    /// `v` is a descendant of `u` reached earlier through another path.
+    Forward,
+    /// `v` is in an already finished subtree that is neither ancestor nor descendant.
+    Cross,
+}
+
+/// Output of a DFS run.
+///
+/// `discovery` and `finish` share one clock that ticks on every discovery and every finish, so for
+/// visited `u` and `v` the intervals `[discovery, finish]` are either nested (ancestor/descendant) or
+/// disjoint. Unvisited nodes have `usize::MAX` in both.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfsResult {
+    pub discovery: Vec<usize>,
+    pub finish: Vec<usize>,
+    /// DFS-tree parent per node; `usize::MAX` for roots and unvisited nodes.
+    pub parent: Vec<usize>,
+    /// Visited nodes in discovery order.
+    pub preorder: Vec<usize>,
+    /// Visited nodes in finish order.
+    pub postorder: Vec<usize>,
+    /// Kind of every CSR edge; `None` for edges leaving unvisited nodes.
+    pub edge_kinds: Vec<Option<EdgeKind>>,
+}
+
+impl DfsResult {
+    pub fn is_visited(&self, v: usize) -> bool {
+        self.discovery[v] != usize::MAX
+    }
+
+    /// True if `u` is a proper ancestor of `v` in the DFS forest.
+    pub fn is_ancestor(&self, u: usize, v: usize) -> bool {
+        u != v
+            && self.is_visited(u)
+            && self.is_visited(v)
+            && self.discovery[u] < self.discovery[v]
+            && self.finish[v] < self.finish[u]
+    }
+
+    /// Number of edges of each kind, as (tree, back, forward, cross).
+    pub fn edge_counts(&self) -> (usize, usize, usize, usize) {
+        let mut counts = (0, 0, 0, 0);
+        for kind in self.edge_kinds.iter().flatten() {
+            match kind {
+                EdgeKind::Tree => counts.0 += 1,
+                EdgeKind::Back => counts.1 += 1,
+                EdgeKind::Forward => counts.2 += 1,
+                EdgeKind::Cross => counts.3 += 1,
+            }
+        }
+        counts
+    }
+
+    /// True if a back edge was found, i.e. the visited part of the graph has a directed cycle.
+    pub fn has_cycle(&self) -> bool {
+        self.edge_kinds.contains(&Some(EdgeKind::Back))
+    }
+
+    /// Visited nodes in topological order (reverse postorder), or `None` if there is a cycle.
+    pub fn topological_order(&self) -> Option<Vec<usize>> {
+        if self.has_cycle() {
+            return None;
+        }
+        Some(self.postorder.iter().rev().copied().collect())
+    }
+}
+
+/// DFS from `source`, visiting out-neighbors in CSR order; nodes it cannot reach stay unvisited.
+///
+/// Returns [`Error::InvalidNode`] if `source` is out of range.
+#[tracing::instrument(level = "debug", skip(graph))]
+pub fn dfs(graph: &Graph, source: usize) -> Result<DfsResult> {
+    if !graph.is_valid_node(source) {
+        return Err(Error::InvalidNode { node: source, num_nodes: graph.num_nodes });
+    }
+    let mut search = Search::new(graph);
+    search.visit(source);
+    Ok(search.into_result())
+}
+
+/// DFS over the whole graph: every still-unvisited node, in id order, roots a new tree, so every node
+/// and edge is classified.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn dfs_forest(graph: &Graph) -> DfsResult {
+    let mut search = Search::new(graph);
+    for root in 0..graph.num_nodes {
+        if search.discovery[root] == usize::MAX {
+            search.visit(root);
+        }
+    }
+    search.into_result()
+}
+
+struct Search<'a> {
+    graph: &'a Graph,
+    clock: usize,
+    discovery: Vec<usize>,
+    finish: Vec<usize>,
+    parent: Vec<usize>,
+    preorder: Vec<usize>,
+    postorder: Vec<usize>,
+    edge_kinds: Vec<Option<EdgeKind>>,
+    /// Path from the current root: node and its next CSR edge to explore.
+    stack: Vec<(usize, usize)>,
+}
+
+impl<'a> Search<'a> {
+    fn new(graph: &'a Graph) -> Self {
+        let n = graph.num_nodes;
+        Self {
+            graph,
+            clock: 0,
+            discovery: vec![usize::MAX; n],
+            finish: vec![usize::MAX; n],
+            parent: vec![usize::MAX; n],
+            preorder: Vec::new(),
+            postorder: Vec::new(),
+            edge_kinds: vec![None; graph.num_edges],
+            stack: Vec::new(),
+        }
+    }
+
+    fn discover(&mut self, v: usize) {
+        self.discovery[v] = self.clock;
+        self.clock += 1;
+        self.preorder.push(v);
+        self.stack.push((v, self.graph.offsets[v]));
+    }
+
+    /// Iterative DFS from `root`, which must be unvisited.
+    fn visit(&mut self, root: usize) {
+        self.discover(root);
+        while let Some(&(u, e)) = self.stack.last() {
+            if e == self.graph.offsets[u + 1] {
+                self.stack.pop();
+                self.finish[u] = self.clock;
+                self.clock += 1;
+                self.postorder.push(u);
+                continue;
+            }
+            self.stack.last_mut().unwrap().1 += 1;
+            let v = self.graph.neighbors[e];
+            self.edge_kinds[e] = Some(if self.discovery[v] == usize::MAX {
+                self.parent[v] = u;
+                self.discover(v);
+                EdgeKind::Tree
+            } else if self.finish[v] == usize::MAX {
+                EdgeKind::Back
+            } else if self.discovery[u] < self.discovery[v] {
+                EdgeKind::Forward
+            } else {
+                EdgeKind::Cross
+            });
+        }
+    }
+
+    fn into_result(self) -> DfsResult {
+        DfsResult {
+            discovery: self.discovery,
+            finish: self.finish,
+            parent: self.parent,
+            preorder: self.preorder,
+            postorder: self.postorder,
+            edge_kinds: self.edge_kinds,
+        }
+    }
+}
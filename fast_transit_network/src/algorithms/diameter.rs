@@ -0,0 +1,133 @@
+use crate::graph::graph::Graph;
+use crate::utils::rng::Rng;
+use super::wcc::wcc_parallel;
+#[cfg(feature = "parallel")]
+use super::threadpool::get_thread_pool;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::VecDeque;
+
+/// Seed of the random sweep starts, so repeated runs give the same bounds.
+const SWEEP_SEED: u64 = 0xD1A3_E7E5;
+
+/// Bounds on the hop diameter from [`approx_diameter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiameterBounds {
+    /// Longest shortest path found; the diameter is at least this.
+    pub lower: usize,
+    /// The diameter is at most this.
+    pub upper: usize,
+    /// Endpoints of a path of length `lower`.
+    pub endpoints: (usize, usize),
+    /// Double sweeps run.
+    pub sweeps: usize,
+}
+
+impl DiameterBounds {
+    /// Whether the bounds meet, i.e. `lower` is the diameter.
+    pub fn is_exact(&self) -> bool {
+        self.lower == self.upper
+    }
+}
+
+/// Lower and upper bounds on the diameter (the longest finite shortest path, in hops, ignoring edge
+/// direction) from `sweeps` double sweeps run in parallel.
+///
+/// A double sweep runs a BFS from a start node, then another from the farthest node found; that
+/// node's eccentricity is a lower bound, and often the diameter itself on road and transit networks.
+/// Half of the starts are the highest-degree nodes, whose small eccentricity `e` gives the upper
+/// bound `2e` for their component; the other half are random. A component without a start is
+/// bounded by its node count minus one.
+#[tracing::instrument(level = "debug", skip_all, fields(nodes = graph.num_nodes, sweeps))]
+pub fn approx_diameter(graph: &Graph, sweeps: usize, num_threads: usize) -> DiameterBounds {
+    let n = graph.num_nodes;
+    let mut bounds = DiameterBounds { lower: 0, upper: 0, endpoints: (0, 0), sweeps: 0 };
+    if n == 0 {
+        return bounds;
+    }
+    let transposed = graph.directed.then(|| graph.transpose());
+    let adjacency = Undirected { graph, transposed: transposed.as_ref() };
+    let labels = wcc_parallel(graph, num_threads).labels;
+    let starts = sweep_starts(graph, sweeps.max(1));
+
+    let sweep = |&start: &usize| {
+        let (far, start_ecc) = adjacency.farthest(start);
+        let (end, ecc) = adjacency.farthest(far);
+        (start, start_ecc, far, end, ecc)
+    };
+    #[cfg(feature = "parallel")]
+    let results: Vec<_> = get_thread_pool(num_threads).install(|| starts.par_iter().map(sweep).collect());
+    #[cfg(not(feature = "parallel"))]
+    let results: Vec<_> = starts.iter().map(sweep).collect();
+
+    // Per component (labels are node ids): its size, then the best upper bound found for it
+    let mut size = vec![0usize; n];
+    for &label in &labels {
+        size[label] += 1;
+    }
+    let mut component_upper: Vec<usize> = size.iter().map(|&s| s.saturating_sub(1)).collect();
+    for &(start, start_ecc, far, end, ecc) in &results {
+        if ecc > bounds.lower || bounds.sweeps == 0 {
+            bounds.lower = ecc;
+            bounds.endpoints = (far, end);
+        }
+        let upper = &mut component_upper[labels[start]];
+        *upper = (*upper).min(2 * start_ecc).min(2 * ecc);
+        bounds.sweeps += 1;
+    }
+    bounds.upper = (0..n).filter(|&v| size[v] > 0).map(|v| component_upper[v]).max().unwrap_or(0).max(bounds.lower);
+    bounds
+}
+
+/// The `sweeps` start nodes: the highest-degree half first, then distinct random nodes.
+fn sweep_starts(graph: &Graph, sweeps: usize) -> Vec<usize> {
+    let n = graph.num_nodes;
+    let sweeps = sweeps.min(n);
+    let mut by_degree: Vec<usize> = (0..n).collect();
+    let hubs = sweeps.div_ceil(2);
+    if hubs < n {
+        by_degree.select_nth_unstable_by_key(hubs, |&v| std::cmp::Reverse(graph.out_degree[v]));
+    }
+    let mut starts = by_degree[..hubs].to_vec();
+    let mut chosen = vec![false; n];
+    starts.iter().for_each(|&v| chosen[v] = true);
+    let mut rng = Rng::new(SWEEP_SEED);
+    while starts.len() < sweeps {
+        let v = rng.below(n);
+        if !chosen[v] {
+            chosen[v] = true;
+            starts.push(v);
+        }
+    }
+    starts
+}
+
+/// Out- and in-neighbors of a directed graph (out-neighbors only if it is already undirected).
+struct Undirected<'a> {
+    graph: &'a Graph,
+    transposed: Option<&'a Graph>,
+}
+
+impl Undirected<'_> {
+    /// The node farthest from `source` (lowest id among ties) and its distance.
+    fn farthest(&self, source: usize) -> (usize, usize) {
+        let mut dist = vec![usize::MAX; self.graph.num_nodes];
+        let mut queue = VecDeque::from([source]);
+        dist[source] = 0;
+        let mut farthest = (source, 0);
+        while let Some(u) = queue.pop_front() {
+            let d = dist[u];
+            if d > farthest.1 || (d == farthest.1 && u < farthest.0) {
+                farthest = (u, d);
+            }
+            let backward = self.transposed.map_or(&[][..], |t| t.neighbors(u));
+            for &v in self.graph.neighbors(u).iter().chain(backward) {
+                if dist[v] == usize::MAX {
+                    dist[v] = d + 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+        farthest
+    }
+}
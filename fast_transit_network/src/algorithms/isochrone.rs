@@ -0,0 +1,116 @@
+use crate::algorithms::sssp::find_negative_edge;
+use crate::error::{Error, Result};
+use crate::graph::graph::Graph;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Nodes reachable from a source within a cost budget.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Isochrone {
+    pub source: usize,
+    pub max_cost: f64,
+    /// Reachable nodes (source included) with their cheapest cost, in order of increasing cost.
+    pub nodes: Vec<(usize, f64)>,
+    /// Edges `u -> v` with `u` inside the isochrone and `v` outside it.
+    pub boundary_edges: Vec<(usize, usize)>,
+}
+
+impl Isochrone {
+    /// Number of reachable nodes, including the source.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, v: usize) -> bool {
+        self.nodes.iter().any(|&(u, _)| u == v)
+    }
+}
+
+/// Min-heap entry (cost, node) for the budgeted Dijkstra.
+struct Entry(f64, usize);
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Entry {}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0)
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Returns every node whose cheapest path from `source` costs at most `max_cost`.
+///
+/// Unweighted graphs count hops (a level-limited BFS); weighted graphs run Dijkstra that stops
+/// at the budget, so only the isochrone and its boundary are ever touched. Returns
+/// [`Error::InvalidNode`] for a bad source and [`Error::NegativeWeight`] for negative weights.
+#[tracing::instrument(level = "debug", skip(graph))]
+pub fn isochrone(graph: &Graph, source: usize, max_cost: f64) -> Result<Isochrone> {
+    if !graph.is_valid_node(source) {
+        return Err(Error::InvalidNode { node: source, num_nodes: graph.num_nodes });
+    }
+    if let Some((from, to)) = find_negative_edge(graph) {
+        return Err(Error::NegativeWeight { from, to });
+    }
+
+    let mut cost = vec![f64::INFINITY; graph.num_nodes];
+    let mut nodes = Vec::new();
+    if max_cost >= 0.0 {
+        cost[source] = 0.0;
+        if graph.is_weighted() {
+            let mut heap = BinaryHeap::new();
+            heap.push(Entry(0.0, source));
+            while let Some(Entry(d, u)) = heap.pop() {
+                if d > cost[u] {
+                    continue;
+                }
+                nodes.push((u, d));
+                for (v, w) in graph.weighted_neighbors(u) {
+                    let candidate = d + w;
+                    if candidate <= max_cost && candidate < cost[v] {
+                        cost[v] = candidate;
+                        heap.push(Entry(candidate, v));
+                    }
+                }
+            }
+        } else {
+            nodes.push((source, 0.0));
+            let mut head = 0;
+            while head < nodes.len() {
+                let (u, d) = nodes[head];
+                head += 1;
+                if d + 1.0 > max_cost {
+                    continue;
+                }
+                for &v in graph.neighbors(u) {
+                    if cost[v].is_infinite() {
+                        cost[v] = d + 1.0;
+                        nodes.push((v, d + 1.0));
+                    }
+                }
+            }
+        }
+    }
+
+    let boundary_edges = nodes
+        .iter()
+        .flat_map(|&(u, _)| graph.neighbors(u).iter().map(move |&v| (u, v)))
+        .filter(|&(_, v)| cost[v].is_infinite())
+        .collect();
+
+    Ok(Isochrone { source, max_cost, nodes, boundary_edges })
+}
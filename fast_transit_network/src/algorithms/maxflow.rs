@@ -0,0 +1,181 @@
+use crate::error::{Error, Result};
+use crate::graph::graph::Graph;
+use super::sssp::find_negative_edge;
+use std::collections::VecDeque;
+
+/// Residual capacities at or below this are treated as saturated, absorbing float rounding.
+const EPSILON: f64 = 1e-12;
+
+/// Output of a maximum-flow run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaxFlowResult {
+    pub source: usize,
+    pub sink: usize,
+    /// Total flow from source to sink, equal to the capacity of the minimum cut.
+    pub value: f64,
+    /// Flow on each graph edge, aligned with `Graph::neighbors`.
+    pub edge_flows: Vec<f64>,
+    /// `true` for nodes on the source side of the minimum cut.
+    pub source_side: Vec<bool>,
+}
+
+impl MaxFlowResult {
+    /// Edges `(from, to, capacity)` crossing the minimum cut from the source side to the sink side.
+    pub fn min_cut_edges(&self, graph: &Graph) -> Vec<(usize, usize, f64)> {
+        (0..graph.num_nodes)
+            .filter(|&u| self.source_side[u])
+            .flat_map(|u| graph.weighted_neighbors(u).map(move |(v, c)| (u, v, c)))
+            .filter(|&(_, v, _)| !self.source_side[v])
+            .collect()
+    }
+}
+
+/// Residual graph in CSR form; edge `e ^ 1` is the reverse of edge `e`.
+struct Residual {
+    offsets: Vec<usize>,
+    /// Residual edge ids grouped by tail node.
+    order: Vec<usize>,
+    to: Vec<usize>,
+    capacity: Vec<f64>,
+}
+
+impl Residual {
+    fn new(graph: &Graph) -> Self {
+        let n = graph.num_nodes;
+        let mut to = Vec::with_capacity(2 * graph.num_edges);
+        let mut capacity = Vec::with_capacity(2 * graph.num_edges);
+        let mut tail = Vec::with_capacity(2 * graph.num_edges);
+        for u in 0..n {
+            for (v, c) in graph.weighted_neighbors(u) {
+                to.extend([v, u]);
+                capacity.extend([c, 0.0]);
+                tail.extend([u, v]);
+            }
+        }
+
+        let mut offsets = vec![0; n + 1];
+        for &u in &tail {
+            offsets[u + 1] += 1;
+        }
+        for i in 0..n {
+            offsets[i + 1] += offsets[i];
+        }
+        let mut cursor = offsets.clone();
+        let mut order = vec![0; tail.len()];
+        for (e, &u) in tail.iter().enumerate() {
+            order[cursor[u]] = e;
+            cursor[u] += 1;
+        }
+        Self { offsets, order, to, capacity }
+    }
+
+    fn edges(&self, u: usize) -> &[usize] {
+        &self.order[self.offsets[u]..self.offsets[u + 1]]
+    }
+
+    /// BFS levels over edges with spare capacity; `usize::MAX` marks unreached nodes.
+    fn levels(&self, source: usize) -> Vec<usize> {
+        let mut level = vec![usize::MAX; self.offsets.len() - 1];
+        let mut queue = VecDeque::from([source]);
+        level[source] = 0;
+        while let Some(u) = queue.pop_front() {
+            for &e in self.edges(u) {
+                let v = self.to[e];
+                if self.capacity[e] > EPSILON && level[v] == usize::MAX {
+                    level[v] = level[u] + 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+        level
+    }
+
+    /// Pushes a blocking flow through the level graph and returns its value.
+    ///
+    /// Iterative DFS with per-node edge cursors, so deep graphs cannot overflow the stack.
+    fn blocking_flow(&mut self, source: usize, sink: usize, level: &mut [usize]) -> f64 {
+        let mut cursor: Vec<usize> = self.offsets[..self.offsets.len() - 1].to_vec();
+        let mut path: Vec<usize> = Vec::new();
+        let mut total = 0.0;
+        let mut u = source;
+
+        loop {
+            if u == sink {
+                let bottleneck = path.iter().map(|&e| self.capacity[e]).fold(f64::INFINITY, f64::min);
+                for &e in &path {
+                    self.capacity[e] -= bottleneck;
+                    self.capacity[e ^ 1] += bottleneck;
+                }
+                total += bottleneck;
+                // Retreat to the tail of the first saturated edge
+                let saturated = path.iter().position(|&e| self.capacity[e] <= EPSILON).unwrap_or(0);
+                path.truncate(saturated);
+                u = path.last().map_or(source, |&e| self.to[e]);
+                continue;
+            }
+
+            let end = self.offsets[u + 1];
+            while cursor[u] < end {
+                let e = self.order[cursor[u]];
+                let v = self.to[e];
+                if self.capacity[e] > EPSILON && level[v] == level[u].wrapping_add(1) {
+                    break;
+                }
+                cursor[u] += 1;
+            }
+
+            if cursor[u] < end {
+                let e = self.order[cursor[u]];
+                path.push(e);
+                u = self.to[e];
+            } else {
+                // Dead end: drop `u` from the level graph and step back
+                level[u] = usize::MAX;
+                match path.pop() {
+                    Some(e) => {
+                        u = self.to[e ^ 1];
+                        cursor[u] += 1;
+                    }
+                    None => return total,
+                }
+            }
+        }
+    }
+}
+
+/// Maximum flow from `source` to `sink` with Dinic's algorithm, O(n²·m).
+///
+/// Edge weights are capacities (1 per edge if unweighted); parallel edges add up. Returns
+/// [`Error::NegativeWeight`] for negative capacities and [`Error::InvalidNode`] for out-of-range
+/// terminals. The result also carries the minimum cut.
+#[tracing::instrument(level = "debug", skip(graph))]
+pub fn dinic(graph: &Graph, source: usize, sink: usize) -> Result<MaxFlowResult> {
+    for node in [source, sink] {
+        if !graph.is_valid_node(node) {
+            return Err(Error::InvalidNode { node, num_nodes: graph.num_nodes });
+        }
+    }
+    if let Some((from, to)) = find_negative_edge(graph) {
+        return Err(Error::NegativeWeight { from, to });
+    }
+
+    let mut residual = Residual::new(graph);
+    let mut value = 0.0;
+    let mut phases = 0;
+    if source != sink {
+        loop {
+            let mut level = residual.levels(source);
+            if level[sink] == usize::MAX {
+                break;
+            }
+            value += residual.blocking_flow(source, sink, &mut level);
+            phases += 1;
+        }
+    }
+    tracing::debug!(phases, value, "dinic finished");
+
+    let reached = residual.levels(source);
+    let source_side = reached.iter().map(|&l| l != usize::MAX).collect();
+    let edge_flows = (0..graph.num_edges).map(|i| residual.capacity[2 * i + 1]).collect();
+    Ok(MaxFlowResult { source, sink, value, edge_flows, source_side })
+}
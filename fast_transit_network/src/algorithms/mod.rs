@@ -1,6 +1,27 @@
 pub mod bfs;
+pub mod dfs;
+pub mod chunking;
+#[cfg(feature = "parallel")]
 pub mod threadpool;
 pub mod union_find;
 pub mod atomic_union_find;
 pub mod wcc;
-pub mod pagerank;
\ No newline at end of file
+pub mod pagerank;
+pub mod pagerank_incremental;
+pub mod sssp;
+pub mod isochrone;
+pub mod route;
+pub mod apsp;
+pub mod alt;
+pub mod scc;
+pub mod bridges;
+pub mod reachability;
+pub mod maxflow;
+pub mod coloring;
+pub mod centrality;
+pub mod diameter;
+pub mod anf;
+pub mod triangles;
+pub mod descendants;
+pub mod robustness;
+pub mod random_walk;
\ No newline at end of file
@@ -1,12 +1,47 @@
-use crate::graph::graph::Graph;
-use crate::utils::io::{write_pagerank_result, write_pagerank_stats, write_pagerank_top_nodes};
-use anyhow::Result;
+use crate::analysis::Component;
+use crate::config::{Config, ParallelismPolicy};
+#[cfg(feature = "fs")]
+use crate::config::Mode;
+use crate::error::{Error, Result};
+use crate::graph::graph::{Graph, GraphLike};
+#[cfg(feature = "fs")]
+use crate::cache::ResultCache;
+#[cfg(feature = "fs")]
+use crate::graph::compact::GraphStorage;
+#[cfg(feature = "fs")]
+use crate::graph::reorder::Permutation;
+use crate::utils::simd::l1_distance;
+#[cfg(feature = "parallel")]
+use crate::utils::simd::add_offset;
+use crate::progress::{ProgressEvent, SharedProgress};
+#[cfg(feature = "fs")]
+use crate::utils::io::{
+    self, derived_path, derived_path_with_extension, write_convergence_csv, write_pagerank_result_as, write_pagerank_stats, write_pagerank_top_nodes,
+    OutputFormat,
+};
+#[cfg(feature = "parallel")]
+use super::threadpool::get_thread_pool;
+#[cfg(feature = "parallel")]
+use super::chunking::edge_balanced_ranges;
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+#[derive(Clone)]
 pub struct PageRankConfig {
     pub alpha: f64,
     pub max_iterations: usize,
     pub tolerance: f64,
+    /// Receives per-iteration and convergence events; `None` keeps the run silent.
+    pub progress: Option<SharedProgress>,
+    /// Periodically saves the rank vector so a long run can be resumed (needs the `fs` feature).
+    pub checkpoint: Option<Checkpointing>,
+    /// Continues from a saved checkpoint instead of the uniform start vector; iterations are counted
+    /// from the checkpoint's. Panics if its rank vector does not match the graph's node count.
+    pub resume: Option<PageRankCheckpoint>,
+    /// Splits a node's rank over its out-edges in proportion to their weights (e.g. passenger volumes)
+    /// instead of evenly. Negative and non-finite weights count as 0, and a node whose out-edges all
+    /// weigh 0 is treated as dangling. Has no effect on unweighted graphs.
+    pub weighted: bool,
 }
 
 impl Default for PageRankConfig {
@@ -15,112 +50,381 @@ impl Default for PageRankConfig {
             alpha: 0.85,
             max_iterations: 100,
             tolerance: 1e-6,
+            progress: None,
+            checkpoint: None,
+            resume: None,
+            weighted: false,
         }
     }
 }
 
-pub fn pagerank_sequential(graph: &Graph, config: &PageRankConfig) -> Vec<f64> {
-    let n = graph.num_nodes;
+impl PageRankConfig {
+    fn report(&self, event: &ProgressEvent) {
+        if let Some(progress) = &self.progress {
+            progress.event(event);
+        }
+    }
+
+    /// Reports the end of iteration `iteration` (0-based); returns true if `delta` is below tolerance.
+    fn finish_iteration(&self, iteration: usize, delta: f64) -> bool {
+        self.report(&ProgressEvent::PageRankIteration {
+            iteration: iteration + 1,
+            delta,
+        });
+
+        let converged = delta < self.tolerance;
+        if converged || iteration + 1 == self.max_iterations {
+            self.report(&ProgressEvent::PageRankFinished {
+                iterations: iteration + 1,
+                delta,
+                converged,
+            });
+        }
+        converged
+    }
+
+    /// Total usable out-weight per node when running weighted.
+    fn out_weights<G: GraphLike>(&self, graph: &G) -> Option<Vec<f64>> {
+        self.weighted.then(|| {
+            (0..graph.node_count())
+                .map(|u| graph.weighted_neighbor_iter(u).map(|(_, w)| rank_weight(w)).sum())
+                .collect()
+        })
+    }
+
+    /// Start vector and number of iterations already done.
+    fn start(&self, n: usize) -> (Vec<f64>, usize, f64) {
+        match &self.resume {
+            Some(saved) => {
+                assert_eq!(saved.ranks.len(), n, "PageRank checkpoint holds {} ranks, graph has {} nodes", saved.ranks.len(), n);
+                tracing::info!(iteration = saved.iteration, "starting PageRank from a saved rank vector");
+                (saved.ranks.clone(), saved.iteration, saved.delta)
+            }
+            None => (vec![1.0 / n as f64; n], 0, f64::INFINITY),
+        }
+    }
+
+    /// Saves `ranks` if `iterations` completed iterations fall on the checkpoint interval. A failed write
+    /// only logs a warning: losing a checkpoint is better than aborting the run.
+    fn save_checkpoint(&self, iterations: usize, delta: f64, ranks: &[f64]) {
+        #[cfg(feature = "fs")]
+        if let Some(checkpoint) = &self.checkpoint
+            && checkpoint.every > 0
+            && iterations.is_multiple_of(checkpoint.every)
+        {
+            match write_checkpoint(&checkpoint.path, iterations, delta, ranks) {
+                Ok(()) => tracing::debug!(iterations, path = %checkpoint.path, "saved PageRank checkpoint"),
+                Err(e) => tracing::warn!(iterations, path = %checkpoint.path, "failed to save PageRank checkpoint: {}", e),
+            }
+        }
+        #[cfg(not(feature = "fs"))]
+        let _ = (iterations, delta, ranks);
+    }
+}
+
+/// Share of an edge weight in weighted PageRank; unusable weights carry no rank.
+fn rank_weight(w: f64) -> f64 {
+    if w.is_finite() && w > 0.0 { w } else { 0.0 }
+}
+
+const CHECKPOINT_MAGIC: &[u8; 8] = b"FTNPRCK1";
+
+/// Where and how often [`PageRankConfig::checkpoint`] saves the rank vector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpointing {
+    pub path: String,
+    /// Save after every `every` iterations; 0 never saves.
+    pub every: usize,
+}
+
+/// Rank vector saved in the middle of a PageRank run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageRankCheckpoint {
+    /// Iterations completed when the ranks were saved.
+    pub iteration: usize,
+    /// L1 change of the last completed iteration.
+    pub delta: f64,
+    pub ranks: Vec<f64>,
+}
+
+impl PageRankCheckpoint {
+    /// Writes the checkpoint as `FTNPRCK1`, node count and iteration (u64 LE), delta, then the ranks
+    /// (f64 LE). The file is written next to `path` and renamed over it, so a crash mid-write keeps
+    /// the previous checkpoint.
+    #[cfg(feature = "fs")]
+    pub fn write_binary(&self, path: &str) -> std::io::Result<()> {
+        write_checkpoint(path, self.iteration, self.delta, &self.ranks)
+    }
+
+    /// Reads a checkpoint written by [`PageRankCheckpoint::write_binary`].
+    #[cfg(feature = "fs")]
+    pub fn read_binary(path: &str) -> Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Parses the binary checkpoint format from memory.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let header = CHECKPOINT_MAGIC.len() + 24;
+        if bytes.len() < header || &bytes[..CHECKPOINT_MAGIC.len()] != CHECKPOINT_MAGIC {
+            return Err(Error::parse(1, "not a PageRank checkpoint"));
+        }
+        let word = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+        let n = word(CHECKPOINT_MAGIC.len()) as usize;
+        let iteration = word(CHECKPOINT_MAGIC.len() + 8) as usize;
+        let delta = f64::from_bits(word(CHECKPOINT_MAGIC.len() + 16));
+        let body = &bytes[header..];
+        if n.checked_mul(8) != Some(body.len()) {
+            return Err(Error::parse(1, format!("expected {} ranks, checkpoint holds {} bytes", n, body.len())));
+        }
+        let ranks = body.chunks_exact(8).map(|c| f64::from_le_bytes(c.try_into().unwrap())).collect();
+        Ok(Self { iteration, delta, ranks })
+    }
+
+    /// Checks that the checkpoint was taken on a graph with `num_nodes` nodes.
+    pub fn check_nodes(&self, num_nodes: usize) -> Result<()> {
+        if self.ranks.len() != num_nodes {
+            return Err(Error::AttributeLength {
+                name: "checkpoint".to_string(),
+                expected: num_nodes,
+                actual: self.ranks.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "fs")]
+fn write_checkpoint(path: &str, iteration: usize, delta: f64, ranks: &[f64]) -> std::io::Result<()> {
+    use std::io::Write;
+    let tmp = format!("{}.tmp", path);
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(&tmp)?);
+    writer.write_all(CHECKPOINT_MAGIC)?;
+    writer.write_all(&(ranks.len() as u64).to_le_bytes())?;
+    writer.write_all(&(iteration as u64).to_le_bytes())?;
+    writer.write_all(&delta.to_le_bytes())?;
+    for r in ranks {
+        writer.write_all(&r.to_le_bytes())?;
+    }
+    writer.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    std::fs::rename(&tmp, path)
+}
+
+/// Output of a PageRank run.
+#[derive(Debug, Clone)]
+pub struct PageRankResult {
+    /// Rank per node; sums to ~1.0.
+    pub ranks: Vec<f64>,
+    /// Number of power iterations performed.
+    pub iterations: usize,
+    /// True if the L1 change dropped below the tolerance before `max_iterations`.
+    pub converged: bool,
+    /// L1 change of the last iteration (infinite if no iteration ran).
+    pub final_delta: f64,
+    /// L1 change of every iteration this run performed, in order. After a resume it starts at
+    /// iteration `iterations - history.len() + 1`; push-based results leave it empty.
+    pub history: Vec<f64>,
+}
+
+impl PageRankResult {
+    fn empty() -> Self {
+        Self {
+            ranks: Vec::new(),
+            iterations: 0,
+            converged: true,
+            final_delta: 0.0,
+            history: Vec::new(),
+        }
+    }
+}
+
+/// Sequential PageRank (power iteration); dangling nodes spread their rank uniformly.
+///
+/// Works on any [`GraphLike`], including filtered views. With [`PageRankConfig::weighted`], rank
+/// follows the weights reported by [`GraphLike::weighted_neighbor_iter`].
+#[tracing::instrument(level = "debug", skip_all, fields(alpha = config.alpha))]
+pub fn pagerank_sequential<G: GraphLike>(graph: &G, config: &PageRankConfig) -> PageRankResult {
+    let n = graph.node_count();
     if n == 0 {
-        return vec![];
+        return PageRankResult::empty();
     }
 
-    let initial_value = 1.0 / n as f64;
-    let mut rank = vec![initial_value; n];
+    let (mut rank, done, mut final_delta) = config.start(n);
+    let mut iterations = done;
+    let mut history = Vec::new();
     let mut new_rank = vec![0.0; n];
     let teleport = (1.0 - config.alpha) / n as f64;
+    let out_weights = config.out_weights(graph);
+    let mut converged = false;
 
-    for iteration in 0..config.max_iterations {
+    for iteration in done..config.max_iterations {
         new_rank.fill(teleport);
-
-        for u in 0..n {
-            let neighbors = graph.neighbors(u);
-
-            if neighbors.is_empty() {
-                let contribution = config.alpha * rank[u] / n as f64;
-                for v in 0..n {
-                    new_rank[v] += contribution;
+        let mut sink_sum = 0.0;
+
+        for (u, &r) in rank.iter().enumerate() {
+            match &out_weights {
+                Some(out_weights) if out_weights[u] > 0.0 => {
+                    let scale = config.alpha * r / out_weights[u];
+                    for (v, w) in graph.weighted_neighbor_iter(u) {
+                        new_rank[v] += scale * rank_weight(w);
+                    }
                 }
-            } else {
-                let contribution = config.alpha * rank[u] / neighbors.len() as f64;
-                for &v in neighbors {
-                    new_rank[v] += contribution;
+                Some(_) => sink_sum += r,
+                None => {
+                    let degree = graph.degree(u);
+
+                    if degree == 0 {
+                        sink_sum += r;
+                    } else {
+                        let contribution = config.alpha * r / degree as f64;
+                        for v in graph.neighbor_iter(u) {
+                            new_rank[v] += contribution;
+                        }
+                    }
                 }
             }
         }
 
-        let delta: f64 = rank
-            .iter()
-            .zip(new_rank.iter())
-            .map(|(old, new)| (old - new).abs())
-            .sum();
+        if sink_sum > 0.0 {
+            let contribution = config.alpha * sink_sum / n as f64;
+            for r in new_rank.iter_mut() {
+                *r += contribution;
+            }
+        }
+
+        let delta = l1_distance(&rank, &new_rank);
 
         std::mem::swap(&mut rank, &mut new_rank);
 
-        if delta < config.tolerance {
-            println!(
-                "PageRank converged after {} iterations (delta: {:.2e})",
-                iteration + 1,
-                delta
-            );
+        iterations = iteration + 1;
+        final_delta = delta;
+        history.push(delta);
+        if config.finish_iteration(iteration, delta) {
+            converged = true;
             break;
         }
+        config.save_checkpoint(iterations, delta, &rank);
+    }
 
-        if iteration == config.max_iterations - 1 {
-            println!(
-                "PageRank reached max iterations ({}) without full convergence (delta: {:.2e})",
-                config.max_iterations, delta
-            );
-        }
+    PageRankResult {
+        ranks: rank,
+        iterations,
+        converged,
+        final_delta,
+        history,
     }
+}
 
-    rank
+/// PageRank of the largest weakly connected component alone, in `graph`'s node ids with rank 0 for
+/// every node outside it. Each tiny component otherwise keeps its share of the teleport mass, so
+/// isolated pairs rank like hubs; here the component's ranks sum to 1.
+///
+/// Runs the parallel WCC and PageRank with `num_threads` workers, or the sequential ones for `None`.
+/// [`PageRankConfig::resume`] must hold the component's ranks, not the whole graph's.
+pub fn pagerank_largest_component(
+    graph: &Graph,
+    config: &PageRankConfig,
+    num_threads: Option<usize>,
+    policy: ParallelismPolicy,
+) -> PageRankResult {
+    let component = Component::largest(graph, num_threads);
+    tracing::debug!(nodes = component.graph.num_nodes, of = graph.num_nodes, "PageRank on the largest component");
+    let result = match num_threads {
+        Some(threads) => pagerank_parallel_with_policy(&component.graph, config, threads, policy),
+        None => pagerank_sequential(&component.graph, config),
+    };
+    PageRankResult {
+        ranks: component.expand(&result.ranks),
+        ..result
+    }
 }
 
+/// Parallel PageRank; falls back to sequential below `Config::pagerank_min_nodes`.
+///
+/// Uses at most `Config::pagerank_max_threads` workers and `Config::chunk_size` nodes per task.
 pub fn pagerank_parallel(
     graph: &Graph,
     config: &PageRankConfig,
     num_threads: usize,
-) -> Vec<f64> {
-    const THRESHOLD: usize = 10_000;
-    if graph.num_nodes < THRESHOLD {
+) -> PageRankResult {
+    pagerank_parallel_with_policy(graph, config, num_threads, ParallelismPolicy::Auto)
+}
+
+/// Parallel PageRank with an explicit sequential-fallback policy.
+#[tracing::instrument(level = "debug", skip_all, fields(alpha = config.alpha, num_threads))]
+pub fn pagerank_parallel_with_policy(
+    graph: &Graph,
+    config: &PageRankConfig,
+    num_threads: usize,
+    policy: ParallelismPolicy,
+) -> PageRankResult {
+    let global = Config::global();
+    if !policy.use_parallel(graph.num_nodes, global.pagerank_min_nodes) {
         return pagerank_sequential(graph, config);
     }
 
-    let actual_threads = num_threads.min(8);
-    let n = graph.num_nodes;
-    let min_chunk = n / actual_threads; 
+    let actual_threads = num_threads.clamp(1, global.pagerank_max_threads.max(1));
+    let min_chunk = global.chunk_size.unwrap_or(graph.num_nodes / actual_threads);
+
+    #[cfg(feature = "parallel")]
+    {
+        get_thread_pool(actual_threads).install(|| pagerank_parallel_impl(graph, config, min_chunk))
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        let _ = min_chunk;
+        pagerank_sequential(graph, config)
+    }
+}
 
-    let initial_value = 1.0 / n as f64;
-    let mut rank = vec![initial_value; n];
+/// Elements per parallel task in the dense vector passes; large enough for the SIMD kernels to pay off.
+#[cfg(feature = "parallel")]
+const SIMD_BLOCK: usize = 1 << 14;
+
+#[cfg(feature = "parallel")]
+fn pagerank_parallel_impl(graph: &Graph, config: &PageRankConfig, min_chunk: usize) -> PageRankResult {
+    let n = graph.num_nodes;
+    let (mut rank, done, mut final_delta) = config.start(n);
+    let mut iterations = done;
+    let mut history = Vec::new();
     let mut new_rank = vec![0.0; n];
     let teleport = (1.0 - config.alpha) / n as f64;
 
+    // Only weighted graphs need the weight sums; otherwise the weighted run is the plain one
+    let out_weights = if graph.is_weighted() { config.out_weights(graph) } else { None };
     let sink_nodes: Vec<usize> = (0..n)
-        .filter(|&u| graph.out_degree[u] == 0)
+        .filter(|&u| match &out_weights {
+            Some(out_weights) => out_weights[u] == 0.0,
+            None => graph.out_degree[u] == 0,
+        })
         .collect();
+    // Each chunk folds into its own n-sized buffer, so keep about `n / min_chunk` chunks but cut them by edge count
+    let ranges = edge_balanced_ranges(&graph.offsets, n.div_ceil(min_chunk.max(1)));
 
-    rayon::ThreadPoolBuilder::new()
-        .num_threads(actual_threads)
-        .build()
-        .expect("rayon pool")
-        .install(|| {
-    for iteration in 0..config.max_iterations {
+    let mut converged = false;
+    for iteration in done..config.max_iterations {
         let sink_sum: f64 = sink_nodes.par_iter().map(|&u| rank[u]).sum();
         let sink_contribution = config.alpha * sink_sum / n as f64;
         let base_rank = teleport + sink_contribution;
 
-        let contributions = (0..n)
-            .into_par_iter()
-            .with_min_len(min_chunk.max(1)) 
+        let contributions = ranges
+            .par_iter()
             .fold(
                 || vec![0.0; n],
-                |mut local_rank, u| {
-                    let neighbors = graph.neighbors(u);
-                    if !neighbors.is_empty() {
-                        let contribution = config.alpha * rank[u] / neighbors.len() as f64;
-                        for &v in neighbors {
-                            local_rank[v] += contribution;
+                |mut local_rank, range| {
+                    for u in range.clone() {
+                        let neighbors = graph.neighbors(u);
+                        if let Some(out_weights) = &out_weights {
+                            if out_weights[u] > 0.0 {
+                                let scale = config.alpha * rank[u] / out_weights[u];
+                                for (&v, &w) in neighbors.iter().zip(graph.edge_weights(u).unwrap_or_default()) {
+                                    local_rank[v] += scale * rank_weight(w);
+                                }
+                            }
+                        } else if !neighbors.is_empty() {
+                            let contribution = config.alpha * rank[u] / neighbors.len() as f64;
+                            for &v in neighbors {
+                                local_rank[v] += contribution;
+                            }
                         }
                     }
                     local_rank
@@ -137,38 +441,69 @@ pub fn pagerank_parallel(
             );
 
         new_rank
-            .par_iter_mut()
-            .zip(contributions.par_iter())
-            .for_each(|(r, &c)| {
-                *r = base_rank + c;
-            });
+            .par_chunks_mut(SIMD_BLOCK)
+            .zip(contributions.par_chunks(SIMD_BLOCK))
+            .for_each(|(r, c)| add_offset(r, c, base_rank));
 
         let delta: f64 = rank
-            .par_iter()
-            .zip(new_rank.par_iter())
-            .map(|(old, new)| (old - new).abs())
+            .par_chunks(SIMD_BLOCK)
+            .zip(new_rank.par_chunks(SIMD_BLOCK))
+            .map(|(old, new)| l1_distance(old, new))
             .sum();
 
         std::mem::swap(&mut rank, &mut new_rank);
 
-        if delta < config.tolerance {
-            println!(
-                "PageRank converged after {} iterations (delta: {:.2e})",
-                iteration + 1,
-                delta
-            );
+        iterations = iteration + 1;
+        final_delta = delta;
+        history.push(delta);
+        if config.finish_iteration(iteration, delta) {
+            converged = true;
             break;
         }
+        config.save_checkpoint(iterations, delta, &rank);
+    }
 
-        if iteration == config.max_iterations - 1 {
-            println!(
-                "PageRank reached max iterations without convergence (delta: {:.2e})",
-                delta
-            );
-        }
+    PageRankResult {
+        ranks: rank,
+        iterations,
+        converged,
+        final_delta,
+        history,
     }
+}
 
-    rank
+/// PageRank started from `initial` instead of the uniform vector, e.g. the ranks of a previous run with
+/// a slightly different alpha or of the graph before an update, so it converges in a few iterations.
+///
+/// `initial` is normalized to sum to 1 (negative entries count as 0; an all-zero vector falls back to
+/// uniform). Runs [`pagerank_parallel`] with `num_threads` workers, or [`pagerank_sequential`] for
+/// `None`, and ignores `config.resume`. Returns [`Error::InvalidInput`] unless `initial` has one value
+/// per node.
+pub fn pagerank_with_initial(
+    graph: &Graph,
+    config: &PageRankConfig,
+    initial: &[f64],
+    num_threads: Option<usize>,
+) -> Result<PageRankResult> {
+    let n = graph.num_nodes;
+    if initial.len() != n {
+        return Err(Error::invalid_input(format!("initial ranks have {} values, expected {}", initial.len(), n)));
+    }
+    let mut ranks: Vec<f64> = initial.iter().map(|&r| if r.is_finite() { r.max(0.0) } else { 0.0 }).collect();
+    let total: f64 = ranks.iter().sum();
+    if total > 0.0 {
+        ranks.iter_mut().for_each(|r| *r /= total);
+    } else {
+        tracing::warn!("initial PageRank vector has no positive entries; starting from uniform");
+        ranks.fill(1.0 / n.max(1) as f64);
+    }
+    let config = PageRankConfig {
+        resume: Some(PageRankCheckpoint { iteration: 0, delta: f64::INFINITY, ranks }),
+        ..config.clone()
+    };
+    Ok(match num_threads {
+        Some(threads) => pagerank_parallel(graph, &config, threads),
+        None => pagerank_sequential(graph, &config),
     })
 }
 
@@ -176,7 +511,7 @@ pub fn pagerank_parallel_optimized(
     graph: &Graph,
     config: &PageRankConfig,
     num_threads: usize,
-) -> Vec<f64> {
+) -> PageRankResult {
     pagerank_parallel(graph, config, num_threads)
 }
 
@@ -209,38 +544,95 @@ pub fn pagerank_stats(ranks: &[f64]) {
     }
 }
 
+/// Runs PageRank in `mode` and writes the ranks with their top-100, statistics and convergence
+/// files; with `largest_component`, only on the largest weakly connected component (see
+/// [`pagerank_largest_component`]). `cache` is skipped when `config` checkpoints or resumes. A
+/// compact `graph` always runs [`pagerank_sequential`] without weights (and is cached as such), and
+/// cannot take `largest_component`.
+#[cfg(feature = "fs")]
+#[allow(clippy::too_many_arguments)]
 pub fn run_pagerank_and_save(
-    graph: &Graph,
+    graph: &GraphStorage,
     config: &PageRankConfig,
-    mode: &str,
+    mode: Mode,
     num_threads: usize,
     output_path: &str,
-) -> Result<()> {
+    permutation: Option<&Permutation>,
+    policy: ParallelismPolicy,
+    format: OutputFormat,
+    cache: Option<&ResultCache>,
+    largest_component: bool,
+) -> anyhow::Result<PageRankResult> {
     use std::time::Instant;
     
     let start = Instant::now();
     
-    let ranks = match mode {
-        "seq" => pagerank_sequential(graph, config),
-        "par" | "par-opt" => pagerank_parallel(graph, config, num_threads),
-        _ => return Err(anyhow::anyhow!("Invalid mode: {}", mode)),
+    let threads = match mode {
+        Mode::Seq => None,
+        Mode::Par | Mode::ParOpt => Some(num_threads),
+    };
+    let unweighted;
+    let config = match graph {
+        GraphStorage::Compact(_) if config.weighted => {
+            unweighted = PageRankConfig { weighted: false, ..config.clone() };
+            &unweighted
+        }
+        _ => config,
+    };
+    let compute = || {
+        let graph = match graph {
+            GraphStorage::Compact(_) if largest_component => anyhow::bail!("the largest component needs the wide graph"),
+            GraphStorage::Compact(compact) => return Ok(pagerank_sequential(compact, config)),
+            GraphStorage::Wide(graph) => graph,
+        };
+        Ok::<_, anyhow::Error>(match threads {
+            _ if largest_component => pagerank_largest_component(graph, config, threads, policy),
+            None => pagerank_sequential(graph, config),
+            Some(threads) => pagerank_parallel_with_policy(graph, config, threads, policy),
+        })
+    };
+    // Parallel summation order changes the low bits, so the mode is part of the key; checkpointed and
+    // resumed runs always compute, so their checkpoint files are written and their start vector is used
+    let cache = cache.filter(|_| config.checkpoint.is_none() && config.resume.is_none());
+    let result = match cache {
+        Some(cache) => {
+            let params = format!(
+                "mode={},alpha={},iters={},eps={},weighted={},largest_component={}",
+                mode, config.alpha, config.max_iterations, config.tolerance, config.weighted, largest_component
+            );
+            cache.get_or_insert_with(graph.fingerprint(), &params, compute)?
+        }
+        None => compute()?,
     };
     
     let elapsed = start.elapsed();
-    println!("PageRank completed in {:?}", elapsed);
+    tracing::info!("PageRank completed in {:?}", elapsed);
+
+    let result = match permutation {
+        Some(perm) => PageRankResult {
+            ranks: perm.to_original(&result.ranks),
+            ..result
+        },
+        None => result,
+    };
+    let ranks = &result.ranks;
 
-    write_pagerank_result(&ranks, output_path)?;
-    println!("Results saved to: {}", output_path);
+    write_pagerank_result_as(&result, output_path, format)?;
+    tracing::info!("Results saved to: {}", format.output_path(output_path));
+    
+    let top_path = derived_path(output_path, "_top100");
+    write_pagerank_top_nodes(ranks, &top_path, 100)?;
+    tracing::info!("Top 100 nodes saved to: {}", io::output_path(&top_path));
     
-    let top_path = output_path.replace(".txt", "_top100.txt");
-    write_pagerank_top_nodes(&ranks, &top_path, 100)?;
-    println!("Top 100 nodes saved to: {}", top_path);
+    let stats_path = derived_path(output_path, "_stats");
+    write_pagerank_stats(ranks, &stats_path)?;
+    tracing::info!("Statistics saved to: {}", io::output_path(&stats_path));
     
-    let stats_path = output_path.replace(".txt", "_stats.txt");
-    write_pagerank_stats(&ranks, &stats_path)?;
-    println!("Statistics saved to: {}", stats_path);
+    let convergence_path = derived_path_with_extension(output_path, "_convergence", "csv");
+    write_convergence_csv(&result, &convergence_path)?;
+    tracing::info!("Convergence history saved to: {}", io::output_path(&convergence_path));
 
-    pagerank_stats(&ranks);
+    pagerank_stats(ranks);
     
-    Ok(())
+    Ok(result)
 }
\ No newline at end of file
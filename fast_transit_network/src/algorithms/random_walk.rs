@@ -0,0 +1,272 @@
+use crate::graph::graph::Graph;
+use crate::utils::rng::Rng;
+#[cfg(feature = "fs")]
+use crate::utils::io::{create_output, write_walk_corpus};
+#[cfg(feature = "parallel")]
+use super::threadpool::get_thread_pool;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Settings for [`random_walks`].
+#[derive(Debug, Clone)]
+pub struct RandomWalkConfig {
+    /// Steps per walk after the start node.
+    pub walk_length: usize,
+    /// Walks started from every node.
+    pub walks_per_node: usize,
+    /// Chance per step of jumping back to the walk's start node instead of following an edge.
+    pub restart_probability: f64,
+    pub seed: u64,
+    /// Keep the node sequence of every walk in [`RandomWalkResult::traces`].
+    pub record_traces: bool,
+    pub num_threads: usize,
+}
+
+impl Default for RandomWalkConfig {
+    fn default() -> Self {
+        Self {
+            walk_length: 80,
+            walks_per_node: 10,
+            restart_probability: 0.0,
+            seed: 42,
+            record_traces: false,
+            num_threads: 4,
+        }
+    }
+}
+
+/// Output of [`random_walks`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RandomWalkResult {
+    /// Times each node was visited, start nodes included.
+    pub visit_counts: Vec<u64>,
+    pub total_visits: u64,
+    /// One node sequence per walk, ordered by start node then walk number; empty unless `record_traces`.
+    pub traces: Vec<Vec<usize>>,
+}
+
+impl RandomWalkResult {
+    /// Visit counts normalized to sum to 1.
+    ///
+    /// With `restart_probability = 1 - alpha` and long walks this estimates PageRank, since
+    /// PageRank is the average of the personalized PageRanks of all start nodes.
+    pub fn visit_frequencies(&self) -> Vec<f64> {
+        let total = self.total_visits.max(1) as f64;
+        self.visit_counts.iter().map(|&c| c as f64 / total).collect()
+    }
+}
+
+/// Runs `walks_per_node` random walks from every node, in parallel when available.
+///
+/// Each step restarts at the start node with `restart_probability`, otherwise follows a uniformly
+/// chosen out-edge (edge weights are ignored); dangling nodes jump to a uniformly random node.
+/// Walk `i` draws from its own seeded stream, so results do not depend on the thread count.
+#[tracing::instrument(level = "debug", skip(graph), fields(nodes = graph.num_nodes))]
+pub fn random_walks(graph: &Graph, config: &RandomWalkConfig) -> RandomWalkResult {
+    let n = graph.num_nodes;
+    let num_walks = n * config.walks_per_node;
+    let walk = |i: usize, visit: &mut dyn FnMut(usize)| {
+        let start = i / config.walks_per_node.max(1);
+        let mut rng = Rng::for_stream(config.seed, i as u64);
+        let mut v = start;
+        visit(v);
+        for _ in 0..config.walk_length {
+            let neighbors = graph.neighbors(v);
+            v = if config.restart_probability > 0.0 && rng.next_f64() < config.restart_probability {
+                start
+            } else if neighbors.is_empty() {
+                rng.below(n)
+            } else {
+                neighbors[rng.below(neighbors.len())]
+            };
+            visit(v);
+        }
+    };
+
+    let traces: Vec<Vec<usize>> = if config.record_traces {
+        let trace = |i: usize| {
+            let mut trace = Vec::with_capacity(config.walk_length + 1);
+            walk(i, &mut |v| trace.push(v));
+            trace
+        };
+        #[cfg(feature = "parallel")]
+        let traces = get_thread_pool(config.num_threads).install(|| (0..num_walks).into_par_iter().map(trace).collect());
+        #[cfg(not(feature = "parallel"))]
+        let traces = (0..num_walks).map(trace).collect();
+        traces
+    } else {
+        Vec::new()
+    };
+
+    let mut visit_counts = vec![0u64; n];
+    if config.record_traces {
+        for &v in traces.iter().flatten() {
+            visit_counts[v] += 1;
+        }
+    } else {
+        let count = |mut counts: Vec<u64>, i: usize| {
+            walk(i, &mut |v| counts[v] += 1);
+            counts
+        };
+        #[cfg(feature = "parallel")]
+        {
+            visit_counts = get_thread_pool(config.num_threads).install(|| {
+                (0..num_walks).into_par_iter().fold(|| vec![0u64; n], count).reduce(
+                    || vec![0u64; n],
+                    |mut a, b| {
+                        a.iter_mut().zip(b).for_each(|(x, y)| *x += y);
+                        a
+                    },
+                )
+            });
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            visit_counts = (0..num_walks).fold(visit_counts, count);
+        }
+    }
+
+    let total_visits = visit_counts.iter().sum();
+    RandomWalkResult { visit_counts, total_visits, traces }
+}
+
+/// Settings for node2vec-style biased walks ([`node2vec_walks`]).
+#[derive(Debug, Clone)]
+pub struct Node2VecConfig {
+    /// Return parameter: higher values make stepping straight back to the previous node less likely.
+    pub p: f64,
+    /// In-out parameter: below 1 favors moving away from the previous node (DFS-like), above 1 stays local (BFS-like).
+    pub q: f64,
+    /// Steps per walk after the start node; walks end early at dangling nodes.
+    pub walk_length: usize,
+    pub walks_per_node: usize,
+    pub seed: u64,
+    pub num_threads: usize,
+}
+
+impl Default for Node2VecConfig {
+    fn default() -> Self {
+        Self {
+            p: 1.0,
+            q: 1.0,
+            walk_length: 80,
+            walks_per_node: 10,
+            seed: 42,
+            num_threads: 4,
+        }
+    }
+}
+
+/// Walks generated per batch by [`run_node2vec_and_save`], bounding memory for large corpora.
+#[cfg(feature = "fs")]
+const CORPUS_BATCH_WALKS: usize = 1 << 16;
+
+/// Per-node sorted copy of the adjacency, for O(log d) "is `x` a neighbor of `t`?" checks; no copy
+/// if the graph's lists are already sorted.
+struct SortedAdjacency {
+    neighbors: Option<Vec<usize>>,
+}
+
+impl SortedAdjacency {
+    fn new(graph: &Graph) -> Self {
+        if graph.neighbors_sorted {
+            return Self { neighbors: None };
+        }
+        let mut neighbors = graph.neighbors.clone();
+        for u in 0..graph.num_nodes {
+            neighbors[graph.edge_range(u)].sort_unstable();
+        }
+        Self { neighbors: Some(neighbors) }
+    }
+
+    fn has_edge(&self, graph: &Graph, u: usize, v: usize) -> bool {
+        match &self.neighbors {
+            Some(neighbors) => neighbors[graph.edge_range(u)].binary_search(&v).is_ok(),
+            None => graph.has_edge(u, v),
+        }
+    }
+}
+
+/// One biased walk, using rejection sampling instead of precomputed alias tables (O(m) memory).
+///
+/// From `v` (reached from `t`) a uniform candidate `x` is accepted with weight `1/p` if `x == t`,
+/// `1` if `t -> x` is an edge and `1/q` otherwise, scaled by the largest of the three.
+fn node2vec_walk(graph: &Graph, adjacency: &SortedAdjacency, config: &Node2VecConfig, start: usize, rng: &mut Rng) -> Vec<usize> {
+    let mut walk = Vec::with_capacity(config.walk_length + 1);
+    walk.push(start);
+    let (return_weight, out_weight) = (1.0 / config.p, 1.0 / config.q);
+    let max_weight = return_weight.max(1.0).max(out_weight);
+
+    while walk.len() <= config.walk_length {
+        let v = walk[walk.len() - 1];
+        let neighbors = graph.neighbors(v);
+        if neighbors.is_empty() {
+            break;
+        }
+        let next = match walk.len() {
+            1 => neighbors[rng.below(neighbors.len())],
+            len => {
+                let t = walk[len - 2];
+                loop {
+                    let x = neighbors[rng.below(neighbors.len())];
+                    let weight = if x == t {
+                        return_weight
+                    } else if adjacency.has_edge(graph, t, x) {
+                        1.0
+                    } else {
+                        out_weight
+                    };
+                    if rng.next_f64() * max_weight < weight {
+                        break x;
+                    }
+                }
+            }
+        };
+        walk.push(next);
+    }
+    walk
+}
+
+/// Generates walks `range` of the node2vec corpus; walk `i` starts at node `i % n`.
+fn node2vec_batch(
+    graph: &Graph,
+    adjacency: &SortedAdjacency,
+    config: &Node2VecConfig,
+    range: std::ops::Range<usize>,
+) -> Vec<Vec<usize>> {
+    let n = graph.num_nodes;
+    let walk = |i: usize| {
+        let mut rng = Rng::for_stream(config.seed, i as u64);
+        node2vec_walk(graph, adjacency, config, i % n, &mut rng)
+    };
+    #[cfg(feature = "parallel")]
+    let walks = get_thread_pool(config.num_threads).install(|| range.into_par_iter().map(walk).collect());
+    #[cfg(not(feature = "parallel"))]
+    let walks = range.map(walk).collect();
+    walks
+}
+
+/// Node2vec biased walks: `walks_per_node` rounds, each starting one walk from every node.
+///
+/// Edge weights are ignored. Walk `i` starts at node `i % n` and uses its own seeded stream, so the
+/// corpus is reproducible for any thread count.
+#[tracing::instrument(level = "debug", skip(graph), fields(nodes = graph.num_nodes))]
+pub fn node2vec_walks(graph: &Graph, config: &Node2VecConfig) -> Vec<Vec<usize>> {
+    let adjacency = SortedAdjacency::new(graph);
+    node2vec_batch(graph, &adjacency, config, 0..graph.num_nodes * config.walks_per_node)
+}
+
+/// Generates the node2vec corpus in batches and writes one walk per line (space-separated node ids),
+/// the plain-text format word2vec-style trainers read. Returns the number of walks written.
+#[cfg(feature = "fs")]
+pub fn run_node2vec_and_save(graph: &Graph, config: &Node2VecConfig, output_path: &str) -> anyhow::Result<usize> {
+    let adjacency = SortedAdjacency::new(graph);
+    let num_walks = graph.num_nodes * config.walks_per_node;
+    let mut writer = create_output(output_path)?;
+    for start in (0..num_walks).step_by(CORPUS_BATCH_WALKS) {
+        let end = (start + CORPUS_BATCH_WALKS).min(num_walks);
+        write_walk_corpus(&node2vec_batch(graph, &adjacency, config, start..end), &mut writer)?;
+    }
+    writer.finish()?;
+    Ok(num_walks)
+}
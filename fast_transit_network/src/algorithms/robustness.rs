@@ -0,0 +1,188 @@
+use super::centrality::{approximate_betweenness, degree_centrality};
+use super::pagerank::{pagerank_parallel, PageRankConfig};
+use super::union_find::UnionFind;
+use crate::graph::graph::Graph;
+use crate::utils::rng::Rng;
+use std::str::FromStr;
+
+/// Order in which [`robustness`] removes nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemovalStrategy {
+    /// Highest total (in + out) degree first.
+    Degree,
+    /// Highest PageRank first.
+    PageRank,
+    /// Highest (sampled) betweenness first.
+    Betweenness,
+    /// Uniformly random order: random failures rather than an attack.
+    Random,
+}
+
+impl FromStr for RemovalStrategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "degree" => Ok(RemovalStrategy::Degree),
+            "pagerank" => Ok(RemovalStrategy::PageRank),
+            "betweenness" => Ok(RemovalStrategy::Betweenness),
+            "random" => Ok(RemovalStrategy::Random),
+            _ => Err(anyhow::anyhow!(
+                "Invalid removal strategy: {}. Use 'degree', 'pagerank', 'betweenness' or 'random'",
+                s
+            )),
+        }
+    }
+}
+
+/// Parameters for [`robustness`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RobustnessConfig {
+    pub strategy: RemovalStrategy,
+    /// Number of removal batches; the curve gets one point per batch plus the intact graph.
+    pub steps: usize,
+    /// Stop after removing this fraction of the nodes.
+    pub max_fraction: f64,
+    /// Seed for [`RemovalStrategy::Random`].
+    pub seed: u64,
+    /// BFS sources for [`RemovalStrategy::Betweenness`].
+    pub samples: usize,
+    pub num_threads: usize,
+}
+
+impl Default for RobustnessConfig {
+    fn default() -> Self {
+        Self {
+            strategy: RemovalStrategy::Degree,
+            steps: 100,
+            max_fraction: 1.0,
+            seed: 42,
+            samples: 256,
+            num_threads: 4,
+        }
+    }
+}
+
+/// State of the network after one removal batch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RobustnessPoint {
+    pub removed: usize,
+    pub removed_fraction: f64,
+    /// Largest weakly connected component over the original node count.
+    pub giant_fraction: f64,
+    /// Weakly connected components among the remaining nodes.
+    pub components: usize,
+}
+
+/// Degradation curve from [`robustness`], starting with the intact graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RobustnessCurve {
+    pub strategy: RemovalStrategy,
+    pub points: Vec<RobustnessPoint>,
+}
+
+impl RobustnessCurve {
+    /// Mean giant-component fraction over the removal points (Schneider's R when the whole graph is
+    /// removed one node at a time); higher means more robust.
+    pub fn r_index(&self) -> f64 {
+        let removals = &self.points[1.min(self.points.len())..];
+        if removals.is_empty() {
+            return 0.0;
+        }
+        removals.iter().map(|p| p.giant_fraction).sum::<f64>() / removals.len() as f64
+    }
+}
+
+/// Removes nodes in `config.strategy` order, in `config.steps` equal batches up to
+/// `config.max_fraction` of the graph, and reports the giant weakly connected component after each.
+///
+/// The ranking is computed once on the intact graph. Instead of recomputing components per batch,
+/// the nodes are added back in reverse removal order into a union-find, so the whole curve costs one
+/// pass over the edges.
+#[tracing::instrument(level = "debug", skip(graph))]
+pub fn robustness(graph: &Graph, config: &RobustnessConfig) -> RobustnessCurve {
+    let n = graph.num_nodes;
+    let order = removal_order(graph, config);
+    let total = ((config.max_fraction.clamp(0.0, 1.0) * n as f64).ceil() as usize).min(n);
+    let batch = total.div_ceil(config.steps.max(1)).max(1);
+    let mut checkpoints: Vec<usize> = (0..total).step_by(batch).collect();
+    checkpoints.push(total);
+
+    let transpose = graph.transpose();
+    let mut removed = vec![false; n];
+    for &v in &order[..total] {
+        removed[v] = true;
+    }
+    let mut alive = vec![false; n];
+    let mut uf = UnionFind::new(n);
+    let mut size = vec![1; n];
+    let (mut giant, mut components) = (0, 0);
+    let mut add = |v: usize, giant: &mut usize, components: &mut usize| {
+        alive[v] = true;
+        *components += 1;
+        *giant = (*giant).max(1);
+        for &w in graph.neighbors(v).iter().chain(transpose.neighbors(v)) {
+            if !alive[w] {
+                continue;
+            }
+            let (a, b) = (uf.find(v), uf.find(w));
+            if a != b {
+                uf.union(a, b);
+                let root = uf.find(a);
+                size[root] = size[a] + size[b];
+                *giant = (*giant).max(size[root]);
+                *components -= 1;
+            }
+        }
+    };
+    // Survivors first, then the removed batches in reverse
+    for v in (0..n).filter(|&v| !removed[v]) {
+        add(v, &mut giant, &mut components);
+    }
+
+    let point = |removed: usize, giant: usize, components: usize| RobustnessPoint {
+        removed,
+        removed_fraction: if n == 0 { 0.0 } else { removed as f64 / n as f64 },
+        giant_fraction: if n == 0 { 0.0 } else { giant as f64 / n as f64 },
+        components,
+    };
+    let mut points = vec![point(total, giant, components)];
+    for window in checkpoints.windows(2).rev() {
+        for &v in order[window[0]..window[1]].iter().rev() {
+            add(v, &mut giant, &mut components);
+        }
+        points.push(point(window[0], giant, components));
+    }
+    points.reverse();
+    RobustnessCurve { strategy: config.strategy, points }
+}
+
+/// All nodes, most important first according to the strategy; ties go to the smaller id.
+fn removal_order(graph: &Graph, config: &RobustnessConfig) -> Vec<usize> {
+    let n = graph.num_nodes;
+    let mut order: Vec<usize> = (0..n).collect();
+    let by_score = |order: &mut Vec<usize>, score: &[f64]| {
+        order.sort_by(|&a, &b| score[b].total_cmp(&score[a]).then(a.cmp(&b)));
+    };
+    match config.strategy {
+        RemovalStrategy::Degree => {
+            let (in_degree, out_degree) = degree_centrality(graph);
+            order.sort_by_key(|&v| (std::cmp::Reverse(in_degree[v] + out_degree[v]), v));
+        }
+        RemovalStrategy::PageRank => {
+            let ranks = pagerank_parallel(graph, &PageRankConfig::default(), config.num_threads).ranks;
+            by_score(&mut order, &ranks);
+        }
+        RemovalStrategy::Betweenness => {
+            let betweenness = approximate_betweenness(graph, config.samples, config.num_threads);
+            by_score(&mut order, &betweenness);
+        }
+        RemovalStrategy::Random => {
+            let mut rng = Rng::new(config.seed);
+            for i in (1..n).rev() {
+                order.swap(i, rng.below(i + 1));
+            }
+        }
+    }
+    order
+}
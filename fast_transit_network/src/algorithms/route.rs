@@ -0,0 +1,173 @@
+use crate::algorithms::sssp::find_negative_edge;
+use crate::error::{Error, Result};
+use crate::graph::attributes::Categorical;
+use crate::graph::graph::Graph;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Limits on a route besides its cost; `None` means unlimited.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteConstraints {
+    /// Most mode changes allowed (the first boarding is not a transfer).
+    pub max_transfers: Option<usize>,
+    /// Most edges allowed.
+    pub max_hops: Option<usize>,
+    /// Categorical edge attribute holding each edge's mode; without it a route never transfers.
+    pub mode_attr: String,
+}
+
+impl Default for RouteConstraints {
+    fn default() -> Self {
+        Self {
+            max_transfers: None,
+            max_hops: None,
+            mode_attr: "type".to_string(),
+        }
+    }
+}
+
+/// Cheapest route that satisfies the constraints.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route {
+    pub cost: f64,
+    pub transfers: usize,
+    /// Nodes from source to target, both included.
+    pub path: Vec<usize>,
+    /// Mode of every edge on the path (empty labels if the graph has no mode attribute).
+    pub modes: Vec<String>,
+}
+
+impl Route {
+    /// Number of edges on the route.
+    pub fn hops(&self) -> usize {
+        self.path.len() - 1
+    }
+}
+
+/// One partial route ending at `node`; `pred` indexes the label it extends.
+struct Label {
+    node: usize,
+    cost: f64,
+    transfers: usize,
+    hops: usize,
+    /// Mode of the last edge, `None` at the source.
+    mode: Option<u32>,
+    pred: usize,
+}
+
+impl Label {
+    /// True if `self` is at least as good as `other` in every constrained criterion. A different last
+    /// mode may cost `self` one more transfer later, so it only dominates with a transfer to spare.
+    fn dominates(&self, other: &Label, constraints: &RouteConstraints) -> bool {
+        let extra = usize::from(self.mode.is_some() && self.mode != other.mode);
+        self.cost <= other.cost
+            && (constraints.max_transfers.is_none() || self.transfers + extra <= other.transfers)
+            && (constraints.max_hops.is_none() || self.hops <= other.hops)
+    }
+}
+
+/// Min-heap entry (cost, label index).
+struct Entry(f64, usize);
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Entry {}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0).then(other.1.cmp(&self.1))
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Cheapest route from `source` to `target` with at most `max_transfers` mode changes and
+/// `max_hops` edges, or `None` if no route satisfies both.
+///
+/// Bi-criteria label-correcting search: every node keeps its Pareto set of (cost, transfers, hops)
+/// labels and a label is only extended if no other label at its node dominates it. Labels leave the
+/// queue in cost order, so the first label to reach `target` is optimal. Weights must be
+/// non-negative (1 per edge if unweighted). Returns [`Error::InvalidNode`] for bad endpoints and
+/// [`Error::NegativeWeight`] for negative weights.
+#[tracing::instrument(level = "debug", skip(graph, constraints))]
+pub fn constrained_shortest_path(
+    graph: &Graph,
+    source: usize,
+    target: usize,
+    constraints: &RouteConstraints,
+) -> Result<Option<Route>> {
+    for node in [source, target] {
+        if !graph.is_valid_node(node) {
+            return Err(Error::InvalidNode { node, num_nodes: graph.num_nodes });
+        }
+    }
+    if let Some((from, to)) = find_negative_edge(graph) {
+        return Err(Error::NegativeWeight { from, to });
+    }
+    let modes = graph.edge_attr::<Categorical>(&constraints.mode_attr);
+    let max_transfers = constraints.max_transfers.unwrap_or(usize::MAX);
+    let max_hops = constraints.max_hops.unwrap_or(usize::MAX);
+
+    let mut labels = vec![Label { node: source, cost: 0.0, transfers: 0, hops: 0, mode: None, pred: usize::MAX }];
+    let mut at_node: Vec<Vec<usize>> = vec![Vec::new(); graph.num_nodes];
+    at_node[source].push(0);
+    let mut heap = BinaryHeap::new();
+    heap.push(Entry(0.0, 0));
+
+    while let Some(Entry(_, index)) = heap.pop() {
+        let (u, cost, transfers, hops, mode) = {
+            let l = &labels[index];
+            (l.node, l.cost, l.transfers, l.hops, l.mode)
+        };
+        if u == target {
+            return Ok(Some(build_route(&labels, index, modes)));
+        }
+        if hops >= max_hops {
+            continue;
+        }
+        for (e, (v, w)) in graph.edge_range(u).zip(graph.weighted_neighbors(u)) {
+            let edge_mode = modes.map(|m| m.codes()[e]);
+            let transfers = transfers + usize::from(mode.is_some() && mode != edge_mode);
+            if transfers > max_transfers {
+                continue;
+            }
+            let label = Label { node: v, cost: cost + w, transfers, hops: hops + 1, mode: edge_mode, pred: index };
+            if at_node[v].iter().any(|&other| labels[other].dominates(&label, constraints)) {
+                continue;
+            }
+            at_node[v].push(labels.len());
+            heap.push(Entry(label.cost, labels.len()));
+            labels.push(label);
+        }
+    }
+    Ok(None)
+}
+
+fn build_route(labels: &[Label], last: usize, modes: Option<&Categorical>) -> Route {
+    let mut chain = Vec::new();
+    let mut index = last;
+    while index != usize::MAX {
+        chain.push(index);
+        index = labels[index].pred;
+    }
+    chain.reverse();
+    let label = &labels[last];
+    let name = |code: Option<u32>| match (modes, code) {
+        (Some(m), Some(c)) => m.labels()[c as usize].clone(),
+        _ => String::new(),
+    };
+    Route {
+        cost: label.cost,
+        transfers: label.transfers,
+        path: chain.iter().map(|&i| labels[i].node).collect(),
+        modes: chain[1..].iter().map(|&i| name(labels[i].mode)).collect(),
+    }
+}
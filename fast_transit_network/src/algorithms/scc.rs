@@ -0,0 +1,89 @@
+use crate::graph::graph::GraphLike;
+
+/// Output of a strongly-connected-components run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SccResult {
+    /// Component id per node, in `0..num_components`.
+    ///
+    /// Ids are a reverse topological order of the condensation: every edge `u -> v` between
+    /// different components has `component[u] > component[v]`.
+    pub component: Vec<usize>,
+    pub num_components: usize,
+}
+
+impl SccResult {
+    /// Size of every component, indexed by component id.
+    pub fn sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.num_components];
+        for &c in &self.component {
+            sizes[c] += 1;
+        }
+        sizes
+    }
+}
+
+/// Strongly connected components via an iterative Tarjan's algorithm, O(n + m).
+///
+/// Works on any [`GraphLike`]; hidden nodes end up as singleton components.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn strongly_connected_components<G: GraphLike>(graph: &G) -> SccResult {
+    const UNVISITED: usize = usize::MAX;
+    let n = graph.node_count();
+    let mut index = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut component = vec![UNVISITED; n];
+    let mut num_components = 0;
+    let mut next_index = 0;
+    let mut call_stack = Vec::new();
+
+    for root in 0..n {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        index[root] = next_index;
+        low[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        call_stack.push((root, graph.neighbor_iter(root)));
+
+        while let Some((v, neighbors)) = call_stack.last_mut() {
+            let v = *v;
+            match neighbors.next() {
+                Some(w) if index[w] == UNVISITED => {
+                    index[w] = next_index;
+                    low[w] = next_index;
+                    next_index += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    call_stack.push((w, graph.neighbor_iter(w)));
+                }
+                Some(w) => {
+                    if on_stack[w] {
+                        low[v] = low[v].min(index[w]);
+                    }
+                }
+                None => {
+                    call_stack.pop();
+                    if let Some(&(parent, _)) = call_stack.last() {
+                        low[parent] = low[parent].min(low[v]);
+                    }
+                    if low[v] == index[v] {
+                        while let Some(w) = stack.pop() {
+                            on_stack[w] = false;
+                            component[w] = num_components;
+                            if w == v {
+                                break;
+                            }
+                        }
+                        num_components += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    SccResult { component, num_components }
+}
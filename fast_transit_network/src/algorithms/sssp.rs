@@ -0,0 +1,208 @@
+use crate::error::{Error, Result};
+use crate::graph::graph::Graph;
+use super::bfs::{StopAt, TargetDistances, TargetSet};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Output of a weighted single-source shortest-path run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SsspResult {
+    pub source: usize,
+    /// Cost of the cheapest path from the source; `f64::INFINITY` if unreachable.
+    pub distances: Vec<f64>,
+    /// Previous node on the cheapest path; `usize::MAX` for the source and unreachable nodes.
+    pub predecessors: Vec<usize>,
+}
+
+impl SsspResult {
+    pub fn is_reachable(&self, v: usize) -> bool {
+        self.distances.get(v).is_some_and(|d| d.is_finite())
+    }
+
+    /// Number of nodes reached, including the source.
+    pub fn reachable(&self) -> usize {
+        self.distances.iter().filter(|d| d.is_finite()).count()
+    }
+
+    /// Cheapest path from the source to `target` (both included), or `None` if unreachable.
+    pub fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+        if !self.is_reachable(target) {
+            return None;
+        }
+        let mut path = vec![target];
+        let mut v = target;
+        while v != self.source {
+            v = self.predecessors[v];
+            path.push(v);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Bellman-Ford shortest paths from `source`; edge weights may be negative.
+///
+/// Unweighted graphs use weight 1 per edge. Runs at most `n - 1` relaxation rounds, stopping early
+/// once a round changes nothing, so it is O(n·m) in the worst case. Returns
+/// [`Error::NegativeCycle`] if a negative cycle is reachable from `source` (unreachable cycles are
+/// ignored) and [`Error::InvalidNode`] for an out-of-range source.
+#[tracing::instrument(level = "debug", skip(graph))]
+pub fn bellman_ford(graph: &Graph, source: usize) -> Result<SsspResult> {
+    if !graph.is_valid_node(source) {
+        return Err(Error::InvalidNode { node: source, num_nodes: graph.num_nodes });
+    }
+
+    let n = graph.num_nodes;
+    let mut distances = vec![f64::INFINITY; n];
+    let mut predecessors = vec![usize::MAX; n];
+    distances[source] = 0.0;
+
+    let relax = |distances: &mut [f64], predecessors: &mut [usize]| -> Option<usize> {
+        let mut last_changed = None;
+        for u in 0..n {
+            let du = distances[u];
+            if du == f64::INFINITY {
+                continue;
+            }
+            for (v, w) in graph.weighted_neighbors(u) {
+                if du + w < distances[v] {
+                    distances[v] = du + w;
+                    predecessors[v] = u;
+                    last_changed = Some(v);
+                }
+            }
+        }
+        last_changed
+    };
+
+    for round in 1..n.max(1) {
+        if relax(&mut distances, &mut predecessors).is_none() {
+            tracing::debug!(rounds = round, "bellman-ford converged");
+            return Ok(SsspResult { source, distances, predecessors });
+        }
+    }
+
+    // After n - 1 rounds every shortest path is final; any further improvement means a negative cycle
+    if let Some(node) = relax(&mut distances, &mut predecessors) {
+        return Err(Error::NegativeCycle { node });
+    }
+    Ok(SsspResult { source, distances, predecessors })
+}
+
+/// Returns the first edge with a negative weight, if any.
+pub fn find_negative_edge(graph: &Graph) -> Option<(usize, usize)> {
+    graph.weights.as_ref()?;
+    (0..graph.num_nodes).find_map(|u| {
+        graph
+            .weighted_neighbors(u)
+            .find(|&(_, w)| w < 0.0)
+            .map(|(v, _)| (u, v))
+    })
+}
+
+/// Min-heap entry ordered by distance.
+#[derive(PartialEq)]
+struct HeapEntry(f64, usize);
+
+impl Eq for HeapEntry {}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0).then_with(|| other.1.cmp(&self.1))
+    }
+}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Dijkstra shortest paths from `source` for non-negative edge weights (1 per edge if unweighted).
+///
+/// Returns [`Error::NegativeWeight`] if any edge weight is negative; use [`bellman_ford`] for those graphs.
+#[tracing::instrument(level = "debug", skip(graph))]
+pub fn dijkstra(graph: &Graph, source: usize) -> Result<SsspResult> {
+    if !graph.is_valid_node(source) {
+        return Err(Error::InvalidNode { node: source, num_nodes: graph.num_nodes });
+    }
+    if let Some((from, to)) = find_negative_edge(graph) {
+        return Err(Error::NegativeWeight { from, to });
+    }
+    Ok(dijkstra_unchecked(graph, source))
+}
+
+/// Dijkstra without the source and weight checks, for callers that validated the graph once.
+pub(crate) fn dijkstra_unchecked(graph: &Graph, source: usize) -> SsspResult {
+    let n = graph.num_nodes;
+    let mut distances = vec![f64::INFINITY; n];
+    let mut predecessors = vec![usize::MAX; n];
+    let mut heap = BinaryHeap::new();
+    distances[source] = 0.0;
+    heap.push(HeapEntry(0.0, source));
+
+    while let Some(HeapEntry(d, u)) = heap.pop() {
+        if d > distances[u] {
+            continue;
+        }
+        for (v, w) in graph.weighted_neighbors(u) {
+            let candidate = d + w;
+            if candidate < distances[v] {
+                distances[v] = candidate;
+                predecessors[v] = u;
+                heap.push(HeapEntry(candidate, v));
+            }
+        }
+    }
+
+    SsspResult { source, distances, predecessors }
+}
+
+/// Dijkstra from `source` that stops as soon as the `targets` are settled (all of them, or the
+/// nearest one with [`StopAt::Any`]) instead of settling every reachable node.
+///
+/// Weights must be non-negative. Only the edges the search scans are checked, returning
+/// [`Error::NegativeWeight`] for the first negative one: checking all of them up front, as
+/// [`dijkstra`] does, would cost a pass over the whole graph.
+#[tracing::instrument(level = "debug", skip_all, fields(source, targets = targets.len()))]
+pub fn dijkstra_to_targets(graph: &Graph, source: usize, targets: &[usize], stop: StopAt) -> Result<TargetDistances<f64>> {
+    for &v in std::iter::once(&source).chain(targets) {
+        if !graph.is_valid_node(v) {
+            return Err(Error::InvalidNode { node: v, num_nodes: graph.num_nodes });
+        }
+    }
+    let n = graph.num_nodes;
+    let mut distances = vec![f64::INFINITY; n];
+    let mut open = TargetSet::new(n, targets, stop);
+    let mut heap = BinaryHeap::new();
+    distances[source] = 0.0;
+    heap.push(HeapEntry(0.0, source));
+    let mut settled = 0;
+
+    while !open.done() && let Some(HeapEntry(d, u)) = heap.pop() {
+        if d > distances[u] {
+            continue;
+        }
+        settled += 1;
+        if open.settle(u) {
+            break;
+        }
+        for (v, w) in graph.weighted_neighbors(u) {
+            if w < 0.0 {
+                return Err(Error::NegativeWeight { from: u, to: v });
+            }
+            let candidate = d + w;
+            if candidate < distances[v] {
+                distances[v] = candidate;
+                heap.push(HeapEntry(candidate, v));
+            }
+        }
+    }
+
+    // Tentative distances of targets left in the heap are not final
+    let distances = targets
+        .iter()
+        .map(|&t| if open.is_open(t) { f64::INFINITY } else { distances[t] })
+        .collect();
+    Ok(TargetDistances { source, targets: targets.to_vec(), distances, settled })
+}
@@ -13,6 +13,24 @@ impl UnionFind {
         }
     }
 
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Adds singleton sets until there are `n` elements; does nothing if there already are.
+    pub fn grow(&mut self, n: usize) {
+        let old = self.parent.len();
+        if n > old {
+            self.parent.extend(old..n);
+            self.rank.resize(n, 0);
+        }
+    }
+
     /// Returns the representative (root) of the set containing `x`.
     pub fn find(&mut self, x: usize) -> usize {
         if self.parent[x] != x {
@@ -1,20 +1,221 @@
-use crate::graph::graph::Graph;
-use crate::utils::io::write_wcc_result;
+use crate::config::{Config, ParallelismPolicy};
+#[cfg(feature = "fs")]
+use crate::config::Mode;
+use crate::graph::graph::{Graph, GraphLike};
+#[cfg(feature = "fs")]
+use crate::cache::ResultCache;
+#[cfg(feature = "fs")]
+use crate::graph::compact::GraphStorage;
+#[cfg(feature = "fs")]
+use crate::graph::reorder::Permutation;
+#[cfg(feature = "fs")]
+use crate::utils::io::{write_wcc_result_as, OutputFormat, WccColumns};
 use super::union_find::UnionFind;
+#[cfg(feature = "parallel")]
 use super::atomic_union_find::AtomicUnionFind;
+#[cfg(feature = "parallel")]
+use super::threadpool::get_thread_pool;
+#[cfg(feature = "parallel")]
+use super::chunking::{edge_balanced_ranges, CHUNKS_PER_THREAD};
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
+#[cfg(feature = "parallel")]
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::str::FromStr;
+
+/// How WCC joins the nodes of a component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WccMethod {
+    /// One union per edge ([`wcc_sequential`], [`wcc_parallel`]).
+    #[default]
+    UnionFind,
+    /// Sweeps that pass the minimum label along every edge until nothing changes
+    /// ([`wcc_label_propagation`]).
+    LabelPropagation,
+}
+
+impl FromStr for WccMethod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "union-find" | "uf" => Ok(WccMethod::UnionFind),
+            "label-propagation" | "lp" => Ok(WccMethod::LabelPropagation),
+            _ => Err(anyhow::anyhow!("Invalid WCC method: {}. Use 'union-find' or 'label-propagation'", s)),
+        }
+    }
+}
+
+/// Output of a WCC run: a component label per node, plus summary statistics.
+///
+/// [`wcc_sequential`], [`wcc_parallel`] and [`IncrementalWcc::to_result`] label every component by
+/// its smallest node id, so their outputs are identical for the same graph.
+#[derive(Debug, Clone)]
+pub struct WccResult {
+    pub labels: Vec<usize>,
+    pub stats: WccStats,
+}
+
+impl WccResult {
+    pub fn from_labels(labels: Vec<usize>) -> Self {
+        let stats = wcc_stats(&labels);
+        Self { labels, stats }
+    }
+
+    /// Nodes of the largest component (smallest label on ties), in increasing id order.
+    pub fn largest_component(&self) -> Vec<usize> {
+        let mut sizes = vec![0usize; self.labels.len()];
+        for &label in &self.labels {
+            sizes[label] += 1;
+        }
+        let largest = (0..sizes.len()).max_by_key(|&l| (sizes[l], std::cmp::Reverse(l))).unwrap_or(0);
+        (0..self.labels.len()).filter(|&v| self.labels[v] == largest).collect()
+    }
+}
 
 /// Sequential WCC: finds weakly connected components (treats graph as undirected).
-pub fn wcc_sequential(graph: &Graph) -> Vec<usize> {
-    let mut uf = UnionFind::new(graph.num_nodes);
+///
+/// Works on any [`GraphLike`], including filtered views.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn wcc_sequential<G: GraphLike>(graph: &G) -> WccResult {
+    let mut uf = UnionFind::new(graph.node_count());
 
-    for u in 0..graph.num_nodes {
-        for &v in graph.neighbors(u) {
+    for u in 0..graph.node_count() {
+        for v in graph.neighbor_iter(u) {
             uf.union(u, v);
         }
     }
 
-    uf.get_components()
+    let mut labels = uf.get_components();
+    canonicalize_labels(&mut labels);
+    WccResult::from_labels(labels)
+}
+
+/// Relabels every component by its smallest node id, whatever root the union-find picked.
+///
+/// Union-find roots depend on the union order, which differs between the sequential and parallel
+/// runs (and between parallel runs); after this pass the same partition always has the same labels.
+pub fn canonicalize_labels(labels: &mut [usize]) {
+    let n = labels.len();
+    if labels.iter().all(|&label| label < n) {
+        let mut smallest = vec![usize::MAX; n];
+        for (v, &label) in labels.iter().enumerate() {
+            if smallest[label] == usize::MAX {
+                smallest[label] = v;
+            }
+        }
+        labels.iter_mut().for_each(|label| *label = smallest[*label]);
+    } else {
+        let mut smallest = std::collections::HashMap::new();
+        for (v, label) in labels.iter_mut().enumerate() {
+            *label = *smallest.entry(*label).or_insert(v);
+        }
+    }
+}
+
+/// [`canonicalize_labels`] for union-find roots (labels `< n`), run on the current thread pool.
+#[cfg(feature = "parallel")]
+fn canonicalize_roots_parallel(labels: &mut [usize]) {
+    let smallest: Vec<AtomicUsize> = (0..labels.len()).map(|_| AtomicUsize::new(usize::MAX)).collect();
+    labels.par_iter().enumerate().for_each(|(v, &root)| {
+        smallest[root].fetch_min(v, Ordering::Relaxed);
+    });
+    labels.par_iter_mut().for_each(|label| *label = smallest[*label].load(Ordering::Relaxed));
+}
+
+/// WCC labels kept up to date under edge insertions, without recomputation.
+///
+/// Seeded from a graph or an earlier [`WccResult`]; each [`add_edge`](Self::add_edge) is one
+/// near-constant-time union. Node ids beyond the current range are added as new singletons.
+/// Deletions are not supported (they can split components); rerun [`wcc_sequential`] for those.
+pub struct IncrementalWcc {
+    uf: UnionFind,
+    /// Component size, valid at root nodes only.
+    sizes: Vec<usize>,
+    num_components: usize,
+}
+
+impl IncrementalWcc {
+    /// Builds the structure from the edges of `graph`.
+    pub fn new<G: GraphLike>(graph: &G) -> Self {
+        let mut wcc = Self::with_nodes(graph.node_count());
+        for u in 0..graph.node_count() {
+            for v in graph.neighbor_iter(u) {
+                wcc.add_edge(u, v);
+            }
+        }
+        wcc
+    }
+
+    /// Seeds the structure from an existing WCC result (labels from any implementation).
+    pub fn from_result(result: &WccResult) -> Self {
+        let mut wcc = Self::with_nodes(result.labels.len());
+        let mut first_of_label = std::collections::HashMap::new();
+        for (v, &label) in result.labels.iter().enumerate() {
+            let first = *first_of_label.entry(label).or_insert(v);
+            wcc.add_edge(first, v);
+        }
+        wcc
+    }
+
+    fn with_nodes(n: usize) -> Self {
+        Self { uf: UnionFind::new(n), sizes: vec![1; n], num_components: n }
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.uf.len()
+    }
+
+    pub fn num_components(&self) -> usize {
+        self.num_components
+    }
+
+    fn ensure_node(&mut self, v: usize) {
+        if v >= self.uf.len() {
+            self.num_components += v + 1 - self.uf.len();
+            self.uf.grow(v + 1);
+            self.sizes.resize(v + 1, 1);
+        }
+    }
+
+    /// Records the edge `u - v`; returns true if it merged two components.
+    pub fn add_edge(&mut self, u: usize, v: usize) -> bool {
+        self.ensure_node(u.max(v));
+        let (root_u, root_v) = (self.uf.find(u), self.uf.find(v));
+        if !self.uf.union(root_u, root_v) {
+            return false;
+        }
+        let root = self.uf.find(root_u);
+        self.sizes[root] = self.sizes[root_u] + self.sizes[root_v];
+        self.num_components -= 1;
+        true
+    }
+
+    /// Returns true if `u` and `v` are currently connected (ids never seen are singletons).
+    pub fn same_component(&mut self, u: usize, v: usize) -> bool {
+        if u >= self.uf.len() || v >= self.uf.len() {
+            return u == v;
+        }
+        self.uf.find(u) == self.uf.find(v)
+    }
+
+    /// Current component label (root node id) of `v`, or `None` for ids never seen.
+    pub fn component(&mut self, v: usize) -> Option<usize> {
+        (v < self.uf.len()).then(|| self.uf.find(v))
+    }
+
+    /// Number of nodes in the component of `v` (1 for ids never seen).
+    pub fn component_size(&mut self, v: usize) -> usize {
+        self.component(v).map_or(1, |root| self.sizes[root])
+    }
+
+    /// Snapshot of the current labels (smallest node id per component, unlike
+    /// [`component`](Self::component)) and statistics.
+    pub fn to_result(&mut self) -> WccResult {
+        let mut labels = self.uf.get_components();
+        canonicalize_labels(&mut labels);
+        WccResult::from_labels(labels)
+    }
 }
 
 /// Computes statistics for WCC result (component counts and sizes).
@@ -23,8 +224,17 @@ pub fn wcc_stats(components: &[usize]) -> WccStats {
     
     let mut comp_sizes: HashMap<usize, usize> = HashMap::new();
     
-    for &comp in components {
-        *comp_sizes.entry(comp).or_insert(0) += 1;
+    if components.iter().all(|&c| c < components.len()) {
+        // Labels are node ids (the usual case): count densely, then keep only non-empty components.
+        let mut counts = vec![0usize; components.len()];
+        for &comp in components {
+            counts[comp] += 1;
+        }
+        comp_sizes.extend(counts.into_iter().enumerate().filter(|&(_, size)| size > 0));
+    } else {
+        for &comp in components {
+            *comp_sizes.entry(comp).or_insert(0) += 1;
+        }
     }
     
     let num_components = comp_sizes.len();
@@ -39,6 +249,17 @@ pub fn wcc_stats(components: &[usize]) -> WccStats {
     }
 }
 
+/// Labels renumbered `0..k` by decreasing component size (smaller original label first on ties),
+/// plus the size of each renumbered component, for outputs that downstream joins key on.
+pub fn dense_component_ids(components: &[usize]) -> (Vec<usize>, Vec<usize>) {
+    let mut order: Vec<(usize, usize)> = wcc_stats(components).component_sizes.into_iter().collect();
+    order.sort_unstable_by_key(|&(label, size)| (std::cmp::Reverse(size), label));
+    let dense: std::collections::HashMap<usize, usize> = order.iter().enumerate().map(|(id, &(label, _))| (label, id)).collect();
+    let labels = components.iter().map(|label| dense[label]).collect();
+    (labels, order.into_iter().map(|(_, size)| size).collect())
+}
+
+#[derive(Debug, Clone)]
 pub struct WccStats {
     pub num_components: usize,
     pub largest_component: usize,
@@ -56,7 +277,7 @@ impl WccStats {
         if self.num_components <= 10 {
             println!("\nComponent sizes:");
             let mut sizes: Vec<_> = self.component_sizes.iter().collect();
-            sizes.sort_by_key(|&(_, size)| std::cmp::Reverse(size));
+            sizes.sort_by_key(|&(&comp_id, &size)| (std::cmp::Reverse(size), comp_id));
             
             for (comp_id, size) in sizes {
                 println!("  Component {}: {} nodes", comp_id, size);
@@ -64,7 +285,7 @@ impl WccStats {
         } else {
             println!("\nTop 5 largest components:");
             let mut sizes: Vec<_> = self.component_sizes.iter().collect();
-            sizes.sort_by_key(|&(_, size)| std::cmp::Reverse(size));
+            sizes.sort_by_key(|&(&comp_id, &size)| (std::cmp::Reverse(size), comp_id));
             
             for (comp_id, size) in sizes.iter().take(5) {
                 println!("  Component {}: {} nodes", comp_id, size);
@@ -73,57 +294,197 @@ impl WccStats {
     }
 }
 
-/// Parallel WCC using AtomicUnionFind; falls back to sequential for small graphs.
-pub fn wcc_parallel(graph: &Graph, num_threads: usize) -> Vec<usize> {
-    const THRESHOLD: usize = 100_000;
-    if graph.num_nodes < THRESHOLD {
+/// Parallel WCC using AtomicUnionFind; falls back to sequential for graphs below `Config::wcc_min_nodes`.
+pub fn wcc_parallel(graph: &Graph, num_threads: usize) -> WccResult {
+    wcc_parallel_with_policy(graph, num_threads, ParallelismPolicy::Auto)
+}
+
+/// Parallel WCC with an explicit sequential-fallback policy.
+#[tracing::instrument(level = "debug", skip_all, fields(num_threads))]
+pub fn wcc_parallel_with_policy(
+    graph: &Graph,
+    num_threads: usize,
+    policy: ParallelismPolicy,
+) -> WccResult {
+    if !policy.use_parallel(graph.num_nodes, Config::global().wcc_min_nodes) {
         return wcc_sequential(graph);
     }
 
-    rayon::ThreadPoolBuilder::new()
-        .num_threads(num_threads)
-        .build()
-        .expect("rayon thread pool")
-        .install(|| {
-            let uf = AtomicUnionFind::new(graph.num_nodes);
-            (0..graph.num_nodes).into_par_iter().for_each(|u| {
-                for &v in graph.neighbors(u) {
-                    uf.union(u, v);
-                }
+    #[cfg(feature = "parallel")]
+    {
+        let labels = get_thread_pool(num_threads).install(|| {
+                let uf = AtomicUnionFind::new(graph.num_nodes);
+                let ranges = edge_balanced_ranges(&graph.offsets, rayon::current_num_threads() * CHUNKS_PER_THREAD);
+                ranges.into_par_iter().for_each(|range| {
+                    for u in range {
+                        for &v in graph.neighbors(u) {
+                            uf.union(u, v);
+                        }
+                    }
+                });
+                let mut labels = uf.get_components();
+                canonicalize_roots_parallel(&mut labels);
+                labels
             });
-            uf.get_components()
-        })
+        WccResult::from_labels(labels)
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        let _ = num_threads;
+        wcc_sequential(graph)
+    }
 }
 
+/// WCC by label propagation: every node starts with its own id as label, and each sweep lowers both
+/// ends of every edge to the smaller of their labels, then points every label at its label's label
+/// (a shortcut that skips long chains), until a sweep changes nothing.
+///
+/// The inner loop is a plain min over the neighbors' labels, which vectorizes and streams through
+/// the CSR arrays without the random parent chasing of union-find; sweeps in id order settle a
+/// grid or road network in a handful of passes. Labels come out as the smallest node id of each
+/// component, like the union-find variants. `num_threads <= 1` sweeps in place on one thread.
+#[tracing::instrument(level = "debug", skip_all, fields(num_threads))]
+pub fn wcc_label_propagation(graph: &Graph, num_threads: usize) -> WccResult {
+    #[cfg(feature = "parallel")]
+    if num_threads > 1 {
+        let labels = get_thread_pool(num_threads).install(|| label_propagation_parallel(graph));
+        return WccResult::from_labels(labels);
+    }
+    let _ = num_threads;
+
+    let n = graph.num_nodes;
+    let mut labels: Vec<usize> = (0..n).collect();
+    let mut sweeps = 0;
+    let mut changed = true;
+    while changed {
+        changed = false;
+        sweeps += 1;
+        for u in 0..n {
+            let neighbors = graph.neighbors(u);
+            let min = neighbors.iter().fold(labels[u], |min, &v| min.min(labels[v]));
+            if min < labels[u] {
+                labels[u] = min;
+                changed = true;
+            }
+            for &v in neighbors {
+                if min < labels[v] {
+                    labels[v] = min;
+                    changed = true;
+                }
+            }
+        }
+        // A label is a node of the same component, whose own label is at most as large
+        for u in 0..n {
+            labels[u] = labels[labels[u]];
+        }
+    }
+    tracing::debug!(sweeps, "label propagation converged");
+    WccResult::from_labels(labels)
+}
+
+/// [`wcc_label_propagation`] with atomic labels, the nodes split by edges across the current pool.
+///
+/// Labels only ever decrease (`fetch_min`), so concurrent updates can delay but not break convergence.
+#[cfg(feature = "parallel")]
+fn label_propagation_parallel(graph: &Graph) -> Vec<usize> {
+    let labels: Vec<AtomicUsize> = (0..graph.num_nodes).map(AtomicUsize::new).collect();
+    let ranges = edge_balanced_ranges(&graph.offsets, rayon::current_num_threads() * CHUNKS_PER_THREAD);
+    let mut sweeps = 0;
+    let changed = AtomicBool::new(true);
+    while changed.swap(false, Ordering::Relaxed) {
+        sweeps += 1;
+        ranges.par_iter().for_each(|range| {
+            let mut local_changed = false;
+            for u in range.clone() {
+                let neighbors = graph.neighbors(u);
+                let own = labels[u].load(Ordering::Relaxed);
+                let min = neighbors.iter().fold(own, |min, &v| min.min(labels[v].load(Ordering::Relaxed)));
+                if min < own {
+                    labels[u].fetch_min(min, Ordering::Relaxed);
+                    local_changed = true;
+                }
+                for &v in neighbors {
+                    if min < labels[v].fetch_min(min, Ordering::Relaxed) {
+                        local_changed = true;
+                    }
+                }
+            }
+            if local_changed {
+                changed.store(true, Ordering::Relaxed);
+            }
+        });
+        labels.par_iter().for_each(|label| {
+            let jumped = labels[label.load(Ordering::Relaxed)].load(Ordering::Relaxed);
+            label.fetch_min(jumped, Ordering::Relaxed);
+        });
+    }
+    tracing::debug!(sweeps, "label propagation converged");
+    labels.into_iter().map(AtomicUsize::into_inner).collect()
+}
+
+/// Runs WCC with `method` in `mode` and writes the labels and component statistics. A compact
+/// `graph` always runs [`wcc_sequential`], the only method that works on it.
+#[cfg(feature = "fs")]
+#[allow(clippy::too_many_arguments)]
 pub fn run_wcc_and_save(
-    graph: &Graph,
-    mode: &str,
+    graph: &GraphStorage,
+    mode: Mode,
+    method: WccMethod,
     num_threads: usize,
     output_path: &str,
     stats_path: &str,
-) -> anyhow::Result<()> {
+    permutation: Option<&Permutation>,
+    policy: ParallelismPolicy,
+    format: OutputFormat,
+    columns: WccColumns,
+    cache: Option<&ResultCache>,
+) -> anyhow::Result<WccResult> {
     use std::time::Instant;
     
     let start = Instant::now();
     
-    let components = match mode {
-        "seq" => wcc_sequential(graph),
-        "par" => wcc_parallel(graph, num_threads),
-        _ => return Err(anyhow::anyhow!("Invalid mode: {}", mode)),
+    let compute = || {
+        let graph = match graph {
+            GraphStorage::Compact(compact) => return Ok::<_, anyhow::Error>(wcc_sequential(compact)),
+            GraphStorage::Wide(graph) => graph,
+        };
+        Ok(match (method, mode) {
+            (WccMethod::UnionFind, Mode::Seq) => wcc_sequential(graph),
+            (WccMethod::UnionFind, Mode::Par | Mode::ParOpt) => wcc_parallel_with_policy(graph, num_threads, policy),
+            (WccMethod::LabelPropagation, Mode::Seq) => wcc_label_propagation(graph, 1),
+            (WccMethod::LabelPropagation, Mode::Par | Mode::ParOpt) => {
+                let parallel = policy.use_parallel(graph.num_nodes, Config::global().wcc_min_nodes);
+                wcc_label_propagation(graph, if parallel { num_threads } else { 1 })
+            }
+        })
+    };
+    // Labels are canonical, so every mode and method shares one entry (older per-mode entries are not reused)
+    let result = match cache {
+        Some(cache) => cache.get_or_insert_with(graph.fingerprint(), "labels=min-id", compute)?,
+        None => compute()?,
     };
     
     let elapsed = start.elapsed();
     
-    println!("WCC completed in {:?}", elapsed);
+    tracing::info!("WCC completed in {:?}", elapsed);
+
+    let result = match permutation {
+        Some(perm) => {
+            // The smallest reordered id is not the smallest original one
+            let mut labels = perm.labels_to_original(&result.labels);
+            canonicalize_labels(&mut labels);
+            WccResult::from_labels(labels)
+        }
+        None => result,
+    };
 
-    write_wcc_result(&components, output_path)?;
-    println!("Results saved to: {}", output_path);
+    write_wcc_result_as(&result, output_path, format, columns)?;
+    tracing::info!("Results saved to: {}", format.output_path(output_path));
 
-    crate::utils::io::write_wcc_stats(&components, stats_path)?;
-    println!("Statistics saved to: {}", stats_path);
+    crate::utils::io::write_wcc_stats(&result.labels, stats_path)?;
+    tracing::info!("Statistics saved to: {}", crate::utils::io::output_path(stats_path));
 
-    let stats = wcc_stats(&components);
-    stats.print();
+    result.stats.print();
     
-    Ok(())
+    Ok(result)
 }
\ No newline at end of file
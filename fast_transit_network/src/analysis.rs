@@ -0,0 +1,243 @@
+use crate::algorithms::bfs::{bfs_parallel_with_policy, bfs_sequential, BfsResult};
+use crate::algorithms::bridges::{find_bridges, BridgeResult};
+use crate::algorithms::centrality::{
+    centrality_report, centrality_report_with_transpose, closeness_from_transpose, CentralityConfig, CentralityReport,
+};
+use crate::algorithms::pagerank::{pagerank_parallel_with_policy, pagerank_sequential, PageRankConfig, PageRankResult};
+use crate::algorithms::wcc::{wcc_parallel_with_policy, wcc_sequential, WccResult};
+use crate::config::{Config, ParallelismPolicy};
+use crate::error::{Error, Result};
+use crate::graph::graph::Graph;
+use crate::graph::stats::GraphStats;
+use crate::sample::induced_subgraph;
+use std::borrow::Cow;
+
+/// Multi-step analysis of one graph, the library counterpart of the CLI `pipeline` command, e.g.
+/// `Analysis::new(&graph).wcc().largest_component().bfs(0).pagerank(config).run()`.
+///
+/// Steps run in the order they are added. Intermediate results are shared between steps: the
+/// transpose is built once for closeness and centrality, and `largest_component` reuses the
+/// components of a preceding `wcc`.
+pub struct Analysis<'g> {
+    graph: &'g Graph,
+    steps: Vec<Step>,
+    /// Worker threads for BFS, WCC and PageRank; `None` runs the sequential implementations.
+    num_threads: Option<usize>,
+    policy: ParallelismPolicy,
+}
+
+enum Step {
+    Stats,
+    Wcc,
+    LargestComponent,
+    Bfs(usize),
+    PageRank(PageRankConfig),
+    Bridges,
+    Closeness(usize),
+    Centrality(CentralityConfig),
+}
+
+/// Restriction of the analysed graph made by [`Analysis::largest_component`], or by
+/// [`Component::largest`] for algorithms that should ignore the small components.
+pub struct Component {
+    pub graph: Graph,
+    /// Node id in the input graph for every node of `graph`.
+    pub original_ids: Vec<usize>,
+    /// Node count of the input graph.
+    pub input_nodes: usize,
+}
+
+impl Component {
+    /// The largest weakly connected component of `graph` as its own graph, ids in ascending order.
+    /// Runs the parallel WCC with `num_threads` workers, or the sequential one for `None`.
+    pub fn largest(graph: &Graph, num_threads: Option<usize>) -> Self {
+        let wcc = match num_threads {
+            Some(threads) => wcc_parallel_with_policy(graph, threads, ParallelismPolicy::Auto),
+            None => wcc_sequential(graph),
+        };
+        let original_ids = wcc.largest_component();
+        Self { graph: induced_subgraph(graph, &original_ids), original_ids, input_nodes: graph.num_nodes }
+    }
+
+    /// Maps per-node values of the component to the input graph's ids, with `T::default()` (0 for
+    /// scores) for every node outside the component.
+    pub fn expand<T: Clone + Default>(&self, values: &[T]) -> Vec<T> {
+        let mut expanded = vec![T::default(); self.input_nodes];
+        for (&id, value) in self.original_ids.iter().zip(values) {
+            expanded[id] = value.clone();
+        }
+        expanded
+    }
+
+    /// Maps per-edge values of a component made by [`Component::largest`] to the CSR edge indices of
+    /// `input`, with 0 on every other edge. A weakly connected component keeps every edge of its
+    /// nodes, in input order, so its edges line up with theirs one to one.
+    pub fn expand_edges(&self, input: &Graph, values: &[f64]) -> Vec<f64> {
+        let mut expanded = vec![0.0; input.num_edges];
+        let component_edges = self.original_ids.iter().flat_map(|&v| input.edge_range(v));
+        for (e, &value) in component_edges.zip(values) {
+            expanded[e] = value;
+        }
+        expanded
+    }
+}
+
+/// Outputs of [`Analysis::run`]; a step that ran more than once keeps its last result, except BFS.
+///
+/// Results of steps after `largest_component` are indexed by the nodes of [`AnalysisResults::component`].
+#[derive(Default)]
+pub struct AnalysisResults {
+    pub stats: Option<GraphStats>,
+    pub wcc: Option<WccResult>,
+    pub component: Option<Component>,
+    /// Every BFS run, with its source as an id of the input graph.
+    pub bfs: Vec<(usize, BfsResult)>,
+    pub pagerank: Option<PageRankResult>,
+    pub bridges: Option<BridgeResult>,
+    pub closeness: Option<Vec<f64>>,
+    pub centrality: Option<CentralityReport>,
+}
+
+impl<'g> Analysis<'g> {
+    /// Empty analysis running the parallel implementations with [`Config::global`]'s thread count.
+    pub fn new(graph: &'g Graph) -> Self {
+        Self { graph, steps: Vec::new(), num_threads: Some(Config::global().threads), policy: ParallelismPolicy::Auto }
+    }
+
+    /// Worker threads for BFS, WCC and PageRank.
+    pub fn threads(mut self, num_threads: usize) -> Self {
+        self.num_threads = Some(num_threads.max(1));
+        self
+    }
+
+    /// Runs BFS, WCC and PageRank with the sequential implementations.
+    pub fn sequential(mut self) -> Self {
+        self.num_threads = None;
+        self
+    }
+
+    /// Small-graph fallback policy for the parallel implementations.
+    pub fn policy(mut self, policy: ParallelismPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn stats(mut self) -> Self {
+        self.steps.push(Step::Stats);
+        self
+    }
+
+    pub fn wcc(mut self) -> Self {
+        self.steps.push(Step::Wcc);
+        self
+    }
+
+    /// Continues on the largest weakly connected component.
+    pub fn largest_component(mut self) -> Self {
+        self.steps.push(Step::LargestComponent);
+        self
+    }
+
+    /// BFS from `source`, a node id of the input graph even after `largest_component`.
+    pub fn bfs(mut self, source: usize) -> Self {
+        self.steps.push(Step::Bfs(source));
+        self
+    }
+
+    pub fn pagerank(mut self, config: PageRankConfig) -> Self {
+        self.steps.push(Step::PageRank(config));
+        self
+    }
+
+    pub fn bridges(mut self) -> Self {
+        self.steps.push(Step::Bridges);
+        self
+    }
+
+    /// Harmonic closeness from `samples` BFS sources (see
+    /// [`crate::algorithms::centrality::approximate_closeness`]).
+    pub fn closeness(mut self, samples: usize) -> Self {
+        self.steps.push(Step::Closeness(samples));
+        self
+    }
+
+    pub fn centrality(mut self, config: CentralityConfig) -> Self {
+        self.steps.push(Step::Centrality(config));
+        self
+    }
+
+    /// Runs the steps in order. Fails with [`Error::InvalidNode`] if a BFS source is out of range or
+    /// was dropped by `largest_component`.
+    #[tracing::instrument(level = "debug", skip_all, fields(steps = self.steps.len()))]
+    pub fn run(self) -> Result<AnalysisResults> {
+        let mut results = AnalysisResults::default();
+        let mut graph = Cow::Borrowed(self.graph);
+        let mut original_ids: Option<Vec<usize>> = None;
+        let mut transpose: Option<Graph> = None;
+        // Whether `results.wcc` describes the current graph
+        let mut wcc_current = false;
+        let threads = self.num_threads.unwrap_or(1);
+
+        for step in self.steps {
+            match step {
+                Step::Stats => results.stats = Some(GraphStats::compute(&graph)),
+                Step::Wcc => {
+                    results.wcc = Some(match self.num_threads {
+                        Some(threads) => wcc_parallel_with_policy(&graph, threads, self.policy),
+                        None => wcc_sequential(&*graph),
+                    });
+                    wcc_current = true;
+                }
+                Step::LargestComponent => {
+                    let nodes = match &results.wcc {
+                        Some(wcc) if wcc_current => wcc.largest_component(),
+                        _ => wcc_sequential(&*graph).largest_component(),
+                    };
+                    graph = Cow::Owned(induced_subgraph(&graph, &nodes));
+                    original_ids = Some(match original_ids {
+                        Some(ids) => nodes.iter().map(|&v| ids[v]).collect(),
+                        None => nodes,
+                    });
+                    transpose = None;
+                    wcc_current = false;
+                }
+                Step::Bfs(source) => {
+                    let start = match &original_ids {
+                        Some(ids) => ids
+                            .iter()
+                            .position(|&id| id == source)
+                            .ok_or(Error::InvalidNode { node: source, num_nodes: self.graph.num_nodes })?,
+                        None => source,
+                    };
+                    let result = match self.num_threads {
+                        Some(threads) => bfs_parallel_with_policy(&graph, start, threads, self.policy)?,
+                        None => bfs_sequential(&*graph, start)?,
+                    };
+                    results.bfs.push((source, result));
+                }
+                Step::PageRank(config) => {
+                    results.pagerank = Some(match self.num_threads {
+                        Some(threads) => pagerank_parallel_with_policy(&graph, &config, threads, self.policy),
+                        None => pagerank_sequential(&*graph, &config),
+                    });
+                }
+                Step::Bridges => results.bridges = Some(find_bridges(&*graph)),
+                Step::Closeness(samples) => {
+                    let transposed = transpose.get_or_insert_with(|| graph.transpose());
+                    results.closeness = Some(closeness_from_transpose(transposed, samples, threads));
+                }
+                // The component restriction builds its own transpose
+                Step::Centrality(config) if config.largest_component => results.centrality = Some(centrality_report(&graph, &config)),
+                Step::Centrality(config) => {
+                    let transposed = transpose.get_or_insert_with(|| graph.transpose());
+                    results.centrality = Some(centrality_report_with_transpose(&graph, transposed, &config));
+                }
+            }
+        }
+
+        if let Some(original_ids) = original_ids {
+            results.component = Some(Component { graph: graph.into_owned(), original_ids, input_nodes: self.graph.num_nodes });
+        }
+        Ok(results)
+    }
+}
@@ -0,0 +1,186 @@
+use crate::algorithms::bfs::BfsResult;
+use crate::algorithms::pagerank::PageRankResult;
+use crate::algorithms::wcc::WccResult;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const CACHE_MAGIC: &[u8; 8] = b"FTNCACH1";
+
+/// An algorithm result that can be stored in a [`ResultCache`] or a binary result file
+/// ([`crate::results`]).
+pub trait Cached: Sized {
+    /// Short algorithm name used in the cache file name.
+    const KIND: &'static str;
+
+    fn encode(&self) -> Vec<u8>;
+
+    /// Inverse of [`Cached::encode`]; `None` for truncated or foreign bytes.
+    fn decode(bytes: &[u8]) -> Option<Self>;
+}
+
+/// Directory of algorithm results keyed by [`crate::graph::graph::Graph::fingerprint`], algorithm and
+/// parameters, so re-running an algorithm on an unchanged snapshot is a file read.
+///
+/// Entries are never invalidated: a changed graph has a different fingerprint and simply misses. Delete
+/// the directory to reclaim space.
+#[derive(Debug, Clone)]
+pub struct ResultCache {
+    dir: PathBuf,
+}
+
+impl ResultCache {
+    /// Opens (and creates if needed) the cache directory.
+    pub fn new(dir: impl AsRef<Path>) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir.as_ref())?;
+        Ok(Self { dir: dir.as_ref().to_path_buf() })
+    }
+
+    /// File holding the `T` result for a graph and parameter string, e.g. `source=5,mode=par`.
+    pub fn path<T: Cached>(&self, fingerprint: u64, params: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}-{}-{:016x}.bin", fingerprint, T::KIND, params_hash(params)))
+    }
+
+    /// Cached result, or `None` on a miss. Unreadable entries count as misses.
+    pub fn get<T: Cached>(&self, fingerprint: u64, params: &str) -> Option<T> {
+        let path = self.path::<T>(fingerprint, params);
+        let bytes = std::fs::read(&path).ok()?;
+        let value = bytes.strip_prefix(CACHE_MAGIC.as_slice()).and_then(T::decode);
+        match &value {
+            Some(_) => tracing::debug!(path = %path.display(), "result cache hit"),
+            None => tracing::warn!(path = %path.display(), "ignoring corrupt result cache entry"),
+        }
+        value
+    }
+
+    /// Stores a result; written to a temporary file first so readers never see half an entry.
+    pub fn put<T: Cached>(&self, fingerprint: u64, params: &str, value: &T) -> std::io::Result<()> {
+        let path = self.path::<T>(fingerprint, params);
+        let tmp = path.with_extension("tmp");
+        let mut bytes = CACHE_MAGIC.to_vec();
+        bytes.extend(value.encode());
+        std::fs::write(&tmp, bytes)?;
+        std::fs::rename(&tmp, &path)
+    }
+
+    /// Cached result if present, otherwise runs `compute` and stores its result. A failed store only
+    /// logs a warning.
+    pub fn get_or_insert_with<T: Cached, E>(
+        &self,
+        fingerprint: u64,
+        params: &str,
+        compute: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, E> {
+        if let Some(value) = self.get(fingerprint, params) {
+            return Ok(value);
+        }
+        let value = compute()?;
+        if let Err(e) = self.put(fingerprint, params, &value) {
+            tracing::warn!("failed to store {} result in cache: {}", T::KIND, e);
+        }
+        Ok(value)
+    }
+}
+
+/// FNV-1a; file names must not depend on `std`'s randomly seeded hashers.
+fn params_hash(params: &str) -> u64 {
+    params
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |h, b| (h ^ b as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// Little-endian reader over an encoded entry.
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn word(&mut self) -> Option<u64> {
+        let (head, rest) = self.0.split_first_chunk::<8>()?;
+        self.0 = rest;
+        Some(u64::from_le_bytes(*head))
+    }
+
+    /// Remaining bytes as exactly `len` values of `N` bytes each.
+    fn values<const N: usize>(self, len: usize) -> Option<impl Iterator<Item = [u8; N]>> {
+        (self.0.len() == len.checked_mul(N)?).then(|| self.0.chunks_exact(N).map(|c| c.try_into().unwrap()))
+    }
+}
+
+impl Cached for BfsResult {
+    const KIND: &'static str = "bfs";
+
+    /// Elapsed time (ns), node count, then the distances (i32).
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(16 + self.distances.len() * 4);
+        bytes.extend((self.elapsed.as_nanos() as u64).to_le_bytes());
+        bytes.extend((self.distances.len() as u64).to_le_bytes());
+        for &d in &self.distances {
+            bytes.extend(d.to_le_bytes());
+        }
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader(bytes);
+        let elapsed = Duration::from_nanos(reader.word()?);
+        let n = reader.word()? as usize;
+        let distances = reader.values(n)?.map(i32::from_le_bytes).collect();
+        Some(BfsResult::from_distances(distances, elapsed))
+    }
+}
+
+impl Cached for WccResult {
+    const KIND: &'static str = "wcc";
+
+    /// Node count, then the component labels.
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(8 + self.labels.len() * 8);
+        bytes.extend((self.labels.len() as u64).to_le_bytes());
+        for &label in &self.labels {
+            bytes.extend((label as u64).to_le_bytes());
+        }
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader(bytes);
+        let n = reader.word()? as usize;
+        let labels: Vec<usize> = reader.values(n)?.map(|w| u64::from_le_bytes(w) as usize).collect();
+        if labels.iter().any(|&l| l >= n) {
+            return None;
+        }
+        Some(WccResult::from_labels(labels))
+    }
+}
+
+impl Cached for PageRankResult {
+    const KIND: &'static str = "pagerank";
+
+    /// Iterations, converged flag, final delta, the length and entries of the convergence history,
+    /// node count, then the ranks.
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(40 + (self.ranks.len() + self.history.len()) * 8);
+        bytes.extend((self.iterations as u64).to_le_bytes());
+        bytes.extend(u64::from(self.converged).to_le_bytes());
+        bytes.extend(self.final_delta.to_le_bytes());
+        bytes.extend((self.history.len() as u64).to_le_bytes());
+        for &delta in &self.history {
+            bytes.extend(delta.to_le_bytes());
+        }
+        bytes.extend((self.ranks.len() as u64).to_le_bytes());
+        for &r in &self.ranks {
+            bytes.extend(r.to_le_bytes());
+        }
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader(bytes);
+        let iterations = reader.word()? as usize;
+        let converged = reader.word()? != 0;
+        let final_delta = f64::from_bits(reader.word()?);
+        let steps = reader.word()? as usize;
+        let history = (0..steps).map(|_| reader.word().map(f64::from_bits)).collect::<Option<_>>()?;
+        let n = reader.word()? as usize;
+        let ranks = reader.values(n)?.map(f64::from_le_bytes).collect();
+        Some(PageRankResult { ranks, iterations, converged, final_delta, history })
+    }
+}
@@ -1,100 +1,984 @@
-use clap::{Parser, Subcommand};
+use crate::config::{Compression, Config, Connectivity, Mode, Threads};
+use crate::graph::graph::LoadOptions;
+use crate::graph::formats::GraphFormat;
+use clap::{Args, Parser, Subcommand};
 
 #[derive(Parser)]
 #[command(name = "fast_transit_network")]
 #[command(about = "Graph analytics tool for FastTransitNetwork", long_about = None)]
+#[command(arg_required_else_help = true)]
 pub struct Cli {
+    /// Subcommand to run; required unless `--config` is given
     #[command(subcommand)]
-    pub command: Commands,
+    pub command: Option<Commands>,
+    
+    /// Run the steps listed in a TOML run file (see `run_config`) instead of a single subcommand
+    #[arg(long)]
+    pub config: Option<String>,
+    
+    /// Verbose output: debug logs, per-level and per-iteration progress
+    #[arg(short, long, global = true, conflicts_with = "quiet")]
+    pub verbose: bool,
+    
+    /// Only log warnings and errors
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+    
+    /// Minimum node count for parallel BFS (smaller graphs run sequentially)
+    #[arg(long, global = true)]
+    pub bfs_min_nodes: Option<usize>,
+    
+    /// Minimum frontier size for expanding a BFS level in parallel
+    #[arg(long, global = true)]
+    pub bfs_min_frontier: Option<usize>,
+    
+    /// Minimum node count for parallel WCC
+    #[arg(long, global = true)]
+    pub wcc_min_nodes: Option<usize>,
+    
+    /// Minimum node count for parallel PageRank
+    #[arg(long, global = true)]
+    pub pagerank_min_nodes: Option<usize>,
+    
+    /// Maximum worker threads for parallel PageRank
+    #[arg(long, global = true)]
+    pub pagerank_max_threads: Option<usize>,
+    
+    /// Minimum number of nodes per parallel task (default: nodes / threads)
+    #[arg(long, global = true)]
+    pub chunk_size: Option<usize>,
+    
+    /// Minimum edge count for building the loaded graph in parallel
+    #[arg(long, global = true)]
+    pub build_min_edges: Option<usize>,
+    
+    /// Compress result files while writing them (gz or zst; the extension is added to the file names)
+    #[arg(long, global = true, value_enum)]
+    pub compress: Option<Compression>,
+    
+    /// Split per-node result files into this many shards (out.part-000.txt, ...) written in parallel
+    #[arg(long, global = true)]
+    pub shards: Option<usize>,
+    
+    /// Nodes listed at each end by --output-format summary (default: 10)
+    #[arg(long, global = true)]
+    pub summary_top_k: Option<usize>,
+    
+    /// Quantiles written by --output-format summary (default: 0,0.25,0.5,0.75,0.9,0.99,1)
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub summary_quantiles: Option<Vec<f64>>,
+    
+    /// Reuse BFS/WCC/PageRank results stored here for the same graph and parameters
+    #[arg(long, global = true)]
+    pub cache_dir: Option<String>,
+    
+    /// Allocate `max id + 1` nodes even for sparse node ids instead of remapping them (trusted files only)
+    #[arg(long, global = true)]
+    pub assume_dense: bool,
+    
+    /// Drop self-loop edges while loading (they are always counted)
+    #[arg(long, global = true)]
+    pub drop_self_loops: bool,
+    
+    /// Keep only the first of repeated `src dst` edges while loading (they are always counted)
+    #[arg(long, global = true)]
+    pub drop_duplicates: bool,
+    
+    /// Count and skip unparsable edge lines instead of failing
+    #[arg(long, global = true)]
+    pub skip_malformed: bool,
+    
+    /// Treat the edge list as undirected: every edge is stored in both directions
+    #[arg(long, global = true)]
+    pub undirected: bool,
+    
+    /// Sort each adjacency list after loading (edge lookups by binary search)
+    #[arg(long, global = true)]
+    pub sort_neighbors: bool,
+    
+    /// Input file format; detected from each file's first bytes and extension if not given
+    #[arg(long, global = true, value_enum)]
+    pub format: Option<GraphFormat>,
+    
+    /// Keep 64-bit neighbor ids for sequential BFS/WCC/PageRank (by default they use 32-bit ids when the graph fits)
+    #[arg(long, global = true)]
+    pub wide_storage: bool,
+}
+
+impl Cli {
+    /// Log level selected by `--verbose` / `--quiet`.
+    pub fn log_level(&self) -> tracing::Level {
+        if self.verbose {
+            tracing::Level::DEBUG
+        } else if self.quiet {
+            tracing::Level::WARN
+        } else {
+            tracing::Level::INFO
+        }
+    }
+    
+    /// Applies the global flags on top of `config` (usually [`Config::from_env`]).
+    pub fn apply_to(&self, mut config: Config) -> Config {
+        if let Some(v) = self.bfs_min_nodes {
+            config.bfs_min_nodes = v;
+        }
+        if let Some(v) = self.bfs_min_frontier {
+            config.bfs_min_frontier = v;
+        }
+        if let Some(v) = self.wcc_min_nodes {
+            config.wcc_min_nodes = v;
+        }
+        if let Some(v) = self.pagerank_min_nodes {
+            config.pagerank_min_nodes = v;
+        }
+        if let Some(v) = self.pagerank_max_threads {
+            config.pagerank_max_threads = v.max(1);
+        }
+        if let Some(v) = self.chunk_size {
+            config.chunk_size = Some(v.max(1));
+        }
+        if let Some(v) = self.build_min_edges {
+            config.build_min_edges = v;
+        }
+        if let Some(v) = self.compress {
+            config.compress = v;
+        }
+        if let Some(v) = self.shards {
+            config.shards = v;
+        }
+        if let Some(v) = self.summary_top_k {
+            config.summary.top_k = v;
+        }
+        if let Some(v) = &self.summary_quantiles {
+            config.summary.quantiles = v.clone();
+        }
+        config
+    }
+    
+    /// Turns on the loader flags given here on top of `options`.
+    pub fn load_options(&self, mut options: LoadOptions) -> LoadOptions {
+        options.assume_dense |= self.assume_dense;
+        options.drop_self_loops |= self.drop_self_loops;
+        options.drop_duplicates |= self.drop_duplicates;
+        options.skip_malformed |= self.skip_malformed;
+        options.undirected |= self.undirected;
+        options.sort_neighbors |= self.sort_neighbors;
+        options.wide_storage |= self.wide_storage;
+        if self.format.is_some() {
+            options.format = self.format;
+        }
+        options
+    }
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     /// Run BFS (Breadth-First Search)
-    Bfs {
-        /// Input graph file (edge list format)
-        #[arg(short, long)]
-        input: String,
-        
-        /// Source node for BFS
-        #[arg(short, long)]
-        source: usize,
-        
-        /// Mode: seq or par
-        #[arg(short, long, default_value = "seq")]
-        mode: String,
-        
-        /// Number of threads (for parallel mode)
-        #[arg(short, long, default_value_t = 4)]
-        threads: usize,
-        
-        /// Output file path
-        #[arg(short, long)]
-        out: String,
-    },
+    Bfs(BfsArgs),
     
     /// Run WCC (Weakly Connected Components)
-    Wcc {
-        /// Input graph file (edge list format)
-        #[arg(short, long)]
-        input: String,
-        
-        /// Mode: seq or par
-        #[arg(short, long, default_value = "seq")]
-        mode: String,
-        
-        /// Number of threads (for parallel mode)
-        #[arg(short, long, default_value_t = 4)]
-        threads: usize,
-        
-        /// Output file path
-        #[arg(short, long)]
-        out: String,
-    },
+    Wcc(WccArgs),
+    
+    /// Weakly or strongly connected components, with the `wcc` output files and flags
+    Connectivity(ConnectivityArgs),
+    
+    /// Print graph statistics without running any algorithm
+    Stats(StatsArgs),
+    
+    /// Estimate the hop-distance distribution, effective diameter and average distance (HyperANF)
+    Anf(AnfArgs),
+    
+    /// Check a graph for structural corruption, self-loops and duplicate edges; fails if it is corrupt
+    Validate(ValidateArgs),
+    
+    /// Write a smaller representative subgraph (node, edge, random-walk or forest-fire sampling)
+    Sample(SampleArgs),
+    
+    /// Convert a graph file to an edge list or the binary graph format
+    Convert(ConvertArgs),
+    
+    /// Show what a file holds: the header and metadata of a binary graph, or the kind of a result file
+    Inspect(InspectArgs),
+    
+    /// Compare two snapshots of the network: added/removed nodes and edges, degree and component changes
+    Diff(DiffArgs),
+    
+    /// Load a graph once and answer queries from stdin (neighbors, degree, path, dist)
+    Query(QueryArgs),
+    
+    /// Serve BFS distance, shortest path, PageRank and component lookups over HTTP
+    #[cfg(feature = "serve")]
+    Serve(ServeArgs),
+    
+    /// Compute all-pairs shortest path costs and write them as a binary matrix
+    Apsp(ApspArgs),
+    
+    /// Cheapest route between two nodes with at most N transfers (mode changes) and/or hops
+    Route(RouteArgs),
+    
+    /// Find every node reachable from a source within a cost budget (hops if the graph is unweighted)
+    Isochrone(IsochroneArgs),
+    
+    /// Pick k landmarks and save their distances for ALT (A* with landmarks) queries
+    Landmarks(LandmarksArgs),
+    
+    /// Compute the maximum flow and minimum cut between two nodes (edge weights are capacities)
+    Maxflow(MaxflowArgs),
+    
+    /// List bridges and cut vertices (links and stations whose failure disconnects the network)
+    Bridges(BridgesArgs),
+    
+    /// Split the nodes into k parts with few crossing edges and write the assignment
+    Partition(PartitionArgs),
     
     /// Run PageRank
-    Pagerank {
-        /// Input graph file (edge list format)
-        #[arg(short, long)]
-        input: String,
-        
-        /// Mode: seq, par, or par-opt
-        #[arg(short, long, default_value = "seq")]
-        mode: String,
-        
-        /// Number of threads (for parallel mode)
-        #[arg(short, long, default_value_t = 4)]
-        threads: usize,
-        
-        /// Output file path
-        #[arg(short, long)]
-        out: String,
-        
-        /// Damping factor (alpha)
-        #[arg(long, default_value_t = 0.85)]
-        alpha: f64,
-        
-        /// Maximum iterations
-        #[arg(long, default_value_t = 100)]
-        iters: usize,
-        
-        /// Convergence tolerance
-        #[arg(long, default_value_t = 1e-6)]
-        eps: f64,
-    },
+    Pagerank(PagerankArgs),
+    
+    /// Compute degree, PageRank, betweenness and closeness from one graph load into a per-node table
+    CentralityReport(CentralityReportArgs),
+    
+    /// Remove nodes in batches (by degree, PageRank, betweenness or at random) and write the giant-component curve
+    Robustness(RobustnessArgs),
+    
+    /// Generate a node2vec biased random-walk corpus (one walk per line) for embedding trainers
+    Node2vec(Node2vecArgs),
+    
+    /// Run any registered algorithm by name (see `algorithms`) and write its per-node output
+    Run(RunArgs),
+    
+    /// List the algorithms `run` and `benchmark` accept, with their parameters
+    Algorithms,
+    
+    /// Load a graph once and run a sequence of steps on it, writing every output to one directory
+    Pipeline(PipelineArgs),
     
     /// Run benchmark on all algorithms
-    Benchmark {
-        /// Input graph file
-        #[arg(short, long)]
-        input: String,
-        
-        /// Thread counts to test (comma-separated)
-        #[arg(short, long, default_value = "2,4,8,16")]
-        threads: String,
-        
-        /// Output CSV path for benchmark results (default: scripts/results/benchmark_results.csv)
-        #[arg(short, long, default_value = "scripts/results/benchmark_results.csv")]
-        out: String,
-    },
-}
\ No newline at end of file
+    Benchmark(BenchmarkArgs),
+    
+    /// Run the benchmark suite of a TOML or JSON file (graphs, sources, algorithms, thread counts) into one CSV
+    Suite(SuiteArgs),
+}
+
+/// Arguments of `bfs`.
+#[derive(Args, Debug, Clone)]
+pub struct BfsArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Source node for BFS
+    #[arg(short, long)]
+    pub source: usize,
+    
+    /// Mode: seq or par
+    #[arg(short, long, value_enum, default_value_t = Mode::Seq)]
+    pub mode: Mode,
+    
+    /// Number of threads or `auto` (for parallel mode; default: FTN_THREADS or 4)
+    #[arg(short, long)]
+    pub threads: Option<Threads>,
+    
+    /// Output file path
+    #[arg(short, long)]
+    pub out: String,
+    
+    /// Reorder nodes after loading for cache locality: degree, rcm, or gorder
+    #[arg(long)]
+    pub reorder: Option<String>,
+    
+    /// Always use the parallel implementation, even below the small-graph thresholds
+    #[arg(long)]
+    pub force_parallel: bool,
+    
+    /// Output format: text, json, ndjson, parquet (needs the `parquet` feature), summary (top/bottom nodes and quantiles), or binary (reload with `results::read_result`)
+    #[arg(long, default_value = "text")]
+    pub output_format: String,
+    
+    /// Write per-level frontier sizes, scanned edges and expansion times to this CSV (skips the result cache)
+    #[arg(long)]
+    pub levels_csv: Option<String>,
+    
+    #[command(flatten)]
+    pub histogram: HistogramArgs,
+}
+
+/// Arguments of `wcc`.
+#[derive(Args, Debug, Clone)]
+pub struct WccArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Mode: seq or par
+    #[arg(short, long, value_enum, default_value_t = Mode::Seq)]
+    pub mode: Mode,
+    
+    /// Number of threads or `auto` (for parallel mode; default: FTN_THREADS or 4)
+    #[arg(short, long)]
+    pub threads: Option<Threads>,
+    
+    /// Output file path
+    #[arg(short, long)]
+    pub out: String,
+    
+    /// Reorder nodes after loading for cache locality: degree, rcm, or gorder
+    #[arg(long)]
+    pub reorder: Option<String>,
+    
+    /// Always use the parallel implementation, even below the small-graph thresholds
+    #[arg(long)]
+    pub force_parallel: bool,
+    
+    /// Output format: text, json, ndjson, parquet (needs the `parquet` feature), or binary (reload with `results::read_result`)
+    #[arg(long, default_value = "text")]
+    pub output_format: String,
+    
+    /// Number components 0..k, largest first, instead of by root node id
+    #[arg(long)]
+    pub dense_ids: bool,
+    
+    /// Add each node's component size as a third column
+    #[arg(long)]
+    pub component_sizes: bool,
+    
+    /// Method: union-find, or label-propagation (min-label sweeps; often faster on grids and road networks)
+    #[arg(long, default_value = "union-find")]
+    pub method: String,
+}
+
+/// Arguments of `connectivity`.
+#[derive(Args, Debug, Clone)]
+pub struct ConnectivityArgs {
+    /// weak (ignore edge direction) or strong (follow it); strong always runs sequentially
+    #[arg(long, value_enum, default_value_t = Connectivity::Weak)]
+    pub kind: Connectivity,
+    
+    #[command(flatten)]
+    pub wcc: WccArgs,
+}
+
+/// Arguments of `stats`.
+#[derive(Args, Debug, Clone)]
+pub struct StatsArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Also bound the diameter (ignoring edge direction) with this many BFS double sweeps
+    #[arg(long)]
+    pub diameter_sweeps: Option<usize>,
+    
+    /// Number of threads or `auto` for the diameter sweeps (default: FTN_THREADS or 4)
+    #[arg(short, long)]
+    pub threads: Option<Threads>,
+    
+    #[command(flatten)]
+    pub histogram: HistogramArgs,
+}
+
+/// Arguments of `anf`.
+#[derive(Args, Debug, Clone)]
+pub struct AnfArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// log2 of the HyperLogLog registers per node (4-16); each step up halves the variance and doubles the memory
+    #[arg(long, default_value_t = 6)]
+    pub log2_registers: u32,
+    
+    /// Stop after this many hops even if the counters are still growing
+    #[arg(long, default_value_t = 1000)]
+    pub max_distance: usize,
+    
+    /// Fraction of connected pairs the effective diameter must cover
+    #[arg(long, default_value_t = 0.9)]
+    pub quantile: f64,
+    
+    /// Hash seed
+    #[arg(long, default_value_t = 42)]
+    pub seed: u64,
+    
+    /// Number of threads or `auto` (default: FTN_THREADS or 4)
+    #[arg(short, long)]
+    pub threads: Option<Threads>,
+    
+    /// Optional output CSV (distance, pairs, cumulative_pairs, cumulative_fraction)
+    #[arg(short, long)]
+    pub out: Option<String>,
+}
+
+/// Arguments of `validate`.
+#[derive(Args, Debug, Clone)]
+pub struct ValidateArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Optional JSON report path
+    #[arg(short, long)]
+    pub out: Option<String>,
+}
+
+/// Arguments of `sample`.
+#[derive(Args, Debug, Clone)]
+pub struct SampleArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Number of nodes in the sample
+    #[arg(short, long)]
+    pub nodes: usize,
+    
+    /// Method: node, edge, walk (random walk with restarts) or forest-fire
+    #[arg(short, long, default_value = "forest-fire")]
+    pub method: String,
+    
+    /// RNG seed
+    #[arg(long, default_value_t = 42)]
+    pub seed: u64,
+    
+    /// Output edge list (renumbered); original ids go to <out>_ids.txt
+    #[arg(short, long)]
+    pub out: String,
+}
+
+/// Arguments of `convert`.
+#[derive(Args, Debug, Clone)]
+pub struct ConvertArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Output format: edge-list or binary
+    #[arg(long, value_enum, default_value_t = GraphFormat::Binary)]
+    pub to: GraphFormat,
+    
+    /// Output graph file
+    #[arg(short, long)]
+    pub out: String,
+    
+    /// Name stored in the metadata of a binary graph (shown by `inspect`)
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+/// Arguments of `inspect`.
+#[derive(Args, Debug, Clone)]
+pub struct InspectArgs {
+    /// Binary graph, binary result or any other graph file
+    pub file: String,
+}
+
+/// Arguments of `diff`.
+#[derive(Args, Debug, Clone)]
+pub struct DiffArgs {
+    /// Older snapshot (edge list)
+    pub old: String,
+    
+    /// Newer snapshot (edge list)
+    pub new: String,
+    
+    /// Optional output file listing every change, one per line
+    #[arg(short, long)]
+    pub out: Option<String>,
+}
+
+/// Arguments of `query`.
+#[derive(Args, Debug, Clone)]
+pub struct QueryArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+}
+
+#[cfg(feature = "serve")]
+/// Arguments of `serve`.
+#[derive(Args, Debug, Clone)]
+pub struct ServeArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    pub addr: String,
+}
+
+/// Arguments of `apsp`.
+#[derive(Args, Debug, Clone)]
+pub struct ApspArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Method: fw (blocked Floyd-Warshall, allows negative weights) or sssp (parallel BFS/Dijkstra per source)
+    #[arg(short, long, default_value = "sssp")]
+    pub method: String,
+    
+    /// Number of threads or `auto` (default: FTN_THREADS or 4)
+    #[arg(short, long)]
+    pub threads: Option<Threads>,
+    
+    /// Output path for the binary distance matrix
+    #[arg(short, long)]
+    pub out: String,
+}
+
+/// Arguments of `route`.
+#[derive(Args, Debug, Clone)]
+pub struct RouteArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format; a fourth edge-list column `type` gives each edge's mode); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Source node
+    #[arg(short, long)]
+    pub source: usize,
+    
+    /// Target node
+    #[arg(short, long)]
+    pub target: usize,
+    
+    /// Maximum number of transfers between modes
+    #[arg(long)]
+    pub max_transfers: Option<usize>,
+    
+    /// Maximum number of edges
+    #[arg(long)]
+    pub max_hops: Option<usize>,
+}
+
+/// Arguments of `isochrone`.
+#[derive(Args, Debug, Clone)]
+pub struct IsochroneArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Source node
+    #[arg(short, long)]
+    pub source: usize,
+    
+    /// Cost budget (travel time, distance or hops)
+    #[arg(long)]
+    pub max_cost: f64,
+    
+    /// Node coordinates (`id lon lat` per line); with it the output is GeoJSON
+    #[arg(short, long)]
+    pub coords: Option<String>,
+    
+    /// Optional output file (node cost lines, or GeoJSON with --coords)
+    #[arg(short, long)]
+    pub out: Option<String>,
+}
+
+/// Arguments of `landmarks`.
+#[derive(Args, Debug, Clone)]
+pub struct LandmarksArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Number of landmarks
+    #[arg(short = 'k', long, default_value_t = 16)]
+    pub landmarks: usize,
+    
+    /// Output path for the binary landmark file
+    #[arg(short, long)]
+    pub out: String,
+}
+
+/// Arguments of `maxflow`.
+#[derive(Args, Debug, Clone)]
+pub struct MaxflowArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Source node
+    #[arg(short, long)]
+    pub source: usize,
+    
+    /// Sink node
+    #[arg(short = 't', long)]
+    pub sink: usize,
+    
+    /// Optional output file for the minimum-cut edges (from, to, capacity)
+    #[arg(short, long)]
+    pub out: Option<String>,
+}
+
+/// Arguments of `bridges`.
+#[derive(Args, Debug, Clone)]
+pub struct BridgesArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// How many of each to print, most critical first
+    #[arg(long, default_value_t = 20)]
+    pub top: usize,
+    
+    /// Optional JSON output with every bridge and cut vertex
+    #[arg(short, long)]
+    pub out: Option<String>,
+}
+
+/// Arguments of `partition`.
+#[derive(Args, Debug, Clone)]
+pub struct PartitionArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Number of parts
+    #[arg(short = 'k', long)]
+    pub parts: usize,
+    
+    /// Method: ldg (streaming greedy) or bfs (BFS-grown regions)
+    #[arg(short, long, default_value = "ldg")]
+    pub method: String,
+    
+    /// Output file path (node, part)
+    #[arg(short, long)]
+    pub out: String,
+}
+
+/// Arguments of `pagerank`.
+#[derive(Args, Debug, Clone)]
+pub struct PagerankArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Mode: seq, par, or par-opt
+    #[arg(short, long, value_enum, default_value_t = Mode::Seq)]
+    pub mode: Mode,
+    
+    /// Number of threads or `auto` (for parallel mode; default: FTN_THREADS or 4)
+    #[arg(short, long)]
+    pub threads: Option<Threads>,
+    
+    /// Output file path
+    #[arg(short, long)]
+    pub out: String,
+    
+    /// Reorder nodes after loading for cache locality: degree, rcm, or gorder
+    #[arg(long)]
+    pub reorder: Option<String>,
+    
+    /// Always use the parallel implementation, even below the small-graph thresholds
+    #[arg(long)]
+    pub force_parallel: bool,
+    
+    /// Output format: text, json, ndjson, parquet (needs the `parquet` feature), summary (top/bottom nodes and quantiles), or binary (reload with `results::read_result`)
+    #[arg(long, default_value = "text")]
+    pub output_format: String,
+    
+    /// Damping factor (alpha)
+    #[arg(long, default_value_t = 0.85)]
+    pub alpha: f64,
+    
+    /// Maximum iterations
+    #[arg(long, default_value_t = 100)]
+    pub iters: usize,
+    
+    /// Convergence tolerance
+    #[arg(long, default_value_t = 1e-6)]
+    pub eps: f64,
+    
+    /// Save the rank vector to this binary file during the run (use the same --reorder when resuming)
+    #[arg(long)]
+    pub checkpoint: Option<String>,
+    
+    /// Iterations between checkpoints
+    #[arg(long, default_value_t = 10)]
+    pub checkpoint_every: usize,
+    
+    /// Continue from the --checkpoint file if it exists instead of starting over
+    #[arg(long)]
+    pub resume: bool,
+    
+    /// Split each node's rank over its out-edges by edge weight (e.g. passenger volume) instead of evenly
+    #[arg(long)]
+    pub weighted: bool,
+    
+    /// Rank only the largest weakly connected component (WCC first); every other node gets rank 0
+    #[arg(long)]
+    pub largest_component: bool,
+    
+    #[command(flatten)]
+    pub histogram: HistogramArgs,
+}
+
+/// Arguments of `centrality-report`.
+#[derive(Args, Debug, Clone)]
+pub struct CentralityReportArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Number of threads or `auto` (default: FTN_THREADS or 4)
+    #[arg(short, long)]
+    pub threads: Option<Threads>,
+    
+    /// BFS sources sampled for betweenness and closeness (node count or more = exact)
+    #[arg(long, default_value_t = 256)]
+    pub samples: usize,
+    
+    /// Output file path
+    #[arg(short, long)]
+    pub out: String,
+    
+    /// Output format: text, json, ndjson, or parquet (needs the `parquet` feature)
+    #[arg(long, default_value = "text")]
+    pub output_format: String,
+    
+    /// PageRank damping factor (alpha)
+    #[arg(long, default_value_t = 0.85)]
+    pub alpha: f64,
+    
+    /// Also write edge betweenness as an edge list `src dst score`, loadable as a weighted graph
+    #[arg(long)]
+    pub edge_scores: Option<String>,
+    
+    /// Score only the largest weakly connected component; every other node gets 0
+    #[arg(long)]
+    pub largest_component: bool,
+}
+
+/// Arguments of `robustness`.
+#[derive(Args, Debug, Clone)]
+pub struct RobustnessArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Removal order: degree, pagerank, betweenness, or random
+    #[arg(short, long, default_value = "degree")]
+    pub strategy: String,
+    
+    /// Number of removal batches
+    #[arg(long, default_value_t = 100)]
+    pub steps: usize,
+    
+    /// Stop after removing this fraction of the nodes
+    #[arg(long, default_value_t = 1.0)]
+    pub max_fraction: f64,
+    
+    /// Seed for random removal
+    #[arg(long, default_value_t = 42)]
+    pub seed: u64,
+    
+    /// BFS sources sampled for betweenness
+    #[arg(long, default_value_t = 256)]
+    pub samples: usize,
+    
+    /// Number of threads or `auto` (default: FTN_THREADS or 4)
+    #[arg(short, long)]
+    pub threads: Option<Threads>,
+    
+    /// Output CSV (removed, removed_fraction, giant_fraction, components)
+    #[arg(short, long)]
+    pub out: String,
+}
+
+/// Arguments of `node2vec`.
+#[derive(Args, Debug, Clone)]
+pub struct Node2vecArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Return parameter p
+    #[arg(long, default_value_t = 1.0)]
+    pub p: f64,
+    
+    /// In-out parameter q
+    #[arg(long, default_value_t = 1.0)]
+    pub q: f64,
+    
+    /// Steps per walk
+    #[arg(long, default_value_t = 80)]
+    pub walk_length: usize,
+    
+    /// Walks started from every node
+    #[arg(long, default_value_t = 10)]
+    pub walks_per_node: usize,
+    
+    /// RNG seed
+    #[arg(long, default_value_t = 42)]
+    pub seed: u64,
+    
+    /// Number of threads or `auto` (default: FTN_THREADS or 4)
+    #[arg(short, long)]
+    pub threads: Option<Threads>,
+    
+    /// Output corpus path
+    #[arg(short, long)]
+    pub out: String,
+}
+
+/// Arguments of `run`.
+#[derive(Args, Debug, Clone)]
+pub struct RunArgs {
+    /// Algorithm name, e.g. bfs, wcc, scc, pagerank, closeness, betweenness
+    pub algorithm: String,
+    
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Algorithm parameters as key=value (repeatable or comma-separated), e.g. -p mode=par,threads=8
+    #[arg(short, long = "param", value_delimiter = ',')]
+    pub params: Vec<String>,
+    
+    /// Output file path
+    #[arg(short, long)]
+    pub out: String,
+    
+    /// Output format: text, json, ndjson, parquet (needs the `parquet` feature), or summary (top/bottom nodes and quantiles)
+    #[arg(long, default_value = "text")]
+    pub output_format: String,
+}
+
+/// Arguments of `pipeline`.
+#[derive(Args, Debug, Clone)]
+pub struct PipelineArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Comma-separated steps: stats, wcc, largest-component (lcc), bfs:<source>, pagerank, bridges, export
+    #[arg(short, long)]
+    pub steps: String,
+    
+    /// Output directory
+    #[arg(short, long)]
+    pub out: String,
+    
+    /// Mode for BFS, WCC and PageRank: seq or par
+    #[arg(short, long, value_enum, default_value_t = Mode::Par)]
+    pub mode: Mode,
+    
+    /// Number of threads or `auto` (for parallel mode; default: FTN_THREADS or 4)
+    #[arg(short, long)]
+    pub threads: Option<Threads>,
+    
+    /// Always use the parallel implementation, even below the small-graph thresholds
+    #[arg(long)]
+    pub force_parallel: bool,
+    
+    /// Output format for per-node results: text, json, ndjson, or parquet (needs the `parquet` feature)
+    #[arg(long, default_value = "text")]
+    pub output_format: String,
+    
+    /// PageRank damping factor (alpha)
+    #[arg(long, default_value_t = 0.85)]
+    pub alpha: f64,
+    
+    /// PageRank maximum iterations
+    #[arg(long, default_value_t = 100)]
+    pub iters: usize,
+    
+    /// PageRank convergence tolerance
+    #[arg(long, default_value_t = 1e-6)]
+    pub eps: f64,
+    
+    /// PageRank: split each node's rank over its out-edges by edge weight
+    #[arg(long)]
+    pub weighted: bool,
+}
+
+/// Arguments of `benchmark`.
+#[derive(Args, Debug, Clone)]
+pub struct BenchmarkArgs {
+    /// Input graph file (edge list, METIS, Matrix Market or binary; see --format); repeat (or comma-separate) to merge several files
+    #[arg(short, long, required_unless_present = "graphs", value_delimiter = ',')]
+    pub input: Vec<String>,
+    
+    /// Directory or glob (`*`, `?` in the file name, e.g. `scripts/data/small/*.txt`) of graphs to benchmark one by one into the same CSV; repeat (or comma-separate) for several
+    #[arg(long, value_delimiter = ',')]
+    pub graphs: Vec<String>,
+    
+    /// Benchmark up to this many small graphs (see --small-graph-edges) at the same time; larger graphs always run alone. Concurrent runs share the CPU and the peak-memory counter, so use it for quick sweeps rather than final timings
+    #[arg(long, default_value = "1")]
+    pub graph_jobs: usize,
+    
+    /// Graphs with at most this many edges count as small for --graph-jobs
+    #[arg(long, default_value = "1000000")]
+    pub small_graph_edges: usize,
+    
+    /// Thread counts to test (comma-separated); `auto` expands to powers of two up to the CPU count
+    #[arg(short, long, default_value = "auto")]
+    pub threads: String,
+    
+    /// Algorithms to benchmark (comma-separated: bfs, wcc, pagerank, triangles, or any name from `algorithms`)
+    #[arg(short, long, default_value = "bfs,wcc,pagerank")]
+    pub algorithms: String,
+    
+    /// BFS source node, in input-file ids (default: the first node)
+    #[arg(short, long)]
+    pub source: Option<usize>,
+    
+    /// Timed repetitions per run; timings report min/mean/stddev
+    #[arg(long, default_value = "1")]
+    pub repeat: usize,
+    
+    /// Untimed warmup runs before the timed repetitions
+    #[arg(long, default_value = "0")]
+    pub warmup: usize,
+    
+    /// Previous benchmark CSV to compare against; exits non-zero on regressions
+    #[arg(long)]
+    pub baseline: Option<String>,
+    
+    /// Relative slowdown (of mean time) counted as a regression, e.g. 0.1 = 10%
+    #[arg(long, default_value = "0.1")]
+    pub regression_threshold: f64,
+    
+    /// Output CSV path for benchmark results (default: scripts/results/benchmark_results.csv)
+    #[arg(short, long, default_value = "scripts/results/benchmark_results.csv")]
+    pub out: String,
+    
+    /// Reorder nodes after loading for cache locality: degree, rcm, or gorder
+    #[arg(long)]
+    pub reorder: Option<String>,
+    
+    /// Always use the parallel implementation, even below the small-graph thresholds
+    #[arg(long)]
+    pub force_parallel: bool,
+}
+
+/// Arguments of `suite`.
+#[derive(Args, Debug, Clone)]
+pub struct SuiteArgs {
+    /// Suite file (TOML, or JSON if it ends in .json; see `suite::Suite`)
+    pub file: String,
+    
+    /// Output CSV path, instead of the suite's `out`
+    #[arg(short, long)]
+    pub out: Option<String>,
+    
+    /// Previous benchmark CSV to compare against, instead of the suite's `baseline`; exits non-zero on regressions
+    #[arg(long)]
+    pub baseline: Option<String>,
+}
+
+/// `--histogram` options of the commands that can write a distribution of their per-node values.
+#[derive(Args, Debug, Clone)]
+pub struct HistogramArgs {
+    /// Also write a histogram of the per-node values (out-degrees for stats) to this file: CSV, or JSON if it ends in .json
+    #[arg(long)]
+    pub histogram: Option<String>,
+    
+    /// Number of histogram bins
+    #[arg(long, default_value_t = 20)]
+    pub histogram_bins: usize,
+    
+    /// Logarithmic histogram bins, for heavy-tailed values such as degrees and ranks (values <= 0 are left out)
+    #[arg(long)]
+    pub log_bins: bool,
+}
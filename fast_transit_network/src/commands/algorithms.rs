@@ -0,0 +1,22 @@
+use super::Context;
+use crate::registry::Registry;
+use anyhow::Result;
+
+/// Outcome of `algorithms`.
+#[derive(Debug)]
+pub struct Report {
+    /// Registered names in listing order.
+    pub names: Vec<&'static str>,
+}
+
+pub fn run(_ctx: &Context) -> Result<Report> {
+    let registry = Registry::builtin();
+    for algorithm in registry.iter() {
+        println!("{:<12} {}", algorithm.name(), algorithm.about());
+        if !algorithm.params().is_empty() {
+            println!("{:<12} params: {}", "", algorithm.params().join(", "));
+        }
+    }
+    
+    Ok(Report { names: registry.iter().map(|a| a.name()).collect() })
+}
@@ -0,0 +1,54 @@
+use super::{load_graph, thread_count, Context};
+use crate::algorithms::anf::{approximate_neighborhood_function, AnfConfig, NeighborhoodFunction};
+use crate::cli::AnfArgs;
+use crate::config::Workload;
+use crate::utils::io::{output_path, write_neighborhood_function_csv};
+use anyhow::Result;
+use std::time::Instant;
+
+/// Outcome of `anf`.
+#[derive(Debug)]
+pub struct Report {
+    pub function: NeighborhoodFunction,
+    /// Interpolated hop count covering `--quantile` of the connected pairs.
+    pub effective_diameter: f64,
+    pub average_distance: f64,
+}
+
+pub fn run(args: &AnfArgs, ctx: &Context) -> Result<Report> {
+    anyhow::ensure!(
+        args.quantile > 0.0 && args.quantile <= 1.0,
+        "--quantile must be in (0, 1], got {}",
+        args.quantile
+    );
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    let config = AnfConfig {
+        log2_registers: args.log2_registers,
+        max_distance: args.max_distance,
+        seed: args.seed,
+        num_threads: thread_count(args.threads, Workload::Other),
+    };
+    
+    println!("\nRunning HyperANF with 2^{} registers per node...", config.log2_registers.clamp(4, 16));
+    let start = Instant::now();
+    let function = approximate_neighborhood_function(&graph, &config);
+    println!("Completed {} hops in {:?}", function.pairs.len() - 1, start.elapsed());
+    if !function.converged {
+        println!("Warning: still growing after --max-distance {} hops; the statistics below are truncated", args.max_distance);
+    }
+    
+    let effective_diameter = function.effective_diameter(args.quantile);
+    let average_distance = function.average_distance();
+    let self_pairs = function.pairs[0];
+    let reachable = function.pairs.last().copied().unwrap_or(0.0) - self_pairs;
+    println!("Reachable pairs (estimate): {:.0}", reachable.max(0.0));
+    println!("Effective diameter ({:.0}th percentile): {:.2}", args.quantile * 100.0, effective_diameter);
+    println!("Average distance: {:.2}", average_distance);
+    
+    if let Some(out) = &args.out {
+        write_neighborhood_function_csv(&function, out)?;
+        println!("Distance distribution saved to: {}", output_path(out));
+    }
+    
+    Ok(Report { function, effective_diameter, average_distance })
+}
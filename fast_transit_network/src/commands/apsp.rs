@@ -0,0 +1,36 @@
+use super::{load_graph, thread_count, Context};
+use crate::algorithms::apsp::{apsp, ApspMethod, DistanceMatrix};
+use crate::cli::ApspArgs;
+use crate::config::Workload;
+use anyhow::Result;
+use std::time::Instant;
+
+/// Outcome of `apsp`.
+#[derive(Debug)]
+pub struct Report {
+    pub matrix: DistanceMatrix,
+    /// Ordered pairs with a finite distance, including every node to itself.
+    pub reachable_pairs: usize,
+}
+
+pub fn run(args: &ApspArgs, ctx: &Context) -> Result<Report> {
+    let method: ApspMethod = args.method.parse()?;
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    let threads = thread_count(args.threads, Workload::Other);
+    
+    println!("\nRunning APSP ({:?}) with {} threads...", method, threads);
+    let start = Instant::now();
+    let matrix = apsp(&graph, method, threads)?;
+    println!("Completed in {:?}", start.elapsed());
+    
+    let n = matrix.num_nodes();
+    let reachable_pairs = (0..n)
+        .map(|u| matrix.row(u).iter().filter(|d| d.is_finite()).count())
+        .sum::<usize>();
+    println!("Reachable pairs: {}/{}", reachable_pairs, n * n);
+    
+    matrix.write_binary(&args.out)?;
+    println!("Distance matrix saved to: {}", args.out);
+    
+    Ok(Report { matrix, reachable_pairs })
+}
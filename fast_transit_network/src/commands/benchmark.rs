@@ -0,0 +1,310 @@
+use super::{load_graph, node_arg, progress_sink, Context};
+use crate::algorithms::pagerank::PageRankConfig;
+use crate::cli::BenchmarkArgs;
+use crate::config::{ParallelismPolicy, Threads};
+use crate::graph::graph::Graph;
+use crate::graph::reorder::Permutation;
+use crate::progress::{ProgressEvent, SharedProgress, StdoutProgress};
+use crate::utils::benchmark::{
+    compare_to_baseline, expand_graph_paths, measure, print_baseline_comparison, BaselineComparison, BenchmarkAlgorithm,
+    BenchmarkInput, EnvironmentInfo, BenchmarkLogger, BenchmarkResult, GraphMetadata, TimingStats, VariantOutput,
+};
+use anyhow::Result;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Outcome of `benchmark`.
+#[derive(Debug)]
+pub struct Report {
+    /// One row per variant and thread count, as written to the CSV.
+    pub results: Vec<BenchmarkResult>,
+    /// Comparison with `--baseline`; empty without it.
+    pub comparisons: Vec<BaselineComparison>,
+}
+
+impl Report {
+    /// Runs slower than the baseline by more than `--regression-threshold`.
+    pub fn regressions(&self) -> usize {
+        self.comparisons.iter().filter(|c| c.regression).count()
+    }
+}
+
+pub fn run(args: &BenchmarkArgs, ctx: &Context) -> Result<Report> {
+    // Load the baseline up front so a bad path fails before hours of benchmarking
+    let baseline = args.baseline.as_deref().map(BenchmarkLogger::load_csv).transpose()?;
+    if let Some(path) = &args.baseline {
+        warn_if_other_machine(path)?;
+    }
+    let runs = measure_graphs(args, ctx)?;
+    save_report(runs, &args.out, baseline.as_deref(), args.regression_threshold)
+}
+
+/// Benchmarks the `--input` graph and every `--graphs` file, in that order.
+pub(crate) fn measure_graphs(args: &BenchmarkArgs, ctx: &Context) -> Result<Vec<GraphRun>> {
+    let algorithms = BenchmarkAlgorithm::parse_list(&args.algorithms)?;
+    
+    let mut thread_counts = Vec::new();
+    for entry in args.threads.split(',') {
+        match entry.parse::<Threads>()? {
+            Threads::Auto => thread_counts.extend(Threads::sweep()),
+            Threads::Count(n) => thread_counts.push(n.max(1)),
+        }
+    }
+    
+    // The merged --input graph, then every file named by --graphs
+    let mut graph_inputs = Vec::new();
+    if !args.input.is_empty() {
+        graph_inputs.push(args.input.clone());
+    }
+    for pattern in &args.graphs {
+        graph_inputs.extend(expand_graph_paths(pattern)?.into_iter().map(|path| vec![path]));
+    }
+    
+    let cases_per_graph: usize = algorithms
+        .iter()
+        .flat_map(|algorithm| algorithm.variants())
+        .map(|variant| if variant.is_parallel() { thread_counts.len() } else { 1 })
+        .sum();
+    let setup = Setup {
+        args,
+        algorithms: &algorithms,
+        thread_counts: &thread_counts,
+        policy: ParallelismPolicy::from_force_flag(args.force_parallel),
+        verbose: ctx.verbose,
+        progress: progress_sink(ctx.verbose, None),
+        completed_cases: AtomicUsize::new(0),
+        total_cases: cases_per_graph * graph_inputs.len(),
+    };
+    
+    // Large graphs run one at a time as they are loaded; small ones wait for --graph-jobs workers
+    let mut runs: Vec<Option<GraphRun>> = graph_inputs.iter().map(|_| None).collect();
+    let mut small = Vec::new();
+    for (index, inputs) in graph_inputs.iter().enumerate() {
+        let (graph, perm) = load_graph(inputs, args.reorder.as_deref(), ctx)?;
+        if args.graph_jobs > 1 && graph.num_edges <= args.small_graph_edges {
+            small.push((index, graph_name(inputs), graph, perm));
+            continue;
+        }
+        runs[index] = Some(benchmark_graph(&setup, graph_name(inputs), &graph, perm.as_ref(), false)?);
+    }
+    if !small.is_empty() {
+        println!("\nBenchmarking {} small graphs, {} at a time", small.len(), args.graph_jobs.min(small.len()));
+        let next = AtomicUsize::new(0);
+        let finished: Vec<(usize, Result<GraphRun>)> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..args.graph_jobs.min(small.len()))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut finished = Vec::new();
+                        while let Some((index, name, graph, perm)) = small.get(next.fetch_add(1, Ordering::Relaxed)) {
+                            finished.push((*index, benchmark_graph(&setup, name.clone(), graph, perm.as_ref(), true)));
+                        }
+                        finished
+                    })
+                })
+                .collect();
+            workers.into_iter().flat_map(|worker| worker.join().expect("benchmark worker panicked")).collect()
+        });
+        for (index, run) in finished {
+            runs[index] = Some(run?);
+        }
+    }
+    
+    Ok(runs.into_iter().flatten().collect())
+}
+
+/// Warns when the baseline CSV was measured on another host, CPU or compiler, where a timing
+/// difference says little about the code.
+pub(crate) fn warn_if_other_machine(baseline: &str) -> Result<()> {
+    let recorded = BenchmarkLogger::load_csv_environment(baseline)?;
+    let current = EnvironmentInfo::capture().fields();
+    let differs: Vec<String> = ["hostname", "cpu_model", "rustc_version", "profile"]
+        .iter()
+        .filter_map(|&key| {
+            let old = recorded.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())?;
+            let new = current.iter().find(|(k, _)| *k == key).map(|(_, v)| v.as_str())?;
+            (old != new).then(|| format!("{} {} -> {}", key, old, new))
+        })
+        .collect();
+    if !differs.is_empty() {
+        tracing::warn!("baseline {} was measured elsewhere ({}); regressions may reflect the machine", baseline, differs.join(", "));
+    }
+    Ok(())
+}
+
+/// Writes the CSV and JSON report of `runs` to `out` (and next to it), prints the summary and compares
+/// with `baseline`.
+pub(crate) fn save_report(runs: Vec<GraphRun>, out: &str, baseline: Option<&[BenchmarkResult]>, regression_threshold: f64) -> Result<Report> {
+    let mut logger = BenchmarkLogger::new();
+    for run in runs {
+        // Output of concurrent runs is held back so graphs do not interleave
+        print!("{}", run.log);
+        logger.add_graph(run.graph);
+        for result in run.results {
+            logger.add_result(result);
+        }
+    }
+    
+    println!("\n{}", "=".repeat(70));
+    if let Some(parent) = Path::new(out).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    logger.save_to_csv(out)?;
+    println!("✓ Results saved to: {}", out);
+    let json_out = Path::new(out).with_extension("json");
+    logger.save_to_json(&json_out.to_string_lossy())?;
+    println!("✓ JSON report saved to: {}", json_out.display());
+    
+    // Print summary
+    logger.print_summary();
+    
+    println!("\n{}", "=".repeat(70));
+    println!("Next steps:");
+    println!("  1. Generate plots:");
+    println!("     python3 scripts/visualize_benchmark.py {}", out);
+    println!("  2. Check plots in: scripts/results/plots/");
+    println!("{}", "=".repeat(70));
+    
+    let comparisons = match baseline {
+        Some(baseline) => {
+            let comparisons = compare_to_baseline(baseline, logger.results(), regression_threshold);
+            print_baseline_comparison(&comparisons, regression_threshold);
+            comparisons
+        }
+        None => Vec::new(),
+    };
+    
+    Ok(Report { results: logger.results().to_vec(), comparisons })
+}
+
+/// Settings shared by the benchmark of every graph.
+struct Setup<'a> {
+    args: &'a BenchmarkArgs,
+    algorithms: &'a [BenchmarkAlgorithm],
+    thread_counts: &'a [usize],
+    policy: ParallelismPolicy,
+    verbose: bool,
+    progress: SharedProgress,
+    completed_cases: AtomicUsize,
+    total_cases: usize,
+}
+
+impl Setup<'_> {
+    fn case_done(&self) {
+        let completed = self.completed_cases.fetch_add(1, Ordering::Relaxed) + 1;
+        self.progress.event(&ProgressEvent::BenchmarkCase { completed, total: self.total_cases });
+    }
+}
+
+/// Benchmark of one graph: its metadata, CSV rows and (for concurrent runs) its console output.
+pub(crate) struct GraphRun {
+    graph: GraphMetadata,
+    results: Vec<BenchmarkResult>,
+    log: String,
+}
+
+/// Name of a graph in the report: its file names without `.txt`, joined by `+` for merged inputs.
+fn graph_name(inputs: &[String]) -> String {
+    inputs
+        .iter()
+        .map(|input| Path::new(input).file_name().and_then(|n| n.to_str()).unwrap_or(input).replace(".txt", ""))
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// Runs every variant of every algorithm on `graph`. With `buffered`, the console report is kept in
+/// [`GraphRun::log`] instead of printed, and PageRank does not report its iterations.
+fn benchmark_graph(setup: &Setup, graph_name: String, graph: &Graph, perm: Option<&Permutation>, buffered: bool) -> Result<GraphRun> {
+    let args = setup.args;
+    let mut log = String::new();
+    let mut line = |text: String| {
+        if buffered {
+            log.push_str(&text);
+            log.push('\n');
+        } else {
+            println!("{}", text);
+        }
+    };
+    // Without --source, BFS starts from the first node before reordering
+    let source = match args.source {
+        Some(id) => node_arg(graph, perm, id, "Source")?,
+        None => 0,
+    };
+    let bfs_source = match perm {
+        Some(perm) if source < perm.len() => perm.new_id[source],
+        _ => source,
+    };
+    
+    let input = BenchmarkInput {
+        graph,
+        bfs_source,
+        policy: setup.policy,
+        pagerank: PageRankConfig {
+            alpha: 0.85,
+            max_iterations: 50,
+            tolerance: 1e-6,
+            progress: (!buffered).then(|| Arc::new(StdoutProgress { verbose: setup.verbose }) as SharedProgress),
+            ..PageRankConfig::default()
+        },
+    };
+    
+    let result = |algorithm: BenchmarkAlgorithm, mode: &str, threads: usize, timing: TimingStats, speedup: f64, correct: bool| {
+        BenchmarkResult {
+            algorithm: algorithm.name().to_string(),
+            graph_name: graph_name.clone(),
+            graph_nodes: graph.num_nodes as u64,
+            graph_edges: graph.num_edges as u64,
+            mode: mode.to_string(),
+            threads,
+            time_ms: timing.mean_ms,
+            min_ms: timing.min_ms,
+            stddev_ms: timing.stddev_ms,
+            runs: timing.runs,
+            peak_memory_mb: timing.peak_memory_mb,
+            speedup,
+            correct,
+        }
+    };
+    
+    let mut results = Vec::new();
+    for &algorithm in setup.algorithms {
+        line(format!("\n{}", "=".repeat(70)));
+        line(format!("{} BENCHMARK ({})", algorithm.name().to_uppercase(), graph_name));
+        line("=".repeat(70));
+        
+        // The first variant is the sequential reference for speedup and correctness
+        let mut reference: Option<(VariantOutput, TimingStats)> = None;
+        for variant in algorithm.variants() {
+            let counts = if variant.is_parallel() { setup.thread_counts } else { &[1] };
+            for &num_threads in counts {
+                let (output, timing) = measure(args.warmup, args.repeat, || variant.run(&input, num_threads));
+                let output = output?;
+                let summary = output.summary();
+                let Some((expected, time_ref)) = &reference else {
+                    line(format!("Reference {}: {}{}", variant.mode(), timing, summary));
+                    results.push(result(algorithm, variant.mode(), num_threads, timing, 1.0, true));
+                    setup.case_done();
+                    reference = Some((output, timing));
+                    continue;
+                };
+                let speedup = time_ref.mean_ms / timing.mean_ms;
+                let correct = output.matches(expected);
+                
+                line(format!("{} ({}): {}{} | Speedup: {:.2}x | {}", 
+                             variant.mode(), num_threads, timing, summary, speedup,
+                             if correct { "OK" } else { "ERROR" }));
+                results.push(result(algorithm, variant.mode(), num_threads, timing, speedup, correct));
+                setup.case_done();
+            }
+        }
+    }
+    
+    let graph = GraphMetadata {
+        name: graph_name,
+        nodes: graph.num_nodes as u64,
+        edges: graph.num_edges as u64,
+        memory_bytes: graph.memory_bytes() as u64,
+        reorder: args.reorder.clone(),
+    };
+    Ok(GraphRun { graph, results, log })
+}
@@ -0,0 +1,75 @@
+use super::{load_graph, node_arg, progress_sink, sequential_storage, thread_count, write_histogram_if_requested, write_ids_if_remapped, Context};
+use crate::algorithms::bfs::{
+    bfs_parallel_with_level_stats, bfs_parallel_with_progress, bfs_sequential_with_level_stats, bfs_sequential_with_progress, BfsResult,
+};
+use crate::cli::BfsArgs;
+use crate::config::{Mode, ParallelismPolicy, Workload};
+use crate::graph::compact::GraphStorage;
+use crate::graph::graph::GraphLike;
+use crate::utils::io::{output_path, write_bfs_levels_csv, write_bfs_result_as, OutputFormat};
+use anyhow::Result;
+
+/// Outcome of `bfs`.
+#[derive(Debug)]
+pub struct Report {
+    /// Distances in original node ids.
+    pub result: BfsResult,
+    pub num_nodes: usize,
+}
+
+pub fn run(args: &BfsArgs, ctx: &Context) -> Result<Report> {
+    let format: OutputFormat = args.output_format.parse()?;
+    let policy = ParallelismPolicy::from_force_flag(args.force_parallel);
+    let (graph, perm) = load_graph(&args.input, args.reorder.as_deref(), ctx)?;
+    let threads = thread_count(args.threads, Workload::Bfs);
+    let source = node_arg(&graph, perm.as_ref(), args.source, "Source")?;
+    let start_node = match &perm {
+        Some(perm) if source < perm.len() => perm.new_id[source],
+        _ => source,
+    };
+    let graph = sequential_storage(graph, args.mode == Mode::Seq, ctx);
+    
+    println!("\nRunning BFS from source {}...", args.source);
+    let progress = progress_sink(ctx.verbose, None);
+    
+    let record_levels = args.levels_csv.is_some();
+    let compute = || match (&graph, args.mode, record_levels) {
+        (GraphStorage::Wide(wide), Mode::Par | Mode::ParOpt, false) => bfs_parallel_with_progress(wide, start_node, threads, policy, &*progress),
+        (GraphStorage::Wide(wide), Mode::Par | Mode::ParOpt, true) => bfs_parallel_with_level_stats(wide, start_node, threads, policy, &*progress),
+        (_, _, false) => bfs_sequential_with_progress(&graph, start_node, &*progress),
+        (_, _, true) => bfs_sequential_with_level_stats(&graph, start_node, &*progress),
+    };
+    // Distances do not depend on the mode, so only the source is part of the key. Level timings are
+    // per run, so they are never served from the cache.
+    let result = match &ctx.cache {
+        Some(cache) if !record_levels => cache.get_or_insert_with(graph.fingerprint(), &format!("source={}", start_node), compute)?,
+        _ => compute()?,
+    };
+    // Clears the BFS spinner before the summary is printed
+    drop(progress);
+    
+    let result = match &perm {
+        Some(perm) => BfsResult {
+            distances: perm.to_original(&result.distances),
+            ..result
+        },
+        None => result,
+    };
+    
+    println!("Completed in {:?}", result.elapsed);
+    println!("Reachable nodes: {}/{}", result.reachable, graph.node_count());
+    
+    write_bfs_result_as(&result, source, &args.out, format)?;
+    println!("Results saved to: {}", format.output_path(&args.out));
+    if let Some(path) = &args.levels_csv {
+        write_bfs_levels_csv(&result.level_stats, path)?;
+        let parallel = result.level_stats.iter().filter(|l| l.parallel).count();
+        println!("Level statistics saved to: {} ({} levels, {} expanded in parallel)", output_path(path), result.level_stats.len(), parallel);
+    }
+    write_ids_if_remapped(graph.original_ids(), perm.as_ref(), &args.out)?;
+    // Unreachable nodes (-1) are left out
+    let distances: Vec<f64> = result.distances.iter().map(|&d| if d < 0 { f64::NAN } else { f64::from(d) }).collect();
+    write_histogram_if_requested(&args.histogram, &distances, "Distance")?;
+    
+    Ok(Report { result, num_nodes: graph.node_count() })
+}
@@ -0,0 +1,37 @@
+use super::{load_graph, Context};
+use crate::algorithms::bridges::{find_bridges, BridgeResult};
+use crate::cli::BridgesArgs;
+use crate::utils::io::{output_path, write_bridges};
+use anyhow::Result;
+use std::time::Instant;
+
+/// Outcome of `bridges`.
+#[derive(Debug)]
+pub struct Report {
+    pub bridges: BridgeResult,
+}
+
+pub fn run(args: &BridgesArgs, ctx: &Context) -> Result<Report> {
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    
+    println!("\nFinding bridges and cut vertices...");
+    let start = Instant::now();
+    let result = find_bridges(&graph);
+    println!("Completed in {:?}", start.elapsed());
+    
+    println!("Bridges: {}", result.bridges.len());
+    for b in result.bridges.iter().take(args.top) {
+        println!("  {} -- {} (strands {} nodes)", b.u, b.v, b.stranded);
+    }
+    println!("Cut vertices: {}", result.cut_vertices.len());
+    for c in result.cut_vertices.iter().take(args.top) {
+        println!("  {} (splits into {} pieces, strands {} nodes)", c.node, c.pieces, c.stranded);
+    }
+    
+    if let Some(out) = &args.out {
+        write_bridges(&result, out)?;
+        println!("Results saved to: {}", output_path(out));
+    }
+    
+    Ok(Report { bridges: result })
+}
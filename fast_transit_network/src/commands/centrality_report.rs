@@ -0,0 +1,52 @@
+use super::{load_graph, thread_count, write_ids_if_remapped, Context};
+use crate::algorithms::centrality::{centrality_report, CentralityConfig, CentralityReport};
+use crate::algorithms::pagerank::PageRankConfig;
+use crate::cli::CentralityReportArgs;
+use crate::config::Workload;
+use crate::utils::io::{output_path, write_centrality_report_as, write_edge_scores, OutputFormat};
+use anyhow::Result;
+use std::time::Instant;
+
+/// Outcome of `centrality-report`.
+#[derive(Debug)]
+pub struct Report {
+    pub centrality: CentralityReport,
+}
+
+pub fn run(args: &CentralityReportArgs, ctx: &Context) -> Result<Report> {
+    let format: OutputFormat = args.output_format.parse()?;
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    let config = CentralityConfig {
+        samples: args.samples,
+        num_threads: thread_count(args.threads, Workload::Other),
+        pagerank: PageRankConfig { alpha: args.alpha, ..Default::default() },
+        edge_betweenness: args.edge_scores.is_some(),
+        largest_component: args.largest_component,
+    };
+    
+    println!("\nComputing centrality ({} sampled sources, {} threads)...", args.samples.min(graph.num_nodes), config.num_threads);
+    let start = Instant::now();
+    let report = centrality_report(&graph, &config);
+    println!("Completed in {:?}", start.elapsed());
+    
+    let mut by_betweenness: Vec<usize> = (0..report.num_nodes()).collect();
+    by_betweenness.sort_by(|&a, &b| report.betweenness[b].total_cmp(&report.betweenness[a]));
+    println!("Top nodes by betweenness{}:", if report.exact { "" } else { " (estimated)" });
+    for &node in by_betweenness.iter().take(5) {
+        println!(
+            "  {}: betweenness {:.1}, closeness {:.4}, pagerank {:.6}, degree {}/{}",
+            node, report.betweenness[node], report.closeness[node], report.pagerank[node],
+            report.in_degree[node], report.out_degree[node]
+        );
+    }
+    
+    write_centrality_report_as(&report, &args.out, format)?;
+    println!("Centrality report saved to: {}", format.output_path(&args.out));
+    write_ids_if_remapped(graph.original_ids(), None, &args.out)?;
+    if let (Some(path), Some(scores)) = (&args.edge_scores, &report.edge_betweenness) {
+        write_edge_scores(&graph, scores, "Betweenness", path)?;
+        println!("Edge betweenness saved to: {}", output_path(path));
+    }
+    
+    Ok(Report { centrality: report })
+}
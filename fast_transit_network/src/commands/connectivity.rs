@@ -0,0 +1,53 @@
+use super::{load_graph, wcc, write_ids_if_remapped, Context};
+use crate::algorithms::scc::strongly_connected_components;
+use crate::algorithms::wcc::WccResult;
+use crate::cli::ConnectivityArgs;
+use crate::config::Connectivity;
+use crate::utils::io::{derived_path, output_path, write_wcc_result_as, write_wcc_stats, OutputFormat, WccColumns};
+use anyhow::Result;
+use std::time::Instant;
+
+/// Outcome of `connectivity`.
+#[derive(Debug)]
+pub struct Report {
+    pub kind: Connectivity,
+    /// Component label per node (SCC labels are the component ids of `strongly_connected_components`).
+    pub result: WccResult,
+    /// Where the component size statistics were written.
+    pub stats_path: String,
+}
+
+pub fn run(args: &ConnectivityArgs, ctx: &Context) -> Result<Report> {
+    if args.kind == Connectivity::Weak {
+        let report = wcc::run(&args.wcc, ctx)?;
+        return Ok(Report { kind: args.kind, result: report.result, stats_path: report.stats_path });
+    }
+    
+    let args = &args.wcc;
+    let format: OutputFormat = args.output_format.parse()?;
+    let (graph, perm) = load_graph(&args.input, args.reorder.as_deref(), ctx)?;
+    if args.mode.is_parallel() {
+        tracing::warn!("--mode {} ignored: strongly connected components run sequentially", args.mode);
+    }
+    
+    println!("\nRunning SCC...");
+    let start = Instant::now();
+    let scc = strongly_connected_components(&graph);
+    println!("Completed in {:?}", start.elapsed());
+    let labels = match &perm {
+        Some(perm) => perm.to_original(&scc.component),
+        None => scc.component,
+    };
+    let result = WccResult::from_labels(labels);
+    
+    let stats_path = derived_path(&args.out, "_stats");
+    let columns = WccColumns { dense_ids: args.dense_ids, sizes: args.component_sizes };
+    write_wcc_result_as(&result, &args.out, format, columns)?;
+    println!("Results saved to: {}", format.output_path(&args.out));
+    write_wcc_stats(&result.labels, &stats_path)?;
+    println!("Statistics saved to: {}", output_path(&stats_path));
+    write_ids_if_remapped(graph.original_ids(), perm.as_ref(), &args.out)?;
+    result.stats.print();
+    
+    Ok(Report { kind: Connectivity::Strong, result, stats_path })
+}
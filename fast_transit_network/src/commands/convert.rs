@@ -0,0 +1,36 @@
+use super::{load_graph, write_ids_if_remapped, Context};
+use crate::cli::ConvertArgs;
+use crate::graph::formats::{write_binary_with_metadata, GraphFormat, BinaryGraphMetadata, SourceFile};
+use crate::utils::io::{derived_path, node_output_path, write_edge_list};
+use anyhow::Result;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Outcome of `convert`.
+#[derive(Debug)]
+pub struct Report {
+    pub nodes: usize,
+    pub edges: usize,
+}
+
+pub fn run(args: &ConvertArgs, ctx: &Context) -> Result<Report> {
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    match args.to {
+        GraphFormat::EdgeList => write_edge_list(&graph, &args.out)?,
+        GraphFormat::Binary => {
+            let metadata = BinaryGraphMetadata {
+                name: args.name.clone(),
+                created_unix: SystemTime::now().duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs()),
+                sources: args.input.iter().map(|path| SourceFile::from_path(path)).collect::<std::io::Result<_>>()?,
+                load_options: ctx.load.flags(),
+                id_map: graph.original_ids().map(|_| node_output_path(&derived_path(&args.out, "_ids"))),
+                ..BinaryGraphMetadata::new()
+            };
+            write_binary_with_metadata(&graph, &metadata, &args.out)?
+        }
+        format => anyhow::bail!("Writing {:?} files is not supported; use edge-list or binary", format),
+    }
+    println!("Graph saved to: {} ({:?})", args.out, args.to);
+    write_ids_if_remapped(graph.original_ids(), None, &args.out)?;
+    
+    Ok(Report { nodes: graph.num_nodes, edges: graph.num_edges })
+}
@@ -0,0 +1,31 @@
+use super::Context;
+use crate::cli::DiffArgs;
+use crate::graph::diff::{diff, GraphDiff};
+use crate::graph::graph::load_graph_from_file_with_options;
+use crate::progress::NoProgress;
+use crate::utils::io::{output_path, write_graph_diff};
+use anyhow::Result;
+use std::time::Instant;
+
+/// Outcome of `diff`.
+#[derive(Debug)]
+pub struct Report {
+    pub diff: GraphDiff,
+}
+
+pub fn run(args: &DiffArgs, ctx: &Context) -> Result<Report> {
+    let start = Instant::now();
+    let old_graph = load_graph_from_file_with_options(&args.old, &ctx.load, &NoProgress)?;
+    let new_graph = load_graph_from_file_with_options(&args.new, &ctx.load, &NoProgress)?;
+    println!("Loaded {} and {} in {:?}\n", args.old, args.new, start.elapsed());
+    
+    let changes = diff(&old_graph, &new_graph);
+    changes.print();
+    
+    if let Some(out) = &args.out {
+        write_graph_diff(&changes, out)?;
+        println!("\nChanges saved to: {}", output_path(out));
+    }
+    
+    Ok(Report { diff: changes })
+}
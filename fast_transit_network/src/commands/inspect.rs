@@ -0,0 +1,83 @@
+use super::Context;
+use crate::cli::InspectArgs;
+use crate::graph::formats::{read_binary_info, BinaryGraphInfo, GraphFormat};
+use crate::results::result_kind;
+use anyhow::Result;
+use std::io::{BufRead, BufReader};
+
+/// Outcome of `inspect`: what the file holds.
+#[derive(Debug)]
+pub enum Report {
+    /// A binary graph, from its header alone.
+    Graph(BinaryGraphInfo),
+    /// A result written with `--output-format binary`, of this kind (`bfs`, `wcc`, `pagerank`).
+    Result { kind: String },
+    /// Any other graph file, by the format it would be read as.
+    Other(GraphFormat),
+}
+
+pub fn run(args: &InspectArgs, _ctx: &Context) -> Result<Report> {
+    let mut reader = BufReader::new(std::fs::File::open(&args.file)?);
+    let head = reader.fill_buf()?;
+    let report = if GraphFormat::detect(head, None) == GraphFormat::Binary {
+        Report::Graph(read_binary_info(reader)?)
+    } else if let Ok(kind) = result_kind(head) {
+        Report::Result { kind: kind.to_string() }
+    } else {
+        Report::Other(GraphFormat::detect(head, Some(&args.file)))
+    };
+
+    println!("File: {}", args.file);
+    match &report {
+        Report::Graph(info) => print_graph_info(info),
+        Report::Result { kind } => println!("Binary {} result (load with results::read_result)", kind),
+        Report::Other(format) => println!("{:?} graph file (no header to inspect; `convert` writes a binary graph with metadata)", format),
+    }
+    Ok(report)
+}
+
+fn print_graph_info(info: &BinaryGraphInfo) {
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
+    println!("Binary graph, format version {}", info.version);
+    println!("  Nodes: {}", info.nodes);
+    println!("  Edges: {}", info.edges);
+    println!("  Directed: {}", yes_no(info.directed));
+    println!("  Weighted: {}", yes_no(info.weighted));
+    let Some(metadata) = &info.metadata else {
+        println!("  No metadata (written before metadata was recorded)");
+        return;
+    };
+    if let Some(name) = &metadata.name {
+        println!("  Name: {}", name);
+    }
+    if let Some(created) = metadata.created_unix {
+        println!("  Created: {}", format_utc(created));
+    }
+    for source in &metadata.sources {
+        println!("  Source: {} ({} bytes, hash {})", source.path, source.bytes, source.hash);
+    }
+    let options = if metadata.load_options.is_empty() { "none".to_string() } else { metadata.load_options.join(" ") };
+    println!("  Load options: {}", options);
+    match &metadata.id_map {
+        Some(path) => println!("  Id map: {} (node ids were remapped from sparse input ids)", path),
+        None => println!("  Id map: none (node ids are the input ids)"),
+    }
+    if !metadata.tool_version.is_empty() {
+        println!("  Written by: version {}", metadata.tool_version);
+    }
+}
+
+/// `YYYY-MM-DD HH:MM:SS UTC` for seconds since the Unix epoch.
+fn format_utc(secs: u64) -> String {
+    let (days, rest) = (secs / 86_400, secs % 86_400);
+    // Civil date from a day count (Howard Hinnant's algorithm), with eras starting in March
+    let z = days + 719_468;
+    let (era, day_of_era) = (z / 146_097, z % 146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC", year, month, day, rest / 3600, rest % 3600 / 60, rest % 60)
+}
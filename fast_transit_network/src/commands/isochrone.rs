@@ -0,0 +1,37 @@
+use super::{load_graph, node_arg, Context};
+use crate::algorithms::isochrone::{isochrone, Isochrone};
+use crate::cli::IsochroneArgs;
+use crate::graph::attributes::{load_node_coordinates_from_file, NodeAttr};
+use crate::utils::io::{output_path, write_isochrone, write_isochrone_geojson};
+use anyhow::Result;
+use std::time::Instant;
+
+/// Outcome of `isochrone`.
+#[derive(Debug)]
+pub struct Report {
+    pub isochrone: Isochrone,
+}
+
+pub fn run(args: &IsochroneArgs, ctx: &Context) -> Result<Report> {
+    let (mut graph, _) = load_graph(&args.input, None, ctx)?;
+    if let Some(coords) = &args.coords {
+        load_node_coordinates_from_file(&mut graph, coords)?;
+    }
+    
+    println!("\nComputing isochrone from {} within cost {}...", args.source, args.max_cost);
+    let start = Instant::now();
+    let iso = isochrone(&graph, node_arg(&graph, None, args.source, "Source")?, args.max_cost)?;
+    println!("Completed in {:?}", start.elapsed());
+    println!("Reachable nodes: {}", iso.len());
+    println!("Boundary edges: {}", iso.boundary_edges.len());
+    
+    if let Some(out) = &args.out {
+        match graph.node_attr::<NodeAttr<Option<[f64; 2]>>>("coords") {
+            Some(points) => write_isochrone_geojson(&iso, points.values(), out)?,
+            None => write_isochrone(&iso, out)?,
+        }
+        println!("Isochrone saved to: {}", output_path(out));
+    }
+    
+    Ok(Report { isochrone: iso })
+}
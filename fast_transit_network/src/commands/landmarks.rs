@@ -0,0 +1,26 @@
+use super::{load_graph, Context};
+use crate::algorithms::alt::{build_landmarks, Landmarks};
+use crate::cli::LandmarksArgs;
+use anyhow::Result;
+use std::time::Instant;
+
+/// Outcome of `landmarks`.
+#[derive(Debug)]
+pub struct Report {
+    pub landmarks: Landmarks,
+}
+
+pub fn run(args: &LandmarksArgs, ctx: &Context) -> Result<Report> {
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    
+    println!("\nSelecting {} landmarks...", args.landmarks);
+    let start = Instant::now();
+    let alt = build_landmarks(&graph, args.landmarks)?;
+    println!("Completed in {:?}", start.elapsed());
+    println!("Landmarks: {:?}", alt.landmarks);
+    
+    alt.write_binary(&args.out)?;
+    println!("Landmark distances ({:.1} MB) saved to: {}", alt.memory_bytes() as f64 / 1e6, args.out);
+    
+    Ok(Report { landmarks: alt })
+}
@@ -0,0 +1,40 @@
+use super::{load_graph, node_arg, Context};
+use crate::algorithms::maxflow::{dinic, MaxFlowResult};
+use crate::cli::MaxflowArgs;
+use crate::utils::io::{output_path, write_min_cut};
+use anyhow::Result;
+use std::time::Instant;
+
+/// Outcome of `maxflow`.
+#[derive(Debug)]
+pub struct Report {
+    pub flow: MaxFlowResult,
+    /// Minimum-cut edges `(from, to, capacity)`.
+    pub cut: Vec<(usize, usize, f64)>,
+}
+
+pub fn run(args: &MaxflowArgs, ctx: &Context) -> Result<Report> {
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    
+    println!("\nRunning max flow from {} to {}...", args.source, args.sink);
+    let start = Instant::now();
+    let result = dinic(&graph, node_arg(&graph, None, args.source, "Source")?, node_arg(&graph, None, args.sink, "Sink")?)?;
+    println!("Completed in {:?}", start.elapsed());
+    
+    let cut = result.min_cut_edges(&graph);
+    println!("Max flow: {}", result.value);
+    println!("Min cut: {} edges", cut.len());
+    for &(from, to, capacity) in cut.iter().take(20) {
+        println!("  {} -> {} (capacity {})", graph.original_id(from), graph.original_id(to), capacity);
+    }
+    if cut.len() > 20 {
+        println!("  ... and {} more", cut.len() - 20);
+    }
+    
+    if let Some(out) = &args.out {
+        write_min_cut(&cut, out)?;
+        println!("Min cut saved to: {}", output_path(out));
+    }
+    
+    Ok(Report { flow: result, cut })
+}
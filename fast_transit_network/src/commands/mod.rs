@@ -0,0 +1,258 @@
+//! Runners behind the `tool` subcommands.
+//!
+//! Every submodule has a `run(&Args, &Context) -> Result<Report>` that loads the input, computes,
+//! writes the requested files and returns what it found, so command behavior can be tested without
+//! spawning the binary. Progress and summaries still go to stdout as the runner goes.
+
+pub mod algorithms;
+pub mod anf;
+pub mod apsp;
+pub mod benchmark;
+pub mod bfs;
+pub mod bridges;
+pub mod centrality_report;
+pub mod connectivity;
+pub mod convert;
+pub mod diff;
+pub mod inspect;
+pub mod isochrone;
+pub mod landmarks;
+pub mod maxflow;
+pub mod node2vec;
+pub mod pagerank;
+pub mod partition;
+pub mod pipeline;
+pub mod query;
+pub mod robustness;
+pub mod route;
+pub mod run;
+pub mod sample;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod stats;
+pub mod suite;
+pub mod validate;
+pub mod wcc;
+
+use crate::cache::ResultCache;
+use crate::cli::{Commands, HistogramArgs};
+use crate::config::{Config, Threads, Workload};
+use crate::graph::compact::GraphStorage;
+use crate::graph::graph::{load_graph_from_files_with_options, Graph, LoadOptions};
+use crate::graph::reorder::{reorder, Permutation, ReorderStrategy};
+use crate::progress::{SharedProgress, StdoutProgress};
+use crate::utils::io::{derived_path, node_output_path, output_path, write_histogram, write_id_map};
+use crate::utils::stats::{histogram, Bins};
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Settings shared by every command of one invocation (the global flags).
+#[derive(Default)]
+pub struct Context {
+    /// `--verbose`: per-step progress lines instead of progress bars.
+    pub verbose: bool,
+    /// `--cache-dir`: reuse stored BFS/WCC/PageRank results.
+    pub cache: Option<ResultCache>,
+    /// `--assume-dense`, `--wide-storage` and the other loader flags.
+    pub load: LoadOptions,
+}
+
+/// Runs `command`, failing if the command's report says it failed (an invalid graph for
+/// `validate`, regressions for `benchmark --baseline` and `suite`).
+pub fn execute(command: Commands, ctx: &Context) -> Result<()> {
+    match command {
+        Commands::Bfs(args) => {
+            bfs::run(&args, ctx)?;
+        }
+        Commands::Wcc(args) => {
+            wcc::run(&args, ctx)?;
+        }
+        Commands::Connectivity(args) => {
+            connectivity::run(&args, ctx)?;
+        }
+        Commands::Stats(args) => {
+            stats::run(&args, ctx)?;
+        }
+        Commands::Anf(args) => {
+            anf::run(&args, ctx)?;
+        }
+        Commands::Validate(args) => {
+            let report = validate::run(&args, ctx)?;
+            if !report.validation.is_valid() {
+                anyhow::bail!("{} failed validation with {} structural errors", args.input.join(", "), report.validation.error_count);
+            }
+        }
+        Commands::Sample(args) => {
+            sample::run(&args, ctx)?;
+        }
+        Commands::Convert(args) => {
+            convert::run(&args, ctx)?;
+        }
+        Commands::Inspect(args) => {
+            inspect::run(&args, ctx)?;
+        }
+        Commands::Diff(args) => {
+            diff::run(&args, ctx)?;
+        }
+        Commands::Query(args) => query::run(&args, ctx)?,
+        #[cfg(feature = "serve")]
+        Commands::Serve(args) => serve::run(&args, ctx)?,
+        Commands::Apsp(args) => {
+            apsp::run(&args, ctx)?;
+        }
+        Commands::Route(args) => {
+            route::run(&args, ctx)?;
+        }
+        Commands::Isochrone(args) => {
+            isochrone::run(&args, ctx)?;
+        }
+        Commands::Landmarks(args) => {
+            landmarks::run(&args, ctx)?;
+        }
+        Commands::Maxflow(args) => {
+            maxflow::run(&args, ctx)?;
+        }
+        Commands::Bridges(args) => {
+            bridges::run(&args, ctx)?;
+        }
+        Commands::Partition(args) => {
+            partition::run(&args, ctx)?;
+        }
+        Commands::Pagerank(args) => {
+            pagerank::run(&args, ctx)?;
+        }
+        Commands::CentralityReport(args) => {
+            centrality_report::run(&args, ctx)?;
+        }
+        Commands::Robustness(args) => {
+            robustness::run(&args, ctx)?;
+        }
+        Commands::Node2vec(args) => {
+            node2vec::run(&args, ctx)?;
+        }
+        Commands::Run(args) => {
+            run::run(&args, ctx)?;
+        }
+        Commands::Algorithms => {
+            algorithms::run(ctx)?;
+        }
+        Commands::Pipeline(args) => {
+            pipeline::run(&args, ctx)?;
+        }
+        Commands::Benchmark(args) => {
+            let report = benchmark::run(&args, ctx)?;
+            let regressions = report.regressions();
+            if regressions > 0 {
+                anyhow::bail!(
+                    "{} run(s) regressed by more than {:.1}% against the baseline",
+                    regressions,
+                    args.regression_threshold * 100.0
+                );
+            }
+        }
+        Commands::Suite(args) => {
+            let report = suite::run(&args, ctx)?;
+            let regressions = report.regressions();
+            if regressions > 0 {
+                anyhow::bail!("{} run(s) of suite {} regressed against the baseline", regressions, args.file);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Progress reporting for long-running commands: progress bars when built with `progress-bars`
+/// (unless `--verbose` asks for the per-step lines), stdout lines otherwise. `iteration_limit` sizes
+/// the PageRank bar.
+#[cfg_attr(not(feature = "progress-bars"), allow(unused_variables))]
+pub(crate) fn progress_sink(verbose: bool, iteration_limit: Option<usize>) -> SharedProgress {
+    #[cfg(feature = "progress-bars")]
+    if !verbose {
+        return match iteration_limit {
+            Some(limit) => Arc::new(crate::progress::BarProgress::with_iteration_limit(limit)),
+            None => Arc::new(crate::progress::BarProgress::new()),
+        };
+    }
+    Arc::new(StdoutProgress { verbose })
+}
+
+/// Loads the input graph (the union of `inputs`), optionally reordering it for locality.
+pub(crate) fn load_graph(inputs: &[String], strategy: Option<&str>, ctx: &Context) -> Result<(Graph, Option<Permutation>)> {
+    println!("Loading graph from: {}", inputs.join(", "));
+    let graph = load_graph_from_files_with_options(inputs, &ctx.load, &*progress_sink(false, None))?;
+    graph.print_info();
+    if let Some(ids) = graph.original_ids() {
+        println!("Sparse node ids remapped to 0..{} (max id {}); node arguments use file ids", ids.len(), ids.last().copied().unwrap_or(0));
+    }
+
+    let Some(strategy) = strategy else {
+        return Ok((graph, None));
+    };
+    let strategy: ReorderStrategy = strategy.parse()?;
+    let start = Instant::now();
+    let (graph, perm) = reorder(&graph, strategy);
+    println!("Reordered nodes ({:?}) in {:?}", strategy, start.elapsed());
+    Ok((graph, Some(perm)))
+}
+
+/// The loaded graph for a run of a [`GraphLike`](crate::graph::graph::GraphLike) kernel: narrowed
+/// with [`GraphStorage::compact_if_fits`] (about half the adjacency memory) when `sequential` and
+/// `--wide-storage` is not set, kept as loaded otherwise. Take node arguments and weights from the
+/// [`Graph`] before calling this.
+pub(crate) fn sequential_storage(graph: Graph, sequential: bool, ctx: &Context) -> GraphStorage {
+    if !sequential || ctx.load.wide_storage {
+        return GraphStorage::Wide(graph);
+    }
+    let wide = graph.memory_bytes();
+    let storage = GraphStorage::compact_if_fits(graph);
+    if let GraphStorage::Compact(compact) = &storage {
+        tracing::debug!(wide, compact = compact.memory_bytes(), "narrowed the graph to 32-bit neighbor ids");
+    }
+    storage
+}
+
+/// Writes the `--histogram` of `values` if one was asked for; `what` names them in the message.
+pub(crate) fn write_histogram_if_requested(args: &HistogramArgs, values: &[f64], what: &str) -> Result<()> {
+    let Some(path) = &args.histogram else {
+        return Ok(());
+    };
+    let bins = if args.log_bins { Bins::log(args.histogram_bins) } else { Bins::linear(args.histogram_bins) };
+    let histogram = histogram(values, bins);
+    write_histogram(&histogram, path)?;
+    println!("{} histogram ({} bins, {} values left out) saved to: {}", what, histogram.counts.len(), histogram.excluded, output_path(path));
+    Ok(())
+}
+
+/// `--threads` resolved for `workload`; without the flag, `Config::global().threads`.
+pub(crate) fn thread_count(threads: Option<Threads>, workload: Workload) -> usize {
+    threads.map_or(Config::global().threads, |t| t.resolve(workload))
+}
+
+/// Node for a node-id argument given in input-file ids, numbered as before `perm` reordered the
+/// graph. Unchanged unless the ids were remapped, so out-of-range ids still get the algorithm's own
+/// error.
+pub(crate) fn node_arg(graph: &Graph, perm: Option<&Permutation>, id: usize, what: &str) -> Result<usize> {
+    if graph.original_ids().is_none() {
+        return Ok(id);
+    }
+    let Some(v) = graph.node_for_id(id) else {
+        anyhow::bail!("{} {} does not appear in the input", what, id);
+    };
+    Ok(perm.map_or(v, |perm| perm.old_id[v]))
+}
+
+/// Per-node outputs of a remapped graph are indexed by the dense ids, so this writes the file id of
+/// each next to them (`<out>_ids.txt`, as for `sample`).
+pub(crate) fn write_ids_if_remapped(original_ids: Option<&[usize]>, perm: Option<&Permutation>, out: &str) -> Result<()> {
+    if let Some(ids) = original_ids {
+        let ids = match perm {
+            Some(perm) => perm.to_original(ids),
+            None => ids.to_vec(),
+        };
+        let ids_path = derived_path(out, "_ids");
+        write_id_map(&ids, &ids_path)?;
+        println!("Original ids saved to: {}", node_output_path(&ids_path));
+    }
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use super::{load_graph, thread_count, Context};
+use crate::algorithms::random_walk::{run_node2vec_and_save, Node2VecConfig};
+use crate::cli::Node2vecArgs;
+use crate::config::Workload;
+use crate::utils::io::output_path;
+use anyhow::Result;
+use std::time::Instant;
+
+/// Outcome of `node2vec`.
+#[derive(Debug)]
+pub struct Report {
+    /// Walks written to the corpus.
+    pub walks: usize,
+}
+
+pub fn run(args: &Node2vecArgs, ctx: &Context) -> Result<Report> {
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    let config = Node2VecConfig {
+        p: args.p,
+        q: args.q,
+        walk_length: args.walk_length,
+        walks_per_node: args.walks_per_node,
+        seed: args.seed,
+        num_threads: thread_count(args.threads, Workload::Other),
+    };
+    
+    println!("\nGenerating node2vec walks (p={}, q={}, {} per node, length {})...", args.p, args.q, args.walks_per_node, args.walk_length);
+    let start = Instant::now();
+    let walks = run_node2vec_and_save(&graph, &config, &args.out)?;
+    println!("Wrote {} walks in {:?}", walks, start.elapsed());
+    println!("Corpus saved to: {}", output_path(&args.out));
+    
+    Ok(Report { walks })
+}
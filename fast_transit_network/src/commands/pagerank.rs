@@ -0,0 +1,67 @@
+use super::{load_graph, progress_sink, sequential_storage, thread_count, write_histogram_if_requested, write_ids_if_remapped, Context};
+use crate::algorithms::pagerank::{run_pagerank_and_save, Checkpointing, PageRankCheckpoint, PageRankConfig, PageRankResult};
+use crate::cli::PagerankArgs;
+use crate::config::{Mode, ParallelismPolicy, Workload};
+use crate::utils::io::OutputFormat;
+use anyhow::Result;
+
+/// Outcome of `pagerank`.
+#[derive(Debug)]
+pub struct Report {
+    /// Ranks in original node ids.
+    pub result: PageRankResult,
+}
+
+pub fn run(args: &PagerankArgs, ctx: &Context) -> Result<Report> {
+    let format: OutputFormat = args.output_format.parse()?;
+    let policy = ParallelismPolicy::from_force_flag(args.force_parallel);
+    let (graph, perm) = load_graph(&args.input, args.reorder.as_deref(), ctx)?;
+    
+    if args.resume && args.checkpoint.is_none() {
+        anyhow::bail!("--resume needs --checkpoint");
+    }
+    // A checkpoint would hold the component's ranks, which cannot be checked against the graph
+    if args.largest_component && args.checkpoint.is_some() {
+        anyhow::bail!("--checkpoint cannot be combined with --largest-component");
+    }
+    // A missing checkpoint just means the first attempt died before saving one
+    let resume_from = match &args.checkpoint {
+        Some(path) if args.resume && std::path::Path::new(path).exists() => {
+            let saved = PageRankCheckpoint::read_binary(path)?;
+            saved.check_nodes(graph.num_nodes)?;
+            println!("Resuming from {} after {} iterations", path, saved.iteration);
+            Some(saved)
+        }
+        _ => None,
+    };
+    
+    let config = PageRankConfig {
+        alpha: args.alpha,
+        max_iterations: args.iters,
+        tolerance: args.eps,
+        progress: Some(progress_sink(ctx.verbose, Some(args.iters))),
+        checkpoint: args.checkpoint.clone().map(|path| Checkpointing { path, every: args.checkpoint_every }),
+        resume: resume_from,
+        weighted: args.weighted,
+    };
+    if args.weighted && !graph.is_weighted() {
+        tracing::warn!("--weighted ignored: {} has no edge weights", args.input.join(", "));
+    }
+    
+    println!("\nPageRank Config:");
+    println!("  Alpha: {}", config.alpha);
+    println!("  Max iterations: {}", config.max_iterations);
+    println!("  Tolerance: {:.2e}", config.tolerance);
+    println!("  Weighted: {}", config.weighted);
+    println!("  Largest component only: {}", args.largest_component);
+    
+    let threads = thread_count(args.threads, Workload::PageRank);
+    // Compact storage has no weights and no component view
+    let sequential = args.mode == Mode::Seq && !(args.weighted && graph.is_weighted()) && !args.largest_component;
+    let graph = sequential_storage(graph, sequential, ctx);
+    let result = run_pagerank_and_save(&graph, &config, args.mode, threads, &args.out, perm.as_ref(), policy, format, ctx.cache.as_ref(), args.largest_component)?;
+    write_ids_if_remapped(graph.original_ids(), perm.as_ref(), &args.out)?;
+    write_histogram_if_requested(&args.histogram, &result.ranks, "Rank")?;
+    
+    Ok(Report { result })
+}
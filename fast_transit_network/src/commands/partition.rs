@@ -0,0 +1,29 @@
+use super::{load_graph, write_ids_if_remapped, Context};
+use crate::cli::PartitionArgs;
+use crate::partition::{partition, Partition, PartitionMethod};
+use crate::utils::io::{node_output_path, write_partition};
+use anyhow::Result;
+use std::time::Instant;
+
+/// Outcome of `partition`.
+#[derive(Debug)]
+pub struct Report {
+    pub partition: Partition,
+}
+
+pub fn run(args: &PartitionArgs, ctx: &Context) -> Result<Report> {
+    let method: PartitionMethod = args.method.parse()?;
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    
+    println!("\nPartitioning into {} parts ({:?})...", args.parts, method);
+    let start = Instant::now();
+    let result = partition(&graph, args.parts, method);
+    println!("Completed in {:?}\n", start.elapsed());
+    result.print(&graph);
+    
+    write_partition(&result.parts, &args.out)?;
+    println!("\nAssignment saved to: {}", node_output_path(&args.out));
+    write_ids_if_remapped(graph.original_ids(), None, &args.out)?;
+    
+    Ok(Report { partition: result })
+}
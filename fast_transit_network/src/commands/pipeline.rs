@@ -0,0 +1,45 @@
+use super::{load_graph, progress_sink, thread_count, Context};
+use crate::algorithms::pagerank::PageRankConfig;
+use crate::cli::PipelineArgs;
+use crate::config::{ParallelismPolicy, Workload};
+use crate::pipeline::{run_pipeline, PipelineConfig, PipelineStep};
+use crate::utils::io::OutputFormat;
+use anyhow::Result;
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// Outcome of `pipeline`.
+#[derive(Debug)]
+pub struct Report {
+    pub steps: Vec<PipelineStep>,
+    /// Every file the steps wrote, in order.
+    pub written: Vec<PathBuf>,
+}
+
+pub fn run(args: &PipelineArgs, ctx: &Context) -> Result<Report> {
+    // Parse everything before the (possibly long) load
+    let steps = PipelineStep::parse_list(&args.steps)?;
+    let format: OutputFormat = args.output_format.parse()?;
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    
+    let config = PipelineConfig {
+        out_dir: args.out.clone().into(),
+        mode: args.mode,
+        num_threads: thread_count(args.threads, Workload::Other),
+        policy: ParallelismPolicy::from_force_flag(args.force_parallel),
+        format,
+        pagerank: PageRankConfig {
+            alpha: args.alpha,
+            max_iterations: args.iters,
+            tolerance: args.eps,
+            progress: Some(progress_sink(ctx.verbose, Some(args.iters))),
+            weighted: args.weighted,
+            ..PageRankConfig::default()
+        },
+    };
+    let start = Instant::now();
+    let written = run_pipeline(graph, &steps, &config)?;
+    println!("\nPipeline finished in {:?}: {} steps, {} files in {}", start.elapsed(), steps.len(), written.len(), config.out_dir.display());
+    
+    Ok(Report { steps, written })
+}
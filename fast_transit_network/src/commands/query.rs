@@ -0,0 +1,18 @@
+use super::{load_graph, Context};
+use crate::cli::QueryArgs;
+use crate::query::run_interactive;
+use anyhow::Result;
+use std::io::IsTerminal;
+
+/// Answers queries from stdin until it closes; the answers are the output, so there is no report.
+pub fn run(args: &QueryArgs, ctx: &Context) -> Result<()> {
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    let stdin = std::io::stdin();
+    let interactive = stdin.is_terminal();
+    if interactive {
+        println!("\nType 'help' for commands, 'quit' to exit.");
+    }
+    run_interactive(&graph, stdin.lock(), std::io::stdout().lock(), interactive)?;
+    
+    Ok(())
+}
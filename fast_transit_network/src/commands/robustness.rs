@@ -0,0 +1,41 @@
+use super::{load_graph, thread_count, Context};
+use crate::algorithms::robustness::{robustness, RemovalStrategy, RobustnessConfig, RobustnessCurve};
+use crate::cli::RobustnessArgs;
+use crate::config::Workload;
+use crate::utils::io::{output_path, write_robustness_curve};
+use anyhow::Result;
+use std::time::Instant;
+
+/// Outcome of `robustness`.
+#[derive(Debug)]
+pub struct Report {
+    pub curve: RobustnessCurve,
+}
+
+pub fn run(args: &RobustnessArgs, ctx: &Context) -> Result<Report> {
+    let strategy: RemovalStrategy = args.strategy.parse()?;
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    let config = RobustnessConfig {
+        strategy,
+        steps: args.steps,
+        max_fraction: args.max_fraction,
+        seed: args.seed,
+        samples: args.samples,
+        num_threads: thread_count(args.threads, Workload::Other),
+    };
+    
+    println!("\nRemoving nodes by {:?} in {} steps...", strategy, args.steps);
+    let start = Instant::now();
+    let curve = robustness(&graph, &config);
+    println!("Completed in {:?}", start.elapsed());
+    
+    if let Some(last) = curve.points.last() {
+        println!("Giant component after removing {} nodes: {:.1}%", last.removed, 100.0 * last.giant_fraction);
+    }
+    println!("R index: {:.4}", curve.r_index());
+    
+    write_robustness_curve(&curve, &args.out)?;
+    println!("Curve saved to: {}", output_path(&args.out));
+    
+    Ok(Report { curve })
+}
@@ -0,0 +1,43 @@
+use super::{load_graph, node_arg, Context};
+use crate::algorithms::route::{constrained_shortest_path, Route, RouteConstraints};
+use crate::cli::RouteArgs;
+use anyhow::Result;
+use std::time::Instant;
+
+/// Outcome of `route`.
+#[derive(Debug)]
+pub struct Report {
+    /// `None` if no route satisfies the constraints.
+    pub route: Option<Route>,
+}
+
+pub fn run(args: &RouteArgs, ctx: &Context) -> Result<Report> {
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    let source = node_arg(&graph, None, args.source, "Source")?;
+    let target = node_arg(&graph, None, args.target, "Target")?;
+    let constraints = RouteConstraints { max_transfers: args.max_transfers, max_hops: args.max_hops, ..RouteConstraints::default() };
+    
+    println!("\nRouting {} -> {}...", args.source, args.target);
+    let start = Instant::now();
+    let route = constrained_shortest_path(&graph, source, target, &constraints)?;
+    println!("Completed in {:?}", start.elapsed());
+    
+    match &route {
+        Some(route) => {
+            println!("Cost: {}", route.cost);
+            println!("Transfers: {}, hops: {}", route.transfers, route.hops());
+            let mut line = graph.original_id(route.path[0]).to_string();
+            for (node, mode) in route.path[1..].iter().zip(&route.modes) {
+                if mode.is_empty() {
+                    line.push_str(&format!(" -> {}", graph.original_id(*node)));
+                } else {
+                    line.push_str(&format!(" -[{}]-> {}", mode, graph.original_id(*node)));
+                }
+            }
+            println!("Path: {}", line);
+        }
+        None => println!("No route satisfies the constraints"),
+    }
+    
+    Ok(Report { route })
+}
@@ -0,0 +1,32 @@
+use super::{load_graph, write_ids_if_remapped, Context};
+use crate::cli::RunArgs;
+use crate::registry::{Output, Params, Registry};
+use crate::utils::io::{write_output_as, OutputFormat};
+use anyhow::Result;
+use std::time::Instant;
+
+/// Outcome of `run`.
+#[derive(Debug)]
+pub struct Report {
+    pub output: Output,
+}
+
+pub fn run(args: &RunArgs, ctx: &Context) -> Result<Report> {
+    // Check the name and parameters before the (possibly long) load
+    let registry = Registry::builtin();
+    let params = Params::parse(&args.params)?;
+    let entry = registry.lookup(&args.algorithm, &params)?;
+    let format: OutputFormat = args.output_format.parse()?;
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    
+    println!("\nRunning {} ({})...", entry.name(), entry.about());
+    let start = Instant::now();
+    let output = entry.run(&graph, &params)?;
+    println!("Completed in {:?}", start.elapsed());
+    
+    write_output_as(&output, &args.algorithm, &args.out, format)?;
+    println!("Results saved to: {}", format.output_path(&args.out));
+    write_ids_if_remapped(graph.original_ids(), None, &args.out)?;
+    
+    Ok(Report { output })
+}
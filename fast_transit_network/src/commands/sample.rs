@@ -0,0 +1,34 @@
+use super::{load_graph, Context};
+use crate::cli::SampleArgs;
+use crate::sample::{sample, Sample, SampleConfig, SampleMethod};
+use crate::utils::io::{derived_path, write_edge_list, write_id_map};
+use anyhow::Result;
+use std::time::Instant;
+
+/// Outcome of `sample`.
+#[derive(Debug)]
+pub struct Report {
+    pub sample: Sample,
+    /// Where the original ids of the sampled nodes were written.
+    pub ids_path: String,
+}
+
+pub fn run(args: &SampleArgs, ctx: &Context) -> Result<Report> {
+    let method: SampleMethod = args.method.parse()?;
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    
+    println!("\nSampling {} nodes ({:?})...", args.nodes, method);
+    let start = Instant::now();
+    let config = SampleConfig { method, nodes: args.nodes, seed: args.seed, ..SampleConfig::default() };
+    let sampled = sample(&graph, &config);
+    println!("Completed in {:?}", start.elapsed());
+    println!("Sample: {} nodes, {} edges", sampled.graph.num_nodes, sampled.graph.num_edges);
+    
+    write_edge_list(&sampled.graph, &args.out)?;
+    let ids_path = derived_path(&args.out, "_ids");
+    write_id_map(&sampled.original_ids, &ids_path)?;
+    println!("Sample saved to: {}", args.out);
+    println!("Original ids saved to: {}", ids_path);
+    
+    Ok(Report { sample: sampled, ids_path })
+}
@@ -0,0 +1,9 @@
+use super::{load_graph, Context};
+use crate::cli::ServeArgs;
+use anyhow::Result;
+
+/// Serves until the process is stopped.
+pub fn run(args: &ServeArgs, ctx: &Context) -> Result<()> {
+    let (graph, _) = load_graph(&args.input, None, ctx)?;
+    tokio::runtime::Runtime::new()?.block_on(crate::server::serve(graph, &args.addr))
+}
@@ -0,0 +1,54 @@
+use super::{thread_count, write_histogram_if_requested, Context};
+use crate::algorithms::diameter::{approx_diameter, DiameterBounds};
+use crate::cli::StatsArgs;
+use crate::config::Workload;
+use crate::graph::graph::load_graph_from_files_with_options;
+use crate::progress::NoProgress;
+use crate::graph::stats::GraphStats;
+use anyhow::Result;
+use std::time::{Duration, Instant};
+
+/// Outcome of `stats`.
+#[derive(Debug)]
+pub struct Report {
+    pub stats: GraphStats,
+    pub load_time: Duration,
+    /// With `--diameter-sweeps`.
+    pub diameter: Option<DiameterBounds>,
+}
+
+pub fn run(args: &StatsArgs, ctx: &Context) -> Result<Report> {
+    println!("Loading graph from: {}", args.input.join(", "));
+    let start = Instant::now();
+    let graph = load_graph_from_files_with_options(&args.input, &ctx.load, &NoProgress)?;
+    let load_time = start.elapsed();
+    println!("Loaded in {:?}", load_time);
+    if let Some(report) = &graph.load_report {
+        report.print();
+    }
+    println!();
+    let stats = GraphStats::compute(&graph);
+    stats.print();
+    
+    let diameter = args.diameter_sweeps.map(|sweeps| {
+        let start = Instant::now();
+        let bounds = approx_diameter(&graph, sweeps, thread_count(args.threads, Workload::Other));
+        if bounds.is_exact() {
+            println!("  Diameter (undirected): {}", bounds.lower);
+        } else {
+            println!("  Diameter (undirected): between {} and {}", bounds.lower, bounds.upper);
+        }
+        println!(
+            "  ({} double sweeps in {:?}; longest path found {} -> {})",
+            bounds.sweeps,
+            start.elapsed(),
+            graph.original_id(bounds.endpoints.0),
+            graph.original_id(bounds.endpoints.1)
+        );
+        bounds
+    });
+    let degrees: Vec<f64> = graph.out_degree.iter().map(|&d| d as f64).collect();
+    write_histogram_if_requested(&args.histogram, &degrees, "Out-degree")?;
+    
+    Ok(Report { stats, load_time, diameter })
+}
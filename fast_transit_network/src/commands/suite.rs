@@ -0,0 +1,29 @@
+use super::benchmark::{self, Report};
+use super::Context;
+use crate::cli::SuiteArgs;
+use crate::suite::Suite;
+use crate::utils::benchmark::{BenchmarkAlgorithm, BenchmarkLogger};
+use anyhow::Result;
+
+/// Outcome of `suite`: the rows of every graph entry, as for `benchmark`.
+pub fn run(args: &SuiteArgs, ctx: &Context) -> Result<Report> {
+    let suite = Suite::load(&args.file)?;
+    let steps = suite.benchmark_args()?;
+    // Check every entry and the baseline before the first graph is loaded
+    for step in &steps {
+        BenchmarkAlgorithm::parse_list(&step.algorithms)?;
+    }
+    let baseline_path = args.baseline.clone().or_else(|| suite.baseline_path());
+    let baseline = baseline_path.as_deref().map(BenchmarkLogger::load_csv).transpose()?;
+    if let Some(path) = &baseline_path {
+        benchmark::warn_if_other_machine(path)?;
+    }
+    let out = args.out.clone().unwrap_or_else(|| suite.out_path());
+
+    let mut runs = Vec::new();
+    for (index, step) in steps.iter().enumerate() {
+        println!("\n[{}/{}] {}", index + 1, steps.len(), suite.graphs[index].path);
+        runs.extend(benchmark::measure_graphs(step, ctx)?);
+    }
+    benchmark::save_report(runs, &out, baseline.as_deref(), suite.regression_threshold())
+}
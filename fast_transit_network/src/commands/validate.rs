@@ -0,0 +1,26 @@
+use super::Context;
+use crate::cli::ValidateArgs;
+use crate::graph::graph::load_graph_from_files_with_options;
+use crate::progress::NoProgress;
+use crate::graph::validate::ValidationReport;
+use crate::utils::io::{output_path, write_validation_report};
+use anyhow::Result;
+
+/// Outcome of `validate`; the command fails unless `validation.is_valid()`.
+#[derive(Debug)]
+pub struct Report {
+    pub validation: ValidationReport,
+}
+
+pub fn run(args: &ValidateArgs, ctx: &Context) -> Result<Report> {
+    let graph = load_graph_from_files_with_options(&args.input, &ctx.load, &NoProgress)?;
+    let validation = graph.validate();
+    validation.print();
+    
+    if let Some(out) = &args.out {
+        write_validation_report(&validation, out)?;
+        println!("\nReport saved to: {}", output_path(out));
+    }
+    
+    Ok(Report { validation })
+}
@@ -0,0 +1,32 @@
+use super::{load_graph, sequential_storage, thread_count, write_ids_if_remapped, Context};
+use crate::algorithms::wcc::{run_wcc_and_save, WccMethod, WccResult};
+use crate::cli::WccArgs;
+use crate::config::{Mode, ParallelismPolicy, Workload};
+use crate::utils::io::{derived_path, OutputFormat, WccColumns};
+use anyhow::Result;
+
+/// Outcome of `wcc`.
+#[derive(Debug)]
+pub struct Report {
+    /// Labels in original node ids.
+    pub result: WccResult,
+    /// Where the component size statistics were written.
+    pub stats_path: String,
+}
+
+pub fn run(args: &WccArgs, ctx: &Context) -> Result<Report> {
+    let format: OutputFormat = args.output_format.parse()?;
+    let method: WccMethod = args.method.parse()?;
+    let policy = ParallelismPolicy::from_force_flag(args.force_parallel);
+    let (graph, perm) = load_graph(&args.input, args.reorder.as_deref(), ctx)?;
+    
+    let stats_path = derived_path(&args.out, "_stats");
+    let threads = thread_count(args.threads, Workload::Wcc);
+    let columns = WccColumns { dense_ids: args.dense_ids, sizes: args.component_sizes };
+    let graph = sequential_storage(graph, args.mode == Mode::Seq && method == WccMethod::UnionFind, ctx);
+    
+    let result = run_wcc_and_save(&graph, args.mode, method, threads, &args.out, &stats_path, perm.as_ref(), policy, format, columns, ctx.cache.as_ref())?;
+    write_ids_if_remapped(graph.original_ids(), perm.as_ref(), &args.out)?;
+    
+    Ok(Report { result, stats_path })
+}
@@ -0,0 +1,306 @@
+use crate::utils::summary::SummaryConfig;
+use std::sync::{OnceLock, RwLock};
+
+/// Process-wide defaults for parallel execution and result files.
+///
+/// Initialized from environment variables on first use (`FTN_THREADS`, which also accepts `auto`,
+/// `FTN_BFS_MIN_NODES`, `FTN_BFS_MIN_FRONTIER`, `FTN_WCC_MIN_NODES`, `FTN_PAGERANK_MIN_NODES`,
+/// `FTN_PAGERANK_MAX_THREADS`, `FTN_CHUNK_SIZE`, `FTN_BUILD_MIN_EDGES`, `FTN_COMPRESS`, `FTN_SHARDS`, `FTN_SUMMARY_TOP_K`, `FTN_SUMMARY_QUANTILES`); the CLI applies its flags on top via [`Config::set_global`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// Default worker count when a command does not specify `--threads`.
+    pub threads: usize,
+    /// Below this many nodes, parallel BFS runs sequentially to avoid thread-pool and atomic overhead.
+    pub bfs_min_nodes: usize,
+    /// Minimum frontier size to expand a BFS level in parallel; smaller levels are processed sequentially.
+    pub bfs_min_frontier: usize,
+    /// Below this many nodes, parallel WCC runs sequentially.
+    pub wcc_min_nodes: usize,
+    /// Below this many nodes, parallel PageRank runs sequentially.
+    pub pagerank_min_nodes: usize,
+    /// Upper bound on PageRank workers; the per-thread accumulation vectors stop paying off past this.
+    pub pagerank_max_threads: usize,
+    /// Minimum number of nodes per rayon task in PageRank; `None` splits the nodes evenly across threads.
+    pub chunk_size: Option<usize>,
+    /// Below this many edges, the loader builds the CSR arrays sequentially instead of with
+    /// [`build_csr_parallel`](crate::graph::graph::build_csr_parallel).
+    pub build_min_edges: usize,
+    /// Compression of result files written through `utils::io` (needs the `compression` feature).
+    pub compress: Compression,
+    /// Files each per-node result is split into, written in parallel (`out.part-000.txt`, ...); 1
+    /// writes a single file.
+    pub shards: usize,
+    /// Nodes and quantiles kept by `--output-format summary`.
+    pub summary: SummaryConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            threads: 4,
+            bfs_min_nodes: 50_000,
+            bfs_min_frontier: 1024,
+            wcc_min_nodes: 100_000,
+            pagerank_min_nodes: 10_000,
+            pagerank_max_threads: 8,
+            chunk_size: None,
+            build_min_edges: 1_000_000,
+            compress: Compression::None,
+            shards: 1,
+            summary: SummaryConfig::default(),
+        }
+    }
+}
+
+static GLOBAL: OnceLock<RwLock<Config>> = OnceLock::new();
+
+impl Config {
+    /// Default configuration overridden by any `FTN_*` environment variables that are set.
+    pub fn from_env() -> Self {
+        let mut config = Config::default();
+        if std::env::var("FTN_THREADS").is_ok_and(|v| v.trim() == "auto") {
+            config.threads = available_threads(None);
+        } else if let Some(v) = env_usize("FTN_THREADS") {
+            config.threads = v.max(1);
+        }
+        if let Some(v) = env_usize("FTN_BFS_MIN_NODES") {
+            config.bfs_min_nodes = v;
+        }
+        if let Some(v) = env_usize("FTN_BFS_MIN_FRONTIER") {
+            config.bfs_min_frontier = v;
+        }
+        if let Some(v) = env_usize("FTN_WCC_MIN_NODES") {
+            config.wcc_min_nodes = v;
+        }
+        if let Some(v) = env_usize("FTN_PAGERANK_MIN_NODES") {
+            config.pagerank_min_nodes = v;
+        }
+        if let Some(v) = env_usize("FTN_PAGERANK_MAX_THREADS") {
+            config.pagerank_max_threads = v.max(1);
+        }
+        if let Some(v) = env_usize("FTN_CHUNK_SIZE") {
+            config.chunk_size = Some(v.max(1));
+        }
+        if let Some(v) = env_usize("FTN_BUILD_MIN_EDGES") {
+            config.build_min_edges = v;
+        }
+        if let Some(v) = env_usize("FTN_SHARDS") {
+            config.shards = v;
+        }
+        if let Some(v) = env_usize("FTN_SUMMARY_TOP_K") {
+            config.summary.top_k = v;
+        }
+        if let Ok(value) = std::env::var("FTN_SUMMARY_QUANTILES") {
+            match value.split(',').map(|q| q.trim().parse::<f64>()).collect::<Result<Vec<_>, _>>() {
+                Ok(v) => config.summary.quantiles = v,
+                Err(_) => tracing::warn!("Ignoring FTN_SUMMARY_QUANTILES={}: expected comma-separated numbers", value),
+            }
+        }
+        if let Ok(value) = std::env::var("FTN_COMPRESS") {
+            match <Compression as clap::ValueEnum>::from_str(value.trim(), true) {
+                Ok(v) => config.compress = v,
+                Err(_) => tracing::warn!("Ignoring FTN_COMPRESS={}: expected none, gz or zst", value),
+            }
+        }
+        config
+    }
+
+    /// Returns a copy of the current process-wide configuration.
+    pub fn global() -> Config {
+        Self::cell()
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replaces the process-wide configuration.
+    pub fn set_global(config: Config) {
+        *Self::cell().write().unwrap_or_else(|e| e.into_inner()) = config;
+    }
+
+    fn cell() -> &'static RwLock<Config> {
+        GLOBAL.get_or_init(|| RwLock::new(Config::from_env()))
+    }
+}
+
+fn env_usize(name: &str) -> Option<usize> {
+    let value = std::env::var(name).ok()?;
+    match value.trim().parse() {
+        Ok(v) => Some(v),
+        Err(_) => {
+            tracing::warn!("Ignoring {}={}: expected a non-negative integer", name, value);
+            None
+        }
+    }
+}
+
+/// Parallel BFS is bound by memory bandwidth, which is usually saturated well before this many workers.
+pub const BFS_AUTO_MAX_THREADS: usize = 16;
+
+/// Logical CPUs available to this process (1 if unknown, e.g. on wasm), capped at `cap` if given.
+pub fn available_threads(cap: Option<usize>) -> usize {
+    let cpus = std::thread::available_parallelism().map_or(1, |n| n.get());
+    cap.map_or(cpus, |cap| cpus.min(cap.max(1)))
+}
+
+/// Algorithm families that scale differently with the worker count, for [`Threads::resolve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Workload {
+    /// Capped at [`BFS_AUTO_MAX_THREADS`].
+    Bfs,
+    Wcc,
+    /// Still clamped to [`Config::pagerank_max_threads`] by the PageRank implementation.
+    PageRank,
+    /// Independent per-source or per-walk tasks (APSP, centrality sampling, random walks).
+    Other,
+}
+
+/// Worker count from a `--threads` flag: a fixed number or `auto`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Threads {
+    /// Chosen per [`Workload`] from the available CPUs.
+    Auto,
+    Count(usize),
+}
+
+impl Threads {
+    /// Worker count for `workload`; `Count(0)` counts as 1.
+    pub fn resolve(self, workload: Workload) -> usize {
+        match (self, workload) {
+            (Threads::Count(n), _) => n.max(1),
+            (Threads::Auto, Workload::Bfs) => available_threads(Some(BFS_AUTO_MAX_THREADS)),
+            (Threads::Auto, _) => available_threads(None),
+        }
+    }
+
+    /// Thread counts for a scaling sweep up to the available CPUs: powers of two from 2, then the CPU
+    /// count itself (just `[1]` on a single CPU).
+    pub fn sweep() -> Vec<usize> {
+        let cpus = available_threads(None);
+        let mut counts: Vec<usize> = std::iter::successors(Some(2), |&n| Some(n * 2)).take_while(|&n| n < cpus).collect();
+        counts.push(cpus);
+        counts
+    }
+}
+
+impl std::str::FromStr for Threads {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim() {
+            "auto" => Ok(Threads::Auto),
+            n => n.parse().map(Threads::Count).map_err(|_| anyhow::anyhow!("Invalid thread count: {}. Use a number or 'auto'", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Threads {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Threads::Auto => write!(f, "auto"),
+            Threads::Count(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+/// Per-call choice between the parallel implementation and the small-graph sequential fallback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParallelismPolicy {
+    /// Fall back to sequential below the thresholds in [`Config::global`].
+    #[default]
+    Auto,
+    /// Always run the parallel implementation (every BFS level is expanded in parallel).
+    ForceParallel,
+    /// Fall back to sequential below this many nodes, ignoring the global threshold.
+    MinNodes(usize),
+}
+
+impl ParallelismPolicy {
+    /// Maps a `--force-parallel` style flag to a policy.
+    pub fn from_force_flag(force_parallel: bool) -> Self {
+        if force_parallel {
+            ParallelismPolicy::ForceParallel
+        } else {
+            ParallelismPolicy::Auto
+        }
+    }
+
+    /// Returns true if a graph with `num_nodes` nodes should use the parallel path,
+    /// given the global threshold `default_min_nodes`.
+    pub fn use_parallel(self, num_nodes: usize, default_min_nodes: usize) -> bool {
+        match self {
+            ParallelismPolicy::Auto => num_nodes >= default_min_nodes,
+            ParallelismPolicy::ForceParallel => true,
+            ParallelismPolicy::MinNodes(min_nodes) => num_nodes >= min_nodes,
+        }
+    }
+}
+
+/// Implementation choice of the `--mode` flag; unknown values are rejected when the arguments are parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Mode {
+    /// Sequential implementation.
+    #[default]
+    Seq,
+    /// Parallel implementation with `--threads` workers.
+    Par,
+    /// Same as `par`; kept for existing scripts from when PageRank had a separate optimized variant.
+    ParOpt,
+}
+
+impl Mode {
+    pub fn is_parallel(self) -> bool {
+        !matches!(self, Mode::Seq)
+    }
+}
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mode::Seq => write!(f, "seq"),
+            Mode::Par => write!(f, "par"),
+            Mode::ParOpt => write!(f, "par-opt"),
+        }
+    }
+}
+
+/// `--kind` of the `connectivity` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Connectivity {
+    /// Components ignoring edge direction (WCC).
+    #[default]
+    Weak,
+    /// Nodes that reach each other along edge directions (SCC).
+    Strong,
+}
+
+/// Compression of result files (`--compress`): streamed while writing, with the extension appended
+/// to the file name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Compression {
+    /// Plain text files.
+    #[default]
+    None,
+    /// gzip (`.gz`): readable everywhere with `zcat`.
+    Gz,
+    /// Zstandard (`.zst`): smaller and several times faster than gzip.
+    Zst,
+}
+
+impl Compression {
+    /// File name extension, including the dot.
+    pub fn extension(self) -> Option<&'static str> {
+        match self {
+            Compression::None => None,
+            Compression::Gz => Some(".gz"),
+            Compression::Zst => Some(".zst"),
+        }
+    }
+
+    /// Compression named by the extension of `path`, if it ends in `.gz` or `.zst`.
+    pub fn from_path(path: &str) -> Option<Compression> {
+        [Compression::Gz, Compression::Zst]
+            .into_iter()
+            .find(|c| c.extension().is_some_and(|ext| path.ends_with(ext)))
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt;
+
+/// Errors returned by graph loading and algorithm entry points.
+#[derive(Debug)]
+pub enum Error {
+    /// A node id outside `0..num_nodes` (or hidden by a view) was passed to an algorithm.
+    InvalidNode { node: usize, num_nodes: usize },
+    /// Malformed input at the given 1-based line.
+    Parse { line: usize, message: String },
+    /// A negative-weight cycle is reachable from the source, so shortest paths are undefined.
+    NegativeCycle { node: usize },
+    /// An algorithm that needs non-negative weights (e.g. Dijkstra) found edge `from -> to` with a negative one.
+    NegativeWeight { from: usize, to: usize },
+    /// An algorithm that needs a DAG found a directed cycle through `node`.
+    Cycle { node: usize },
+    /// An attribute column does not have one value per edge (or per node, for node attributes).
+    AttributeLength { name: String, expected: usize, actual: usize },
+    /// An argument passed to an algorithm entry point is unusable (e.g. a start vector of the wrong length).
+    InvalidInput { message: String },
+    /// Underlying I/O failure.
+    Io(std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn parse(line: usize, message: impl Into<String>) -> Self {
+        Error::Parse {
+            line,
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Error::InvalidInput {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidNode { node, num_nodes } => {
+                write!(f, "Invalid node {} (graph has {} nodes)", node, num_nodes)
+            }
+            Error::Parse { line, message } => write!(f, "Parse error on line {}: {}", line, message),
+            Error::NegativeCycle { node } => {
+                write!(f, "Negative-weight cycle reachable from the source (through node {})", node)
+            }
+            Error::NegativeWeight { from, to } => {
+                write!(f, "Edge {} -> {} has a negative weight; use Bellman-Ford instead", from, to)
+            }
+            Error::Cycle { node } => write!(f, "Graph has a directed cycle through node {}; expected a DAG", node),
+            Error::AttributeLength { name, expected, actual } => {
+                write!(f, "Attribute '{}' has {} values, expected {}", name, actual, expected)
+            }
+            Error::InvalidInput { message } => write!(f, "Invalid input: {}", message),
+            Error::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
@@ -0,0 +1,254 @@
+//! C API over the graph loader and the BFS, WCC and PageRank algorithms.
+//!
+//! Graphs are opaque `FtnGraph` handles. Results are returned by value and own their arrays until
+//! passed to the matching `ftn_free_*` function. On failure a call returns a null handle (or a
+//! result with a null array) and [`ftn_last_error`] describes the error.
+
+use crate::algorithms::bfs::{bfs_parallel, bfs_sequential};
+use crate::algorithms::pagerank::{pagerank_parallel, pagerank_sequential, PageRankConfig};
+use crate::algorithms::wcc::{wcc_parallel, wcc_sequential};
+use crate::graph::graph::{build_csr, load_graph_from_file, Graph};
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+/// Opaque graph handle.
+pub struct FtnGraph(Graph);
+
+/// Hop distances from a BFS; `distances[v]` is -1 if `v` is unreachable.
+#[repr(C)]
+pub struct FtnBfsResult {
+    pub distances: *mut i32,
+    pub len: usize,
+    pub reachable: usize,
+    pub levels: usize,
+}
+
+/// Component label per node.
+#[repr(C)]
+pub struct FtnWccResult {
+    pub labels: *mut usize,
+    pub len: usize,
+    pub num_components: usize,
+}
+
+/// PageRank score per node.
+#[repr(C)]
+pub struct FtnPageRankResult {
+    pub ranks: *mut f64,
+    pub len: usize,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: impl ToString) {
+    let message = CString::new(message.to_string().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+}
+
+fn into_raw<T>(values: Vec<T>) -> (*mut T, usize) {
+    let len = values.len();
+    (Box::into_raw(values.into_boxed_slice()) as *mut T, len)
+}
+
+/// # Safety
+/// `data` and `len` must come from [`into_raw`] and not have been freed.
+unsafe fn free_raw<T>(data: *mut T, len: usize) {
+    if !data.is_null() {
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(data, len)) });
+    }
+}
+
+/// Message of the last failed call on this thread, or null. Valid until the next failing call.
+#[unsafe(no_mangle)]
+pub extern "C" fn ftn_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+/// Loads an edge-list file. Returns null on failure.
+///
+/// # Safety
+/// `path` must be a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftn_load_graph(path: *const c_char) -> *mut FtnGraph {
+    if path.is_null() {
+        set_last_error("path is null");
+        return ptr::null_mut();
+    }
+    let path = match unsafe { CStr::from_ptr(path) }.to_str() {
+        Ok(p) => p,
+        Err(e) => {
+            set_last_error(e);
+            return ptr::null_mut();
+        }
+    };
+    match load_graph_from_file(path) {
+        Ok(graph) => Box::into_raw(Box::new(FtnGraph(graph))),
+        Err(e) => {
+            set_last_error(e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Builds a graph from `num_edges` edges `src[i] -> dst[i]`. Returns null on failure.
+///
+/// # Safety
+/// `src` and `dst` must each point to `num_edges` readable values.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftn_graph_from_edges(
+    num_nodes: usize,
+    src: *const usize,
+    dst: *const usize,
+    num_edges: usize,
+) -> *mut FtnGraph {
+    if num_edges > 0 && (src.is_null() || dst.is_null()) {
+        set_last_error("edge arrays are null");
+        return ptr::null_mut();
+    }
+    let (src, dst) = if num_edges == 0 {
+        (&[][..], &[][..])
+    } else {
+        unsafe {
+            (
+                std::slice::from_raw_parts(src, num_edges),
+                std::slice::from_raw_parts(dst, num_edges),
+            )
+        }
+    };
+    if let Some(&bad) = src.iter().chain(dst).find(|&&v| v >= num_nodes) {
+        set_last_error(crate::error::Error::InvalidNode { node: bad, num_nodes });
+        return ptr::null_mut();
+    }
+    let edges = src.iter().copied().zip(dst.iter().copied()).collect();
+    Box::into_raw(Box::new(FtnGraph(build_csr(num_nodes, edges))))
+}
+
+/// # Safety
+/// `graph` must be a live handle or null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftn_graph_num_nodes(graph: *const FtnGraph) -> usize {
+    unsafe { graph.as_ref() }.map_or(0, |g| g.0.num_nodes)
+}
+
+/// # Safety
+/// `graph` must be a live handle or null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftn_graph_num_edges(graph: *const FtnGraph) -> usize {
+    unsafe { graph.as_ref() }.map_or(0, |g| g.0.num_edges)
+}
+
+/// # Safety
+/// `graph` must be a handle from this library (or null) and not used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftn_free_graph(graph: *mut FtnGraph) {
+    if !graph.is_null() {
+        drop(unsafe { Box::from_raw(graph) });
+    }
+}
+
+/// BFS from `source`; `threads == 0` runs the sequential version.
+/// On failure `distances` is null.
+///
+/// # Safety
+/// `graph` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftn_bfs(graph: *const FtnGraph, source: usize, threads: usize) -> FtnBfsResult {
+    let empty = FtnBfsResult { distances: ptr::null_mut(), len: 0, reachable: 0, levels: 0 };
+    let Some(FtnGraph(graph)) = (unsafe { graph.as_ref() }) else {
+        set_last_error("graph is null");
+        return empty;
+    };
+    let result = if threads == 0 {
+        bfs_sequential(graph, source)
+    } else {
+        bfs_parallel(graph, source, threads)
+    };
+    match result {
+        Ok(result) => {
+            let (distances, len) = into_raw(result.distances);
+            FtnBfsResult { distances, len, reachable: result.reachable, levels: result.levels }
+        }
+        Err(e) => {
+            set_last_error(e);
+            empty
+        }
+    }
+}
+
+/// # Safety
+/// `result` must come from [`ftn_bfs`] and not have been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftn_free_bfs_result(result: FtnBfsResult) {
+    unsafe { free_raw(result.distances, result.len) }
+}
+
+/// Weakly connected components; `threads == 0` runs the sequential version.
+///
+/// # Safety
+/// `graph` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftn_wcc(graph: *const FtnGraph, threads: usize) -> FtnWccResult {
+    let Some(FtnGraph(graph)) = (unsafe { graph.as_ref() }) else {
+        set_last_error("graph is null");
+        return FtnWccResult { labels: ptr::null_mut(), len: 0, num_components: 0 };
+    };
+    let result = if threads == 0 {
+        wcc_sequential(graph)
+    } else {
+        wcc_parallel(graph, threads)
+    };
+    let num_components = result.stats.num_components;
+    let (labels, len) = into_raw(result.labels);
+    FtnWccResult { labels, len, num_components }
+}
+
+/// # Safety
+/// `result` must come from [`ftn_wcc`] and not have been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftn_free_wcc_result(result: FtnWccResult) {
+    unsafe { free_raw(result.labels, result.len) }
+}
+
+/// PageRank with the given damping factor, iteration cap and L1 tolerance;
+/// `threads == 0` runs the sequential version.
+///
+/// # Safety
+/// `graph` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftn_pagerank(
+    graph: *const FtnGraph,
+    alpha: f64,
+    max_iterations: usize,
+    tolerance: f64,
+    threads: usize,
+) -> FtnPageRankResult {
+    let Some(FtnGraph(graph)) = (unsafe { graph.as_ref() }) else {
+        set_last_error("graph is null");
+        return FtnPageRankResult { ranks: ptr::null_mut(), len: 0, iterations: 0, converged: false };
+    };
+    let config = PageRankConfig {
+        alpha,
+        max_iterations,
+        tolerance,
+        ..PageRankConfig::default()
+    };
+    let result = if threads == 0 {
+        pagerank_sequential(graph, &config)
+    } else {
+        pagerank_parallel(graph, &config, threads)
+    };
+    let (ranks, len) = into_raw(result.ranks);
+    FtnPageRankResult { ranks, len, iterations: result.iterations, converged: result.converged }
+}
+
+/// # Safety
+/// `result` must come from [`ftn_pagerank`] and not have been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftn_free_pagerank_result(result: FtnPageRankResult) {
+    unsafe { free_raw(result.ranks, result.len) }
+}
@@ -0,0 +1,366 @@
+use crate::error::{Error, Result};
+use crate::graph::graph::Graph;
+use std::any::Any;
+use std::io::BufRead;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A typed attribute column that can live in an [`AttrStore`].
+///
+/// Implemented for [`EdgeAttr`], [`NodeAttr`] and [`Categorical`]; `gather` lets graph transformations
+/// (transpose, reordering) carry columns along when they move edges or renumber nodes.
+pub trait AttrColumn: Any + Send + Sync + fmt::Debug {
+    /// Number of values (one per edge or node).
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// New column whose value `i` is this column's value `order[i]`.
+    fn gather(&self, order: &[usize]) -> Box<dyn AttrColumn>;
+
+    fn clone_column(&self) -> Box<dyn AttrColumn>;
+
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// Per-edge values of one attribute, indexed by CSR edge index (the position in `Graph::neighbors`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeAttr<T> {
+    values: Vec<T>,
+}
+
+impl<T> EdgeAttr<T> {
+    pub fn new(values: Vec<T>) -> Self {
+        Self { values }
+    }
+
+    /// Value of edge `edge`, or `None` if out of range.
+    pub fn get(&self, edge: usize) -> Option<&T> {
+        self.values.get(edge)
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Values of the out-edges of `v`, aligned with `graph.neighbors(v)`; empty if `v` is out of range.
+    pub fn for_node(&self, graph: &Graph, v: usize) -> &[T] {
+        if v >= graph.num_nodes {
+            return &self.values[0..0];
+        }
+        &self.values[graph.offsets[v]..graph.offsets[v + 1]]
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<T: Clone + Send + Sync + fmt::Debug + 'static> AttrColumn for EdgeAttr<T> {
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn gather(&self, order: &[usize]) -> Box<dyn AttrColumn> {
+        Box::new(EdgeAttr::new(order.iter().map(|&i| self.values[i].clone()).collect()))
+    }
+
+    fn clone_column(&self) -> Box<dyn AttrColumn> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Per-node values of one attribute, indexed by node id.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeAttr<T> {
+    values: Vec<T>,
+}
+
+impl<T> NodeAttr<T> {
+    pub fn new(values: Vec<T>) -> Self {
+        Self { values }
+    }
+
+    /// Value of node `v`, or `None` if out of range.
+    pub fn get(&self, v: usize) -> Option<&T> {
+        self.values.get(v)
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<T: Clone + Send + Sync + fmt::Debug + 'static> AttrColumn for NodeAttr<T> {
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn gather(&self, order: &[usize]) -> Box<dyn AttrColumn> {
+        Box::new(NodeAttr::new(order.iter().map(|&i| self.values[i].clone()).collect()))
+    }
+
+    fn clone_column(&self) -> Box<dyn AttrColumn> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// String labels with few distinct values (e.g. transport mode), stored as `u32` codes into `labels`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Categorical {
+    codes: Vec<u32>,
+    labels: Vec<String>,
+}
+
+impl Categorical {
+    /// Interns `values`; codes are assigned in order of first appearance.
+    pub fn from_labels<I, S>(values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut column = Self::default();
+        for value in values {
+            column.push(value.as_ref());
+        }
+        column
+    }
+
+    /// Builds a column from codes and their labels; every code must index `labels`.
+    pub fn from_codes(codes: Vec<u32>, labels: Vec<String>) -> Self {
+        debug_assert!(codes.iter().all(|&c| (c as usize) < labels.len()));
+        Self { codes, labels }
+    }
+
+    /// Appends a value, interning `label` if it is new. Lookup is a linear scan, which is
+    /// faster than hashing for the handful of distinct labels this is meant for.
+    pub fn push(&mut self, label: &str) {
+        let code = self.code(label).unwrap_or_else(|| {
+            self.labels.push(label.to_string());
+            (self.labels.len() - 1) as u32
+        });
+        self.codes.push(code);
+    }
+
+    /// Code of `label`, or `None` if no value has it.
+    pub fn code(&self, label: &str) -> Option<u32> {
+        self.labels.iter().position(|l| l == label).map(|c| c as u32)
+    }
+
+    /// Label of value `i`, or `None` if out of range.
+    pub fn get(&self, i: usize) -> Option<&str> {
+        self.codes.get(i).map(|&c| self.labels[c as usize].as_str())
+    }
+
+    /// True if value `i` has label `label`.
+    pub fn is(&self, i: usize, label: &str) -> bool {
+        self.get(i) == Some(label)
+    }
+
+    pub fn codes(&self) -> &[u32] {
+        &self.codes
+    }
+
+    /// Distinct labels, indexed by code.
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+}
+
+impl AttrColumn for Categorical {
+    fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    fn gather(&self, order: &[usize]) -> Box<dyn AttrColumn> {
+        Box::new(Categorical {
+            codes: order.iter().map(|&i| self.codes[i]).collect(),
+            labels: self.labels.clone(),
+        })
+    }
+
+    fn clone_column(&self) -> Box<dyn AttrColumn> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Named attribute columns of one graph; every column has one value per edge (`Graph::edge_attrs`)
+/// or per node (`Graph::node_attrs`).
+#[derive(Debug, Default)]
+pub struct AttrStore {
+    columns: BTreeMap<String, Box<dyn AttrColumn>>,
+}
+
+impl AttrStore {
+    /// Adds or replaces column `name`. Prefer [`Graph::set_edge_attr`] / [`Graph::set_node_attr`],
+    /// which check the length.
+    pub fn insert(&mut self, name: impl Into<String>, column: impl AttrColumn) {
+        self.columns.insert(name.into(), Box::new(column));
+    }
+
+    /// Column `name` if it exists and has type `C`, e.g. `get::<EdgeAttr<f64>>("time")`.
+    pub fn get<C: AttrColumn>(&self, name: &str) -> Option<&C> {
+        self.columns.get(name)?.as_any().downcast_ref()
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.columns.remove(name).is_some()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.columns.contains_key(name)
+    }
+
+    /// Column names in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.columns.keys().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Store with every column gathered by `order` (see [`AttrColumn::gather`]).
+    pub fn gather(&self, order: &[usize]) -> AttrStore {
+        AttrStore {
+            columns: self.columns.iter().map(|(name, c)| (name.clone(), c.gather(order))).collect(),
+        }
+    }
+}
+
+impl Clone for AttrStore {
+    fn clone(&self) -> Self {
+        AttrStore {
+            columns: self.columns.iter().map(|(name, c)| (name.clone(), c.clone_column())).collect(),
+        }
+    }
+}
+
+/// Loads node attributes from a comma-separated file with one `id,name,zone,type` line per node
+/// and stores them as `"name"` ([`NodeAttr<String>`]), `"zone"` and `"type"` ([`Categorical`]).
+///
+/// Empty lines and lines starting with `#` are skipped, as is a first line starting with `id`
+/// (a header). Missing trailing fields and nodes not listed in the file get empty values.
+/// Returns [`Error::Parse`] for a bad or out-of-range id.
+pub fn load_node_attributes<R: BufRead>(graph: &mut Graph, reader: R) -> Result<()> {
+    let n = graph.num_nodes;
+    let mut names = vec![String::new(); n];
+    let mut zones = vec![String::new(); n];
+    let mut types = vec![String::new(); n];
+
+    for (index, line) in reader.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || (line_no == 1 && line.starts_with("id")) {
+            continue;
+        }
+
+        let mut fields = line.split(',').map(str::trim);
+        let id = fields.next().unwrap_or_default();
+        let v: usize = id
+            .parse()
+            .map_err(|_| Error::parse(line_no, format!("Invalid node id: {}", id)))?;
+        if v >= n {
+            return Err(Error::parse(line_no, format!("node {} is not in the graph ({} nodes)", v, n)));
+        }
+        for column in [&mut names, &mut zones, &mut types] {
+            column[v] = fields.next().unwrap_or_default().to_string();
+        }
+    }
+
+    graph.set_node_attr("name", NodeAttr::new(names))?;
+    graph.set_node_attr("zone", Categorical::from_labels(&zones))?;
+    graph.set_node_attr("type", Categorical::from_labels(&types))?;
+    Ok(())
+}
+
+/// Loads node coordinates from `id lon lat` lines (whitespace or comma separated) into the node
+/// attribute `"coords"` ([`NodeAttr<Option<[f64; 2]>>`], `None` for nodes not in the file).
+///
+/// Skips the same comment and header lines as [`load_node_attributes`].
+pub fn load_node_coordinates<R: BufRead>(graph: &mut Graph, reader: R) -> Result<()> {
+    let n = graph.num_nodes;
+    let mut coords: Vec<Option<[f64; 2]>> = vec![None; n];
+
+    for (index, line) in reader.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || (line_no == 1 && line.starts_with("id")) {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split([',', ' ', '\t']).filter(|f| !f.is_empty()).collect();
+        if fields.len() < 3 {
+            return Err(Error::parse(line_no, format!("expected `id lon lat`, got `{}`", line)));
+        }
+        let v: usize = fields[0]
+            .parse()
+            .map_err(|_| Error::parse(line_no, format!("Invalid node id: {}", fields[0])))?;
+        if v >= n {
+            return Err(Error::parse(line_no, format!("node {} is not in the graph ({} nodes)", v, n)));
+        }
+        let coordinate = |text: &str| {
+            text.parse::<f64>()
+                .map_err(|_| Error::parse(line_no, format!("Invalid coordinate: {}", text)))
+        };
+        coords[v] = Some([coordinate(fields[1])?, coordinate(fields[2])?]);
+    }
+
+    graph.set_node_attr("coords", NodeAttr::new(coords))
+}
+
+/// Reads a node attribute file (see [`load_node_attributes`]).
+#[cfg(feature = "fs")]
+pub fn load_node_attributes_from_file(graph: &mut Graph, path: &str) -> Result<()> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| Error::Io(std::io::Error::new(e.kind(), format!("{}: {}", path, e))))?;
+    load_node_attributes(graph, std::io::BufReader::new(file))
+}
+
+/// Reads a node coordinate file (see [`load_node_coordinates`]).
+#[cfg(feature = "fs")]
+pub fn load_node_coordinates_from_file(graph: &mut Graph, path: &str) -> Result<()> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| Error::Io(std::io::Error::new(e.kind(), format!("{}: {}", path, e))))?;
+    load_node_coordinates(graph, std::io::BufReader::new(file))
+}
@@ -0,0 +1,294 @@
+use super::graph::{Graph, GraphLike};
+
+/// Adjacency storage of a [`CompactGraph`].
+enum Adjacency {
+    /// Plain `u32` neighbor ids, in CSR order.
+    Plain(Vec<u32>),
+    /// Per-node sorted neighbor lists: first id as a varint, then varint deltas between consecutive ids.
+    DeltaVarint(Vec<u8>),
+}
+
+/// CSR graph with `u32` neighbor ids, roughly halving the adjacency footprint of [`Graph`] on 64-bit targets.
+///
+/// Offsets stay `usize` so graphs with more than `u32::MAX` edges are still representable; only node ids are narrowed.
+pub struct CompactGraph {
+    pub num_nodes: usize,
+    pub num_edges: usize,
+    /// Start of each node's adjacency: an index into the neighbor array (plain) or the byte stream (delta-varint).
+    offsets: Vec<usize>,
+    degrees: Vec<u32>,
+    adjacency: Adjacency,
+    fingerprint: u64,
+    original_ids: Option<Vec<usize>>,
+}
+
+impl CompactGraph {
+    /// Converts `graph` to `u32` neighbor ids, keeping the CSR neighbor order.
+    /// Returns `None` if the node ids or out-degrees do not fit in `u32`.
+    pub fn from_graph(graph: &Graph) -> Option<Self> {
+        if !Self::fits_graph(graph) {
+            return None;
+        }
+        let neighbors = graph.neighbors.iter().map(|&v| v as u32).collect();
+        Some(Self {
+            num_nodes: graph.num_nodes,
+            num_edges: graph.num_edges,
+            offsets: graph.offsets.clone(),
+            degrees: Self::degrees_of(graph),
+            adjacency: Adjacency::Plain(neighbors),
+            fingerprint: graph.fingerprint(),
+            original_ids: graph.original_ids().map(<[usize]>::to_vec),
+        })
+    }
+
+    /// Converts `graph` to sorted, delta-varint encoded adjacency lists.
+    /// Typically 1–2 bytes per edge on locality-friendly graphs; neighbors are yielded in ascending order.
+    /// Returns `None` if the node ids or out-degrees do not fit in `u32`.
+    pub fn from_graph_encoded(graph: &Graph) -> Option<Self> {
+        if !Self::fits_graph(graph) {
+            return None;
+        }
+        let mut offsets = Vec::with_capacity(graph.num_nodes + 1);
+        let mut bytes = Vec::with_capacity(graph.num_edges);
+        let mut sorted: Vec<u32> = Vec::new();
+
+        for u in 0..graph.num_nodes {
+            offsets.push(bytes.len());
+            sorted.clear();
+            sorted.extend(graph.neighbors(u).iter().map(|&v| v as u32));
+            sorted.sort_unstable();
+
+            let mut prev = 0u32;
+            for &v in &sorted {
+                write_varint(&mut bytes, v - prev);
+                prev = v;
+            }
+        }
+        offsets.push(bytes.len());
+        bytes.shrink_to_fit();
+
+        Some(Self {
+            num_nodes: graph.num_nodes,
+            num_edges: graph.num_edges,
+            offsets,
+            degrees: Self::degrees_of(graph),
+            adjacency: Adjacency::DeltaVarint(bytes),
+            fingerprint: graph.fingerprint(),
+            original_ids: graph.original_ids().map(<[usize]>::to_vec),
+        })
+    }
+
+    /// Returns true if a graph with `num_nodes` nodes can use `u32` node ids.
+    pub fn fits(num_nodes: usize) -> bool {
+        num_nodes <= u32::MAX as usize
+    }
+
+    /// Returns true if `graph` fits: `u32` node ids and every out-degree within `u32`.
+    /// Edge offsets stay `usize`, so the total edge count may exceed `u32::MAX`.
+    pub fn fits_graph(graph: &Graph) -> bool {
+        Self::fits(graph.num_nodes) && graph.out_degree.iter().all(|&d| d <= u32::MAX as usize)
+    }
+
+    /// Returns true if adjacency is delta-varint encoded.
+    pub fn is_encoded(&self) -> bool {
+        matches!(self.adjacency, Adjacency::DeltaVarint(_))
+    }
+
+    /// Approximate heap size of the graph in bytes.
+    pub fn memory_bytes(&self) -> usize {
+        let adjacency = match &self.adjacency {
+            Adjacency::Plain(neighbors) => neighbors.len() * std::mem::size_of::<u32>(),
+            Adjacency::DeltaVarint(bytes) => bytes.len(),
+        };
+        (self.offsets.len() + self.original_ids.as_ref().map_or(0, Vec::len)) * std::mem::size_of::<usize>()
+            + self.degrees.len() * std::mem::size_of::<u32>()
+            + adjacency
+    }
+
+    /// [`Graph::fingerprint`] of the graph this was built from, so results cached for it are reused.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// Input-file ids of the graph this was built from (see [`Graph::original_ids`]).
+    pub fn original_ids(&self) -> Option<&[usize]> {
+        self.original_ids.as_deref()
+    }
+
+    /// Iterates the out-neighbors of node `v`; empty if `v` is out of range.
+    pub fn neighbors(&self, v: usize) -> CompactNeighbors<'_> {
+        if v >= self.num_nodes {
+            return CompactNeighbors::Plain([].iter());
+        }
+        let (start, end) = (self.offsets[v], self.offsets[v + 1]);
+        match &self.adjacency {
+            Adjacency::Plain(neighbors) => CompactNeighbors::Plain(neighbors[start..end].iter()),
+            Adjacency::DeltaVarint(bytes) => CompactNeighbors::DeltaVarint {
+                bytes: &bytes[start..end],
+                prev: 0,
+            },
+        }
+    }
+
+    /// Out-degree of node `v` (0 if out of range).
+    pub fn out_degree(&self, v: usize) -> usize {
+        self.degrees.get(v).map_or(0, |&d| d as usize)
+    }
+
+    fn degrees_of(graph: &Graph) -> Vec<u32> {
+        graph.out_degree.iter().map(|&d| d as u32).collect()
+    }
+}
+
+/// Iterator over the neighbors of one node of a [`CompactGraph`].
+pub enum CompactNeighbors<'a> {
+    Plain(std::slice::Iter<'a, u32>),
+    DeltaVarint { bytes: &'a [u8], prev: u32 },
+}
+
+impl Iterator for CompactNeighbors<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        match self {
+            CompactNeighbors::Plain(iter) => iter.next().map(|&v| v as usize),
+            CompactNeighbors::DeltaVarint { bytes, prev } => {
+                if bytes.is_empty() {
+                    return None;
+                }
+                let (delta, used) = read_varint(bytes);
+                *bytes = &bytes[used..];
+                *prev += delta;
+                Some(*prev as usize)
+            }
+        }
+    }
+}
+
+impl GraphLike for CompactGraph {
+    fn node_count(&self) -> usize {
+        self.num_nodes
+    }
+
+    fn neighbor_iter(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.neighbors(v)
+    }
+
+    fn degree(&self, v: usize) -> usize {
+        self.out_degree(v)
+    }
+}
+
+/// Either a [`CompactGraph`] or the wide [`Graph`] it could not be narrowed from, behind one
+/// [`GraphLike`] type.
+///
+/// The `bfs`, `wcc` and `pagerank` commands narrow the loaded graph with
+/// [`GraphStorage::compact_if_fits`] for their sequential runs, which only need adjacency, unless
+/// [`LoadOptions::wide_storage`](super::graph::LoadOptions::wide_storage) is set. Library callers do the same before running the
+/// [`GraphLike`] kernels (`bfs_sequential`, `wcc_sequential`, `pagerank_sequential`,
+/// `strongly_connected_components`, `find_bridges`, ...). Edge weights and attributes stay with the
+/// wide graph.
+pub enum GraphStorage {
+    Wide(Graph),
+    Compact(CompactGraph),
+}
+
+impl GraphStorage {
+    /// Compact (`u32`) storage if `graph` fits, dropping the wide graph afterwards; `graph` itself otherwise.
+    pub fn compact_if_fits(graph: Graph) -> Self {
+        match CompactGraph::from_graph(&graph) {
+            Some(compact) => GraphStorage::Compact(compact),
+            None => GraphStorage::Wide(graph),
+        }
+    }
+
+    /// Like [`GraphStorage::compact_if_fits`], but delta-varint encodes the adjacency when compact storage is used.
+    pub fn encoded_if_fits(graph: Graph) -> Self {
+        match CompactGraph::from_graph_encoded(&graph) {
+            Some(compact) => GraphStorage::Compact(compact),
+            None => GraphStorage::Wide(graph),
+        }
+    }
+
+    pub fn is_compact(&self) -> bool {
+        matches!(self, GraphStorage::Compact(_))
+    }
+
+    /// [`Graph::fingerprint`] of the loaded graph, whichever storage holds it.
+    pub fn fingerprint(&self) -> u64 {
+        match self {
+            GraphStorage::Wide(g) => g.fingerprint(),
+            GraphStorage::Compact(g) => g.fingerprint(),
+        }
+    }
+
+    /// Input-file ids of a remapped graph (see [`Graph::original_ids`]).
+    pub fn original_ids(&self) -> Option<&[usize]> {
+        match self {
+            GraphStorage::Wide(g) => g.original_ids(),
+            GraphStorage::Compact(g) => g.original_ids(),
+        }
+    }
+}
+
+impl GraphLike for GraphStorage {
+    fn node_count(&self) -> usize {
+        match self {
+            GraphStorage::Wide(g) => g.num_nodes,
+            GraphStorage::Compact(g) => g.num_nodes,
+        }
+    }
+
+    fn neighbor_iter(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        match self {
+            GraphStorage::Wide(g) => StorageNeighbors::Wide(g.neighbors(v).iter()),
+            GraphStorage::Compact(g) => StorageNeighbors::Compact(g.neighbors(v)),
+        }
+    }
+
+    fn degree(&self, v: usize) -> usize {
+        match self {
+            GraphStorage::Wide(g) => g.neighbors(v).len(),
+            GraphStorage::Compact(g) => g.out_degree(v),
+        }
+    }
+}
+
+enum StorageNeighbors<'a> {
+    Wide(std::slice::Iter<'a, usize>),
+    Compact(CompactNeighbors<'a>),
+}
+
+impl Iterator for StorageNeighbors<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        match self {
+            StorageNeighbors::Wide(iter) => iter.next().copied(),
+            StorageNeighbors::Compact(iter) => iter.next(),
+        }
+    }
+}
+
+/// Appends `value` as an LEB128 varint.
+fn write_varint(out: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Decodes one LEB128 varint; returns the value and the number of bytes consumed.
+fn read_varint(bytes: &[u8]) -> (u32, usize) {
+    let mut value = 0u32;
+    let mut shift = 0;
+    for (i, &b) in bytes.iter().enumerate() {
+        value |= ((b & 0x7f) as u32) << shift;
+        if b & 0x80 == 0 {
+            return (value, i + 1);
+        }
+        shift += 7;
+    }
+    (value, bytes.len())
+}
@@ -0,0 +1,176 @@
+use crate::algorithms::wcc::wcc_sequential;
+use crate::graph::graph::Graph;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Degree of a node in both snapshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DegreeChange {
+    pub node: usize,
+    pub old_out: usize,
+    pub new_out: usize,
+    pub old_in: usize,
+    pub new_in: usize,
+}
+
+/// How the weakly connected components moved between snapshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentChanges {
+    pub old_components: usize,
+    pub new_components: usize,
+    pub old_largest: usize,
+    pub new_largest: usize,
+    /// Old components whose shared nodes now fall into more than one component.
+    pub split: usize,
+    /// New components that join shared nodes from more than one old component.
+    pub merged: usize,
+}
+
+/// Differences between two snapshots of the same network (same node ids).
+///
+/// A node counts as present when it has at least one edge, so stops that lost all their edges show
+/// up as removed even though their id stays in range. Repeated edges are compared as one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphDiff {
+    pub added_nodes: Vec<usize>,
+    pub removed_nodes: Vec<usize>,
+    pub added_edges: Vec<(usize, usize)>,
+    pub removed_edges: Vec<(usize, usize)>,
+    /// `(from, to, old_weight, new_weight)` for edges in both snapshots whose weight changed.
+    pub weight_changes: Vec<(usize, usize, f64, f64)>,
+    /// Nodes whose in- or out-degree changed.
+    pub degree_changes: Vec<DegreeChange>,
+    pub components: ComponentChanges,
+}
+
+impl GraphDiff {
+    /// True if the snapshots have the same nodes, edges and weights.
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.weight_changes.is_empty()
+    }
+
+    pub fn print(&self) {
+        println!("Graph Diff:");
+        println!("  Nodes: +{} -{}", self.added_nodes.len(), self.removed_nodes.len());
+        println!("  Edges: +{} -{}", self.added_edges.len(), self.removed_edges.len());
+        println!("  Weight changes: {}", self.weight_changes.len());
+        println!("  Nodes with degree changes: {}", self.degree_changes.len());
+        let c = &self.components;
+        println!("  Components: {} -> {}", c.old_components, c.new_components);
+        println!("  Largest component: {} -> {}", c.old_largest, c.new_largest);
+        println!("  Components split: {}, merged: {}", c.split, c.merged);
+    }
+}
+
+/// Sorted, deduplicated adjacency of `u` with the weight of each edge's first occurrence.
+fn sorted_edges(graph: &Graph, u: usize) -> Vec<(usize, f64)> {
+    if u >= graph.num_nodes {
+        return Vec::new();
+    }
+    let mut edges: Vec<(usize, f64)> = graph.weighted_neighbors(u).collect();
+    edges.sort_by_key(|&(v, _)| v);
+    edges.dedup_by_key(|&mut (v, _)| v);
+    edges
+}
+
+fn degrees(graph: &Graph, n: usize) -> (Vec<usize>, Vec<usize>) {
+    let mut out_degree = graph.out_degree.clone();
+    out_degree.resize(n, 0);
+    let mut in_degree = vec![0; n];
+    for &v in &graph.neighbors {
+        in_degree[v] += 1;
+    }
+    (out_degree, in_degree)
+}
+
+/// Compares two snapshots of a network.
+#[tracing::instrument(level = "debug", skip_all, fields(old_nodes = old.num_nodes, new_nodes = new.num_nodes))]
+pub fn diff(old: &Graph, new: &Graph) -> GraphDiff {
+    let n = old.num_nodes.max(new.num_nodes);
+    let mut added_edges = Vec::new();
+    let mut removed_edges = Vec::new();
+    let mut weight_changes = Vec::new();
+
+    for u in 0..n {
+        let (a, b) = (sorted_edges(old, u), sorted_edges(new, u));
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            let order = match (a.get(i), b.get(j)) {
+                (Some(x), Some(y)) => x.0.cmp(&y.0),
+                (Some(_), None) => Ordering::Less,
+                _ => Ordering::Greater,
+            };
+            match order {
+                Ordering::Less => {
+                    removed_edges.push((u, a[i].0));
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    added_edges.push((u, b[j].0));
+                    j += 1;
+                }
+                Ordering::Equal => {
+                    if a[i].1 != b[j].1 {
+                        weight_changes.push((u, a[i].0, a[i].1, b[j].1));
+                    }
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+    }
+
+    let (old_out, old_in) = degrees(old, n);
+    let (new_out, new_in) = degrees(new, n);
+    let present = |out: &[usize], inc: &[usize], v: usize| out[v] + inc[v] > 0;
+    let added_nodes = (0..n)
+        .filter(|&v| present(&new_out, &new_in, v) && !present(&old_out, &old_in, v))
+        .collect();
+    let removed_nodes = (0..n)
+        .filter(|&v| present(&old_out, &old_in, v) && !present(&new_out, &new_in, v))
+        .collect();
+    let degree_changes = (0..n)
+        .filter(|&v| old_out[v] != new_out[v] || old_in[v] != new_in[v])
+        .map(|v| DegreeChange { node: v, old_out: old_out[v], new_out: new_out[v], old_in: old_in[v], new_in: new_in[v] })
+        .collect();
+
+    GraphDiff {
+        added_nodes,
+        removed_nodes,
+        added_edges,
+        removed_edges,
+        weight_changes,
+        degree_changes,
+        components: component_changes(old, new),
+    }
+}
+
+/// Compares WCC partitions on the node ids both snapshots share.
+fn component_changes(old: &Graph, new: &Graph) -> ComponentChanges {
+    let (old_wcc, new_wcc) = (wcc_sequential(old), wcc_sequential(new));
+    let shared = old.num_nodes.min(new.num_nodes);
+
+    // Distinct (old label, new label) pairs over shared nodes
+    let mut pairs: Vec<(usize, usize)> = (0..shared).map(|v| (old_wcc.labels[v], new_wcc.labels[v])).collect();
+    pairs.sort_unstable();
+    pairs.dedup();
+    let mut new_per_old: HashMap<usize, usize> = HashMap::new();
+    let mut old_per_new: HashMap<usize, usize> = HashMap::new();
+    for &(a, b) in &pairs {
+        *new_per_old.entry(a).or_default() += 1;
+        *old_per_new.entry(b).or_default() += 1;
+    }
+
+    ComponentChanges {
+        old_components: old_wcc.stats.num_components,
+        new_components: new_wcc.stats.num_components,
+        old_largest: old_wcc.stats.largest_component,
+        new_largest: new_wcc.stats.largest_component,
+        split: new_per_old.values().filter(|&&k| k > 1).count(),
+        merged: old_per_new.values().filter(|&&k| k > 1).count(),
+    }
+}
@@ -0,0 +1,177 @@
+use crate::algorithms::sssp::SsspResult;
+use crate::error::{Error, Result};
+use crate::graph::attributes::Categorical;
+use crate::graph::graph::{build_csr_weighted, Graph};
+use std::collections::HashMap;
+
+/// What changing from one mode to another costs at a node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransferCost {
+    /// Added to the cost of the outgoing edge.
+    Penalty(f64),
+    /// The turn cannot be taken.
+    Forbidden,
+}
+
+/// Overrides the default transfer cost for one `from -> to` mode change, at one node or everywhere.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferRule {
+    /// Node the rule applies at; `None` applies everywhere (node-specific rules win).
+    pub node: Option<usize>,
+    pub from: String,
+    pub to: String,
+    pub cost: TransferCost,
+}
+
+/// How [`expand_modes`] prices mode changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferRules {
+    /// Categorical edge attribute holding each edge's mode.
+    pub mode_attr: String,
+    /// Cost of any mode change not covered by `rules`; staying on the same mode is free.
+    pub default_penalty: f64,
+    pub rules: Vec<TransferRule>,
+}
+
+impl Default for TransferRules {
+    fn default() -> Self {
+        Self {
+            mode_attr: "type".to_string(),
+            default_penalty: 0.0,
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// A graph whose nodes are `(node, incoming mode)` states, plus the mapping back to the original nodes.
+///
+/// States `0..num_nodes` are origin states `(v, None)` with no incoming edges: start searches there.
+/// The other states are `(v, m)` for every mode `m` with an edge into `v`. An edge `u -> v` of mode `m`
+/// leaving state `(u, m_in)` costs its weight plus the transfer cost from `m_in` to `m` at `u`
+/// (nothing when leaving an origin state).
+#[derive(Debug, Clone)]
+pub struct ExpandedGraph {
+    /// Weighted state graph; run [`crate::algorithms::sssp::dijkstra`] or Bellman-Ford on it.
+    pub graph: Graph,
+    /// Original node of every state.
+    pub state_node: Vec<usize>,
+    /// Incoming mode code of every state (`None` for origin states).
+    pub state_mode: Vec<Option<u32>>,
+    /// Mode labels, indexed by code.
+    pub modes: Vec<String>,
+    num_nodes: usize,
+}
+
+impl ExpandedGraph {
+    /// Number of nodes of the original graph.
+    pub fn num_nodes(&self) -> usize {
+        self.num_nodes
+    }
+
+    /// State to start a search from node `v`.
+    pub fn origin_state(&self, v: usize) -> usize {
+        v
+    }
+
+    /// Per original node, the cheapest distance over all of its states.
+    pub fn node_distances(&self, result: &SsspResult) -> Vec<f64> {
+        let mut best = vec![f64::INFINITY; self.num_nodes];
+        for (state, &d) in result.distances.iter().enumerate() {
+            let v = self.state_node[state];
+            best[v] = best[v].min(d);
+        }
+        best
+    }
+
+    /// Original nodes on the cheapest path to `target` (consecutive repeats collapsed), or `None` if unreachable.
+    pub fn node_path(&self, result: &SsspResult, target: usize) -> Option<Vec<usize>> {
+        let state = (0..self.state_node.len())
+            .filter(|&s| self.state_node[s] == target && result.is_reachable(s))
+            .min_by(|&a, &b| result.distances[a].total_cmp(&result.distances[b]))?;
+        let mut path: Vec<usize> = result.path_to(state)?.into_iter().map(|s| self.state_node[s]).collect();
+        path.dedup();
+        Some(path)
+    }
+}
+
+/// Expands `graph` into `(node, incoming mode)` states so transfer penalties and forbidden turns
+/// between modes become ordinary weighted edges (see [`ExpandedGraph`]).
+///
+/// Modes come from the [`Categorical`] edge attribute `rules.mode_attr`; without it every edge has the
+/// same mode, so only origin states and one state per reached node are created. Unweighted edges cost 1.
+/// Returns [`Error::InvalidNode`] for a rule at a node outside the graph.
+pub fn expand_modes(graph: &Graph, rules: &TransferRules) -> Result<ExpandedGraph> {
+    let n = graph.num_nodes;
+    let single = Categorical::from_labels(std::iter::repeat_n("", graph.num_edges));
+    let modes = graph.edge_attr::<Categorical>(&rules.mode_attr).unwrap_or(&single);
+
+    let mut table: HashMap<(Option<usize>, u32, u32), TransferCost> = HashMap::new();
+    for rule in &rules.rules {
+        if let Some(node) = rule.node
+            && node >= n
+        {
+            return Err(Error::InvalidNode { node, num_nodes: n });
+        }
+        // Rules naming modes that no edge has cannot match anything
+        if let (Some(from), Some(to)) = (modes.code(&rule.from), modes.code(&rule.to)) {
+            table.insert((rule.node, from, to), rule.cost);
+        }
+    }
+    let transfer = |at: usize, from: u32, to: u32| {
+        if from == to {
+            return TransferCost::Penalty(0.0);
+        }
+        table
+            .get(&(Some(at), from, to))
+            .or_else(|| table.get(&(None, from, to)))
+            .copied()
+            .unwrap_or(TransferCost::Penalty(rules.default_penalty))
+    };
+
+    // Arrival states, grouped by node: (v, mode) pairs sorted so each node's states are contiguous
+    let mut arrivals: Vec<(usize, u32)> = (0..n)
+        .flat_map(|u| graph.edge_range(u))
+        .map(|e| (graph.neighbors[e], modes.codes()[e]))
+        .collect();
+    arrivals.sort_unstable();
+    arrivals.dedup();
+    let mut first_arrival = vec![0; n + 1];
+    for &(v, _) in &arrivals {
+        first_arrival[v + 1] += 1;
+    }
+    for v in 0..n {
+        first_arrival[v + 1] += first_arrival[v];
+    }
+    let state_of = |v: usize, mode: u32| {
+        let range = &arrivals[first_arrival[v]..first_arrival[v + 1]];
+        n + first_arrival[v] + range.partition_point(|&(_, m)| m < mode)
+    };
+
+    let mut state_node: Vec<usize> = (0..n).collect();
+    let mut state_mode: Vec<Option<u32>> = vec![None; n];
+    state_node.extend(arrivals.iter().map(|&(v, _)| v));
+    state_mode.extend(arrivals.iter().map(|&(_, m)| Some(m)));
+
+    let mut edges = Vec::new();
+    for (state, (&u, &incoming)) in state_node.iter().zip(&state_mode).enumerate() {
+        for e in graph.edge_range(u) {
+            let (v, mode) = (graph.neighbors[e], modes.codes()[e]);
+            let weight = graph.weights.as_ref().map_or(1.0, |w| w[e]);
+            let penalty = match incoming.map(|m| transfer(u, m, mode)) {
+                Some(TransferCost::Forbidden) => continue,
+                Some(TransferCost::Penalty(p)) => p,
+                None => 0.0,
+            };
+            edges.push((state, state_of(v, mode), weight + penalty));
+        }
+    }
+    tracing::debug!(nodes = n, states = state_node.len(), edges = edges.len(), "expanded modes");
+
+    Ok(ExpandedGraph {
+        graph: build_csr_weighted(state_node.len(), edges),
+        state_node,
+        state_mode,
+        modes: modes.labels().to_vec(),
+        num_nodes: n,
+    })
+}
@@ -0,0 +1,425 @@
+//! Graph file formats besides the edge list, and detection of a file's format from its first bytes.
+//!
+//! Every format is read into the loader's edge list, so [`LoadOptions`](super::graph::LoadOptions)
+//! (dropping self-loops, `--undirected`, sorting) applies to all of them alike.
+
+use super::graph::{for_each_line, next_field, parse_node_id, parse_u64, EdgeList, Graph};
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Read, Write};
+
+/// Magic bytes at the start of a binary graph file.
+const GRAPH_MAGIC: &[u8; 8] = b"FTNGRPH2";
+/// Magic bytes of the first binary format, without a metadata block; still read.
+const GRAPH_MAGIC_V1: &[u8; 8] = b"FTNGRPH1";
+
+/// Binary header flag: the file holds one weight per edge.
+const FLAG_WEIGHTED: u64 = 1;
+/// Binary header flag: the graph is undirected (both directions of every edge are stored).
+const FLAG_UNDIRECTED: u64 = 2;
+
+/// On-disk graph format of an input file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GraphFormat {
+    /// `src dst [weight [type]]` per line.
+    EdgeList,
+    /// METIS: a `n m [fmt [ncon]]` header, then the 1-based neighbors of node `i` on line `i`.
+    Metis,
+    /// Matrix Market coordinate matrix (`.mtx`); entry `i j` is the edge `i - 1 -> j - 1`.
+    MatrixMarket,
+    /// CSR arrays as written by [`write_binary`].
+    Binary,
+}
+
+impl GraphFormat {
+    /// Guesses the format from the first bytes of a file and its path (if any).
+    ///
+    /// Binary files and Matrix Market files are recognized by their magic bytes and banner. A
+    /// METIS file is recognized by a leading `%` comment or a `.graph` / `.metis` extension; its
+    /// header line alone looks like an edge, so other METIS files need `--format metis`. Everything
+    /// else is read as an edge list.
+    pub fn detect(head: &[u8], path: Option<&str>) -> GraphFormat {
+        if head.starts_with(GRAPH_MAGIC) || head.starts_with(GRAPH_MAGIC_V1) {
+            return GraphFormat::Binary;
+        }
+        if head.len() >= 14 && head[..14].eq_ignore_ascii_case(b"%%MatrixMarket") {
+            return GraphFormat::MatrixMarket;
+        }
+        if head.starts_with(b"%") {
+            return GraphFormat::Metis;
+        }
+        let extension = path
+            .and_then(|p| std::path::Path::new(p).extension())
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("mtx") => GraphFormat::MatrixMarket,
+            Some("graph" | "metis") => GraphFormat::Metis,
+            _ => GraphFormat::EdgeList,
+        }
+    }
+}
+
+/// Appends a METIS graph to `list`. METIS lists both directions of every edge, so the graph is
+/// undirected; `fmt` edge weights are loaded, vertex sizes and weights are skipped.
+pub(crate) fn read_metis<R: BufRead>(list: &mut EdgeList, reader: R) -> Result<()> {
+    // (nodes, edges, has vertex size, vertex weights per node, has edge weights) once the header is read
+    let mut header: Option<(usize, usize, bool, usize, bool)> = None;
+    let mut node = 0;
+    let mut arcs = 0;
+    let mut last_line = 0;
+    for_each_line(reader, |line, line_no| {
+        last_line = line_no;
+        let line = line.trim_ascii();
+        if line.starts_with(b"%") {
+            return Ok(());
+        }
+        let mut rest = line;
+        let Some((n, _, has_size, vertex_weights, edge_weights)) = header else {
+            if line.is_empty() {
+                return Ok(());
+            }
+            let fields: Vec<&[u8]> = std::iter::from_fn(|| next_field(&mut rest)).collect();
+            let [n, m, ref options @ ..] = fields[..] else {
+                return Err(Error::parse(line_no, "expected a METIS header `n m [fmt [ncon]]`"));
+            };
+            let (n, m) = (parse_count(n, line_no, "node count")?, parse_count(m, line_no, "edge count")?);
+            let fmt = options.first().copied().unwrap_or(b"0");
+            if fmt.len() > 3 || !fmt.iter().all(|&b| b == b'0' || b == b'1') {
+                return Err(Error::parse(line_no, format!("Invalid METIS fmt: {}", String::from_utf8_lossy(fmt))));
+            }
+            let flag = |from_right: usize| fmt.len() > from_right && fmt[fmt.len() - 1 - from_right] == b'1';
+            let ncon = options.get(1).map(|f| parse_count(f, line_no, "ncon")).transpose()?.unwrap_or(1);
+            header = Some((n, m, flag(2), if flag(1) { ncon } else { 0 }, flag(0)));
+            list.declare_nodes(n);
+            return Ok(());
+        };
+        if node == n {
+            if line.is_empty() {
+                return Ok(());
+            }
+            return Err(Error::parse(line_no, format!("more than the {} node lines the header declares", n)));
+        }
+        for _ in 0..usize::from(has_size) + vertex_weights {
+            next_field(&mut rest);
+        }
+        while let Some(field) = next_field(&mut rest) {
+            let v = parse_node_id(field, line_no, "neighbor")?;
+            if v == 0 || v > n {
+                return Err(Error::parse(line_no, format!("neighbor {} outside 1..={}", v, n)));
+            }
+            let weight = if edge_weights {
+                let w = next_field(&mut rest).ok_or_else(|| Error::parse(line_no, format!("missing weight of neighbor {}", v)))?;
+                Some(parse_weight(w, line_no)?)
+            } else {
+                None
+            };
+            list.push(node, v - 1, weight, line_no)?;
+            arcs += 1;
+        }
+        node += 1;
+        Ok(())
+    })?;
+    let Some((n, m, ..)) = header else {
+        return Err(Error::parse(last_line.max(1), "missing METIS header"));
+    };
+    if node < n {
+        return Err(Error::parse(last_line.max(1), format!("{} node lines, header declares {}", node, n)));
+    }
+    if arcs != 2 * m {
+        tracing::warn!("METIS header declares {} edges, file lists {} neighbor entries (expected {})", m, arcs, 2 * m);
+    }
+    list.undirected = true;
+    Ok(())
+}
+
+/// Appends a Matrix Market `coordinate` matrix to `list`: entry `i j [value]` becomes the edge
+/// `i - 1 -> j - 1` with the value as weight (`pattern` matrices are unweighted). A `symmetric`
+/// matrix stores each edge once and loads as an undirected graph.
+pub(crate) fn read_matrix_market<R: BufRead>(list: &mut EdgeList, reader: R) -> Result<()> {
+    // (rows, cols, entries) once the size line is read
+    let mut size: Option<(usize, usize, usize)> = None;
+    let mut pattern = false;
+    let mut entries = 0;
+    let mut last_line = 0;
+    for_each_line(reader, |line, line_no| {
+        last_line = line_no;
+        let line = line.trim_ascii();
+        if line_no == 1 {
+            let banner = String::from_utf8_lossy(line).to_ascii_lowercase();
+            let words: Vec<&str> = banner.split_whitespace().collect();
+            let [_, "matrix", "coordinate", field, symmetry] = words[..] else {
+                return Err(Error::parse(1, format!("unsupported Matrix Market banner `{}` (need `matrix coordinate`)", banner)));
+            };
+            pattern = match field {
+                "real" | "integer" | "double" => false,
+                "pattern" => true,
+                _ => return Err(Error::parse(1, format!("unsupported Matrix Market field `{}`", field))),
+            };
+            list.symmetric = match symmetry {
+                "general" => false,
+                "symmetric" => true,
+                _ => return Err(Error::parse(1, format!("unsupported Matrix Market symmetry `{}`", symmetry))),
+            };
+            return Ok(());
+        }
+        if line.is_empty() || line.starts_with(b"%") {
+            return Ok(());
+        }
+        let mut rest = line;
+        let mut number = |what: &str| -> Result<usize> {
+            let field = next_field(&mut rest).ok_or_else(|| Error::parse(line_no, format!("missing {}", what)))?;
+            parse_count(field, line_no, what)
+        };
+        let Some((rows, cols, nnz)) = size else {
+            let (rows, cols, nnz) = (number("row count")?, number("column count")?, number("entry count")?);
+            size = Some((rows, cols, nnz));
+            list.declare_nodes(rows.max(cols));
+            return Ok(());
+        };
+        let (i, j) = (number("row")?, number("column")?);
+        if i == 0 || i > rows || j == 0 || j > cols {
+            return Err(Error::parse(line_no, format!("entry ({}, {}) outside the {}x{} matrix", i, j, rows, cols)));
+        }
+        if entries == nnz {
+            return Err(Error::parse(line_no, format!("more than the {} entries the size line declares", nnz)));
+        }
+        let weight = match (pattern, next_field(&mut rest)) {
+            (false, Some(value)) => Some(parse_weight(value, line_no)?),
+            (false, None) => return Err(Error::parse(line_no, "missing entry value")),
+            (true, _) => None,
+        };
+        list.push(i - 1, j - 1, weight, line_no)?;
+        entries += 1;
+        Ok(())
+    })?;
+    let Some((_, _, nnz)) = size else {
+        return Err(Error::parse(last_line.max(1), "missing Matrix Market size line"));
+    };
+    if entries < nnz {
+        return Err(Error::parse(last_line.max(1), format!("{} entries, size line declares {}", entries, nnz)));
+    }
+    Ok(())
+}
+
+/// What a binary graph file was built from, stored as a JSON block after the magic bytes so
+/// `inspect` can tell what a file holds without loading it. Missing fields read as empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BinaryGraphMetadata {
+    /// Free-form name, e.g. the network and snapshot date.
+    pub name: Option<String>,
+    /// When the file was written, in seconds since the Unix epoch.
+    pub created_unix: Option<u64>,
+    /// Input files the graph was built from.
+    pub sources: Vec<SourceFile>,
+    /// Loader flags the graph was built with (see [`LoadOptions::flags`](super::graph::LoadOptions::flags)).
+    pub load_options: Vec<String>,
+    /// File with the input-file id of every node, if the loader remapped sparse ids; node ids in
+    /// the graph are then `0..n`, not the file's ids.
+    pub id_map: Option<String>,
+    /// Version of the tool that wrote the file.
+    pub tool_version: String,
+}
+
+impl BinaryGraphMetadata {
+    /// Empty metadata stamped with this crate's version.
+    pub fn new() -> Self {
+        Self { tool_version: env!("CARGO_PKG_VERSION").to_string(), ..Self::default() }
+    }
+}
+
+/// An input file recorded in [`BinaryGraphMetadata::sources`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourceFile {
+    pub path: String,
+    pub bytes: u64,
+    /// 64-bit FNV-1a hash of the contents, in hex; tells whether a file still matches the graph.
+    pub hash: String,
+}
+
+impl SourceFile {
+    /// Size and content hash of `reader`, recorded under `path`.
+    pub fn from_reader<R: Read>(path: &str, mut reader: R) -> std::io::Result<Self> {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut bytes = 0;
+        let mut buffer = vec![0u8; 1 << 16];
+        loop {
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            bytes += read as u64;
+            hash = buffer[..read].iter().fold(hash, |h, &b| (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3));
+        }
+        Ok(Self { path: path.to_string(), bytes, hash: format!("{:016x}", hash) })
+    }
+
+    /// [`SourceFile::from_reader`] over the file at `path`.
+    #[cfg(feature = "fs")]
+    pub fn from_path(path: &str) -> std::io::Result<Self> {
+        Self::from_reader(path, std::fs::File::open(path)?)
+    }
+}
+
+/// The header of a binary graph file, read by [`read_binary_info`] without loading the graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BinaryGraphInfo {
+    /// Format version; version 1 files have no metadata.
+    pub version: u32,
+    pub directed: bool,
+    pub weighted: bool,
+    pub nodes: usize,
+    pub edges: usize,
+    pub metadata: Option<BinaryGraphMetadata>,
+}
+
+/// Reads the magic bytes, metadata block and counts at the start of a binary graph file.
+pub fn read_binary_info<R: Read>(mut reader: R) -> Result<BinaryGraphInfo> {
+    let truncated = || Error::parse(1, "truncated binary graph header");
+    let word = |reader: &mut R| -> Result<u64> {
+        let mut bytes = [0u8; 8];
+        reader.read_exact(&mut bytes).map_err(|_| truncated())?;
+        Ok(u64::from_le_bytes(bytes))
+    };
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic).map_err(|_| Error::parse(1, "not a binary graph file"))?;
+    let (version, metadata) = match &magic {
+        m if m == GRAPH_MAGIC => {
+            let len = word(&mut reader)?;
+            let mut block = Vec::new();
+            reader.by_ref().take(len).read_to_end(&mut block)?;
+            if block.len() as u64 != len {
+                return Err(truncated());
+            }
+            (2, Some(parse_metadata(&block)?))
+        }
+        m if m == GRAPH_MAGIC_V1 => (1, None),
+        _ => return Err(Error::parse(1, "not a binary graph file")),
+    };
+    let (flags, nodes, edges) = (word(&mut reader)?, word(&mut reader)?, word(&mut reader)?);
+    Ok(BinaryGraphInfo {
+        version,
+        directed: flags & FLAG_UNDIRECTED == 0,
+        weighted: flags & FLAG_WEIGHTED != 0,
+        nodes: nodes as usize,
+        edges: edges as usize,
+        metadata,
+    })
+}
+
+fn parse_metadata(block: &[u8]) -> Result<BinaryGraphMetadata> {
+    serde_json::from_slice(block).map_err(|e| Error::parse(1, format!("invalid binary graph metadata: {}", e)))
+}
+
+/// The CSR part of a binary graph file, after the magic bytes and any metadata block.
+fn binary_body(bytes: &[u8]) -> Result<&[u8]> {
+    if let Some(body) = bytes.strip_prefix(GRAPH_MAGIC_V1.as_slice()) {
+        return Ok(body);
+    }
+    let Some(rest) = bytes.strip_prefix(GRAPH_MAGIC.as_slice()) else {
+        return Err(Error::parse(1, "not a binary graph file"));
+    };
+    let truncated = || Error::parse(1, "truncated binary graph header");
+    let (len, rest) = rest.split_first_chunk::<8>().ok_or_else(truncated)?;
+    let len = usize::try_from(u64::from_le_bytes(*len)).map_err(|_| truncated())?;
+    if rest.len() < len {
+        return Err(truncated());
+    }
+    parse_metadata(&rest[..len])?;
+    Ok(&rest[len..])
+}
+
+/// Appends a graph written by [`write_binary`] (either version) to `list`.
+pub(crate) fn read_binary(list: &mut EdgeList, bytes: &[u8]) -> Result<()> {
+    let invalid = |message: String| Error::parse(1, message);
+    let body = binary_body(bytes)?;
+    let words: Vec<u64> = body.chunks_exact(8).map(|c| u64::from_le_bytes(c.try_into().unwrap())).collect();
+    if body.len() % 8 != 0 || words.len() < 3 {
+        return Err(invalid("truncated binary graph header".to_string()));
+    }
+    let (flags, n, m) = (words[0], words[1] as usize, words[2] as usize);
+    let weighted = flags & FLAG_WEIGHTED != 0;
+    let expected = (n as u128 + 1) + m as u128 * (1 + weighted as u128);
+    if (words.len() - 3) as u128 != expected {
+        return Err(invalid(format!("{} nodes and {} edges need {} words after the header, file holds {}", n, m, expected, words.len() - 3)));
+    }
+    let offsets = &words[3..n + 4];
+    let neighbors = &words[n + 4..n + 4 + m];
+    if offsets[0] != 0 || offsets[n] as usize != m || offsets.windows(2).any(|w| w[0] > w[1]) {
+        return Err(invalid("offsets are not a valid CSR index".to_string()));
+    }
+    list.declare_nodes(n);
+    for u in 0..n {
+        for e in offsets[u] as usize..offsets[u + 1] as usize {
+            let v = neighbors[e] as usize;
+            if v >= n {
+                return Err(invalid(format!("edge {} -> {} outside 0..{}", u, v, n)));
+            }
+            let weight = weighted.then(|| f64::from_bits(words[n + 4 + m + e]));
+            list.push(u, v, weight, 1)?;
+        }
+    }
+    list.undirected |= flags & FLAG_UNDIRECTED != 0;
+    Ok(())
+}
+
+/// [`write_binary_with_metadata_to`] with [`BinaryGraphMetadata::new`].
+pub fn write_binary_to<W: Write>(graph: &Graph, writer: W) -> std::io::Result<()> {
+    write_binary_with_metadata_to(graph, &BinaryGraphMetadata::new(), writer)
+}
+
+/// Writes `graph` as `FTNGRPH2`, the length (little-endian u64) and JSON of `metadata`, then
+/// little-endian u64 flags (1: weighted, 2: undirected), node count and edge count, the `n + 1`
+/// offsets and `m` neighbors as u64, and the weights as f64 if weighted. Edge and node attributes
+/// are not stored. `FTNGRPH1` files are the same without the metadata block.
+pub fn write_binary_with_metadata_to<W: Write>(graph: &Graph, metadata: &BinaryGraphMetadata, mut writer: W) -> std::io::Result<()> {
+    let flags = if graph.weights.is_some() { FLAG_WEIGHTED } else { 0 } | if graph.directed { 0 } else { FLAG_UNDIRECTED };
+    let block = serde_json::to_vec(metadata)?;
+    writer.write_all(GRAPH_MAGIC)?;
+    writer.write_all(&(block.len() as u64).to_le_bytes())?;
+    writer.write_all(&block)?;
+    for word in [flags, graph.num_nodes as u64, graph.num_edges as u64] {
+        writer.write_all(&word.to_le_bytes())?;
+    }
+    for &word in graph.offsets.iter().chain(&graph.neighbors) {
+        writer.write_all(&(word as u64).to_le_bytes())?;
+    }
+    for weight in graph.weights.iter().flatten() {
+        writer.write_all(&weight.to_le_bytes())?;
+    }
+    writer.flush()
+}
+
+/// [`write_binary_to`] a new file at `path`.
+#[cfg(feature = "fs")]
+pub fn write_binary(graph: &Graph, path: &str) -> std::io::Result<()> {
+    write_binary_with_metadata(graph, &BinaryGraphMetadata::new(), path)
+}
+
+/// [`write_binary_with_metadata_to`] a new file at `path`.
+#[cfg(feature = "fs")]
+pub fn write_binary_with_metadata(graph: &Graph, metadata: &BinaryGraphMetadata, path: &str) -> std::io::Result<()> {
+    write_binary_with_metadata_to(graph, metadata, std::io::BufWriter::new(std::fs::File::create(path)?))
+}
+
+/// [`read_binary_info`] of the file at `path`, reading only its header.
+#[cfg(feature = "fs")]
+pub fn read_binary_info_from_file(path: &str) -> Result<BinaryGraphInfo> {
+    read_binary_info(std::io::BufReader::new(std::fs::File::open(path)?))
+}
+
+/// A node or entry count of a header line.
+fn parse_count(field: &[u8], line_no: usize, what: &str) -> Result<usize> {
+    parse_u64(field)
+        .and_then(|v| usize::try_from(v).ok())
+        .ok_or_else(|| Error::parse(line_no, format!("Invalid {}: {}", what, String::from_utf8_lossy(field))))
+}
+
+fn parse_weight(field: &[u8], line_no: usize) -> Result<f64> {
+    std::str::from_utf8(field)
+        .ok()
+        .and_then(|w| w.parse().ok())
+        .ok_or_else(|| Error::parse(line_no, format!("Invalid weight: {}", String::from_utf8_lossy(field))))
+}
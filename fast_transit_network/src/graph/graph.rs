@@ -1,12 +1,67 @@
-use anyhow::{Context, Result};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use crate::error::{Error, Result};
+use crate::graph::attributes::{AttrColumn, AttrStore, Categorical, NodeAttr};
+use crate::graph::formats::{self, GraphFormat};
+use crate::graph::reorder::Permutation;
+#[cfg(feature = "fs")]
+use crate::progress::{NoProgress, ProgressEvent, ProgressSink};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::io::BufRead;
+
+/// Read-only adjacency access shared by [`Graph`] and lightweight views over it.
+///
+/// Node ids are always in `0..node_count()`; a view may hide nodes or edges but never renumbers them,
+/// so per-node result vectors stay indexable by the original ids.
+pub trait GraphLike {
+    /// Number of node slots (including any nodes hidden by a view).
+    fn node_count(&self) -> usize;
+
+    /// Iterates the out-neighbors of node `v`; empty if `v` is out of range or hidden.
+    fn neighbor_iter(&self, v: usize) -> impl Iterator<Item = usize> + '_;
+
+    /// Returns true if `v` is a node id that algorithms should visit.
+    fn contains_node(&self, v: usize) -> bool {
+        v < self.node_count()
+    }
+
+    /// Number of visible out-edges of node `v`.
+    fn degree(&self, v: usize) -> usize {
+        self.neighbor_iter(v).count()
+    }
+
+    /// Visible out-edges of node `v` as `(target, weight)`; storage without weights reports 1.0.
+    fn weighted_neighbor_iter(&self, v: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.neighbor_iter(v).map(|t| (t, 1.0))
+    }
+}
+
+/// Compressed sparse row graph.
+///
+/// Node ids, edge counts and `offsets` are `usize`, i.e. 64-bit on 64-bit targets, so graphs with more
+/// than `u32::MAX` edges are supported there. On 32-bit targets (e.g. wasm32) the loaders reject inputs
+/// that would not fit instead of wrapping.
+#[derive(Debug, Clone)]
 pub struct Graph {
     pub num_nodes: usize,
     pub num_edges: usize,
     pub offsets: Vec<usize>,
     pub neighbors: Vec<usize>,
     pub out_degree: Vec<usize>,
+    /// Per-edge weights aligned with `neighbors`; `None` for unweighted graphs (every edge costs 1).
+    pub weights: Option<Vec<f64>>,
+    /// Extra per-edge columns (travel time, transport mode, ...) keyed by CSR edge index.
+    pub edge_attrs: AttrStore,
+    /// Per-node columns (stop name, zone, ...) keyed by node id.
+    pub node_attrs: AttrStore,
+    /// What the edge-list loader counted, skipped and dropped; `None` for graphs built in memory.
+    pub load_report: Option<LoadReport>,
+    /// False if every edge is stored in both directions (see [`build_csr_undirected`]), so the graph
+    /// is its own transpose and its weakly connected components are plain connected components.
+    /// `num_edges` still counts both directions.
+    pub directed: bool,
+    /// True once every adjacency list is in ascending order (see [`Graph::sort_neighbors`]), so
+    /// [`Graph::has_edge`] and [`Graph::common_neighbors`] binary-search or merge instead of scanning.
+    pub neighbors_sorted: bool,
 }
 
 impl Graph {
@@ -17,6 +72,12 @@ impl Graph {
             offsets: vec![0; num_nodes + 1],
             neighbors: Vec::new(),
             out_degree: vec![0; num_nodes],
+            weights: None,
+            edge_attrs: AttrStore::default(),
+            node_attrs: AttrStore::default(),
+            load_report: None,
+            directed: true,
+            neighbors_sorted: false,
         }
     }
     
@@ -32,16 +93,330 @@ impl Graph {
         v < self.num_nodes
     }
     
+    pub fn is_weighted(&self) -> bool {
+        self.weights.is_some()
+    }
+    
+    /// Input-file id of every node if the loader remapped a sparse id space (see [`LoadOptions`]);
+    /// ascending unless the graph was reordered since. `None` when node ids are the file's ids.
+    pub fn original_ids(&self) -> Option<&[usize]> {
+        self.node_attr::<NodeAttr<usize>>(ORIGINAL_ID_ATTR).map(NodeAttr::values)
+    }
+    
+    /// Input-file id of node `v`.
+    pub fn original_id(&self, v: usize) -> usize {
+        self.original_ids().map_or(v, |ids| ids[v])
+    }
+    
+    /// Node loaded from input-file id `id`, or `None` if the file has no such node.
+    pub fn node_for_id(&self, id: usize) -> Option<usize> {
+        match self.original_ids() {
+            // A reordered graph's ids are no longer sorted, so a miss falls back to a scan
+            Some(ids) => ids.binary_search(&id).ok().or_else(|| ids.iter().position(|&x| x == id)),
+            None => self.is_valid_node(id).then_some(id),
+        }
+    }
+    
+    /// Weights of the out-edges of `v`, in the same order as [`Graph::neighbors`]; `None` if unweighted.
+    pub fn edge_weights(&self, v: usize) -> Option<&[f64]> {
+        let weights = self.weights.as_ref()?;
+        if v >= self.num_nodes {
+            return Some(&weights[0..0]);
+        }
+        Some(&weights[self.offsets[v]..self.offsets[v + 1]])
+    }
+    
+    /// Out-edges of `v` as `(target, weight)`; weight is 1.0 on unweighted graphs.
+    pub fn weighted_neighbors(&self, v: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let weights = self.edge_weights(v);
+        self.neighbors(v)
+            .iter()
+            .enumerate()
+            .map(move |(i, &t)| (t, weights.map_or(1.0, |w| w[i])))
+    }
+    
+    /// Adds or replaces edge attribute `name`; fails unless the column has one value per edge.
+    pub fn set_edge_attr(&mut self, name: &str, column: impl AttrColumn) -> Result<()> {
+        if column.len() != self.num_edges {
+            return Err(Error::AttributeLength {
+                name: name.to_string(),
+                expected: self.num_edges,
+                actual: column.len(),
+            });
+        }
+        self.edge_attrs.insert(name, column);
+        Ok(())
+    }
+
+    /// Edge attribute `name` if it exists with column type `C`, e.g. `edge_attr::<EdgeAttr<f64>>("time")`.
+    pub fn edge_attr<C: AttrColumn>(&self, name: &str) -> Option<&C> {
+        self.edge_attrs.get(name)
+    }
+
+    /// Adds or replaces node attribute `name`; fails unless the column has one value per node.
+    pub fn set_node_attr(&mut self, name: &str, column: impl AttrColumn) -> Result<()> {
+        if column.len() != self.num_nodes {
+            return Err(Error::AttributeLength {
+                name: name.to_string(),
+                expected: self.num_nodes,
+                actual: column.len(),
+            });
+        }
+        self.node_attrs.insert(name, column);
+        Ok(())
+    }
+
+    /// Node attribute `name` if it exists with column type `C`, e.g. `node_attr::<Categorical>("zone")`.
+    pub fn node_attr<C: AttrColumn>(&self, name: &str) -> Option<&C> {
+        self.node_attrs.get(name)
+    }
+
+    /// Sorts every adjacency list ascending, moving weights and edge attributes with their edges.
+    pub fn sort_neighbors(&mut self) {
+        if self.neighbors_sorted {
+            return;
+        }
+        let mut order: Vec<usize> = (0..self.num_edges).collect();
+        for v in 0..self.num_nodes {
+            // Stable, so duplicate edges keep their input order
+            order[self.edge_range(v)].sort_by_key(|&e| self.neighbors[e]);
+        }
+        self.neighbors = order.iter().map(|&e| self.neighbors[e]).collect();
+        if let Some(weights) = &self.weights {
+            self.weights = Some(order.iter().map(|&e| weights[e]).collect());
+        }
+        if !self.edge_attrs.is_empty() {
+            self.edge_attrs = self.edge_attrs.gather(&order);
+        }
+        self.neighbors_sorted = true;
+    }
+
+    /// Whether `u -> v` is an edge: a binary search if [`Graph::neighbors_sorted`], a scan of `u`'s
+    /// adjacency otherwise.
+    pub fn has_edge(&self, u: usize, v: usize) -> bool {
+        let adjacency = self.neighbors(u);
+        if self.neighbors_sorted {
+            adjacency.binary_search(&v).is_ok()
+        } else {
+            adjacency.contains(&v)
+        }
+    }
+
+    /// Distinct nodes that are out-neighbors of both `u` and `v`, ascending. A linear merge when
+    /// [`Graph::neighbors_sorted`]; otherwise both lists are sorted first.
+    pub fn common_neighbors(&self, u: usize, v: usize) -> Vec<usize> {
+        let (mut a, mut b) = (self.neighbors(u), self.neighbors(v));
+        let sorted;
+        if !self.neighbors_sorted {
+            let (mut x, mut y) = (a.to_vec(), b.to_vec());
+            x.sort_unstable();
+            y.sort_unstable();
+            sorted = (x, y);
+            (a, b) = (&sorted.0, &sorted.1);
+        }
+        let mut common = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            match a[i].cmp(&b[j]) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    if common.last() != Some(&a[i]) {
+                        common.push(a[i]);
+                    }
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        common
+    }
+
+    /// Every edge as `(source, target)` in CSR order, so the `k`-th item is edge `k` (the index into
+    /// `neighbors`, `weights` and the edge attributes).
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.num_nodes).flat_map(move |u| self.neighbors(u).iter().map(move |&v| (u, v)))
+    }
+
+    /// [`Graph::edges`] as a rayon iterator, split by source node.
+    #[cfg(feature = "parallel")]
+    pub fn par_edges(&self) -> impl ParallelIterator<Item = (usize, usize)> + '_ {
+        (0..self.num_nodes).into_par_iter().flat_map_iter(move |u| self.neighbors(u).iter().map(move |&v| (u, v)))
+    }
+
+    /// Range of CSR edge indices holding the out-edges of `v`; empty if `v` is out of range.
+    pub fn edge_range(&self, v: usize) -> std::ops::Range<usize> {
+        if v >= self.num_nodes {
+            return 0..0;
+        }
+        self.offsets[v]..self.offsets[v + 1]
+    }
+
+    /// Approximate heap size of the CSR arrays in bytes.
+    pub fn memory_bytes(&self) -> usize {
+        (self.offsets.len() + self.neighbors.len() + self.out_degree.len()) * std::mem::size_of::<usize>()
+            + self.weights.as_ref().map_or(0, |w| w.len() * std::mem::size_of::<f64>())
+    }
+    
+    /// Content hash of the structure and weights, stable across runs and platforms.
+    ///
+    /// Two graphs with the same fingerprint have (barring collisions) the same nodes, edges in the same
+    /// CSR order and the same weights, so any result computed on one holds for the other. Attributes
+    /// are not hashed.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hash = Fingerprint::default();
+        hash.write(self.num_nodes as u64);
+        hash.write(self.num_edges as u64);
+        for &o in &self.offsets {
+            hash.write(o as u64);
+        }
+        for &v in &self.neighbors {
+            hash.write(v as u64);
+        }
+        match &self.weights {
+            Some(weights) => weights.iter().for_each(|w| hash.write(w.to_bits())),
+            None => hash.write(u64::MAX),
+        }
+        hash.finish()
+    }
+
+    /// True if both graphs have the same nodes and the same out-edges with the same weights, in any
+    /// CSR order: each adjacency list is compared as a multiset, so sorting or rebuilding a graph
+    /// keeps it equal, while a dropped duplicate edge does not. Weighted and unweighted graphs are
+    /// never equal; attributes and the [`Graph::directed`] flag are ignored.
+    pub fn structural_eq(&self, other: &Graph) -> bool {
+        self.eq_with(other, |v| v)
+    }
+
+    /// [`Graph::structural_eq`] between `other` and this graph relabeled by `mapping`, i.e. whether
+    /// edge `u -> v` of this graph is edge `new_id[u] -> new_id[v]` of `other` and nothing else is.
+    /// Checks that a reordering or partition-wise renumbering preserved the structure without
+    /// building the permuted graph; false if `mapping` does not cover every node.
+    pub fn eq_under_mapping(&self, other: &Graph, mapping: &Permutation) -> bool {
+        mapping.len() == self.num_nodes && self.eq_with(other, |v| mapping.new_id[v])
+    }
+
+    fn eq_with(&self, other: &Graph, map: impl Fn(usize) -> usize) -> bool {
+        if self.num_nodes != other.num_nodes || self.num_edges != other.num_edges || self.is_weighted() != other.is_weighted() {
+            return false;
+        }
+        let by_target = |a: &(usize, f64), b: &(usize, f64)| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1));
+        let (mut mine, mut theirs) = (Vec::new(), Vec::new());
+        (0..self.num_nodes).all(|u| {
+            let v = map(u);
+            if self.neighbors(u).len() != other.neighbors(v).len() {
+                return false;
+            }
+            mine.clear();
+            mine.extend(self.weighted_neighbors(u).map(|(t, w)| (map(t), w)));
+            mine.sort_unstable_by(by_target);
+            theirs.clear();
+            theirs.extend(other.weighted_neighbors(v));
+            theirs.sort_unstable_by(by_target);
+            mine.iter().zip(&theirs).all(|(a, b)| a.0 == b.0 && a.1.to_bits() == b.1.to_bits())
+        })
+    }
+
+    /// Returns the graph with every edge reversed (in-neighbors become out-neighbors).
+    /// Edge attributes follow their edges; node attributes are copied.
+    /// An undirected graph is returned as is.
+    pub fn transpose(&self) -> Graph {
+        if !self.directed {
+            return self.clone();
+        }
+        let mut transposed = self.transpose_structure();
+        transposed.node_attrs = self.node_attrs.clone();
+        if !self.edge_attrs.is_empty() {
+            // build_csr is a stable counting sort, so reversed edge k of target v lands at the next free slot of v
+            let mut next = transposed.offsets.clone();
+            let mut order = vec![0; self.num_edges];
+            for u in 0..self.num_nodes {
+                for e in self.edge_range(u) {
+                    let v = self.neighbors[e];
+                    order[next[v]] = e;
+                    next[v] += 1;
+                }
+            }
+            transposed.edge_attrs = self.edge_attrs.gather(&order);
+        }
+        transposed
+    }
+
+    fn transpose_structure(&self) -> Graph {
+        if self.is_weighted() {
+            let mut edges = Vec::with_capacity(self.num_edges);
+            for u in 0..self.num_nodes {
+                for (v, w) in self.weighted_neighbors(u) {
+                    edges.push((v, u, w));
+                }
+            }
+            return build_csr_weighted(self.num_nodes, edges);
+        }
+        build_csr(self.num_nodes, self.edges().map(|(u, v)| (v, u)).collect())
+    }
+
     pub fn print_info(&self) {
         println!("Graph Info:");
         println!("  Nodes: {}", self.num_nodes);
         println!("  Edges: {}", self.num_edges);
+        if !self.directed {
+            println!("  Undirected: each edge stored in both directions");
+        }
         let avg = if self.num_nodes == 0 {
             0.0
         } else {
             self.num_edges as f64 / self.num_nodes as f64
         };
         println!("  Avg degree: {:.2}", avg);
+        if let Some(report) = &self.load_report {
+            report.print();
+        }
+    }
+}
+
+impl GraphLike for Graph {
+    fn node_count(&self) -> usize {
+        self.num_nodes
+    }
+
+    fn neighbor_iter(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.neighbors(v).iter().copied()
+    }
+
+    fn contains_node(&self, v: usize) -> bool {
+        self.is_valid_node(v)
+    }
+
+    fn degree(&self, v: usize) -> usize {
+        self.neighbors(v).len()
+    }
+
+    fn weighted_neighbor_iter(&self, v: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.weighted_neighbors(v)
+    }
+}
+
+/// Word-at-a-time multiplicative hash behind [`Graph::fingerprint`]; fixed constants keep it
+/// stable, unlike `std`'s randomly seeded hashers.
+struct Fingerprint(u64);
+
+impl Default for Fingerprint {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fingerprint {
+    fn write(&mut self, word: u64) {
+        self.0 = (self.0 ^ word).wrapping_mul(0x9e37_79b9_7f4a_7c15).rotate_left(29);
+    }
+
+    /// Final avalanche (splitmix64) so nearby inputs give unrelated fingerprints.
+    fn finish(&self) -> u64 {
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
     }
 }
 
@@ -49,64 +424,649 @@ impl Graph {
 ///
 /// Nodes must be in `0..num_nodes`. Duplicate edges are kept.
 pub fn build_csr(num_nodes: usize, edges: Vec<(usize, usize)>) -> Graph {
-    let mut graph = Graph::new(num_nodes);
-    graph.num_edges = edges.len();
+    let mut graph = csr_skeleton(num_nodes, edges.len(), edges.iter().map(|&(src, _)| src));
 
-    for &(src, _dst) in &edges {
-        graph.out_degree[src] += 1;
+    graph.neighbors = vec![0; edges.len()];
+    let mut current_pos = graph.offsets.clone();
+    
+    for (src, dst) in edges {
+        graph.neighbors[current_pos[src]] = dst;
+        current_pos[src] += 1;
     }
+    
+    graph
+}
 
+/// [`build_csr`] using all rayon workers: one pass counts out-degrees with atomic counters, a second
+/// places each neighbor at an atomic per-node cursor.
+///
+/// The cursors hand out slots in whatever order the workers reach the edges, so each adjacency list
+/// is then sorted to make the result deterministic: it equals `build_csr` followed by
+/// [`Graph::sort_neighbors`].
+#[cfg(feature = "parallel")]
+pub fn build_csr_parallel(num_nodes: usize, edges: Vec<(usize, usize)>) -> Graph {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    
+    let atomic_zeros = |n: usize| -> Vec<AtomicUsize> { (0..n).into_par_iter().map(|_| AtomicUsize::new(0)).collect() };
+    let degrees = atomic_zeros(num_nodes);
+    edges.par_iter().for_each(|&(src, _)| {
+        degrees[src].fetch_add(1, Ordering::Relaxed);
+    });
+    
+    let mut graph = Graph::new(num_nodes);
+    graph.num_edges = edges.len();
+    graph.out_degree = degrees.into_par_iter().map(AtomicUsize::into_inner).collect();
     let mut offset = 0;
     for i in 0..num_nodes {
         graph.offsets[i] = offset;
         offset += graph.out_degree[i];
     }
     graph.offsets[num_nodes] = offset;
+    
+    let cursors: Vec<AtomicUsize> = graph.offsets[..num_nodes].par_iter().map(|&o| AtomicUsize::new(o)).collect();
+    let neighbors = atomic_zeros(edges.len());
+    edges.par_iter().for_each(|&(src, dst)| {
+        let slot = cursors[src].fetch_add(1, Ordering::Relaxed);
+        neighbors[slot].store(dst, Ordering::Relaxed);
+    });
+    drop(edges);
+    graph.neighbors = neighbors.into_par_iter().map(AtomicUsize::into_inner).collect();
+    
+    // Split the neighbor array at node boundaries into disjoint chunks of similar size
+    let ranges = crate::algorithms::chunking::edge_balanced_ranges(&graph.offsets, rayon::current_num_threads() * 4);
+    let mut chunks = Vec::with_capacity(ranges.len());
+    let mut rest = graph.neighbors.as_mut_slice();
+    for range in ranges {
+        let (chunk, tail) = rest.split_at_mut(graph.offsets[range.end] - graph.offsets[range.start]);
+        chunks.push((range, chunk));
+        rest = tail;
+    }
+    let offsets = &graph.offsets;
+    chunks.into_par_iter().for_each(|(range, chunk)| {
+        let base = offsets[range.start];
+        for v in range {
+            chunk[offsets[v] - base..offsets[v + 1] - base].sort_unstable();
+        }
+    });
+    graph.neighbors_sorted = true;
+    graph
+}
+
+/// Builds an undirected CSR graph: each edge `(a, b)` is stored as `a -> b` and `b -> a` (a self-loop
+/// once), and [`Graph::directed`] is false.
+pub fn build_csr_undirected(num_nodes: usize, mut edges: Vec<(usize, usize)>) -> Graph {
+    let reversed: Vec<(usize, usize)> = edges.iter().filter(|(s, d)| s != d).map(|&(s, d)| (d, s)).collect();
+    edges.extend(reversed);
+    let mut graph = build_csr(num_nodes, edges);
+    graph.directed = false;
+    graph
+}
+
+/// Builds a weighted CSR graph from `(source, target, weight)` edges.
+pub fn build_csr_weighted(num_nodes: usize, edges: Vec<(usize, usize, f64)>) -> Graph {
+    let (mut graph, weights) = build_csr_with(num_nodes, edges);
+    graph.weights = Some(weights);
+    graph
+}
+
+/// Builds a CSR graph from `(source, target, payload)` edges and returns the payloads in CSR edge order.
+fn build_csr_with<T: Copy + Default>(num_nodes: usize, edges: Vec<(usize, usize, T)>) -> (Graph, Vec<T>) {
+    let mut graph = csr_skeleton(num_nodes, edges.len(), edges.iter().map(|&(src, _, _)| src));
 
     graph.neighbors = vec![0; edges.len()];
+    let mut payloads = vec![T::default(); edges.len()];
     let mut current_pos = graph.offsets.clone();
     
-    for (src, dst) in edges {
+    for (src, dst, p) in edges {
         graph.neighbors[current_pos[src]] = dst;
+        payloads[current_pos[src]] = p;
         current_pos[src] += 1;
     }
     
+    (graph, payloads)
+}
+
+/// Graph with out-degrees and offsets filled in from the edge sources; neighbors are left empty.
+fn csr_skeleton(num_nodes: usize, num_edges: usize, sources: impl Iterator<Item = usize>) -> Graph {
+    let mut graph = Graph::new(num_nodes);
+    graph.num_edges = num_edges;
+
+    for src in sources {
+        graph.out_degree[src] += 1;
+    }
+
+    let mut offset = 0;
+    for i in 0..num_nodes {
+        graph.offsets[i] = offset;
+        offset += graph.out_degree[i];
+    }
+    graph.offsets[num_nodes] = offset;
     graph
 }
 
 /// Loads a graph from a text file.
 ///
-/// Format: each line is `src dst` (one edge per line). Empty lines and lines starting with `//` or `#` are skipped. Node count is inferred as max node id + 1.
-/// Returns `Err` on I/O or parse errors.
+/// Format: each line is `src dst [weight]` (one edge per line). Empty lines and lines starting with `//` or `#` are skipped. Node count is inferred as max node id + 1,
+/// unless the ids are sparse enough to be remapped (see [`LoadOptions`]).
+/// If any line has a weight the graph is weighted, and edges without one get weight 1.
+/// An optional fourth column (`src dst weight type`) is loaded as the [`Categorical`] edge attribute
+/// `"type"` (e.g. the transport mode); edges without one get the empty label.
+/// Returns [`Error::Io`] if the file cannot be read and [`Error::Parse`] (with the 1-based line number)
+/// for lines that do not start with two node ids, or whose ids or edge count exceed what `usize` can index
+/// on this platform.
+///
+/// METIS, Matrix Market and binary graph files are recognized and read as such (see
+/// [`GraphFormat::detect`]).
+#[cfg(feature = "fs")]
 pub fn load_graph_from_file(path: &str) -> Result<Graph> {
-    let file = File::open(path).context("Failed to open file")?;
-    let reader = BufReader::new(file);
+    load_graph_from_file_with_progress(path, &NoProgress)
+}
+
+/// [`load_graph_from_file`] that reports bytes read (with the file size as total) and the finished
+/// graph to `progress`.
+#[cfg(feature = "fs")]
+pub fn load_graph_from_file_with_progress(path: &str, progress: &dyn ProgressSink) -> Result<Graph> {
+    load_graph_from_file_with_options(path, &LoadOptions::default(), progress)
+}
+
+/// [`load_graph_from_file_with_progress`] with explicit [`LoadOptions`].
+#[cfg(feature = "fs")]
+pub fn load_graph_from_file_with_options(path: &str, options: &LoadOptions, progress: &dyn ProgressSink) -> Result<Graph> {
+    load_graph_from_files_with_options(&[path], options, progress)
+}
+
+/// Loads the union of several edge lists (e.g. one per operator) as one graph. Node ids mean the
+/// same node in every file, and sparse ids are remapped over all files together. Progress is
+/// reported per file; parse errors name the file when there is more than one.
+#[cfg(feature = "fs")]
+#[tracing::instrument(level = "debug", skip_all, fields(files = paths.len()))]
+pub fn load_graph_from_files_with_options<P: AsRef<str>>(paths: &[P], options: &LoadOptions, progress: &dyn ProgressSink) -> Result<Graph> {
+    let mut list = EdgeList::default();
+    for path in paths {
+        let path = path.as_ref();
+        tracing::debug!(path, "reading edge list");
+        let file = std::fs::File::open(path)
+            .map_err(|e| Error::Io(std::io::Error::new(e.kind(), format!("{}: {}", path, e))))?;
+        let total = file.metadata().ok().map(|m| m.len());
+        let reader = ProgressReader { inner: file, bytes: 0, reported: 0, total, progress };
+        list.read_any(std::io::BufReader::new(reader), Some(path), options).map_err(|e| match e {
+            Error::Parse { line, message } if paths.len() > 1 => Error::Parse { line, message: format!("{}: {}", path, message) },
+            e => e,
+        })?;
+    }
+    let graph = list.build(options);
+    progress.event(&ProgressEvent::LoadFinished { nodes: graph.num_nodes, edges: graph.num_edges });
+    Ok(graph)
+}
+
+/// Bytes between two [`ProgressEvent::LoadProgress`] reports.
+#[cfg(feature = "fs")]
+const LOAD_REPORT_BYTES: u64 = 8 << 20;
+
+/// Reader that reports how far into the file the parser is.
+#[cfg(feature = "fs")]
+struct ProgressReader<'a, R> {
+    inner: R,
+    bytes: u64,
+    reported: u64,
+    total: Option<u64>,
+    progress: &'a dyn ProgressSink,
+}
+
+#[cfg(feature = "fs")]
+impl<R: std::io::Read> std::io::Read for ProgressReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.bytes += read as u64;
+        // Report every LOAD_REPORT_BYTES, and once more at end of file
+        if self.bytes - self.reported >= LOAD_REPORT_BYTES || (read == 0 && self.bytes > self.reported) {
+            self.reported = self.bytes;
+            self.progress.event(&ProgressEvent::LoadProgress { bytes: self.bytes, total: self.total });
+        }
+        Ok(read)
+    }
+}
+
+/// Parses an edge list held in memory (same format as [`load_graph_from_file`]).
+///
+/// Available without the `fs` feature, e.g. for WebAssembly builds that receive the graph as text.
+pub fn load_graph_from_str(text: &str) -> Result<Graph> {
+    load_graph_from_reader(text.as_bytes())
+}
+
+/// Node attribute holding the input-file ids of a remapped graph, see [`Graph::original_ids`].
+pub const ORIGINAL_ID_ATTR: &str = "original_id";
+
+/// An id space counts as sparse when `max id + 1` exceeds both this many nodes and
+/// [`SPARSE_NODES_PER_EDGE`] times the edge count.
+pub const SPARSE_MIN_NODES: usize = 1 << 20;
+
+/// See [`SPARSE_MIN_NODES`].
+pub const SPARSE_NODES_PER_EDGE: usize = 4;
+
+/// How the edge-list loaders size the node range and treat suspicious lines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadOptions {
+    /// Always allocate `max id + 1` nodes. By default a sparse id space (e.g. one edge `0 4000000000`)
+    /// is remapped to `0..distinct ids` in ascending id order, with the file ids kept in
+    /// [`Graph::original_ids`].
+    pub assume_dense: bool,
+    /// Drop edges `v -> v` instead of only counting them.
+    pub drop_self_loops: bool,
+    /// Keep only the first of repeated `src -> dst` edges (with its weight and type).
+    pub drop_duplicates: bool,
+    /// Count and skip lines that do not parse instead of failing with [`Error::Parse`].
+    pub skip_malformed: bool,
+    /// Sort every adjacency list after loading (see [`Graph::sort_neighbors`]).
+    pub sort_neighbors: bool,
+    /// Store every edge in both directions, as in [`build_csr_undirected`]; weights and types are
+    /// copied to the reverse edge.
+    pub undirected: bool,
+    /// Read every input as this format instead of detecting it (see [`GraphFormat::detect`]).
+    pub format: Option<GraphFormat>,
+    /// Keep the `usize` adjacency for the commands' sequential BFS, WCC and PageRank runs instead of
+    /// narrowing it to a [`CompactGraph`](super::compact::CompactGraph). The loaders always return a
+    /// [`Graph`]; only the commands read this.
+    pub wide_storage: bool,
+}
+
+/// Summary of one edge-list load, kept in [`Graph::load_report`] and printed by [`Graph::print_info`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadReport {
+    /// Edge lines parsed (before any were dropped).
+    pub edges_read: usize,
+    /// Edges `v -> v`, whether or not they were dropped.
+    pub self_loops: usize,
+    /// Edges repeating an earlier `src -> dst` edge, whether or not they were dropped.
+    pub duplicate_edges: usize,
+    /// Edges removed by [`LoadOptions::drop_self_loops`] and [`LoadOptions::drop_duplicates`].
+    pub dropped_edges: usize,
+    /// Lines skipped by [`LoadOptions::skip_malformed`].
+    pub malformed_lines: usize,
+    /// 1-based line number and parse error of the first skipped line.
+    pub first_malformed: Option<(usize, String)>,
+}
+
+impl LoadReport {
+    pub fn print(&self) {
+        println!("  Edges read: {}", self.edges_read);
+        println!("  Self-loops: {}", self.self_loops);
+        println!("  Duplicate edges: {}", self.duplicate_edges);
+        if self.dropped_edges > 0 {
+            println!("  Dropped edges: {}", self.dropped_edges);
+        }
+        if let Some((line, message)) = &self.first_malformed {
+            println!("  Skipped malformed lines: {} (first at line {}: {})", self.malformed_lines, line, message);
+        }
+    }
+}
+
+impl LoadOptions {
+    /// The command-line flags that select these options, e.g. `["--undirected", "--format metis"]`.
+    /// `--wide-storage` is left out: it does not change the loaded graph.
+    pub fn flags(&self) -> Vec<String> {
+        let switches = [
+            (self.assume_dense, "--assume-dense"),
+            (self.drop_self_loops, "--drop-self-loops"),
+            (self.drop_duplicates, "--drop-duplicates"),
+            (self.skip_malformed, "--skip-malformed"),
+            (self.undirected, "--undirected"),
+            (self.sort_neighbors, "--sort-neighbors"),
+        ];
+        let mut flags: Vec<String> = switches.iter().filter(|(on, _)| *on).map(|(_, flag)| flag.to_string()).collect();
+        if let Some(format) = self.format.and_then(|f| clap::ValueEnum::to_possible_value(&f)) {
+            flags.push(format!("--format {}", format.get_name()));
+        }
+        flags
+    }
+
+    fn remaps(&self, id_range: usize, num_edges: usize) -> bool {
+        !self.assume_dense && id_range > SPARSE_MIN_NODES.max(num_edges.saturating_mul(SPARSE_NODES_PER_EDGE))
+    }
+}
+
+/// Largest edge list the loader will buffer: the `(src, dst)` vector must stay within `isize::MAX` bytes.
+const MAX_EDGES: usize = isize::MAX as usize / std::mem::size_of::<(usize, usize)>();
+
+/// Parses a node id as `u64` first so ids too large for this platform's `usize` (or equal to
+/// `usize::MAX`, which would overflow the node count) get a precise error instead of "invalid".
+pub(crate) fn parse_node_id(text: &[u8], line_no: usize, what: &str) -> Result<usize> {
+    let id = parse_u64(text)
+        .ok_or_else(|| Error::parse(line_no, format!("Invalid {}: {}", what, String::from_utf8_lossy(text))))?;
+    usize::try_from(id)
+        .ok()
+        .filter(|&id| id < usize::MAX)
+        .ok_or_else(|| {
+            Error::parse(
+                line_no,
+                format!("{} id {} exceeds the largest node id supported on this platform ({})", what, id, usize::MAX - 1),
+            )
+        })
+}
+
+/// Parses an edge list from any buffered reader (same format as [`load_graph_from_file`]).
+pub fn load_graph_from_reader<R: BufRead>(reader: R) -> Result<Graph> {
+    load_graph_from_reader_with_options(reader, &LoadOptions::default())
+}
+
+/// [`load_graph_from_reader`] with explicit [`LoadOptions`].
+pub fn load_graph_from_reader_with_options<R: BufRead>(reader: R, options: &LoadOptions) -> Result<Graph> {
+    let mut list = EdgeList::default();
+    list.read_any(reader, None, options)?;
+    Ok(list.build(options))
+}
+
+/// Calls `f` with every line of `reader` (without the `\n`) and its 1-based number.
+///
+/// Lines are passed as bytes straight from the reader's buffer; only a line split across two buffer
+/// refills is copied.
+pub(crate) fn for_each_line<R: BufRead>(mut reader: R, mut f: impl FnMut(&[u8], usize) -> Result<()>) -> Result<()> {
+    let mut carry = Vec::new();
+    let mut line_no = 0;
+    loop {
+        let chunk = reader.fill_buf()?;
+        if chunk.is_empty() {
+            break;
+        }
+        let len = chunk.len();
+        let mut start = 0;
+        for end in memchr::memchr_iter(b'\n', chunk) {
+            line_no += 1;
+            if carry.is_empty() {
+                f(&chunk[start..end], line_no)?;
+            } else {
+                carry.extend_from_slice(&chunk[start..end]);
+                f(&carry, line_no)?;
+                carry.clear();
+            }
+            start = end + 1;
+        }
+        carry.extend_from_slice(&chunk[start..]);
+        reader.consume(len);
+    }
+    if !carry.is_empty() {
+        f(&carry, line_no + 1)?;
+    }
+    Ok(())
+}
+
+/// Edges parsed from one or more graph files that share one node id space, before the CSR build.
+#[derive(Default)]
+pub(crate) struct EdgeList {
+    edges: Vec<(usize, usize)>,
+    weights: Vec<f64>,
+    weighted: bool,
+    max_id: usize,
+    types: Categorical,
+    report: LoadReport,
+    /// Node count declared by the file (METIS, Matrix Market, binary): at least this many nodes, and
+    /// the ids are never remapped.
+    nodes: Option<usize>,
+    /// Every edge is stored once and stands for both directions (symmetric Matrix Market).
+    pub(crate) symmetric: bool,
+    /// Both directions of every edge are already listed (METIS, binary files of undirected graphs).
+    pub(crate) undirected: bool,
+}
+
+impl EdgeList {
+    /// Appends the graph in `reader`, read as `format` or as detected from its first bytes and
+    /// `path`; line numbers in errors are relative to this reader.
+    fn read_any<R: BufRead>(&mut self, mut reader: R, path: Option<&str>, options: &LoadOptions) -> Result<()> {
+        let format = match options.format {
+            Some(format) => format,
+            None => GraphFormat::detect(reader.fill_buf()?, path),
+        };
+        tracing::debug!(?format, "reading graph");
+        match format {
+            GraphFormat::EdgeList => self.read(reader, options),
+            GraphFormat::Metis => formats::read_metis(self, reader),
+            GraphFormat::MatrixMarket => formats::read_matrix_market(self, reader),
+            GraphFormat::Binary => {
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes)?;
+                formats::read_binary(self, &bytes)
+            }
+        }
+    }
     
-    let mut edges = Vec::new();
-    let mut max_id = 0;
+    /// Appends the edges of an edge list.
+    fn read<R: BufRead>(&mut self, reader: R, options: &LoadOptions) -> Result<()> {
+        for_each_line(reader, |line, line_no| self.read_line(line, line_no, options))
+    }
     
-    for line in reader.lines() {
-        let line = line?;
-        let line = line.trim();
-        
-        if line.is_empty() || line.starts_with("//") || line.starts_with("#") {
-            continue;
+    fn read_line(&mut self, line: &[u8], line_no: usize, options: &LoadOptions) -> Result<()> {
+        let line = line.trim_ascii();
+        if line.is_empty() || line.starts_with(b"//") || line.starts_with(b"#") {
+            return Ok(());
         }
         
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() < 2 {
+        let (src, dst, weight, kind) = match parse_edge_line(line, line_no) {
+            Ok(edge) => edge,
+            Err(Error::Parse { line, message }) if options.skip_malformed => {
+                self.report.malformed_lines += 1;
+                self.report.first_malformed.get_or_insert((line, message));
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+        if let Some(kind) = kind {
+            // Backfill edges seen before the first typed line with the empty label
+            while self.types.len() < self.edges.len() {
+                self.types.push("");
+            }
+            self.types.push(kind);
+        }
+        self.push(src, dst, weight, line_no)
+    }
+    
+    /// Raises the node count to at least the `n` nodes a file declares.
+    pub(crate) fn declare_nodes(&mut self, n: usize) {
+        self.nodes = Some(self.nodes.map_or(n, |nodes| nodes.max(n)));
+    }
+    
+    /// Appends one edge without a type (weight 1 if `None`); `line_no` is for the edge-limit error.
+    pub(crate) fn push(&mut self, src: usize, dst: usize, weight: Option<f64>, line_no: usize) -> Result<()> {
+        if self.edges.len() == MAX_EDGES {
+            return Err(Error::parse(
+                line_no,
+                format!("too many edges for this platform (limit {})", MAX_EDGES),
+            ));
+        }
+        self.weighted |= weight.is_some();
+        self.max_id = self.max_id.max(src).max(dst);
+        self.edges.push((src, dst));
+        self.weights.push(weight.unwrap_or(1.0));
+        if !self.types.is_empty() && self.types.len() < self.edges.len() {
+            self.types.push("");
+        }
+        Ok(())
+    }
+
+    fn build(self, options: &LoadOptions) -> Graph {
+        let EdgeList { mut edges, mut weights, weighted, max_id, mut types, mut report, nodes, symmetric, undirected } = self;
+        // `parse_node_id` keeps ids below `usize::MAX`, so this cannot wrap.
+        let id_range = match nodes {
+            Some(nodes) if edges.is_empty() => nodes,
+            Some(nodes) => nodes.max(max_id + 1),
+            None => max_id + 1,
+        };
+        let remap = nodes.is_none() && options.remaps(id_range, edges.len());
+        let original_ids = remap.then(|| remap_ids(&mut edges));
+        if let Some(ids) = &original_ids {
+            tracing::warn!(
+                "sparse node ids (max id {}, {} distinct): remapped to 0..{}; use --assume-dense to keep file ids",
+                max_id,
+                ids.len(),
+                ids.len()
+            );
+        }
+        let num_nodes = original_ids.as_ref().map_or(id_range, Vec::len);
+        tracing::debug!(nodes = num_nodes, edges = edges.len(), weighted, "parsed edge list");
+        report.edges_read = edges.len();
+        if symmetric || (options.undirected && !undirected) {
+            add_reverse_edges(&mut edges, &mut weights, &mut types);
+        }
+        let mut graph = build_parsed(num_nodes, edges, weights, weighted, types);
+        graph.directed = !(options.undirected || symmetric || undirected);
+        if let Some(ids) = original_ids {
+            graph.node_attrs.insert(ORIGINAL_ID_ATTR, NodeAttr::new(ids));
+        }
+        if report.malformed_lines > 0 {
+            tracing::warn!("skipped {} malformed lines", report.malformed_lines);
+        }
+        check_edges(&mut graph, options, &mut report);
+        if options.sort_neighbors {
+            graph.sort_neighbors();
+        }
+        graph.load_report = Some(report);
+        graph
+    }
+}
+
+/// Appends `b -> a` for every parsed edge `a -> b` except self-loops, with the same weight and type.
+fn add_reverse_edges(edges: &mut Vec<(usize, usize)>, weights: &mut Vec<f64>, types: &mut Categorical) {
+    for i in 0..edges.len() {
+        let (src, dst) = edges[i];
+        if src == dst {
             continue;
         }
-        
-        let src: usize = parts[0].parse()
-            .context(format!("Invalid source: {}", parts[0]))?;
-        let dst: usize = parts[1].parse()
-            .context(format!("Invalid dest: {}", parts[1]))?;
-        
-        max_id = max_id.max(src).max(dst);
-        edges.push((src, dst));
+        edges.push((dst, src));
+        weights.push(weights[i]);
+        if !types.is_empty() {
+            let label = types.get(i).unwrap_or("").to_string();
+            types.push(&label);
+        }
+    }
+}
+
+/// `src dst [weight [type]]` of one trimmed non-comment line; fields are separated by spaces or tabs.
+fn parse_edge_line(line: &[u8], line_no: usize) -> Result<(usize, usize, Option<f64>, Option<&str>)> {
+    let mut rest = line;
+    let (Some(src), Some(dst)) = (next_field(&mut rest), next_field(&mut rest)) else {
+        return Err(Error::parse(line_no, format!("expected `src dst`, got `{}`", String::from_utf8_lossy(line))));
+    };
+    let src = parse_node_id(src, line_no, "source")?;
+    let dst = parse_node_id(dst, line_no, "dest")?;
+    let weight = match next_field(&mut rest) {
+        Some(w) => Some(
+            std::str::from_utf8(w)
+                .ok()
+                .and_then(|w| w.parse().ok())
+                .ok_or_else(|| Error::parse(line_no, format!("Invalid weight: {}", String::from_utf8_lossy(w))))?,
+        ),
+        None => None,
+    };
+    let kind = match next_field(&mut rest) {
+        Some(kind) => Some(
+            std::str::from_utf8(kind)
+                .map_err(|_| Error::parse(line_no, format!("Invalid edge type: {}", String::from_utf8_lossy(kind))))?,
+        ),
+        None => None,
+    };
+    Ok((src, dst, weight, kind))
+}
+
+/// Splits the next space- or tab-separated field off the front of `rest`.
+pub(crate) fn next_field<'a>(rest: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let start = rest.iter().position(|&b| b != b' ' && b != b'\t')?;
+    let text = &rest[start..];
+    let end = memchr::memchr2(b' ', b'\t', text).unwrap_or(text.len());
+    *rest = &text[end..];
+    Some(&text[..end])
+}
+
+/// Decimal `u64` with an optional leading `+` (as `str::parse` accepts); `None` on any other byte
+/// or on overflow.
+pub(crate) fn parse_u64(text: &[u8]) -> Option<u64> {
+    let digits = text.strip_prefix(b"+").unwrap_or(text);
+    if digits.is_empty() {
+        return None;
+    }
+    digits.iter().try_fold(0u64, |id, &b| {
+        let digit = b.wrapping_sub(b'0');
+        if digit > 9 {
+            return None;
+        }
+        id.checked_mul(10)?.checked_add(u64::from(digit))
+    })
+}
+
+/// Counts self-loops and repeated edges into `report` and drops them if `options` ask to. Linear:
+/// `seen[t] == v` marks that `v -> t` was already met in `v`'s (input-ordered) adjacency.
+fn check_edges(graph: &mut Graph, options: &LoadOptions, report: &mut LoadReport) {
+    let dropping = options.drop_self_loops || options.drop_duplicates;
+    let mut seen = vec![usize::MAX; graph.num_nodes];
+    // Edge index and source of every kept edge
+    let mut kept = Vec::with_capacity(if dropping { graph.num_edges } else { 0 });
+    let mut kept_sources = Vec::with_capacity(kept.capacity());
+    for v in 0..graph.num_nodes {
+        for e in graph.offsets[v]..graph.offsets[v + 1] {
+            let t = graph.neighbors[e];
+            let self_loop = t == v;
+            let duplicate = seen[t] == v;
+            seen[t] = v;
+            report.self_loops += self_loop as usize;
+            report.duplicate_edges += duplicate as usize;
+            if dropping && !(self_loop && options.drop_self_loops) && !(duplicate && options.drop_duplicates) {
+                kept.push(e);
+                kept_sources.push(v);
+            }
+        }
+    }
+    if report.self_loops + report.duplicate_edges > 0 {
+        tracing::debug!(self_loops = report.self_loops, duplicates = report.duplicate_edges, "checked edges");
+    }
+    if !dropping || kept.len() == graph.num_edges {
+        return;
     }
     
-    let num_nodes = max_id + 1;
-    Ok(build_csr(num_nodes, edges))
+    report.dropped_edges = graph.num_edges - kept.len();
+    let mut pruned = csr_skeleton(graph.num_nodes, kept.len(), kept_sources.into_iter());
+    pruned.neighbors = kept.iter().map(|&e| graph.neighbors[e]).collect();
+    pruned.weights = graph.weights.as_ref().map(|w| kept.iter().map(|&e| w[e]).collect());
+    pruned.edge_attrs = graph.edge_attrs.gather(&kept);
+    pruned.node_attrs = std::mem::take(&mut graph.node_attrs);
+    pruned.directed = graph.directed;
+    pruned.neighbors_sorted = graph.neighbors_sorted;
+    *graph = pruned;
+}
+
+/// Rewrites `edges` to ids `0..k` in ascending original order and returns the `k` original ids.
+fn remap_ids(edges: &mut [(usize, usize)]) -> Vec<usize> {
+    let mut ids: Vec<usize> = edges.iter().flat_map(|&(s, d)| [s, d]).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    let dense = |id: &usize| ids.binary_search(id).expect("every endpoint is in the id list");
+    for (src, dst) in edges.iter_mut() {
+        *src = dense(src);
+        *dst = dense(dst);
+    }
+    ids
+}
+
+fn build_parsed(num_nodes: usize, edges: Vec<(usize, usize)>, weights: Vec<f64>, weighted: bool, types: Categorical) -> Graph {
+    if !types.is_empty() {
+        // Carry each edge's input position through the CSR build, then reorder the labels to match
+        let edges = edges
+            .into_iter()
+            .zip(weights)
+            .enumerate()
+            .map(|(i, ((s, d), w))| (s, d, (w, i)))
+            .collect();
+        let (mut graph, payloads) = build_csr_with(num_nodes, edges);
+        let (weights, order): (Vec<f64>, Vec<usize>) = payloads.into_iter().unzip();
+        graph.weights = Some(weights);
+        graph.edge_attrs.insert("type", types);
+        graph.edge_attrs = graph.edge_attrs.gather(&order);
+        return graph;
+    }
+    if weighted {
+        let edges = edges.into_iter().zip(weights).map(|((s, d), w)| (s, d, w)).collect();
+        return build_csr_weighted(num_nodes, edges);
+    }
+    #[cfg(feature = "parallel")]
+    if edges.len() >= crate::config::Config::global().build_min_edges {
+        return build_csr_parallel(num_nodes, edges);
+    }
+    build_csr(num_nodes, edges)
 }
\ No newline at end of file
@@ -1 +1,13 @@
-pub mod graph;
\ No newline at end of file
+#[allow(clippy::module_inception)]
+pub mod graph;
+pub mod attributes;
+pub mod formats;
+pub mod view;
+pub mod compact;
+pub mod reorder;
+pub mod stats;
+pub mod diff;
+pub mod expand;
+pub mod validate;
+pub mod traversal;
+pub mod testing;
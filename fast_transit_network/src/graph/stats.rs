@@ -0,0 +1,100 @@
+use crate::graph::graph::Graph;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Structural summary of a graph, computed without running any traversal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphStats {
+    pub num_nodes: usize,
+    pub num_edges: usize,
+    pub min_out_degree: usize,
+    pub max_out_degree: usize,
+    pub max_in_degree: usize,
+    pub avg_degree: f64,
+    /// `degree_histogram[0]` counts out-degree 0; bucket `i > 0` counts out-degrees in `[2^(i-1), 2^i)`.
+    pub degree_histogram: Vec<usize>,
+    pub self_loops: usize,
+    /// Edges repeating an earlier (u, v) pair.
+    pub duplicate_edges: usize,
+    /// Nodes with no out-edges (PageRank sinks).
+    pub dangling_nodes: usize,
+    /// Nodes with neither in- nor out-edges.
+    pub isolated_nodes: usize,
+    /// Approximate heap size of the CSR arrays.
+    pub memory_bytes: usize,
+}
+
+impl GraphStats {
+    pub fn compute(graph: &Graph) -> Self {
+        let n = graph.num_nodes;
+        let mut in_degree = vec![0usize; n];
+        for &v in &graph.neighbors {
+            in_degree[v] += 1;
+        }
+
+        // Self-loops and duplicates are per adjacency list, so count them in parallel when available
+        let count = |u: usize| {
+            let mut adj = graph.neighbors(u).to_vec();
+            let loops = adj.iter().filter(|&&v| v == u).count();
+            adj.sort_unstable();
+            let dups = adj.windows(2).filter(|w| w[0] == w[1]).count();
+            (loops, dups)
+        };
+        let add = |a: (usize, usize), b: (usize, usize)| (a.0 + b.0, a.1 + b.1);
+        #[cfg(feature = "parallel")]
+        let (self_loops, duplicate_edges) = (0..n).into_par_iter().map(count).reduce(|| (0, 0), add);
+        #[cfg(not(feature = "parallel"))]
+        let (self_loops, duplicate_edges) = (0..n).map(count).fold((0, 0), add);
+
+        let mut degree_histogram = Vec::new();
+        for &d in &graph.out_degree {
+            let bucket = if d == 0 { 0 } else { d.ilog2() as usize + 1 };
+            if bucket >= degree_histogram.len() {
+                degree_histogram.resize(bucket + 1, 0);
+            }
+            degree_histogram[bucket] += 1;
+        }
+
+        Self {
+            num_nodes: n,
+            num_edges: graph.num_edges,
+            min_out_degree: graph.out_degree.iter().copied().min().unwrap_or(0),
+            max_out_degree: graph.out_degree.iter().copied().max().unwrap_or(0),
+            max_in_degree: in_degree.iter().copied().max().unwrap_or(0),
+            avg_degree: if n == 0 { 0.0 } else { graph.num_edges as f64 / n as f64 },
+            degree_histogram,
+            self_loops,
+            duplicate_edges,
+            dangling_nodes: graph.out_degree.iter().filter(|&&d| d == 0).count(),
+            isolated_nodes: (0..n).filter(|&v| graph.out_degree[v] == 0 && in_degree[v] == 0).count(),
+            memory_bytes: graph.memory_bytes(),
+        }
+    }
+
+    pub fn print(&self) {
+        println!("Graph Statistics:");
+        println!("  Nodes: {}", self.num_nodes);
+        println!("  Edges: {}", self.num_edges);
+        println!("  Avg degree: {:.2}", self.avg_degree);
+        println!("  Out-degree: min {}, max {}", self.min_out_degree, self.max_out_degree);
+        println!("  Max in-degree: {}", self.max_in_degree);
+        println!("  Self-loops: {}", self.self_loops);
+        println!("  Duplicate edges: {}", self.duplicate_edges);
+        println!("  Dangling nodes (out-degree 0): {}", self.dangling_nodes);
+        println!("  Isolated nodes: {}", self.isolated_nodes);
+        println!("  Estimated CSR memory: {:.2} MB", self.memory_bytes as f64 / (1024.0 * 1024.0));
+
+        println!("\nOut-degree distribution:");
+        for (bucket, &count) in self.degree_histogram.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            let range = match bucket {
+                0 => "0".to_string(),
+                1 => "1".to_string(),
+                _ => format!("{}-{}", 1usize << (bucket - 1), (1usize << bucket) - 1),
+            };
+            println!("  {:>12}: {}", range, count);
+        }
+    }
+}
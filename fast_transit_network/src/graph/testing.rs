@@ -0,0 +1,94 @@
+//! Synthetic graphs for tests, property tests and micro-benchmarks.
+//!
+//! The random builders take a seed and use [`Rng`], so a failing case is reproduced from its seed
+//! on any platform. Crates embedding the
+//! algorithms can check their own code against the same inputs, e.g. every graph of
+//! [`arbitrary_graphs`] against a reference implementation.
+
+use crate::graph::graph::{build_csr, build_csr_undirected, build_csr_weighted, Graph};
+use crate::utils::rng::Rng;
+
+/// Directed path `0 -> 1 -> … -> n-1`.
+pub fn path(n: usize) -> Graph {
+    build_csr(n, (1..n).map(|v| (v - 1, v)).collect())
+}
+
+/// Directed cycle `0 -> 1 -> … -> n-1 -> 0`; one self-loop for `n = 1`.
+pub fn cycle(n: usize) -> Graph {
+    build_csr(n, (0..n).map(|v| (v, (v + 1) % n)).collect())
+}
+
+/// Star with center 0 and edges `0 -> 1`, …, `0 -> n-1`.
+pub fn star(n: usize) -> Graph {
+    build_csr(n, (1..n).map(|v| (0, v)).collect())
+}
+
+/// `side × side` lattice, node `row · side + col`, with every link stored in both directions
+/// (right and down neighbor of each node in turn). [`Graph::directed`] is false.
+pub fn grid(side: usize) -> Graph {
+    let mut edges = Vec::with_capacity(4 * side * side);
+    for v in 0..side * side {
+        if v % side + 1 < side {
+            edges.extend([(v, v + 1), (v + 1, v)]);
+        }
+        if v + side < side * side {
+            edges.extend([(v, v + side), (v + side, v)]);
+        }
+    }
+    let mut graph = build_csr(side * side, edges);
+    graph.directed = false;
+    graph
+}
+
+/// `m` uniformly random directed edges on `n` nodes; self-loops and duplicates are kept.
+pub fn random(n: usize, m: usize, seed: u64) -> Graph {
+    let mut rng = Rng::new(seed);
+    let edges = if n == 0 { Vec::new() } else { (0..m).map(|_| (rng.below(n), rng.below(n))).collect() };
+    build_csr(n, edges)
+}
+
+/// [`random`] with weights `min_weight + k / 10` for `k` in `0..100`, so equal-cost paths occur.
+pub fn random_weighted(n: usize, m: usize, seed: u64, min_weight: f64) -> Graph {
+    let mut rng = Rng::new(seed);
+    let edges = if n == 0 {
+        Vec::new()
+    } else {
+        (0..m).map(|_| (rng.below(n), rng.below(n), min_weight + rng.below(100) as f64 / 10.0)).collect()
+    };
+    build_csr_weighted(n, edges)
+}
+
+/// Undirected preferential attachment: every node after the first links to `degree` earlier
+/// endpoints chosen in proportion to their degree, so a few hubs hold most edges.
+pub fn power_law(n: usize, degree: usize, seed: u64) -> Graph {
+    let mut rng = Rng::new(seed);
+    // Picking a random endpoint of the edges so far picks nodes in proportion to their degree
+    let mut endpoints = vec![0];
+    let mut edges = Vec::with_capacity(n.saturating_sub(1) * degree);
+    for v in 1..n {
+        for _ in 0..degree {
+            let target = endpoints[rng.below(endpoints.len())];
+            edges.push((v, target));
+            endpoints.push(target);
+        }
+        endpoints.push(v);
+    }
+    build_csr_undirected(n, edges)
+}
+
+/// Endless stream of small random graphs for property tests: 0 to `max_nodes` nodes, from no
+/// edges up to about four per node, every fourth one weighted. Graph `i` depends only on `seed`
+/// and `i`, so `arbitrary_graphs(seed, max).nth(i)` reproduces a failure.
+pub fn arbitrary_graphs(seed: u64, max_nodes: usize) -> impl Iterator<Item = Graph> {
+    (0u64..).map(move |case| {
+        let mut rng = Rng::for_stream(seed, case);
+        let n = rng.below(max_nodes + 1);
+        let m = rng.below(4 * n + 1);
+        let graph_seed = rng.next_u64();
+        if case % 4 == 3 {
+            random_weighted(n, m, graph_seed, 0.0)
+        } else {
+            random(n, m, graph_seed)
+        }
+    })
+}
@@ -0,0 +1,115 @@
+use super::graph::Graph;
+use std::collections::VecDeque;
+
+/// Lazy breadth-first traversal created by [`Graph::bfs_iter`]; yields `(node, depth)` in BFS order.
+///
+/// Work is done only as items are pulled, so stopping early (e.g. with `take_while` or `find`) skips
+/// the rest of the graph.
+#[derive(Debug, Clone)]
+pub struct BfsIter<'a> {
+    graph: &'a Graph,
+    queue: VecDeque<(usize, usize)>,
+    visited: Vec<bool>,
+}
+
+impl<'a> BfsIter<'a> {
+    fn new(graph: &'a Graph, source: usize) -> Self {
+        let mut visited = vec![false; graph.num_nodes];
+        let mut queue = VecDeque::new();
+        if graph.is_valid_node(source) {
+            visited[source] = true;
+            queue.push_back((source, 0));
+        }
+        Self { graph, queue, visited }
+    }
+
+    /// True once `v` has been discovered (yielded or queued).
+    pub fn is_visited(&self, v: usize) -> bool {
+        self.visited.get(v).copied().unwrap_or(false)
+    }
+}
+
+impl Iterator for BfsIter<'_> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        let (u, depth) = self.queue.pop_front()?;
+        for &v in self.graph.neighbors(u) {
+            if !self.visited[v] {
+                self.visited[v] = true;
+                self.queue.push_back((v, depth + 1));
+            }
+        }
+        Some((u, depth))
+    }
+}
+
+/// Lazy depth-first traversal created by [`Graph::dfs_iter`]; yields `(node, depth)` in preorder,
+/// where depth is the node's depth in the DFS tree.
+///
+/// Neighbors are explored in CSR order, so the order matches a recursive DFS, but the stack is
+/// explicit and deep graphs cannot overflow it.
+#[derive(Debug, Clone)]
+pub struct DfsIter<'a> {
+    graph: &'a Graph,
+    /// Path from the source: node and index of its next out-edge to try.
+    stack: Vec<(usize, usize)>,
+    visited: Vec<bool>,
+    /// Node to yield on the next call; the source initially.
+    pending: Option<usize>,
+}
+
+impl<'a> DfsIter<'a> {
+    fn new(graph: &'a Graph, source: usize) -> Self {
+        let mut visited = vec![false; graph.num_nodes];
+        let pending = graph.is_valid_node(source).then(|| {
+            visited[source] = true;
+            source
+        });
+        Self { graph, stack: Vec::new(), visited, pending }
+    }
+
+    /// True once `v` has been yielded.
+    pub fn is_visited(&self, v: usize) -> bool {
+        self.visited.get(v).copied().unwrap_or(false)
+    }
+}
+
+impl Iterator for DfsIter<'_> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        if let Some(u) = self.pending.take() {
+            self.stack.push((u, self.graph.offsets[u]));
+            return Some((u, self.stack.len() - 1));
+        }
+        while let Some((u, next)) = self.stack.last_mut() {
+            if *next == self.graph.offsets[*u + 1] {
+                self.stack.pop();
+                continue;
+            }
+            let v = self.graph.neighbors[*next];
+            *next += 1;
+            if !self.visited[v] {
+                self.visited[v] = true;
+                self.stack.push((v, self.graph.offsets[v]));
+                return Some((v, self.stack.len() - 1));
+            }
+        }
+        None
+    }
+}
+
+impl Graph {
+    /// Breadth-first traversal from `source` as a lazy iterator of `(node, hop depth)`; empty if
+    /// `source` is out of range.
+    pub fn bfs_iter(&self, source: usize) -> BfsIter<'_> {
+        BfsIter::new(self, source)
+    }
+
+    /// Depth-first traversal from `source` as a lazy iterator of `(node, tree depth)`; empty if
+    /// `source` is out of range.
+    pub fn dfs_iter(&self, source: usize) -> DfsIter<'_> {
+        DfsIter::new(self, source)
+    }
+}
@@ -0,0 +1,177 @@
+use crate::graph::graph::Graph;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::Serialize;
+
+/// At most this many structural errors are listed in a report; `error_count` still counts all of them.
+pub const MAX_REPORTED_ERRORS: usize = 100;
+
+/// A structural defect that makes the CSR arrays unsafe to traverse.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ValidationIssue {
+    /// `offsets` must have `num_nodes + 1` entries.
+    OffsetsLength { expected: usize, actual: usize },
+    /// `offsets[0]` must be 0.
+    OffsetsStart { value: usize },
+    /// `offsets[node] > offsets[node + 1]`.
+    NonMonotonicOffsets { node: usize, start: usize, end: usize },
+    /// The last offset must equal `neighbors.len()`.
+    OffsetsEnd { value: usize, neighbors: usize },
+    /// `num_edges` disagrees with `neighbors.len()`.
+    EdgeCount { num_edges: usize, neighbors: usize },
+    /// `out_degree` must have `num_nodes` entries.
+    DegreesLength { expected: usize, actual: usize },
+    /// `out_degree[node]` disagrees with the offsets.
+    DegreeMismatch { node: usize, out_degree: usize, from_offsets: usize },
+    /// `weights` must be aligned with `neighbors`.
+    WeightsLength { weights: usize, neighbors: usize },
+    /// `neighbors[edge]` is not a node id.
+    TargetOutOfRange { edge: usize, target: usize },
+    /// `neighbors_sorted` is set but the adjacency list of `node` is not ascending, so
+    /// `has_edge` would miss edges.
+    UnsortedNeighbors { node: usize },
+}
+
+/// Result of [`Graph::validate`].
+///
+/// Structural errors (`errors`) mean the arrays are corrupt; self-loops and duplicate edges are
+/// legal but often a sign of a bad export, so they are only counted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationReport {
+    pub num_nodes: usize,
+    pub num_edges: usize,
+    pub num_neighbors: usize,
+    /// First [`MAX_REPORTED_ERRORS`] structural errors, in array order.
+    pub errors: Vec<ValidationIssue>,
+    pub error_count: usize,
+    pub self_loops: usize,
+    /// Edges repeating an earlier (u, v) pair.
+    pub duplicate_edges: usize,
+}
+
+impl ValidationReport {
+    /// True if no structural errors were found.
+    pub fn is_valid(&self) -> bool {
+        self.error_count == 0
+    }
+
+    fn push(&mut self, issue: ValidationIssue) {
+        if self.errors.len() < MAX_REPORTED_ERRORS {
+            self.errors.push(issue);
+        }
+        self.error_count += 1;
+    }
+
+    pub fn print(&self) {
+        println!("Validation:");
+        println!("  Nodes: {}", self.num_nodes);
+        println!("  Edges: {} (neighbors array: {})", self.num_edges, self.num_neighbors);
+        println!("  Self-loops: {}", self.self_loops);
+        println!("  Duplicate edges: {}", self.duplicate_edges);
+        if self.is_valid() {
+            println!("  Structure: OK");
+            return;
+        }
+        println!("  Structural errors: {}", self.error_count);
+        for issue in &self.errors {
+            println!("    {:?}", issue);
+        }
+        if self.error_count > self.errors.len() {
+            println!("    ... {} more", self.error_count - self.errors.len());
+        }
+    }
+}
+
+impl Graph {
+    /// Checks the CSR arrays for corruption without trusting any of them: offset length, start,
+    /// monotonicity and end, `num_edges`, `out_degree`, weight alignment and target ranges.
+    /// Also counts self-loops and duplicate edges in every adjacency list whose range is readable.
+    pub fn validate(&self) -> ValidationReport {
+        let n = self.num_nodes;
+        let m = self.neighbors.len();
+        let mut report = ValidationReport {
+            num_nodes: n,
+            num_edges: self.num_edges,
+            num_neighbors: m,
+            errors: Vec::new(),
+            error_count: 0,
+            self_loops: 0,
+            duplicate_edges: 0,
+        };
+
+        if self.offsets.len() != n + 1 {
+            report.push(ValidationIssue::OffsetsLength { expected: n + 1, actual: self.offsets.len() });
+        }
+        if let Some(&first) = self.offsets.first()
+            && first != 0
+        {
+            report.push(ValidationIssue::OffsetsStart { value: first });
+        }
+        for (node, w) in self.offsets.windows(2).enumerate() {
+            if w[0] > w[1] {
+                report.push(ValidationIssue::NonMonotonicOffsets { node, start: w[0], end: w[1] });
+            }
+        }
+        if let Some(&last) = self.offsets.last()
+            && last != m
+        {
+            report.push(ValidationIssue::OffsetsEnd { value: last, neighbors: m });
+        }
+        if self.num_edges != m {
+            report.push(ValidationIssue::EdgeCount { num_edges: self.num_edges, neighbors: m });
+        }
+        if self.out_degree.len() != n {
+            report.push(ValidationIssue::DegreesLength { expected: n, actual: self.out_degree.len() });
+        }
+        let range = |v: usize| -> Option<(usize, usize)> {
+            let (&start, &end) = (self.offsets.get(v)?, self.offsets.get(v + 1)?);
+            (start <= end && end <= m).then_some((start, end))
+        };
+        for (node, &degree) in self.out_degree.iter().enumerate() {
+            if let Some((start, end)) = range(node)
+                && end - start != degree
+            {
+                report.push(ValidationIssue::DegreeMismatch { node, out_degree: degree, from_offsets: end - start });
+            }
+        }
+        if let Some(weights) = &self.weights
+            && weights.len() != m
+        {
+            report.push(ValidationIssue::WeightsLength { weights: weights.len(), neighbors: m });
+        }
+        for (edge, &target) in self.neighbors.iter().enumerate() {
+            if target >= n {
+                report.push(ValidationIssue::TargetOutOfRange { edge, target });
+            }
+        }
+        if self.neighbors_sorted {
+            for node in 0..n {
+                if let Some((start, end)) = range(node)
+                    && !self.neighbors[start..end].is_sorted()
+                {
+                    report.push(ValidationIssue::UnsortedNeighbors { node });
+                }
+            }
+        }
+
+        // Self-loops and duplicates are per adjacency list, like `GraphStats`; unreadable ranges are skipped
+        let count = |u: usize| {
+            let Some((start, end)) = range(u) else { return (0, 0) };
+            let mut adj = self.neighbors[start..end].to_vec();
+            let loops = adj.iter().filter(|&&v| v == u).count();
+            adj.sort_unstable();
+            let dups = adj.windows(2).filter(|w| w[0] == w[1]).count();
+            (loops, dups)
+        };
+        let add = |a: (usize, usize), b: (usize, usize)| (a.0 + b.0, a.1 + b.1);
+        #[cfg(feature = "parallel")]
+        let (self_loops, duplicate_edges) = (0..n).into_par_iter().map(count).reduce(|| (0, 0), add);
+        #[cfg(not(feature = "parallel"))]
+        let (self_loops, duplicate_edges) = (0..n).map(count).fold((0, 0), add);
+        report.self_loops = self_loops;
+        report.duplicate_edges = duplicate_edges;
+
+        report
+    }
+}
@@ -0,0 +1,126 @@
+use super::graph::{Graph, GraphLike};
+
+/// Edge-id predicate used by views built with [`GraphView::filtered`], which keeps every edge.
+pub type AllEdges = fn(usize) -> bool;
+
+/// Filtered view over a [`Graph`] that hides nodes and edges without copying the CSR.
+///
+/// Hidden nodes keep their ids but have no out-edges and are never reported as neighbors,
+/// so algorithms treat them as isolated nodes (BFS leaves them at -1, WCC puts each in its own component).
+pub struct GraphView<'a, N, E, X = AllEdges>
+where
+    N: Fn(usize) -> bool,
+    E: Fn(usize, usize) -> bool,
+    X: Fn(usize) -> bool,
+{
+    graph: &'a Graph,
+    node_predicate: N,
+    edge_predicate: E,
+    edge_id_predicate: X,
+}
+
+impl<'a, N, E> GraphView<'a, N, E>
+where
+    N: Fn(usize) -> bool,
+    E: Fn(usize, usize) -> bool,
+{
+    /// Creates a view that keeps node `v` when `node_predicate(v)` holds and edge `u -> v`
+    /// when `edge_predicate(u, v)` holds and both endpoints are kept.
+    pub fn filtered(graph: &'a Graph, node_predicate: N, edge_predicate: E) -> Self {
+        Self {
+            graph,
+            node_predicate,
+            edge_predicate,
+            edge_id_predicate: |_| true,
+        }
+    }
+}
+
+impl<'a, N> GraphView<'a, N, fn(usize, usize) -> bool>
+where
+    N: Fn(usize) -> bool,
+{
+    /// Creates a view that hides nodes failing `node_predicate` and keeps every edge between the rest,
+    /// e.g. `GraphView::nodes_where(&graph, |v| !types.is(v, "closed"))` with a node attribute column.
+    pub fn nodes_where(graph: &'a Graph, node_predicate: N) -> Self {
+        Self {
+            graph,
+            node_predicate,
+            edge_predicate: |_, _| true,
+            edge_id_predicate: |_| true,
+        }
+    }
+}
+
+impl<'a, N, X> GraphView<'a, N, fn(usize, usize) -> bool, X>
+where
+    N: Fn(usize) -> bool,
+    X: Fn(usize) -> bool,
+{
+    /// Creates a view whose edge predicate gets the CSR edge index, so it can look up edge
+    /// attributes (e.g. keep only edges whose `"type"` is `"bus"`, or with travel time under a limit).
+    pub fn filtered_by_edge(graph: &'a Graph, node_predicate: N, edge_predicate: X) -> Self {
+        Self {
+            graph,
+            node_predicate,
+            edge_predicate: |_, _| true,
+            edge_id_predicate: edge_predicate,
+        }
+    }
+}
+
+impl<'a, N, E, X> GraphView<'a, N, E, X>
+where
+    N: Fn(usize) -> bool,
+    E: Fn(usize, usize) -> bool,
+    X: Fn(usize) -> bool,
+{
+    /// Returns the underlying graph.
+    pub fn graph(&self) -> &'a Graph {
+        self.graph
+    }
+
+    /// Number of nodes that pass the node predicate.
+    pub fn num_visible_nodes(&self) -> usize {
+        (0..self.graph.num_nodes)
+            .filter(|&v| (self.node_predicate)(v))
+            .count()
+    }
+
+    /// Number of edges that pass both predicates.
+    pub fn num_visible_edges(&self) -> usize {
+        (0..self.graph.num_nodes).map(|u| self.degree(u)).sum()
+    }
+}
+
+impl<N, E, X> GraphLike for GraphView<'_, N, E, X>
+where
+    N: Fn(usize) -> bool,
+    E: Fn(usize, usize) -> bool,
+    X: Fn(usize) -> bool,
+{
+    fn node_count(&self) -> usize {
+        self.graph.num_nodes
+    }
+
+    fn neighbor_iter(&self, u: usize) -> impl Iterator<Item = usize> + '_ {
+        self.weighted_neighbor_iter(u).map(|(v, _)| v)
+    }
+
+    fn weighted_neighbor_iter(&self, u: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let visible = self.contains_node(u);
+        let edges = self.graph.edge_range(u);
+        self.graph.neighbors[edges.clone()]
+            .iter()
+            .copied()
+            .zip(edges)
+            .filter(move |&(v, e)| {
+                visible && (self.node_predicate)(v) && (self.edge_predicate)(u, v) && (self.edge_id_predicate)(e)
+            })
+            .map(|(v, e)| (v, self.graph.weights.as_ref().map_or(1.0, |w| w[e])))
+    }
+
+    fn contains_node(&self, v: usize) -> bool {
+        self.graph.is_valid_node(v) && (self.node_predicate)(v)
+    }
+}
@@ -1,18 +1,55 @@
-pub mod graph {
-    pub mod graph;
-}
+pub mod graph;
+pub mod algorithms;
+pub mod utils;
+pub mod cli;
+pub mod config;
+pub mod progress;
+pub mod error;
+pub mod query;
+pub mod partition;
+pub mod sample;
+pub mod analysis;
+pub mod prelude;
+pub mod registry;
+#[cfg(feature = "fs")]
+pub mod cache;
+#[cfg(feature = "fs")]
+pub mod results;
+#[cfg(feature = "fs")]
+pub mod run_config;
+#[cfg(feature = "fs")]
+pub mod suite;
+#[cfg(feature = "fs")]
+pub mod pipeline;
+#[cfg(feature = "fs")]
+pub mod commands;
+#[cfg(feature = "serve")]
+pub mod server;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 
-pub mod algorithms {
-    pub mod bfs;
-    pub mod wcc;
-    pub mod union_find;
-    pub mod atomic_union_find;
-    pub mod pagerank;
-}
-
-pub mod utils {
-    pub mod io;
-    pub mod benchmark;
-}
-
-pub mod cli;
\ No newline at end of file
+// Flat paths for the most used items; the module paths stay valid
+pub use algorithms::bfs::{bfs_parallel, bfs_parallel_with_policy, bfs_sequential, shortest_path, BfsResult};
+pub use algorithms::bridges::{find_bridges, BridgeResult};
+pub use algorithms::centrality::{centrality_report, CentralityConfig, CentralityReport};
+pub use algorithms::pagerank::{
+    pagerank_parallel, pagerank_parallel_with_policy, pagerank_sequential, pagerank_with_initial, PageRankConfig,
+    PageRankResult,
+};
+pub use algorithms::scc::{strongly_connected_components, SccResult};
+pub use algorithms::sssp::{bellman_ford, dijkstra, SsspResult};
+pub use algorithms::wcc::{wcc_label_propagation, wcc_parallel, wcc_parallel_with_policy, wcc_sequential, IncrementalWcc, WccResult};
+pub use analysis::{Analysis, AnalysisResults};
+pub use config::{Config, Mode, ParallelismPolicy, Threads};
+pub use error::{Error, Result};
+#[cfg(feature = "fs")]
+pub use graph::graph::load_graph_from_file;
+pub use graph::graph::{
+    build_csr, build_csr_undirected, build_csr_weighted, load_graph_from_reader, load_graph_from_reader_with_options, load_graph_from_str, Graph, GraphLike,
+    LoadOptions, LoadReport,
+};
+pub use graph::compact::{CompactGraph, GraphStorage};
+pub use graph::stats::GraphStats;
+pub use graph::view::GraphView;
+pub use progress::{ProgressEvent, ProgressSink};
+pub use sample::induced_subgraph;
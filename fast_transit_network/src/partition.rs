@@ -0,0 +1,161 @@
+use crate::graph::graph::Graph;
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+/// Extra room per part over `ceil(n / k)` that the LDG partitioner allows.
+const LDG_SLACK: f64 = 0.05;
+
+/// How [`partition`] assigns nodes to parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionMethod {
+    /// Linear Deterministic Greedy streaming: each node joins the part holding most of its
+    /// neighbors, penalized by how full that part is.
+    Ldg,
+    /// Grows each part by BFS from an unassigned seed until it reaches `ceil(n / k)` nodes.
+    BfsGrow,
+}
+
+impl FromStr for PartitionMethod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "ldg" => Ok(PartitionMethod::Ldg),
+            "bfs" | "bfs-grow" => Ok(PartitionMethod::BfsGrow),
+            _ => Err(anyhow::anyhow!("Invalid partition method: {}. Use 'ldg' or 'bfs'", s)),
+        }
+    }
+}
+
+/// Node-to-part assignment with its quality metrics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Partition {
+    /// Part id per node, in `0..num_parts`.
+    pub parts: Vec<usize>,
+    pub num_parts: usize,
+    pub part_sizes: Vec<usize>,
+    /// Edges whose endpoints are in different parts.
+    pub edge_cut: usize,
+}
+
+impl Partition {
+    pub fn from_parts(graph: &Graph, parts: Vec<usize>, num_parts: usize) -> Self {
+        let mut part_sizes = vec![0; num_parts];
+        for &p in &parts {
+            part_sizes[p] += 1;
+        }
+        let edge_cut = edge_cut(graph, &parts);
+        Self { parts, num_parts, part_sizes, edge_cut }
+    }
+
+    /// Fraction of edges crossing parts.
+    pub fn cut_ratio(&self, graph: &Graph) -> f64 {
+        if graph.num_edges == 0 { 0.0 } else { self.edge_cut as f64 / graph.num_edges as f64 }
+    }
+
+    /// Largest part size relative to a perfectly even split (1.0 = balanced).
+    pub fn imbalance(&self) -> f64 {
+        let n: usize = self.part_sizes.iter().sum();
+        let max = self.part_sizes.iter().copied().max().unwrap_or(0);
+        if n == 0 { 1.0 } else { max as f64 * self.num_parts as f64 / n as f64 }
+    }
+
+    pub fn print(&self, graph: &Graph) {
+        println!("Partition:");
+        println!("  Parts: {}", self.num_parts);
+        println!("  Edge cut: {} ({:.2}% of edges)", self.edge_cut, self.cut_ratio(graph) * 100.0);
+        println!("  Imbalance: {:.3}", self.imbalance());
+        for (p, size) in self.part_sizes.iter().enumerate().take(16) {
+            println!("  Part {}: {} nodes", p, size);
+        }
+        if self.num_parts > 16 {
+            println!("  ... and {} more parts", self.num_parts - 16);
+        }
+    }
+}
+
+/// Number of edges whose endpoints are assigned to different parts.
+pub fn edge_cut(graph: &Graph, parts: &[usize]) -> usize {
+    (0..graph.num_nodes)
+        .map(|u| graph.neighbors(u).iter().filter(|&&v| parts[u] != parts[v]).count())
+        .sum()
+}
+
+/// Assigns every node to one of `num_parts` parts (at least 1), ignoring edge direction.
+#[tracing::instrument(level = "debug", skip(graph), fields(nodes = graph.num_nodes))]
+pub fn partition(graph: &Graph, num_parts: usize, method: PartitionMethod) -> Partition {
+    let k = num_parts.max(1);
+    let transposed = graph.transpose();
+    let parts = match method {
+        PartitionMethod::Ldg => ldg(graph, &transposed, k),
+        PartitionMethod::BfsGrow => bfs_grow(graph, &transposed, k),
+    };
+    Partition::from_parts(graph, parts, k)
+}
+
+fn ldg(graph: &Graph, transposed: &Graph, k: usize) -> Vec<usize> {
+    let n = graph.num_nodes;
+    let capacity = (n.div_ceil(k) as f64 * (1.0 + LDG_SLACK)).ceil().max(1.0);
+    let mut parts = vec![usize::MAX; n];
+    let mut sizes = vec![0usize; k];
+    let mut neighbor_counts = vec![0usize; k];
+
+    for v in 0..n {
+        neighbor_counts.fill(0);
+        for &w in graph.neighbors(v).iter().chain(transposed.neighbors(v)) {
+            if parts[w] != usize::MAX {
+                neighbor_counts[parts[w]] += 1;
+            }
+        }
+        // Highest penalized score; ties go to the smaller part, so nodes without placed neighbors spread evenly
+        let best = (0..k)
+            .filter(|&p| (sizes[p] as f64) < capacity)
+            .max_by(|&a, &b| {
+                let score = |p: usize| neighbor_counts[p] as f64 * (1.0 - sizes[p] as f64 / capacity);
+                score(a).total_cmp(&score(b)).then(sizes[b].cmp(&sizes[a])).then(b.cmp(&a))
+            })
+            .unwrap_or_else(|| (0..k).min_by_key(|&p| sizes[p]).unwrap_or(0));
+        parts[v] = best;
+        sizes[best] += 1;
+    }
+    parts
+}
+
+fn bfs_grow(graph: &Graph, transposed: &Graph, k: usize) -> Vec<usize> {
+    let n = graph.num_nodes;
+    let target = n.div_ceil(k).max(1);
+    let mut parts = vec![usize::MAX; n];
+    let mut queue = VecDeque::new();
+    let mut part = 0;
+    let mut size = 0;
+    let mut next_seed = 0;
+
+    while next_seed < n {
+        if parts[next_seed] != usize::MAX {
+            next_seed += 1;
+            continue;
+        }
+        // A part continues from a new seed when its BFS ran out of reachable nodes
+        queue.push_back(next_seed);
+        parts[next_seed] = part;
+        size += 1;
+        while let Some(u) = queue.pop_front() {
+            for &v in graph.neighbors(u).iter().chain(transposed.neighbors(u)) {
+                if size == target {
+                    break;
+                }
+                if parts[v] == usize::MAX {
+                    parts[v] = part;
+                    size += 1;
+                    queue.push_back(v);
+                }
+            }
+            if size == target {
+                queue.clear();
+                part = (part + 1).min(k - 1);
+                size = 0;
+            }
+        }
+    }
+    parts
+}
@@ -0,0 +1,204 @@
+use crate::algorithms::bfs::{bfs_parallel_with_policy, bfs_sequential};
+use crate::algorithms::bridges::find_bridges;
+use crate::algorithms::pagerank::{pagerank_parallel_with_policy, pagerank_sequential, PageRankConfig};
+use crate::algorithms::wcc::{wcc_parallel_with_policy, wcc_sequential, WccResult};
+use crate::config::{Mode, ParallelismPolicy};
+use crate::graph::graph::Graph;
+use crate::graph::stats::GraphStats;
+use crate::sample::induced_subgraph;
+use crate::utils::io::{
+    write_bfs_result_as, write_bridges, write_edge_list, write_id_map, write_pagerank_result_as, write_wcc_result_as,
+    write_wcc_stats, OutputFormat, WccColumns,
+};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Instant;
+
+/// One step of [`run_pipeline`]. Steps run in order on the current graph, which starts as the loaded
+/// graph and is replaced by [`PipelineStep::LargestComponent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineStep {
+    /// Print structural statistics.
+    Stats,
+    /// Weakly connected components (`wcc.*` and `wcc_stats.txt`).
+    Wcc,
+    /// Continue on the largest weakly connected component; writes `largest_component_ids.txt` mapping
+    /// the new node ids to the loaded ones. Reuses the components of a preceding `wcc` step.
+    LargestComponent,
+    /// BFS from a node id of the loaded graph (`bfs_<source>.*`).
+    Bfs { source: usize },
+    /// PageRank (`pagerank.*`).
+    PageRank,
+    /// Bridges and cut vertices (`bridges.json`).
+    Bridges,
+    /// Current graph as an edge list (`graph.txt`).
+    Export,
+}
+
+impl FromStr for PipelineStep {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.split_once(':') {
+            Some(("bfs", source)) => {
+                let source = source.parse().map_err(|_| anyhow::anyhow!("Invalid BFS source in step '{}'", s))?;
+                Ok(PipelineStep::Bfs { source })
+            }
+            _ => match s {
+                "stats" => Ok(PipelineStep::Stats),
+                "wcc" => Ok(PipelineStep::Wcc),
+                "largest-component" | "lcc" => Ok(PipelineStep::LargestComponent),
+                "pagerank" => Ok(PipelineStep::PageRank),
+                "bridges" => Ok(PipelineStep::Bridges),
+                "export" => Ok(PipelineStep::Export),
+                _ => Err(anyhow::anyhow!(
+                    "Invalid pipeline step: {}. Use 'stats', 'wcc', 'largest-component', 'bfs:<source>', 'pagerank', \
+                     'bridges' or 'export'",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for PipelineStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PipelineStep::Stats => write!(f, "stats"),
+            PipelineStep::Wcc => write!(f, "wcc"),
+            PipelineStep::LargestComponent => write!(f, "largest-component"),
+            PipelineStep::Bfs { source } => write!(f, "bfs:{}", source),
+            PipelineStep::PageRank => write!(f, "pagerank"),
+            PipelineStep::Bridges => write!(f, "bridges"),
+            PipelineStep::Export => write!(f, "export"),
+        }
+    }
+}
+
+impl PipelineStep {
+    /// Parses a comma-separated step list such as `stats,wcc,lcc,pagerank,export`.
+    pub fn parse_list(list: &str) -> anyhow::Result<Vec<Self>> {
+        list.split(',').map(|s| s.trim().parse()).collect()
+    }
+}
+
+/// Settings shared by all steps of [`run_pipeline`].
+pub struct PipelineConfig {
+    /// Directory for all outputs; created if missing.
+    pub out_dir: PathBuf,
+    pub mode: Mode,
+    pub num_threads: usize,
+    pub policy: ParallelismPolicy,
+    pub format: OutputFormat,
+    pub pagerank: PageRankConfig,
+}
+
+/// Runs `steps` on one loaded graph and returns the files written, in order.
+///
+/// After a `largest-component` step, node ids in later outputs refer to the component; the id map it
+/// writes translates them back.
+pub fn run_pipeline(graph: Graph, steps: &[PipelineStep], config: &PipelineConfig) -> anyhow::Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(&config.out_dir)?;
+    let parallel = config.mode.is_parallel();
+    let extension = match config.format {
+        OutputFormat::Text | OutputFormat::Summary => "txt",
+        OutputFormat::Json => "json",
+        OutputFormat::Ndjson => "ndjson",
+        OutputFormat::Parquet => "parquet",
+        OutputFormat::Binary => "bin",
+    };
+    let path = |name: &str| config.out_dir.join(name);
+    let mut written = Vec::new();
+
+    let mut graph = graph;
+    // Loaded node id per current node, once the graph has been restricted
+    let mut original_ids: Option<Vec<usize>> = None;
+    // Components of the current graph from a wcc step; taken (and so dropped) when the graph changes
+    let mut components: Option<WccResult> = None;
+
+    for (index, &step) in steps.iter().enumerate() {
+        let start = Instant::now();
+        let outputs = match step {
+            PipelineStep::Stats => {
+                GraphStats::compute(&graph).print();
+                vec![]
+            }
+            PipelineStep::Wcc => {
+                let result = if parallel {
+                    wcc_parallel_with_policy(&graph, config.num_threads, config.policy)
+                } else {
+                    wcc_sequential(&graph)
+                };
+                result.stats.print();
+                let (out, stats) = (path(&format!("wcc.{}", extension)), path("wcc_stats.txt"));
+                write_wcc_result_as(&result, &to_str(&out)?, config.format, WccColumns::default())?;
+                write_wcc_stats(&result.labels, &to_str(&stats)?)?;
+                components = Some(result);
+                vec![out, stats]
+            }
+            PipelineStep::LargestComponent => {
+                let result = components.take().unwrap_or_else(|| wcc_sequential(&graph));
+                let nodes = result.largest_component();
+                graph = induced_subgraph(&graph, &nodes);
+                let ids = match &original_ids {
+                    Some(ids) => nodes.iter().map(|&v| ids[v]).collect(),
+                    None => nodes,
+                };
+                println!("Largest component: {} nodes, {} edges", graph.num_nodes, graph.num_edges);
+                let out = path("largest_component_ids.txt");
+                write_id_map(&ids, &to_str(&out)?)?;
+                original_ids = Some(ids);
+                vec![out]
+            }
+            PipelineStep::Bfs { source } => {
+                let start_node = match &original_ids {
+                    Some(ids) => ids.iter().position(|&id| id == source).ok_or_else(|| {
+                        anyhow::anyhow!("BFS source {} is not in the current graph", source)
+                    })?,
+                    None => source,
+                };
+                let result = if parallel {
+                    bfs_parallel_with_policy(&graph, start_node, config.num_threads, config.policy)?
+                } else {
+                    bfs_sequential(&graph, start_node)?
+                };
+                println!("BFS from {}: {}/{} nodes reachable", source, result.reachable, graph.num_nodes);
+                let out = path(&format!("bfs_{}.{}", source, extension));
+                write_bfs_result_as(&result, start_node, &to_str(&out)?, config.format)?;
+                vec![out]
+            }
+            PipelineStep::PageRank => {
+                let result = if parallel {
+                    pagerank_parallel_with_policy(&graph, &config.pagerank, config.num_threads, config.policy)
+                } else {
+                    pagerank_sequential(&graph, &config.pagerank)
+                };
+                let out = path(&format!("pagerank.{}", extension));
+                write_pagerank_result_as(&result, &to_str(&out)?, config.format)?;
+                vec![out]
+            }
+            PipelineStep::Bridges => {
+                let result = find_bridges(&graph);
+                println!("Bridges: {}, cut vertices: {}", result.bridges.len(), result.cut_vertices.len());
+                let out = path("bridges.json");
+                write_bridges(&result, &to_str(&out)?)?;
+                vec![out]
+            }
+            PipelineStep::Export => {
+                let out = path("graph.txt");
+                write_edge_list(&graph, &to_str(&out)?)?;
+                vec![out]
+            }
+        };
+        tracing::info!("Step {}/{} ({}) completed in {:?}", index + 1, steps.len(), step, start.elapsed());
+        for out in &outputs {
+            println!("Saved: {}", out.display());
+        }
+        written.extend(outputs);
+    }
+    Ok(written)
+}
+
+fn to_str(path: &Path) -> anyhow::Result<String> {
+    path.to_str().map(str::to_owned).ok_or_else(|| anyhow::anyhow!("non-UTF-8 output path: {}", path.display()))
+}
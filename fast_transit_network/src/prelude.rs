@@ -0,0 +1,16 @@
+//! Common types and entry points in one import: `use fast_transit_network::prelude::*;`.
+//!
+//! Leaves out [`crate::Result`] so the glob does not shadow `std`'s two-parameter `Result`.
+
+pub use crate::algorithms::bfs::{bfs_parallel, bfs_parallel_with_policy, bfs_sequential, shortest_path, BfsResult};
+pub use crate::algorithms::pagerank::{
+    pagerank_parallel, pagerank_parallel_with_policy, pagerank_sequential, PageRankConfig, PageRankResult,
+};
+pub use crate::algorithms::wcc::{wcc_label_propagation, wcc_parallel, wcc_parallel_with_policy, wcc_sequential, WccResult};
+pub use crate::analysis::{Analysis, AnalysisResults};
+pub use crate::config::{Config, Mode, ParallelismPolicy, Threads};
+pub use crate::error::Error;
+#[cfg(feature = "fs")]
+pub use crate::graph::graph::load_graph_from_file;
+pub use crate::graph::graph::{build_csr, build_csr_weighted, load_graph_from_str, Graph, GraphLike};
+pub use crate::graph::view::GraphView;
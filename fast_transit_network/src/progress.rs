@@ -0,0 +1,242 @@
+use std::sync::Arc;
+
+/// Progress event emitted by long-running algorithms.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgressEvent {
+    /// A BFS level was fully expanded; `frontier` is the number of nodes discovered at `level`.
+    BfsLevel { level: usize, frontier: usize },
+    /// A PageRank iteration finished with L1 change `delta`.
+    PageRankIteration { iteration: usize, delta: f64 },
+    /// PageRank stopped, either converged below tolerance or at the iteration limit.
+    PageRankFinished { iterations: usize, delta: f64, converged: bool },
+    /// The loader has read `bytes` of the input; `total` is the file size when known.
+    LoadProgress { bytes: u64, total: Option<u64> },
+    /// The loader built the graph.
+    LoadFinished { nodes: usize, edges: usize },
+    /// A benchmark finished `completed` of its `total` timed configurations.
+    BenchmarkCase { completed: usize, total: usize },
+}
+
+/// Receiver for [`ProgressEvent`]s. Implemented for closures, so `&|e: &ProgressEvent| ...` works.
+pub trait ProgressSink: Send + Sync {
+    fn event(&self, event: &ProgressEvent);
+}
+
+impl<F> ProgressSink for F
+where
+    F: Fn(&ProgressEvent) + Send + Sync,
+{
+    fn event(&self, event: &ProgressEvent) {
+        self(event)
+    }
+}
+
+/// Discards all events; the default for library calls.
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn event(&self, _event: &ProgressEvent) {}
+}
+
+/// Prints run summaries to stdout (used by the CLI); per-level and per-iteration events only when `verbose`.
+pub struct StdoutProgress {
+    pub verbose: bool,
+}
+
+impl ProgressSink for StdoutProgress {
+    fn event(&self, event: &ProgressEvent) {
+        match *event {
+            ProgressEvent::BfsLevel { level, frontier } => {
+                if self.verbose {
+                    println!("  Level {}: {} nodes", level, frontier);
+                }
+            }
+            ProgressEvent::PageRankIteration { iteration, delta } => {
+                if self.verbose {
+                    println!("  Iteration {}: delta {:.2e}", iteration, delta);
+                }
+            }
+            ProgressEvent::PageRankFinished { iterations, delta, converged } => {
+                if converged {
+                    println!(
+                        "PageRank converged after {} iterations (delta: {:.2e})",
+                        iterations, delta
+                    );
+                } else {
+                    println!(
+                        "PageRank reached max iterations ({}) without full convergence (delta: {:.2e})",
+                        iterations, delta
+                    );
+                }
+            }
+            ProgressEvent::LoadProgress { bytes, .. } => {
+                if self.verbose {
+                    println!("  Read {:.1} MB", bytes as f64 / 1e6);
+                }
+            }
+            ProgressEvent::LoadFinished { .. } | ProgressEvent::BenchmarkCase { .. } => {}
+        }
+    }
+}
+
+/// Forwards events to `tracing` at debug level (summaries at info), for embedding in services.
+pub struct TracingProgress;
+
+impl ProgressSink for TracingProgress {
+    fn event(&self, event: &ProgressEvent) {
+        match *event {
+            ProgressEvent::BfsLevel { level, frontier } => {
+                tracing::debug!(level, frontier, "BFS level complete");
+            }
+            ProgressEvent::PageRankIteration { iteration, delta } => {
+                tracing::debug!(iteration, delta, "PageRank iteration");
+            }
+            ProgressEvent::PageRankFinished { iterations, delta, converged } => {
+                tracing::info!(iterations, delta, converged, "PageRank finished");
+            }
+            ProgressEvent::LoadProgress { bytes, total } => {
+                tracing::debug!(bytes, total, "loading graph");
+            }
+            ProgressEvent::LoadFinished { nodes, edges } => {
+                tracing::info!(nodes, edges, "graph loaded");
+            }
+            ProgressEvent::BenchmarkCase { completed, total } => {
+                tracing::debug!(completed, total, "benchmark case finished");
+            }
+        }
+    }
+}
+
+/// Shared handle to a sink, as stored in algorithm configs.
+pub type SharedProgress = Arc<dyn ProgressSink>;
+
+/// Terminal progress bars (via `indicatif`, drawn on stderr): a byte bar while loading, a spinner per
+/// BFS run, an iteration bar for PageRank and a case bar for benchmarks. Run summaries are printed like
+/// [`StdoutProgress`]. Bars hide themselves when stderr is not a terminal, and the active one is
+/// cleared when the sink is dropped.
+#[cfg(feature = "progress-bars")]
+pub struct BarProgress {
+    /// Length of the PageRank bar; without it PageRank gets a spinner.
+    iteration_limit: Option<u64>,
+    current: std::sync::Mutex<Option<(Phase, indicatif::ProgressBar)>>,
+}
+
+#[cfg(feature = "progress-bars")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Load,
+    Bfs,
+    PageRank,
+    Benchmark,
+}
+
+#[cfg(feature = "progress-bars")]
+impl Default for BarProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "progress-bars")]
+impl BarProgress {
+    pub fn new() -> Self {
+        Self { iteration_limit: None, current: std::sync::Mutex::new(None) }
+    }
+
+    /// Shows PageRank as a bar out of `max_iterations` instead of a spinner.
+    pub fn with_iteration_limit(max_iterations: usize) -> Self {
+        Self { iteration_limit: Some(max_iterations as u64), current: std::sync::Mutex::new(None) }
+    }
+
+    /// Runs `update` on the bar for `phase`, replacing the bar of any other phase.
+    fn with_bar(&self, phase: Phase, create: impl FnOnce() -> indicatif::ProgressBar, update: impl FnOnce(&indicatif::ProgressBar)) {
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        if current.as_ref().is_none_or(|(p, _)| *p != phase) {
+            if let Some((_, old)) = current.take() {
+                old.finish_and_clear();
+            }
+            *current = Some((phase, create()));
+        }
+        if let Some((_, bar)) = current.as_ref() {
+            update(bar);
+        }
+    }
+
+    /// Clears the bar of `phase` if it is showing.
+    fn finish(&self, phase: Phase) {
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        if current.as_ref().is_some_and(|(p, _)| *p == phase)
+            && let Some((_, bar)) = current.take()
+        {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+#[cfg(feature = "progress-bars")]
+impl Drop for BarProgress {
+    fn drop(&mut self) {
+        if let Some((_, bar)) = self.current.get_mut().unwrap_or_else(|e| e.into_inner()).take() {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+#[cfg(feature = "progress-bars")]
+fn styled(bar: indicatif::ProgressBar, template: &str) -> indicatif::ProgressBar {
+    let style = indicatif::ProgressStyle::with_template(template)
+        .unwrap_or_else(|_| indicatif::ProgressStyle::default_bar())
+        .progress_chars("=> ");
+    bar.with_style(style)
+}
+
+#[cfg(feature = "progress-bars")]
+impl ProgressSink for BarProgress {
+    fn event(&self, event: &ProgressEvent) {
+        use indicatif::ProgressBar;
+        match *event {
+            ProgressEvent::LoadProgress { bytes, total } => self.with_bar(
+                Phase::Load,
+                || match total {
+                    Some(total) => styled(ProgressBar::new(total), "Loading [{bar:40}] {bytes}/{total_bytes} ({eta})"),
+                    None => styled(ProgressBar::new_spinner(), "{spinner} Loading {bytes}"),
+                },
+                |bar| bar.set_position(bytes),
+            ),
+            ProgressEvent::LoadFinished { .. } => self.finish(Phase::Load),
+            ProgressEvent::BfsLevel { level, frontier } => self.with_bar(
+                Phase::Bfs,
+                || styled(ProgressBar::new_spinner(), "{spinner} BFS {msg}"),
+                |bar| {
+                    bar.set_message(format!("level {} ({} nodes)", level, frontier));
+                    bar.tick();
+                },
+            ),
+            ProgressEvent::PageRankIteration { iteration, delta } => self.with_bar(
+                Phase::PageRank,
+                || match self.iteration_limit {
+                    Some(limit) => styled(ProgressBar::new(limit), "PageRank [{bar:40}] {pos}/{len} {msg}"),
+                    None => styled(ProgressBar::new_spinner(), "{spinner} PageRank iteration {pos} {msg}"),
+                },
+                |bar| {
+                    bar.set_position(iteration as u64);
+                    bar.set_message(format!("delta {:.2e}", delta));
+                },
+            ),
+            ProgressEvent::PageRankFinished { .. } => {
+                self.finish(Phase::PageRank);
+                StdoutProgress { verbose: false }.event(event);
+            }
+            ProgressEvent::BenchmarkCase { completed, total } => {
+                self.with_bar(
+                    Phase::Benchmark,
+                    || styled(ProgressBar::new(total as u64), "Benchmark [{bar:40}] {pos}/{len} cases ({elapsed})"),
+                    |bar| bar.set_position(completed as u64),
+                );
+                if completed >= total {
+                    self.finish(Phase::Benchmark);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crate::algorithms::bfs::{bfs_to_targets, shortest_path, StopAt};
+use crate::error::Error;
+use crate::graph::graph::Graph;
+use anyhow::Result;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+const HELP: &str = "Commands:
+  neighbors <v>       out-neighbors of v
+  degree <v>          out-degree of v
+  path <u> <v>        fewest-hop path from u to v
+  dist <u> <v>        hop distance from u to v
+  dists <u> <v>...    hop distances from u to each v (-1 if unreachable)
+  nearest <u> <v>...  the v fewest hops from u, and its distance
+  help                show this message
+  quit                exit";
+
+/// One line of the interactive `query` subcommand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    Neighbors(usize),
+    Degree(usize),
+    Path(usize, usize),
+    Dist(usize, usize),
+    /// Source and targets; the BFS stops once it has reached all of them.
+    Dists(usize, Vec<usize>),
+    /// Source and targets; the BFS stops at the first target it reaches.
+    Nearest(usize, Vec<usize>),
+    Help,
+    Quit,
+}
+
+impl FromStr for Query {
+    type Err = anyhow::Error;
+
+    fn from_str(line: &str) -> Result<Self> {
+        let mut parts = line.split_whitespace();
+        let command = parts.next().unwrap_or("");
+        let parse = |token: &str| token.parse().map_err(|_| anyhow::anyhow!("Invalid node id: {}", token));
+        let mut node = |name: &str| -> Result<usize> {
+            let token = parts
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("'{}' expects a node id for <{}>", command, name))?;
+            parse(token)
+        };
+        let targets = |parts: &mut std::str::SplitWhitespace| -> Result<Vec<usize>> {
+            let targets = parts.map(parse).collect::<Result<Vec<usize>>>()?;
+            anyhow::ensure!(!targets.is_empty(), "'{}' expects at least one target node id", command);
+            Ok(targets)
+        };
+        let query = match command {
+            "neighbors" | "n" => Query::Neighbors(node("v")?),
+            "degree" | "d" => Query::Degree(node("v")?),
+            "path" | "p" => Query::Path(node("u")?, node("v")?),
+            "dist" => Query::Dist(node("u")?, node("v")?),
+            "dists" => Query::Dists(node("u")?, targets(&mut parts)?),
+            "nearest" => Query::Nearest(node("u")?, targets(&mut parts)?),
+            "help" | "?" => Query::Help,
+            "quit" | "exit" | "q" => Query::Quit,
+            _ => anyhow::bail!("Unknown command: {}. Type 'help' for the list", command),
+        };
+        if parts.next().is_some() {
+            anyhow::bail!("Too many arguments for '{}'", command);
+        }
+        Ok(query)
+    }
+}
+
+/// Node of input-file id `id` (see [`Graph::node_for_id`]).
+fn node(graph: &Graph, id: usize) -> Result<usize> {
+    graph
+        .node_for_id(id)
+        .ok_or_else(|| Error::InvalidNode { node: id, num_nodes: graph.num_nodes }.into())
+}
+
+/// Input-file ids of `nodes`, joined by `separator`.
+fn file_ids(graph: &Graph, nodes: &[usize], separator: &str) -> String {
+    nodes.iter().map(|&v| graph.original_id(v).to_string()).collect::<Vec<_>>().join(separator)
+}
+
+/// Answers a single query as one line of text. Node ids in queries and answers are input-file ids,
+/// which differ from the dense ids when the loader remapped a sparse id space.
+pub fn answer(graph: &Graph, query: Query) -> Result<String> {
+    match query {
+        Query::Neighbors(v) => Ok(file_ids(graph, graph.neighbors(node(graph, v)?), " ")),
+        Query::Degree(v) => Ok(graph.out_degree[node(graph, v)?].to_string()),
+        Query::Path(u, v) => Ok(match shortest_path(graph, node(graph, u)?, node(graph, v)?)? {
+            Some(path) => file_ids(graph, &path, " -> "),
+            None => "unreachable".to_string(),
+        }),
+        Query::Dist(u, v) => Ok(match shortest_path(graph, node(graph, u)?, node(graph, v)?)? {
+            Some(path) => (path.len() - 1).to_string(),
+            None => "-1".to_string(),
+        }),
+        Query::Dists(u, targets) => {
+            let targets = targets.iter().map(|&t| node(graph, t)).collect::<Result<Vec<_>>>()?;
+            let result = bfs_to_targets(graph, node(graph, u)?, &targets, StopAt::All)?;
+            Ok(result.distances.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(" "))
+        }
+        Query::Nearest(u, targets) => {
+            let targets = targets.iter().map(|&t| node(graph, t)).collect::<Result<Vec<_>>>()?;
+            Ok(match bfs_to_targets(graph, node(graph, u)?, &targets, StopAt::Any)?.nearest() {
+                Some((v, d)) => format!("{} {}", graph.original_id(v), d),
+                None => "unreachable".to_string(),
+            })
+        }
+        Query::Help => Ok(HELP.to_string()),
+        Query::Quit => Ok(String::new()),
+    }
+}
+
+/// Reads queries line by line from `input` until EOF or `quit`, writing one answer per query.
+///
+/// Bad queries print an `error:` line and the session continues. `prompt` writes `> ` before
+/// each read (for terminals).
+pub fn run_interactive<R: BufRead, W: Write>(graph: &Graph, input: R, mut output: W, prompt: bool) -> Result<()> {
+    let mut lines = input.lines();
+    loop {
+        if prompt {
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+        let Some(line) = lines.next() else {
+            break;
+        };
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match line.parse::<Query>().and_then(|q| Ok((q.clone(), answer(graph, q)?))) {
+            Ok((Query::Quit, _)) => break,
+            Ok((_, text)) => writeln!(output, "{}", text)?,
+            Err(e) => writeln!(output, "error: {}", e)?,
+        }
+        output.flush()?;
+    }
+    Ok(())
+}
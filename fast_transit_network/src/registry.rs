@@ -0,0 +1,362 @@
+use crate::algorithms::bfs::{bfs_parallel, bfs_sequential};
+use crate::algorithms::centrality::{approximate_betweenness, approximate_closeness};
+use crate::algorithms::pagerank::{pagerank_parallel, pagerank_sequential, PageRankConfig};
+use crate::algorithms::descendants::{count_descendants, DescendantConfig};
+use crate::algorithms::scc::strongly_connected_components;
+use crate::algorithms::triangles::{count_triangles_ordered, count_triangles_parallel};
+use crate::algorithms::wcc::{wcc_parallel, wcc_sequential};
+use crate::config::{Config, Threads, Workload};
+use crate::graph::graph::Graph;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// `key=value` parameters of a registered algorithm, from `tool run -p key=value` or the benchmark.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces `key`.
+    pub fn set(mut self, key: &str, value: impl ToString) -> Self {
+        self.values.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Parses `key=value` entries; a later entry for the same key wins.
+    pub fn parse(entries: &[String]) -> Result<Self> {
+        let mut params = Self::new();
+        for entry in entries {
+            let Some((key, value)) = entry.split_once('=') else {
+                anyhow::bail!("Invalid parameter '{}': use key=value", entry);
+            };
+            params = params.set(key.trim(), value.trim());
+        }
+        Ok(params)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    /// Value of `key` parsed as `T`, or `None` if it is not set.
+    pub fn get<T>(&self, key: &str) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.values
+            .get(key)
+            .map(|v| v.parse().map_err(|e| anyhow::anyhow!("Invalid value '{}' for parameter {}: {}", v, key, e)))
+            .transpose()
+    }
+
+    pub fn get_or<T>(&self, key: &str, default: T) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        Ok(self.get(key)?.unwrap_or(default))
+    }
+
+    /// `mode`: `seq` (the default) or `par`.
+    pub fn parallel(&self) -> Result<bool> {
+        match self.values.get("mode").map(String::as_str) {
+            None | Some("seq") => Ok(false),
+            Some("par" | "par-opt") => Ok(true),
+            Some(other) => anyhow::bail!("Invalid mode: {}. Use 'seq' or 'par'", other),
+        }
+    }
+
+    /// `threads` (a count or `auto`) resolved for `workload`; defaults to [`Config::global`]'s.
+    pub fn threads(&self, workload: Workload) -> Result<usize> {
+        Ok(self.get::<Threads>("threads")?.map_or(Config::global().threads, |t| t.resolve(workload)))
+    }
+}
+
+/// Per-node result of a registered algorithm.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Output {
+    /// Hop distance per node, -1 if unreachable.
+    Distances(Vec<i32>),
+    /// Component or community label per node.
+    Labels(Vec<usize>),
+    /// Score per node (ranks, centralities).
+    Scores(Vec<f64>),
+}
+
+impl Output {
+    /// Relative difference up to which two scores count as equal.
+    const SCORE_TOLERANCE: f64 = 1e-4;
+
+    pub fn len(&self) -> usize {
+        match self {
+            Output::Distances(d) => d.len(),
+            Output::Labels(l) => l.len(),
+            Output::Scores(s) => s.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether two runs agree: equal distances, the same partition (label values may differ) or
+    /// scores within a relative 1e-4.
+    pub fn matches(&self, other: &Output) -> bool {
+        match (self, other) {
+            (Output::Distances(a), Output::Distances(b)) => a == b,
+            (Output::Labels(a), Output::Labels(b)) => a.len() == b.len() && first_seen(a) == first_seen(b),
+            (Output::Scores(a), Output::Scores(b)) => {
+                a.len() == b.len()
+                    && a.iter().zip(b).all(|(x, y)| (x - y).abs() <= Self::SCORE_TOLERANCE * y.abs().max(1.0))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Labels renumbered in order of first appearance, so equal partitions compare equal.
+fn first_seen(labels: &[usize]) -> Vec<usize> {
+    let mut ids = std::collections::HashMap::new();
+    labels.iter().map(|&l| { let next = ids.len(); *ids.entry(l).or_insert(next) }).collect()
+}
+
+/// An algorithm that `tool run` and `benchmark` can call by name.
+pub trait Algorithm: Send + Sync {
+    fn name(&self) -> &'static str;
+    /// One-line description for `tool algorithms`.
+    fn about(&self) -> &'static str;
+    /// Parameters [`Algorithm::run`] reads; [`Registry::run`] rejects any other.
+    fn params(&self) -> &'static [&'static str];
+    /// True if `mode=par` runs a parallel implementation, so benchmarks sweep thread counts.
+    fn parallel(&self) -> bool;
+    fn run(&self, graph: &Graph, params: &Params) -> Result<Output>;
+}
+
+/// Algorithms by name. [`Registry::builtin`] holds the ones shipped with the crate; downstream code
+/// adds its own with [`Registry::register`].
+#[derive(Clone, Default)]
+pub struct Registry {
+    algorithms: Vec<Arc<dyn Algorithm>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// BFS, WCC, SCC, PageRank, closeness, betweenness, triangles and DAG descendant counts.
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        for algorithm in BUILTIN {
+            registry.register(algorithm.clone());
+        }
+        registry
+    }
+
+    /// Adds `algorithm`, replacing any registered under the same name.
+    pub fn register(&mut self, algorithm: impl Algorithm + 'static) {
+        self.algorithms.retain(|a| a.name() != algorithm.name());
+        self.algorithms.push(Arc::new(algorithm));
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Algorithm>> {
+        self.algorithms.iter().find(|a| a.name() == name).cloned()
+    }
+
+    /// Registered algorithms in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Algorithm> {
+        self.algorithms.iter().map(|a| a.as_ref())
+    }
+
+    /// `name`, after checking that it exists and accepts every key in `params`.
+    pub fn lookup(&self, name: &str, params: &Params) -> Result<Arc<dyn Algorithm>> {
+        let Some(algorithm) = self.get(name) else {
+            let names: Vec<_> = self.iter().map(|a| a.name()).collect();
+            anyhow::bail!("Unknown algorithm: {}. Registered: {}", name, names.join(", "));
+        };
+        if let Some(key) = params.keys().find(|k| !algorithm.params().contains(k)) {
+            anyhow::bail!("{} has no parameter '{}'; it takes: {}", name, key, algorithm.params().join(", "));
+        }
+        Ok(algorithm)
+    }
+
+    /// Runs `name` with `params`, checked as in [`Registry::lookup`].
+    pub fn run(&self, name: &str, graph: &Graph, params: &Params) -> Result<Output> {
+        let algorithm = self.lookup(name, params)?;
+        algorithm.run(graph, params).with_context(|| format!("{} failed", name))
+    }
+}
+
+/// Built-in entry of [`Registry::builtin`].
+#[derive(Clone)]
+struct Builtin {
+    name: &'static str,
+    about: &'static str,
+    params: &'static [&'static str],
+    parallel: bool,
+    run: fn(&Graph, &Params) -> Result<Output>,
+}
+
+impl Algorithm for Builtin {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn about(&self) -> &'static str {
+        self.about
+    }
+
+    fn params(&self) -> &'static [&'static str] {
+        self.params
+    }
+
+    fn parallel(&self) -> bool {
+        self.parallel
+    }
+
+    fn run(&self, graph: &Graph, params: &Params) -> Result<Output> {
+        (self.run)(graph, params)
+    }
+}
+
+const BUILTIN: &[Builtin] = &[
+    Builtin {
+        name: "bfs",
+        about: "Hop distances from `source` (default 0)",
+        params: &["source", "mode", "threads"],
+        parallel: true,
+        run: run_bfs,
+    },
+    Builtin {
+        name: "wcc",
+        about: "Weakly connected component label per node",
+        params: &["mode", "threads"],
+        parallel: true,
+        run: run_wcc,
+    },
+    Builtin {
+        name: "scc",
+        about: "Strongly connected component label per node",
+        params: &[],
+        parallel: false,
+        run: run_scc,
+    },
+    Builtin {
+        name: "pagerank",
+        about: "PageRank (`alpha`, `iters`, `eps`, `weighted`)",
+        params: &["alpha", "iters", "eps", "weighted", "mode", "threads"],
+        parallel: true,
+        run: run_pagerank,
+    },
+    Builtin {
+        name: "closeness",
+        about: "Harmonic closeness from `samples` BFS targets (default 256)",
+        params: &["samples", "threads"],
+        parallel: false,
+        run: run_closeness,
+    },
+    Builtin {
+        name: "betweenness",
+        about: "Betweenness from `samples` BFS sources (default 256)",
+        params: &["samples", "threads"],
+        parallel: false,
+        run: run_betweenness,
+    },
+    Builtin {
+        name: "triangles",
+        about: "Triangles through each node, ignoring edge direction (degree-ordered)",
+        params: &["mode", "threads"],
+        parallel: true,
+        run: run_triangles,
+    },
+    Builtin {
+        name: "descendants",
+        about: "Nodes reachable from each node of a DAG (`method` auto/exact/blocked/sampled, `samples`, `seed`)",
+        params: &["method", "samples", "seed", "mode", "threads"],
+        parallel: true,
+        run: run_descendants,
+    },
+];
+
+/// Default sample count of the centrality built-ins.
+const DEFAULT_SAMPLES: usize = 256;
+
+fn run_bfs(graph: &Graph, params: &Params) -> Result<Output> {
+    let source = params.get_or("source", 0)?;
+    let result = if params.parallel()? {
+        bfs_parallel(graph, source, params.threads(Workload::Bfs)?)?
+    } else {
+        bfs_sequential(graph, source)?
+    };
+    Ok(Output::Distances(result.distances))
+}
+
+fn run_wcc(graph: &Graph, params: &Params) -> Result<Output> {
+    let result = if params.parallel()? {
+        wcc_parallel(graph, params.threads(Workload::Wcc)?)
+    } else {
+        wcc_sequential(graph)
+    };
+    Ok(Output::Labels(result.labels))
+}
+
+fn run_scc(graph: &Graph, _params: &Params) -> Result<Output> {
+    Ok(Output::Labels(strongly_connected_components(graph).component))
+}
+
+fn run_pagerank(graph: &Graph, params: &Params) -> Result<Output> {
+    let defaults = PageRankConfig::default();
+    let config = PageRankConfig {
+        alpha: params.get_or("alpha", defaults.alpha)?,
+        max_iterations: params.get_or("iters", defaults.max_iterations)?,
+        tolerance: params.get_or("eps", defaults.tolerance)?,
+        weighted: params.get_or("weighted", false)?,
+        ..defaults
+    };
+    let result = if params.parallel()? {
+        pagerank_parallel(graph, &config, params.threads(Workload::PageRank)?)
+    } else {
+        pagerank_sequential(graph, &config)
+    };
+    Ok(Output::Scores(result.ranks))
+}
+
+fn run_closeness(graph: &Graph, params: &Params) -> Result<Output> {
+    let samples = params.get_or("samples", DEFAULT_SAMPLES)?;
+    Ok(Output::Scores(approximate_closeness(graph, samples, params.threads(Workload::Other)?)))
+}
+
+fn run_betweenness(graph: &Graph, params: &Params) -> Result<Output> {
+    let samples = params.get_or("samples", DEFAULT_SAMPLES)?;
+    Ok(Output::Scores(approximate_betweenness(graph, samples, params.threads(Workload::Other)?)))
+}
+
+fn run_triangles(graph: &Graph, params: &Params) -> Result<Output> {
+    let result = if params.parallel()? {
+        count_triangles_parallel(graph, params.threads(Workload::Other)?)
+    } else {
+        count_triangles_ordered(graph)
+    };
+    Ok(Output::Scores(result.per_node.into_iter().map(|t| t as f64).collect()))
+}
+
+fn run_descendants(graph: &Graph, params: &Params) -> Result<Output> {
+    let defaults = DescendantConfig::default();
+    let config = DescendantConfig {
+        method: params.get::<String>("method")?.as_deref().unwrap_or("auto").parse()?,
+        samples: params.get_or("samples", defaults.samples)?,
+        seed: params.get_or("seed", defaults.seed)?,
+        num_threads: if params.parallel()? { params.threads(Workload::Other)? } else { 1 },
+        ..defaults
+    };
+    let result = count_descendants(graph, &config)?;
+    Ok(Output::Scores(result.counts.into_iter().map(|c| c as f64).collect()))
+}
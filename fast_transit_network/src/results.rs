@@ -0,0 +1,50 @@
+use crate::cache::Cached;
+use crate::error::{Error, Result};
+
+const RESULT_MAGIC: &[u8; 8] = b"FTNRES01";
+
+/// Encodes a result (`BfsResult`, `WccResult` or `PageRankResult`) as a self-describing binary file
+/// body: a magic number, the result kind, then the little-endian [`Cached`] encoding.
+///
+/// Downstream tools load it with [`from_bytes`] or [`read_result`] without parsing text: a 100M-node
+/// rank vector is one 800 MB read.
+pub fn to_bytes<T: Cached>(value: &T) -> Vec<u8> {
+    let payload = value.encode();
+    let mut bytes = Vec::with_capacity(RESULT_MAGIC.len() + 8 + T::KIND.len() + payload.len());
+    bytes.extend(RESULT_MAGIC);
+    bytes.extend((T::KIND.len() as u64).to_le_bytes());
+    bytes.extend(T::KIND.as_bytes());
+    bytes.extend(payload);
+    bytes
+}
+
+/// Inverse of [`to_bytes`]; fails if the bytes are not a result file or hold another kind of result.
+pub fn from_bytes<T: Cached>(bytes: &[u8]) -> Result<T> {
+    let kind = result_kind(bytes)?;
+    if kind != T::KIND {
+        return Err(Error::parse(1, format!("file holds a {} result, not {}", kind, T::KIND)));
+    }
+    let payload = &bytes[RESULT_MAGIC.len() + 8 + kind.len()..];
+    T::decode(payload).ok_or_else(|| Error::parse(1, format!("truncated or corrupt {} result", kind)))
+}
+
+/// Kind of result (`"bfs"`, `"wcc"`, `"pagerank"`) stored in a result file body.
+pub fn result_kind(bytes: &[u8]) -> Result<&str> {
+    let not_a_result = || Error::parse(1, "not a result file");
+    let rest = bytes.strip_prefix(RESULT_MAGIC.as_slice()).ok_or_else(not_a_result)?;
+    let (len, rest) = rest.split_first_chunk::<8>().ok_or_else(not_a_result)?;
+    let len = usize::try_from(u64::from_le_bytes(*len)).map_err(|_| not_a_result())?;
+    let kind = rest.get(..len).ok_or_else(not_a_result)?;
+    std::str::from_utf8(kind).map_err(|_| not_a_result())
+}
+
+/// Writes a result in the binary format of [`to_bytes`].
+pub fn write_result<T: Cached>(value: &T, path: &str) -> Result<()> {
+    std::fs::write(path, to_bytes(value))?;
+    Ok(())
+}
+
+/// Reads a result written by [`write_result`] (or `--output-format binary`).
+pub fn read_result<T: Cached>(path: &str) -> Result<T> {
+    from_bytes(&std::fs::read(path)?)
+}
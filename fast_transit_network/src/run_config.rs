@@ -0,0 +1,166 @@
+use crate::cli::Cli;
+use anyhow::{Context, Result};
+use clap::{CommandFactory, Parser};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// A run file: a list of CLI steps with shared defaults, so an analysis or benchmark campaign is one
+/// reviewable file instead of a shell script full of flags.
+///
+/// ```toml
+/// output_dir = "results/run1"       # relative `out` paths are placed here
+///
+/// [global]                          # global flags, as on the command line
+/// bfs_min_nodes = 0
+///
+/// [defaults]                        # used by every step that has the flag and does not set it
+/// input = "scripts/data/medium/random_100k.txt"
+/// threads = 8
+///
+/// [[run]]
+/// command = "pagerank"
+/// mode = "par"
+/// alpha = 0.85
+/// out = "pagerank.txt"
+///
+/// [[run]]
+/// command = "benchmark"
+/// threads = [1, 2, 4, 8]
+/// algorithms = ["bfs", "wcc"]
+/// out = "benchmark.csv"
+/// ```
+///
+/// Keys are the long flag names (`output_format` or `output-format`), passed in sorted order, or the
+/// names of positional arguments (`old`, `new` for `diff`). `true` passes a switch, `false` leaves it
+/// out and arrays become comma-separated lists. Input paths are relative to the working directory.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RunConfig {
+    #[serde(default)]
+    pub output_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub global: Table,
+    #[serde(default)]
+    pub defaults: Table,
+    #[serde(default, rename = "run")]
+    pub steps: Vec<Table>,
+}
+
+/// One step of a [`RunConfig`], parsed exactly as the equivalent command line.
+pub struct RunStep {
+    /// Arguments after the program name.
+    pub args: Vec<String>,
+    pub cli: Cli,
+}
+
+impl RunStep {
+    /// Equivalent `tool ...` invocation, for logs and for re-running a single step by hand.
+    pub fn command_line(&self) -> String {
+        std::iter::once("tool".to_string())
+            .chain(self.args.iter().map(|a| if a.contains(' ') { format!("'{}'", a) } else { a.clone() }))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl RunConfig {
+    pub fn from_toml(text: &str) -> Result<Self> {
+        Ok(toml::from_str(text)?)
+    }
+
+    pub fn load(path: &str) -> Result<Self> {
+        let text = std::fs::read_to_string(path).with_context(|| format!("failed to read run file {}", path))?;
+        Self::from_toml(&text).with_context(|| format!("invalid run file {}", path))
+    }
+
+    /// Command-line arguments of every step, without the program name.
+    pub fn step_args(&self) -> Result<Vec<Vec<String>>> {
+        let global = to_flags(&self.global, None)?;
+        let root = Cli::command();
+        self.steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| {
+                let mut step = step.clone();
+                let name = match step.remove("command") {
+                    Some(Value::String(name)) => name,
+                    _ => anyhow::bail!("step {} needs `command = \"<subcommand>\"`", index + 1),
+                };
+                let Some(subcommand) = root.find_subcommand(&name) else {
+                    anyhow::bail!("step {}: unknown command `{}`", index + 1, name);
+                };
+                let accepts = |key: &str| {
+                    let flag = key.replace('_', "-");
+                    subcommand.get_arguments().any(|a| a.get_long() == Some(flag.as_str()))
+                };
+                let set: Vec<String> = step.keys().map(|k| k.replace('_', "-")).collect();
+                for (key, value) in &self.defaults {
+                    if accepts(key) && !set.contains(&key.replace('_', "-")) {
+                        step.insert(key.clone(), value.clone());
+                    }
+                }
+                let mut args = global.clone();
+                args.push(name);
+                for positional in subcommand.get_positionals() {
+                    if let Some(value) = step.remove(positional.get_id().as_str()) {
+                        args.push(scalar(&value)?);
+                    }
+                }
+                args.extend(to_flags(&step, self.output_dir.as_deref())?);
+                Ok(args)
+            })
+            .collect()
+    }
+
+    /// Parses every step up front, so a typo in the last step fails before the first one runs.
+    pub fn steps(&self) -> Result<Vec<RunStep>> {
+        if self.global.contains_key("config") {
+            anyhow::bail!("run files cannot include other run files");
+        }
+        self.step_args()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, args)| {
+                let cli = Cli::try_parse_from(std::iter::once("tool".to_string()).chain(args.iter().cloned()))
+                    .map_err(|e| anyhow::anyhow!("step {}: {}", index + 1, e.render()))?;
+                Ok(RunStep { args, cli })
+            })
+            .collect()
+    }
+}
+
+/// `--flag value` pairs for a table; relative `out` paths are joined onto `output_dir`.
+fn to_flags(table: &Table, output_dir: Option<&Path>) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    for (key, value) in table {
+        let flag = format!("--{}", key.replace('_', "-"));
+        let mut text = match value {
+            Value::Boolean(true) => {
+                args.push(flag);
+                continue;
+            }
+            Value::Boolean(false) => continue,
+            Value::Array(items) => items.iter().map(scalar).collect::<Result<Vec<_>>>()?.join(","),
+            other => scalar(other)?,
+        };
+        if key == "out"
+            && let Some(dir) = output_dir
+            && Path::new(&text).is_relative()
+        {
+            text = dir.join(&text).to_string_lossy().into_owned();
+        }
+        args.push(flag);
+        args.push(text);
+    }
+    Ok(args)
+}
+
+fn scalar(value: &Value) -> Result<String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Integer(i) => Ok(i.to_string()),
+        Value::Float(f) => Ok(f.to_string()),
+        other => anyhow::bail!("unsupported value `{}`: use a string, number, boolean or list", other),
+    }
+}
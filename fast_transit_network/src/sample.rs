@@ -0,0 +1,211 @@
+use crate::graph::graph::{build_csr, Graph};
+use crate::utils::rng::Rng;
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+/// How [`sample`] picks the nodes of the subgraph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleMethod {
+    /// Nodes chosen uniformly at random; keeps the degree distribution but thins out edges.
+    Node,
+    /// Endpoints of edges chosen uniformly at random, so hubs are over-represented like in the full graph.
+    Edge,
+    /// Nodes visited by a random walk with restarts; keeps local structure and connectivity.
+    RandomWalk,
+    /// Forest fire: burns a geometric number of out-neighbors per node from random seeds;
+    /// keeps community structure and densification.
+    ForestFire,
+}
+
+impl FromStr for SampleMethod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "node" => Ok(SampleMethod::Node),
+            "edge" => Ok(SampleMethod::Edge),
+            "walk" | "random-walk" => Ok(SampleMethod::RandomWalk),
+            "ff" | "forest-fire" => Ok(SampleMethod::ForestFire),
+            _ => Err(anyhow::anyhow!("Invalid sample method: {}. Use 'node', 'edge', 'walk' or 'forest-fire'", s)),
+        }
+    }
+}
+
+/// Parameters for [`sample`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleConfig {
+    pub method: SampleMethod,
+    /// Nodes in the sample (capped at the graph size).
+    pub nodes: usize,
+    pub seed: u64,
+    /// Chance that a random walk jumps back to its start at each step.
+    pub restart_probability: f64,
+    /// Forest-fire forward burning probability; each burned node ignites on average `p / (1 - p)` neighbors.
+    pub burn_probability: f64,
+}
+
+impl Default for SampleConfig {
+    fn default() -> Self {
+        Self {
+            method: SampleMethod::ForestFire,
+            nodes: 1000,
+            seed: 42,
+            restart_probability: 0.15,
+            burn_probability: 0.7,
+        }
+    }
+}
+
+/// Subgraph induced by the sampled nodes, renumbered to `0..nodes`.
+#[derive(Debug, Clone)]
+pub struct Sample {
+    pub graph: Graph,
+    /// Original id of every sample node.
+    pub original_ids: Vec<usize>,
+}
+
+/// Samples `config.nodes` nodes with `config.method` and returns the subgraph they induce.
+///
+/// Weights and edge/node attributes are kept for the surviving edges and nodes. The same seed
+/// always gives the same sample.
+#[tracing::instrument(level = "debug", skip(graph))]
+pub fn sample(graph: &Graph, config: &SampleConfig) -> Sample {
+    let target = config.nodes.min(graph.num_nodes);
+    let mut rng = Rng::new(config.seed);
+    let mut picked = Picked::new(graph.num_nodes, target);
+
+    match config.method {
+        SampleMethod::Node => {
+            while !picked.full() {
+                picked.add(rng.below(graph.num_nodes));
+            }
+        }
+        SampleMethod::Edge => {
+            // Graphs with few edges cannot fill the sample from endpoints alone; top up with nodes
+            let mut attempts = 0;
+            while !picked.full() && attempts < 20 * target && graph.num_edges > 0 {
+                let e = rng.below(graph.num_edges);
+                let u = graph.offsets.partition_point(|&o| o <= e) - 1;
+                picked.add(u);
+                picked.add(graph.neighbors[e]);
+                attempts += 1;
+            }
+            while !picked.full() {
+                picked.add(rng.below(graph.num_nodes));
+            }
+        }
+        SampleMethod::RandomWalk => {
+            let mut start = rng.below(graph.num_nodes.max(1));
+            let mut v = start;
+            let mut stale = 0;
+            while !picked.full() {
+                stale = if picked.add(v) { 0 } else { stale + 1 };
+                let neighbors = graph.neighbors(v);
+                // Stuck in a small or dangling region: move the walk somewhere new
+                if neighbors.is_empty() || stale > 100 {
+                    start = rng.below(graph.num_nodes);
+                    v = start;
+                    stale = 0;
+                } else if rng.next_f64() < config.restart_probability {
+                    v = start;
+                } else {
+                    v = neighbors[rng.below(neighbors.len())];
+                }
+            }
+        }
+        SampleMethod::ForestFire => {
+            let p = config.burn_probability.clamp(0.0, 0.99);
+            let mut queue = VecDeque::new();
+            let mut candidates = Vec::new();
+            while !picked.full() {
+                let seed = rng.below(graph.num_nodes);
+                if !picked.add(seed) {
+                    continue;
+                }
+                queue.push_back(seed);
+                while let Some(u) = queue.pop_front() {
+                    // Geometric number of neighbors to burn, mean p / (1 - p)
+                    let mut burn = 0;
+                    while rng.next_f64() < p {
+                        burn += 1;
+                    }
+                    candidates.clear();
+                    candidates.extend(graph.neighbors(u).iter().copied().filter(|&v| !picked.contains(v)));
+                    for i in 0..burn.min(candidates.len()) {
+                        let j = i + rng.below(candidates.len() - i);
+                        candidates.swap(i, j);
+                        if picked.full() {
+                            break;
+                        }
+                        if picked.add(candidates[i]) {
+                            queue.push_back(candidates[i]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut original_ids = picked.nodes;
+    original_ids.sort_unstable();
+    Sample { graph: induced_subgraph(graph, &original_ids), original_ids }
+}
+
+/// Sampled node set with O(1) membership.
+struct Picked {
+    nodes: Vec<usize>,
+    member: Vec<bool>,
+    target: usize,
+}
+
+impl Picked {
+    fn new(n: usize, target: usize) -> Self {
+        Self { nodes: Vec::with_capacity(target), member: vec![false; n], target }
+    }
+
+    fn full(&self) -> bool {
+        self.nodes.len() >= self.target
+    }
+
+    fn contains(&self, v: usize) -> bool {
+        self.member[v]
+    }
+
+    /// Adds `v` unless it is already picked or the sample is full; returns true if added.
+    fn add(&mut self, v: usize) -> bool {
+        if self.member[v] || self.full() {
+            return false;
+        }
+        self.member[v] = true;
+        self.nodes.push(v);
+        true
+    }
+}
+
+/// Subgraph on `nodes` (renumbered by position) with every edge between them, keeping weights
+/// and edge/node attributes.
+pub fn induced_subgraph(graph: &Graph, nodes: &[usize]) -> Graph {
+    let mut new_id = vec![usize::MAX; graph.num_nodes];
+    for (i, &v) in nodes.iter().enumerate() {
+        new_id[v] = i;
+    }
+    // Sources are visited in new-id order, so the kept edges are already in CSR order
+    let mut kept = Vec::new();
+    let mut edges = Vec::new();
+    for (i, &u) in nodes.iter().enumerate() {
+        for e in graph.edge_range(u) {
+            let v = new_id[graph.neighbors[e]];
+            if v != usize::MAX {
+                kept.push(e);
+                edges.push((i, v));
+            }
+        }
+    }
+
+    let mut sub = build_csr(nodes.len(), edges);
+    sub.weights = graph.weights.as_ref().map(|w| kept.iter().map(|&e| w[e]).collect());
+    sub.edge_attrs = graph.edge_attrs.gather(&kept);
+    sub.node_attrs = graph.node_attrs.gather(nodes);
+    sub.directed = graph.directed;
+    sub
+}
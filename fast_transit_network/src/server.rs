@@ -0,0 +1,202 @@
+use crate::algorithms::bfs::shortest_path;
+use crate::algorithms::pagerank::{pagerank_parallel, PageRankConfig};
+use crate::algorithms::wcc::{wcc_parallel, WccResult};
+use crate::config::Config;
+use crate::error::Error;
+use crate::graph::graph::Graph;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, OnceLock};
+
+/// Graph plus lazily computed whole-graph results shared by all requests.
+///
+/// PageRank and WCC run once, on the first request that needs them.
+pub struct ServerState {
+    pub graph: Graph,
+    pagerank: OnceLock<Vec<f64>>,
+    components: OnceLock<WccResult>,
+}
+
+impl ServerState {
+    pub fn new(graph: Graph) -> Self {
+        Self {
+            graph,
+            pagerank: OnceLock::new(),
+            components: OnceLock::new(),
+        }
+    }
+
+    fn pagerank(&self) -> &[f64] {
+        self.pagerank.get_or_init(|| {
+            let threads = Config::global().threads;
+            pagerank_parallel(&self.graph, &PageRankConfig::default(), threads).ranks
+        })
+    }
+
+    fn components(&self) -> &WccResult {
+        self.components
+            .get_or_init(|| wcc_parallel(&self.graph, Config::global().threads))
+    }
+
+    /// Node of input-file id `id`, which differs from the dense id if the loader remapped the ids.
+    fn node(&self, id: usize) -> Result<usize, ApiError> {
+        self.graph
+            .node_for_id(id)
+            .ok_or_else(|| Error::InvalidNode { node: id, num_nodes: self.graph.num_nodes }.into())
+    }
+}
+
+type SharedState = Arc<ServerState>;
+
+/// Error body: `{"error": "..."}` with 404 for unknown nodes and 500 otherwise.
+pub struct ApiError(Error);
+
+impl From<Error> for ApiError {
+    fn from(e: Error) -> Self {
+        ApiError(e)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match self.0 {
+            Error::InvalidNode { .. } => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, Json(serde_json::json!({ "error": self.0.to_string() }))).into_response()
+    }
+}
+
+#[derive(Deserialize)]
+struct RouteQuery {
+    from: usize,
+    to: usize,
+}
+
+#[derive(Serialize)]
+struct HealthResponse {
+    nodes: usize,
+    edges: usize,
+}
+
+#[derive(Serialize)]
+struct DistanceResponse {
+    from: usize,
+    to: usize,
+    /// Hop count; `null` if unreachable.
+    distance: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct PathResponse {
+    from: usize,
+    to: usize,
+    /// Nodes from `from` to `to`; `null` if unreachable.
+    path: Option<Vec<usize>>,
+}
+
+#[derive(Serialize)]
+struct RankResponse {
+    node: usize,
+    rank: f64,
+}
+
+#[derive(Serialize)]
+struct ComponentResponse {
+    node: usize,
+    component: usize,
+    component_size: usize,
+}
+
+async fn health(State(state): State<SharedState>) -> Json<HealthResponse> {
+    Json(HealthResponse {
+        nodes: state.graph.num_nodes,
+        edges: state.graph.num_edges,
+    })
+}
+
+/// Runs a BFS off the async executor so long traversals don't stall other requests. Takes and
+/// returns input-file ids.
+async fn route(state: SharedState, from: usize, to: usize) -> Result<Option<Vec<usize>>, ApiError> {
+    let (from, to) = (state.node(from)?, state.node(to)?);
+    tokio::task::spawn_blocking(move || {
+        let path = shortest_path(&state.graph, from, to)?;
+        Ok(path.map(|path| path.into_iter().map(|v| state.graph.original_id(v)).collect()))
+    })
+    .await
+    .map_err(|e| ApiError(Error::Io(std::io::Error::other(e))))?
+    .map_err(ApiError)
+}
+
+async fn distance(
+    State(state): State<SharedState>,
+    Query(q): Query<RouteQuery>,
+) -> Result<Json<DistanceResponse>, ApiError> {
+    let path = route(state, q.from, q.to).await?;
+    Ok(Json(DistanceResponse {
+        from: q.from,
+        to: q.to,
+        distance: path.map(|p| p.len() - 1),
+    }))
+}
+
+async fn path(
+    State(state): State<SharedState>,
+    Query(q): Query<RouteQuery>,
+) -> Result<Json<PathResponse>, ApiError> {
+    let path = route(state, q.from, q.to).await?;
+    Ok(Json(PathResponse { from: q.from, to: q.to, path }))
+}
+
+async fn pagerank(
+    State(state): State<SharedState>,
+    Path(node): Path<usize>,
+) -> Result<Json<RankResponse>, ApiError> {
+    let v = state.node(node)?;
+    let rank = tokio::task::spawn_blocking(move || state.pagerank()[v])
+        .await
+        .map_err(|e| ApiError(Error::Io(std::io::Error::other(e))))?;
+    Ok(Json(RankResponse { node, rank }))
+}
+
+async fn component(
+    State(state): State<SharedState>,
+    Path(node): Path<usize>,
+) -> Result<Json<ComponentResponse>, ApiError> {
+    let v = state.node(node)?;
+    let (component, component_size) = tokio::task::spawn_blocking(move || {
+        let wcc = state.components();
+        let label = wcc.labels[v];
+        // Labels are the component's smallest node, reported like every other node by its file id
+        (state.graph.original_id(label), wcc.stats.component_sizes.get(&label).copied().unwrap_or(0))
+    })
+    .await
+    .map_err(|e| ApiError(Error::Io(std::io::Error::other(e))))?;
+    Ok(Json(ComponentResponse { node, component, component_size }))
+}
+
+/// REST routes:
+/// `GET /health`, `GET /distance?from=&to=`, `GET /path?from=&to=`,
+/// `GET /pagerank/{node}`, `GET /component/{node}`. Node ids are input-file ids in requests and
+/// responses alike.
+pub fn router(state: SharedState) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/distance", get(distance))
+        .route("/path", get(path))
+        .route("/pagerank/{node}", get(pagerank))
+        .route("/component/{node}", get(component))
+        .with_state(state)
+}
+
+/// Serves `graph` on `addr` until the process is stopped.
+pub async fn serve(graph: Graph, addr: &str) -> anyhow::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("Listening on http://{}", listener.local_addr()?);
+    axum::serve(listener, router(Arc::new(ServerState::new(graph)))).await?;
+    Ok(())
+}
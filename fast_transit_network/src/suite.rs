@@ -0,0 +1,169 @@
+use crate::cli::{BenchmarkArgs, Cli, Commands};
+use anyhow::{Context, Result};
+use clap::Parser;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// A benchmark suite: the graphs to benchmark, each with its BFS source, algorithms and thread
+/// counts, measured into one CSV by `tool suite` (or the `fast_transit_network` binary).
+///
+/// ```toml
+/// out = "results/nightly.csv"            # default: <suite>_results.csv next to the suite file
+/// algorithms = ["bfs", "wcc", "pagerank"]
+/// threads = [1, 2, 4, 8]                 # or ["auto"]
+/// repeat = 3
+///
+/// [[graph]]
+/// path = "data/small"                    # a file, a directory or a glob such as "data/*/random_*.txt"
+///
+/// [[graph]]
+/// path = "data/medium/grid_100k.txt"
+/// source = 500                           # BFS source in file ids (default: the first node)
+/// reorder = "rcm"
+/// algorithms = ["bfs"]                   # overrides the suite-wide list for this graph
+/// ```
+///
+/// The same keys work in a `.json` file. Relative paths (`path`, `out`, `baseline`) are relative to
+/// the suite file, so a suite can be moved together with its data.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Suite {
+    #[serde(default)]
+    pub out: Option<PathBuf>,
+    #[serde(default)]
+    pub algorithms: Option<Vec<String>>,
+    #[serde(default)]
+    pub threads: Option<Vec<ThreadSpec>>,
+    #[serde(default)]
+    pub repeat: Option<usize>,
+    #[serde(default)]
+    pub warmup: Option<usize>,
+    /// Previous CSV to compare against, as for `benchmark --baseline`.
+    #[serde(default)]
+    pub baseline: Option<PathBuf>,
+    #[serde(default)]
+    pub regression_threshold: Option<f64>,
+    /// `benchmark --graph-jobs` for every graph entry.
+    #[serde(default)]
+    pub graph_jobs: Option<usize>,
+    #[serde(default)]
+    pub force_parallel: bool,
+    #[serde(rename = "graph")]
+    pub graphs: Vec<SuiteGraph>,
+    /// Directory of the suite file, which relative paths are resolved against.
+    #[serde(skip)]
+    pub base_dir: PathBuf,
+}
+
+/// One `[[graph]]` entry of a [`Suite`]; unset lists fall back to the suite-wide ones.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SuiteGraph {
+    pub path: String,
+    #[serde(default)]
+    pub source: Option<usize>,
+    #[serde(default)]
+    pub reorder: Option<String>,
+    #[serde(default)]
+    pub algorithms: Option<Vec<String>>,
+    #[serde(default)]
+    pub threads: Option<Vec<ThreadSpec>>,
+}
+
+/// A thread count or `"auto"`, as in `benchmark --threads`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ThreadSpec {
+    Count(usize),
+    Name(String),
+}
+
+impl std::fmt::Display for ThreadSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThreadSpec::Count(n) => write!(f, "{}", n),
+            ThreadSpec::Name(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl Suite {
+    pub fn from_toml(text: &str) -> Result<Self> {
+        Ok(toml::from_str(text)?)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    /// Reads a suite file: JSON if it ends in `.json`, TOML otherwise.
+    pub fn load(path: &str) -> Result<Self> {
+        let text = std::fs::read_to_string(path).with_context(|| format!("failed to read suite file {}", path))?;
+        let is_json = Path::new(path).extension().is_some_and(|e| e.eq_ignore_ascii_case("json"));
+        let suite = if is_json { Self::from_json(&text) } else { Self::from_toml(&text) };
+        let mut suite = suite.with_context(|| format!("invalid suite file {}", path))?;
+        suite.base_dir = Path::new(path).parent().map(Path::to_path_buf).unwrap_or_default();
+        if suite.out.is_none() {
+            let stem = Path::new(path).file_stem().and_then(|s| s.to_str()).unwrap_or("suite");
+            suite.out = Some(PathBuf::from(format!("{}_results.csv", stem)));
+        }
+        Ok(suite)
+    }
+
+    /// `path` resolved against the suite file's directory.
+    pub fn resolve(&self, path: &Path) -> String {
+        self.base_dir.join(path).to_string_lossy().into_owned()
+    }
+
+    /// Where the consolidated CSV goes (`benchmark_results.csv` next to the suite if `out` is unset
+    /// and the suite was not loaded from a file).
+    pub fn out_path(&self) -> String {
+        self.resolve(self.out.as_deref().unwrap_or(Path::new("benchmark_results.csv")))
+    }
+
+    pub fn baseline_path(&self) -> Option<String> {
+        self.baseline.as_deref().map(|path| self.resolve(path))
+    }
+
+    pub fn regression_threshold(&self) -> f64 {
+        self.regression_threshold.unwrap_or(0.1)
+    }
+
+    /// `benchmark` arguments of every graph entry, parsed exactly as the equivalent command line so
+    /// unset options get the command's defaults and a bad entry fails before anything runs.
+    pub fn benchmark_args(&self) -> Result<Vec<BenchmarkArgs>> {
+        if self.graphs.is_empty() {
+            anyhow::bail!("a suite needs at least one [[graph]] entry");
+        }
+        let join = |items: &[String]| items.join(",");
+        self.graphs
+            .iter()
+            .enumerate()
+            .map(|(index, graph)| {
+                let mut args = vec!["tool".to_string(), "benchmark".to_string(), "--graphs".to_string(), self.resolve(Path::new(&graph.path))];
+                let mut flag = |name: &str, value: Option<String>| {
+                    if let Some(value) = value {
+                        args.push(format!("--{}", name));
+                        args.push(value);
+                    }
+                };
+                flag("algorithms", graph.algorithms.as_deref().or(self.algorithms.as_deref()).map(join));
+                let threads = graph.threads.as_deref().or(self.threads.as_deref());
+                flag("threads", threads.map(|list| list.iter().map(ThreadSpec::to_string).collect::<Vec<_>>().join(",")));
+                flag("source", graph.source.map(|s| s.to_string()));
+                flag("reorder", graph.reorder.clone());
+                flag("repeat", self.repeat.map(|n| n.to_string()));
+                flag("warmup", self.warmup.map(|n| n.to_string()));
+                flag("graph-jobs", self.graph_jobs.map(|n| n.to_string()));
+                if self.force_parallel {
+                    args.push("--force-parallel".to_string());
+                }
+                let cli = Cli::try_parse_from(&args).map_err(|e| anyhow::anyhow!("graph {} ({}): {}", index + 1, graph.path, e.render()))?;
+                match cli.command {
+                    Some(Commands::Benchmark(args)) => Ok(args),
+                    _ => unreachable!("the arguments name the benchmark command"),
+                }
+            })
+            .collect()
+    }
+}
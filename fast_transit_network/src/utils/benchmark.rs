@@ -1,47 +1,656 @@
+use std::fmt;
 use std::fs::File;
 use std::io::{BufWriter, Write};
+use std::str::FromStr;
+use std::time::Instant;
 use anyhow::Result;
+use serde::Serialize;
+use crate::algorithms::bfs::{bfs_parallel_with_policy, bfs_sequential, BfsResult};
+use crate::algorithms::pagerank::{
+    pagerank_parallel_optimized, pagerank_parallel_with_policy, pagerank_sequential, PageRankConfig, PageRankResult,
+};
+use crate::algorithms::triangles::{count_triangles, count_triangles_ordered, count_triangles_parallel, TriangleCount};
+use crate::algorithms::wcc::{wcc_label_propagation, wcc_parallel_with_policy, wcc_sequential, WccResult};
+use crate::config::ParallelismPolicy;
+use crate::graph::graph::Graph;
+use crate::graph::testing;
+use crate::registry::{Output, Params, Registry};
+use crate::utils::memory;
 
-#[derive(Debug, Clone)]
+/// Algorithm selectable with `benchmark --algorithms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchmarkAlgorithm {
+    Bfs,
+    Wcc,
+    PageRank,
+    Triangles,
+    /// Any other algorithm of [`Registry::builtin`], run through the registry.
+    Registered(&'static str),
+}
+
+impl BenchmarkAlgorithm {
+    pub const ALL: [BenchmarkAlgorithm; 4] = [Self::Bfs, Self::Wcc, Self::PageRank, Self::Triangles];
+
+    /// Name used in reports and the CSV `algorithm` column.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Bfs => "BFS",
+            Self::Wcc => "WCC",
+            Self::PageRank => "PageRank",
+            Self::Triangles => "Triangles",
+            Self::Registered(name) => name,
+        }
+    }
+
+    /// Parses a comma-separated list such as `bfs,pagerank`, keeping the given order.
+    pub fn parse_list(list: &str) -> Result<Vec<Self>> {
+        let mut algorithms = Vec::new();
+        for name in list.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let algorithm = name.parse()?;
+            if !algorithms.contains(&algorithm) {
+                algorithms.push(algorithm);
+            }
+        }
+        if algorithms.is_empty() {
+            anyhow::bail!("No benchmark algorithms selected");
+        }
+        Ok(algorithms)
+    }
+
+    /// Registered implementations, the sequential reference first. Every variant listed here is run
+    /// by `benchmark` and gets its own `mode` rows in the CSV, so a new implementation only needs an
+    /// entry in this list. Registered algorithms get `seq` and, if they have one, `par`.
+    pub fn variants(self) -> Vec<Box<dyn BenchmarkVariant>> {
+        match self {
+            Self::Bfs => vec![
+                variant("seq", false, |input, _| Ok(VariantOutput::Bfs(bfs_sequential(input.graph, input.bfs_source)?))),
+                variant("par", true, |input, threads| {
+                    Ok(VariantOutput::Bfs(bfs_parallel_with_policy(input.graph, input.bfs_source, threads, input.policy)?))
+                }),
+            ],
+            Self::Wcc => vec![
+                variant("seq", false, |input, _| Ok(VariantOutput::Wcc(wcc_sequential(input.graph)))),
+                variant("par", true, |input, threads| {
+                    Ok(VariantOutput::Wcc(wcc_parallel_with_policy(input.graph, threads, input.policy)))
+                }),
+                variant("seq-lp", false, |input, _| Ok(VariantOutput::Wcc(wcc_label_propagation(input.graph, 1)))),
+                variant("par-lp", true, |input, threads| Ok(VariantOutput::Wcc(wcc_label_propagation(input.graph, threads)))),
+            ],
+            Self::PageRank => vec![
+                variant("seq", false, |input, _| Ok(VariantOutput::PageRank(pagerank_sequential(input.graph, &input.pagerank)))),
+                variant("par", true, |input, threads| {
+                    Ok(VariantOutput::PageRank(pagerank_parallel_with_policy(input.graph, &input.pagerank, threads, input.policy)))
+                }),
+                variant("par-opt", true, |input, threads| {
+                    Ok(VariantOutput::PageRank(pagerank_parallel_optimized(input.graph, &input.pagerank, threads)))
+                }),
+            ],
+            // The unordered count is the reference, so `seq-ordered` shows what the degree ordering buys
+            Self::Triangles => vec![
+                variant("seq", false, |input, _| Ok(VariantOutput::Triangles(count_triangles(input.graph)))),
+                variant("seq-ordered", false, |input, _| Ok(VariantOutput::Triangles(count_triangles_ordered(input.graph)))),
+                variant("par", true, |input, threads| Ok(VariantOutput::Triangles(count_triangles_parallel(input.graph, threads)))),
+            ],
+            Self::Registered(name) => {
+                let registry = Registry::builtin();
+                let parallel = registry.get(name).is_some_and(|a| a.parallel());
+                let mut variants = vec![{
+                    let registry = registry.clone();
+                    variant("seq", false, move |input, _| {
+                        Ok(VariantOutput::Registered(registry.run(name, input.graph, &Params::new())?))
+                    })
+                }];
+                if parallel {
+                    variants.push(variant("par", true, move |input, threads| {
+                        let params = Params::new().set("mode", "par").set("threads", threads);
+                        Ok(VariantOutput::Registered(registry.run(name, input.graph, &params)?))
+                    }));
+                }
+                variants
+            }
+        }
+    }
+}
+
+/// Inputs shared by every variant in one benchmark run.
+pub struct BenchmarkInput<'a> {
+    pub graph: &'a Graph,
+    pub bfs_source: usize,
+    pub policy: ParallelismPolicy,
+    pub pagerank: PageRankConfig,
+}
+
+/// Result of one variant, checked against the reference variant of the same algorithm.
+pub enum VariantOutput {
+    Bfs(BfsResult),
+    Wcc(WccResult),
+    PageRank(PageRankResult),
+    Triangles(TriangleCount),
+    Registered(Output),
+}
+
+impl VariantOutput {
+    /// Largest per-node rank difference still counted as the same PageRank result.
+    const RANK_TOLERANCE: f64 = 1e-4;
+
+    /// Same distances, same number of components, ranks within [`Self::RANK_TOLERANCE`] or the same
+    /// triangle counts.
+    pub fn matches(&self, reference: &VariantOutput) -> bool {
+        match (self, reference) {
+            (Self::Bfs(a), Self::Bfs(b)) => a.distances == b.distances,
+            (Self::Wcc(a), Self::Wcc(b)) => a.stats.num_components == b.stats.num_components,
+            (Self::PageRank(a), Self::PageRank(b)) => {
+                a.ranks.len() == b.ranks.len()
+                    && a.ranks.iter().zip(&b.ranks).all(|(x, y)| (x - y).abs() < Self::RANK_TOLERANCE)
+            }
+            (Self::Triangles(a), Self::Triangles(b)) => a == b,
+            (Self::Registered(a), Self::Registered(b)) => a.matches(b),
+            _ => false,
+        }
+    }
+
+    /// Short description for the console report, e.g. ` | 3 components`.
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Wcc(result) => format!(" | {} components", result.stats.num_components),
+            Self::Triangles(result) => format!(" | {} triangles", result.total),
+            Self::Bfs(_) | Self::PageRank(_) | Self::Registered(_) => String::new(),
+        }
+    }
+}
+
+/// One implementation of a benchmarked algorithm, see [`BenchmarkAlgorithm::variants`].
+pub trait BenchmarkVariant {
+    /// Value of the CSV `mode` column.
+    fn mode(&self) -> &'static str;
+    /// Parallel variants run once per `--threads` entry, sequential ones once with 1 thread.
+    fn is_parallel(&self) -> bool;
+    fn run(&self, input: &BenchmarkInput, threads: usize) -> Result<VariantOutput>;
+}
+
+struct FnVariant<F> {
+    mode: &'static str,
+    parallel: bool,
+    run: F,
+}
+
+impl<F> BenchmarkVariant for FnVariant<F>
+where
+    F: Fn(&BenchmarkInput, usize) -> Result<VariantOutput>,
+{
+    fn mode(&self) -> &'static str {
+        self.mode
+    }
+
+    fn is_parallel(&self) -> bool {
+        self.parallel
+    }
+
+    fn run(&self, input: &BenchmarkInput, threads: usize) -> Result<VariantOutput> {
+        (self.run)(input, threads)
+    }
+}
+
+fn variant<F>(mode: &'static str, parallel: bool, run: F) -> Box<dyn BenchmarkVariant>
+where
+    F: Fn(&BenchmarkInput, usize) -> Result<VariantOutput> + 'static,
+{
+    Box::new(FnVariant { mode, parallel, run })
+}
+
+impl FromStr for BenchmarkAlgorithm {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "bfs" => Ok(Self::Bfs),
+            "wcc" => Ok(Self::Wcc),
+            "pagerank" | "pr" => Ok(Self::PageRank),
+            "triangles" | "tc" => Ok(Self::Triangles),
+            name => match Registry::builtin().get(name) {
+                Some(algorithm) => Ok(Self::Registered(algorithm.name())),
+                None => Err(anyhow::anyhow!(
+                    "Invalid algorithm: {}. Use 'bfs', 'wcc', 'pagerank', 'triangles' or a name listed by `algorithms`",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+/// Wall-clock statistics over the measured repetitions of one run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimingStats {
+    pub runs: usize,
+    pub min_ms: f64,
+    pub mean_ms: f64,
+    /// Sample standard deviation; 0 for a single run.
+    pub stddev_ms: f64,
+    /// Largest peak memory over the timed runs (see [`memory::peak_since_reset`]); 0 if unknown.
+    pub peak_memory_mb: f64,
+}
+
+impl TimingStats {
+    pub fn from_samples(samples_ms: &[f64]) -> Self {
+        let runs = samples_ms.len();
+        if runs == 0 {
+            return Self { runs: 0, min_ms: 0.0, mean_ms: 0.0, stddev_ms: 0.0, peak_memory_mb: 0.0 };
+        }
+        let mean_ms = samples_ms.iter().sum::<f64>() / runs as f64;
+        let min_ms = samples_ms.iter().copied().fold(f64::INFINITY, f64::min);
+        let stddev_ms = if runs > 1 {
+            let var = samples_ms.iter().map(|t| (t - mean_ms).powi(2)).sum::<f64>() / (runs - 1) as f64;
+            var.sqrt()
+        } else {
+            0.0
+        };
+        Self { runs, min_ms, mean_ms, stddev_ms, peak_memory_mb: 0.0 }
+    }
+}
+
+impl fmt::Display for TimingStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.runs > 1 {
+            write!(
+                f,
+                "{:.3}ms (min {:.3}ms, stddev {:.3}ms, {} runs)",
+                self.mean_ms, self.min_ms, self.stddev_ms, self.runs
+            )?;
+        } else {
+            write!(f, "{:.3}ms", self.mean_ms)?;
+        }
+        if self.peak_memory_mb > 0.0 {
+            write!(f, " | peak {:.1}MB", self.peak_memory_mb)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs `f` `warmup` times untimed, then `repeat` (at least one) timed times.
+/// Returns the output of the last timed run together with its timings.
+pub fn measure<T>(warmup: usize, repeat: usize, mut f: impl FnMut() -> T) -> (T, TimingStats) {
+    for _ in 0..warmup {
+        std::hint::black_box(f());
+    }
+    let mut samples = Vec::with_capacity(repeat.max(1));
+    let mut output = None;
+    let mut peak_bytes = 0;
+    for _ in 0..repeat.max(1) {
+        drop(output.take());
+        memory::reset_peak();
+        let start = Instant::now();
+        let value = f();
+        samples.push(start.elapsed().as_secs_f64() * 1000.0);
+        peak_bytes = peak_bytes.max(memory::peak_since_reset());
+        output = Some(value);
+    }
+    let stats = TimingStats {
+        peak_memory_mb: memory::bytes_to_mb(peak_bytes),
+        ..TimingStats::from_samples(&samples)
+    };
+    (output.expect("at least one timed run"), stats)
+}
+
+/// Seed of every [`SyntheticGraph`], so the same graph is measured on every run and machine.
+pub const SYNTHETIC_SEED: u64 = 0x5EED_F7A1;
+
+/// Fixed synthetic graphs for the kernel micro-benchmarks in `benches/`: built in memory by
+/// [`crate::graph::testing`] from [`SYNTHETIC_SEED`], so a timing change comes from the code and not
+/// from the input files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntheticGraph {
+    /// `side × side` undirected lattice: about `2 · side` BFS levels with narrow frontiers.
+    Grid { side: usize },
+    /// `nodes · degree` uniformly random directed edges.
+    Uniform { nodes: usize, degree: usize },
+    /// Undirected preferential attachment, `degree` links per new node: a few hubs hold most edges,
+    /// the skewed case for chunking and frontier splitting.
+    PowerLaw { nodes: usize, degree: usize },
+}
+
+impl SyntheticGraph {
+    /// Short label for benchmark ids, e.g. `grid_300` or `power_law_100000x8`.
+    pub fn name(self) -> String {
+        match self {
+            Self::Grid { side } => format!("grid_{}", side),
+            Self::Uniform { nodes, degree } => format!("uniform_{}x{}", nodes, degree),
+            Self::PowerLaw { nodes, degree } => format!("power_law_{}x{}", nodes, degree),
+        }
+    }
+
+    pub fn build(self) -> Graph {
+        match self {
+            Self::Grid { side } => testing::grid(side),
+            Self::Uniform { nodes, degree } => testing::random(nodes, nodes * degree, SYNTHETIC_SEED),
+            Self::PowerLaw { nodes, degree } => testing::power_law(nodes, degree, SYNTHETIC_SEED),
+        }
+    }
+}
+
+/// Extensions of the graph files [`expand_graph_paths`] picks from a directory, also with a `.gz`
+/// or `.zst` suffix.
+const GRAPH_EXTENSIONS: &[&str] = &["txt", "el", "edges", "mtx", "graph", "metis", "bin"];
+
+/// Graph files named by `pattern`, sorted by path: the graph files (by [`GRAPH_EXTENSIONS`]) directly
+/// inside a directory, the files matching a glob with `*` and `?` in its file name (e.g.
+/// `scripts/data/small/*.txt`), or a single file as given. A directory or glob that matches nothing
+/// is an error, so a typo does not produce an empty benchmark.
+pub fn expand_graph_paths(pattern: &str) -> Result<Vec<String>> {
+    let path = std::path::Path::new(pattern);
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let is_glob = file_name.contains(['*', '?']);
+    if !is_glob && !path.is_dir() {
+        return Ok(vec![pattern.to_string()]);
+    }
+    let dir = match path.parent() {
+        Some(parent) if is_glob && !parent.as_os_str().is_empty() => parent,
+        _ if is_glob => std::path::Path::new("."),
+        _ => path,
+    };
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(|e| anyhow::anyhow!("cannot read {}: {}", dir.display(), e))? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else { continue };
+        let selected = if is_glob { glob_match(file_name, name) } else { !name.starts_with('.') && has_graph_extension(name) };
+        if selected && entry.file_type()?.is_file() {
+            paths.push(dir.join(name).to_string_lossy().into_owned());
+        }
+    }
+    if paths.is_empty() {
+        anyhow::bail!("no graph files found for {}", pattern);
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+fn has_graph_extension(name: &str) -> bool {
+    let name = name.strip_suffix(".gz").or_else(|| name.strip_suffix(".zst")).unwrap_or(name);
+    std::path::Path::new(name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| GRAPH_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+/// Whether `name` matches `pattern`, where `*` is any run of characters and `?` any one character.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let (pattern, name): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+    let (mut p, mut n) = (0, 0);
+    // Position after the last `*` and the name position it currently stands for
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p + 1, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((after, matched)) => {
+                    star = Some((after, matched + 1));
+                    p = after;
+                    n = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Version of the JSON report layout (see `scripts/benchmark_report.schema.json`).
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Machine and build the benchmark ran on.
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentInfo {
+    pub crate_version: &'static str,
+    /// Commit the binary was built from (`git rev-parse --short=12 HEAD` at build time).
+    pub git_commit: Option<&'static str>,
+    /// `rustc --version` of the compiler that built the binary.
+    pub rustc_version: Option<&'static str>,
+    pub hostname: Option<String>,
+    pub cpu_model: Option<String>,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub available_parallelism: usize,
+    /// `release` or `debug`.
+    pub profile: &'static str,
+    /// Seconds since the Unix epoch when the report was created.
+    pub timestamp: u64,
+}
+
+impl EnvironmentInfo {
+    pub fn capture() -> Self {
+        Self {
+            crate_version: env!("CARGO_PKG_VERSION"),
+            git_commit: Some(env!("FTN_GIT_COMMIT")).filter(|s| !s.is_empty()),
+            rustc_version: Some(env!("FTN_RUSTC_VERSION")).filter(|s| !s.is_empty()),
+            hostname: hostname(),
+            cpu_model: cpu_model(),
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            available_parallelism: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            profile: if cfg!(debug_assertions) { "debug" } else { "release" },
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        }
+    }
+
+    /// `key: value` pairs in report order, with `unknown` for what could not be found. Written as
+    /// `#` comment lines above the CSV header.
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        let known = |value: Option<&str>| value.unwrap_or("unknown").to_string();
+        vec![
+            ("crate_version", self.crate_version.to_string()),
+            ("git_commit", known(self.git_commit)),
+            ("rustc_version", known(self.rustc_version)),
+            ("hostname", known(self.hostname.as_deref())),
+            ("cpu_model", known(self.cpu_model.as_deref())),
+            ("os", self.os.to_string()),
+            ("arch", self.arch.to_string()),
+            ("available_parallelism", self.available_parallelism.to_string()),
+            ("profile", self.profile.to_string()),
+            ("timestamp", self.timestamp.to_string()),
+        ]
+    }
+}
+
+fn hostname() -> Option<String> {
+    std::fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .or_else(|| command_output("hostname", &[]))
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// CPU brand string: `model name` of `/proc/cpuinfo` on Linux, `machdep.cpu.brand_string` on macOS.
+fn cpu_model() -> Option<String> {
+    let from_cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok().and_then(|info| {
+        info.lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(key, _)| matches!(key.trim(), "model name" | "Model" | "cpu model"))
+            .map(|(_, value)| value.trim().to_string())
+    });
+    from_cpuinfo
+        .or_else(|| command_output("sysctl", &["-n", "machdep.cpu.brand_string"]))
+        .or_else(|| std::env::var("PROCESSOR_IDENTIFIER").ok())
+        .filter(|model| !model.is_empty())
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program).args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Graph a set of results was measured on.
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphMetadata {
+    pub name: String,
+    /// Counts are `u64` so reports from 32-bit hosts and graphs with more than `u32::MAX` edges agree.
+    pub nodes: u64,
+    pub edges: u64,
+    /// Approximate CSR size in bytes.
+    pub memory_bytes: u64,
+    /// Reordering applied after loading, if any.
+    pub reorder: Option<String>,
+}
+
+#[derive(Serialize)]
+struct BenchmarkReport<'a> {
+    schema_version: u32,
+    environment: EnvironmentInfo,
+    graphs: &'a [GraphMetadata],
+    results: &'a [BenchmarkResult],
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct BenchmarkResult {
     pub algorithm: String,
     pub graph_name: String,
-    pub graph_nodes: usize,
-    pub graph_edges: usize,
+    pub graph_nodes: u64,
+    pub graph_edges: u64,
     pub mode: String,
     pub threads: usize,
+    /// Mean over the timed repetitions.
     pub time_ms: f64,
+    pub min_ms: f64,
+    pub stddev_ms: f64,
+    pub runs: usize,
+    pub peak_memory_mb: f64,
     pub speedup: f64,
     pub correct: bool,
 }
 
+#[derive(Default)]
 pub struct BenchmarkLogger {
+    graphs: Vec<GraphMetadata>,
     results: Vec<BenchmarkResult>,
 }
 
 impl BenchmarkLogger {
     pub fn new() -> Self {
         Self {
+            graphs: Vec::new(),
             results: Vec::new(),
         }
     }
     
+    pub fn add_graph(&mut self, graph: GraphMetadata) {
+        self.graphs.push(graph);
+    }
+    
     pub fn add_result(&mut self, result: BenchmarkResult) {
         self.results.push(result);
     }
     
+    /// Writes the structured report: schema version, environment, graphs and every result.
+    pub fn save_to_json(&self, output_path: &str) -> Result<()> {
+        let report = BenchmarkReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            environment: EnvironmentInfo::capture(),
+            graphs: &self.graphs,
+            results: &self.results,
+        };
+        let mut writer = BufWriter::new(File::create(output_path)?);
+        serde_json::to_writer_pretty(&mut writer, &report)?;
+        writeln!(writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+    
+    pub fn results(&self) -> &[BenchmarkResult] {
+        &self.results
+    }
+    
+    /// Reads a CSV written by [`BenchmarkLogger::save_to_csv`]. Columns are matched by header name,
+    /// so files from older versions without the repetition or memory columns still load.
+    pub fn load_csv(path: &str) -> Result<Vec<BenchmarkResult>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read baseline '{}': {}", path, e))?;
+        let mut lines = content.lines().filter(|l| !l.trim().is_empty() && !l.starts_with('#'));
+        let header: Vec<&str> = lines
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Baseline '{}' is empty", path))?
+            .split(',')
+            .collect();
+        let column = |name: &str| header.iter().position(|&h| h == name);
+        let required = |name: &str| {
+            column(name).ok_or_else(|| anyhow::anyhow!("Baseline '{}' has no '{}' column", path, name))
+        };
+        let (algorithm, graph_name, mode, threads, time_ms) = (
+            required("algorithm")?,
+            required("graph_name")?,
+            required("mode")?,
+            required("threads")?,
+            required("time_ms")?,
+        );
+        
+        let mut results = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let fields: Vec<&str> = line.split(',').collect();
+            let field = |idx: Option<usize>| idx.and_then(|idx| fields.get(idx).copied());
+            let number = |idx: Option<usize>| field(idx).and_then(|f| f.parse::<f64>().ok());
+            let count = |idx: Option<usize>| field(idx).and_then(|f| f.parse::<usize>().ok());
+            let count64 = |idx: Option<usize>| field(idx).and_then(|f| f.parse::<u64>().ok());
+            let bad_row = || anyhow::anyhow!("Baseline '{}': malformed row {}", path, i + 2);
+            
+            let time_ms = number(Some(time_ms)).ok_or_else(bad_row)?;
+            results.push(BenchmarkResult {
+                algorithm: field(Some(algorithm)).ok_or_else(bad_row)?.to_string(),
+                graph_name: field(Some(graph_name)).ok_or_else(bad_row)?.to_string(),
+                graph_nodes: count64(column("nodes")).unwrap_or(0),
+                graph_edges: count64(column("edges")).unwrap_or(0),
+                mode: field(Some(mode)).ok_or_else(bad_row)?.to_string(),
+                threads: count(Some(threads)).ok_or_else(bad_row)?,
+                time_ms,
+                min_ms: number(column("min_ms")).unwrap_or(time_ms),
+                stddev_ms: number(column("stddev_ms")).unwrap_or(0.0),
+                runs: count(column("runs")).unwrap_or(1),
+                peak_memory_mb: number(column("peak_memory_mb")).unwrap_or(0.0),
+                speedup: number(column("speedup")).unwrap_or(0.0),
+                correct: field(column("correct")).is_none_or(|f| f == "true"),
+            });
+        }
+        Ok(results)
+    }
+    
+    /// `# key: value` environment lines of a CSV written by [`Self::save_to_csv`]; empty for CSVs
+    /// written before they were recorded.
+    pub fn load_csv_environment(path: &str) -> Result<Vec<(String, String)>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read baseline '{}': {}", path, e))?;
+        Ok(content
+            .lines()
+            .map_while(|line| line.strip_prefix("# "))
+            .filter_map(|line| line.split_once(": "))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect())
+    }
+    
     pub fn save_to_csv(&self, output_path: &str) -> Result<()> {
         let file = File::create(output_path)?;
         let mut writer = BufWriter::new(file);
         
+        // Where and with what the results were measured, as comments `load_csv` skips
+        for (key, value) in EnvironmentInfo::capture().fields() {
+            writeln!(writer, "# {}: {}", key, value)?;
+        }
+        
         // Header
-        writeln!(writer, "algorithm,graph_name,nodes,edges,mode,threads,time_ms,speedup,correct")?;
+        writeln!(writer, "algorithm,graph_name,nodes,edges,mode,threads,time_ms,speedup,correct,min_ms,stddev_ms,runs,peak_memory_mb")?;
         
         // Data
         for result in &self.results {
             writeln!(
                 writer,
-                "{},{},{},{},{},{},{:.6},{:.4},{}",
+                "{},{},{},{},{},{},{:.6},{:.4},{},{:.6},{:.6},{},{:.3}",
                 result.algorithm,
                 result.graph_name,
                 result.graph_nodes,
@@ -50,7 +659,11 @@ impl BenchmarkLogger {
                 result.threads,
                 result.time_ms,
                 result.speedup,
-                result.correct
+                result.correct,
+                result.min_ms,
+                result.stddev_ms,
+                result.runs,
+                result.peak_memory_mb
             )?;
         }
         
@@ -62,7 +675,13 @@ impl BenchmarkLogger {
         println!("BENCHMARK SUMMARY");
         println!("{}", "=".repeat(70));
         
-        for algo in ["BFS", "WCC", "PageRank"] {
+        let mut algorithms: Vec<&str> = Vec::new();
+        for result in &self.results {
+            if !algorithms.contains(&result.algorithm.as_str()) {
+                algorithms.push(&result.algorithm);
+            }
+        }
+        for algo in algorithms {
             let algo_results: Vec<_> = self.results.iter()
                 .filter(|r| r.algorithm == algo)
                 .collect();
@@ -83,13 +702,86 @@ impl BenchmarkLogger {
                     println!("    Sequential: {:.2}ms", seq.time_ms);
                     
                     let max_speedup = graph_results.iter()
-                        .filter(|r| r.mode == "par")
+                        .filter(|r| r.mode != "seq")
                         .map(|r| r.speedup)
-                        .fold(0.0f64, f64::max);
+                        .reduce(f64::max);
                     
-                    println!("    Max Speedup: {:.2}x", max_speedup);
+                    if let Some(max_speedup) = max_speedup {
+                        println!("    Max Speedup: {:.2}x", max_speedup);
+                    }
                 }
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Timing of one (algorithm, graph, mode, threads) run against a baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BaselineComparison {
+    pub algorithm: String,
+    pub graph_name: String,
+    pub mode: String,
+    pub threads: usize,
+    pub baseline_ms: f64,
+    pub current_ms: f64,
+    /// Relative change, e.g. 0.25 = 25% slower than the baseline.
+    pub change: f64,
+    pub regression: bool,
+}
+
+/// Pairs `current` results with `baseline` ones on (algorithm, graph, mode, threads) and flags
+/// runs more than `threshold` (relative) slower. Runs missing from either side are skipped.
+pub fn compare_to_baseline(
+    baseline: &[BenchmarkResult],
+    current: &[BenchmarkResult],
+    threshold: f64,
+) -> Vec<BaselineComparison> {
+    current
+        .iter()
+        .filter_map(|cur| {
+            let base = baseline.iter().find(|b| {
+                b.algorithm == cur.algorithm
+                    && b.graph_name == cur.graph_name
+                    && b.mode == cur.mode
+                    && b.threads == cur.threads
+            })?;
+            let change = if base.time_ms > 0.0 {
+                (cur.time_ms - base.time_ms) / base.time_ms
+            } else {
+                0.0
+            };
+            Some(BaselineComparison {
+                algorithm: cur.algorithm.clone(),
+                graph_name: cur.graph_name.clone(),
+                mode: cur.mode.clone(),
+                threads: cur.threads,
+                baseline_ms: base.time_ms,
+                current_ms: cur.time_ms,
+                change,
+                regression: change > threshold,
+            })
+        })
+        .collect()
+}
+
+pub fn print_baseline_comparison(comparisons: &[BaselineComparison], threshold: f64) {
+    println!("\n{}", "=".repeat(70));
+    println!("BASELINE COMPARISON (threshold {:.1}%)", threshold * 100.0);
+    println!("{}", "=".repeat(70));
+    for c in comparisons {
+        println!(
+            "  {:<9} {:<20} {:<3} {:>3}t: {:>10.3}ms -> {:>10.3}ms ({:+.1}%){}",
+            c.algorithm,
+            c.graph_name,
+            c.mode,
+            c.threads,
+            c.baseline_ms,
+            c.current_ms,
+            c.change * 100.0,
+            if c.regression { "  REGRESSION" } else { "" }
+        );
+    }
+    if comparisons.is_empty() {
+        println!("  No runs in common with the baseline.");
+    }
+}
@@ -1,29 +1,871 @@
+use std::fmt::Display;
 use std::fs::File;
 use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
 use anyhow::Result;
+use serde::Serialize;
+use crate::algorithms::anf::NeighborhoodFunction;
+use crate::algorithms::bfs::{BfsLevelStats, BfsResult};
+use crate::algorithms::bridges::BridgeResult;
+use crate::algorithms::centrality::CentralityReport;
+use crate::algorithms::isochrone::Isochrone;
+use crate::algorithms::pagerank::PageRankResult;
+use crate::algorithms::robustness::RobustnessCurve;
+use crate::algorithms::sssp::SsspResult;
+use crate::algorithms::wcc::{dense_component_ids, wcc_stats, WccResult};
+use crate::config::{Compression, Config};
+#[cfg(feature = "parallel")]
+use crate::algorithms::threadpool::get_thread_pool;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use crate::graph::diff::GraphDiff;
+use crate::graph::validate::ValidationReport;
+use crate::graph::attributes::Categorical;
+use crate::graph::graph::Graph;
+use crate::registry::Output;
+use crate::results::write_result;
+use crate::utils::stats::{BinScale, Histogram};
+use crate::utils::summary::{summarize, Summary};
 
-/// Writes BFS results (node, distance) to a file, one pair per line.
-pub fn write_bfs_result(dist: &[i32], output_path: &str) -> Result<()> {
-    let file = File::create(output_path)?;
-    let mut writer = BufWriter::new(file);
-    
-    for (node, &distance) in dist.iter().enumerate() {
-        writeln!(writer, "{} {}", node, distance)?;
+/// Format of per-node result files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Space-separated `node value` lines (the original format).
+    #[default]
+    Text,
+    /// One JSON document with run metadata and the full per-node array.
+    Json,
+    /// One JSON object per node and line, for streaming consumers.
+    Ndjson,
+    /// Two-column Parquet file (`node`, value); requires the `parquet` feature.
+    Parquet,
+    /// Top-K, bottom-K and quantiles of the per-node values instead of all of them (see
+    /// [`write_summary`]; sized by `--summary-top-k` and `--summary-quantiles`).
+    Summary,
+    /// The whole BFS, WCC or PageRank result in the binary format of [`crate::results`], for Rust
+    /// tools that reload it with [`crate::results::read_result`].
+    Binary,
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" | "txt" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" | "jsonl" => Ok(OutputFormat::Ndjson),
+            "parquet" => Ok(OutputFormat::Parquet),
+            "summary" => Ok(OutputFormat::Summary),
+            "binary" | "bin" => Ok(OutputFormat::Binary),
+            _ => Err(anyhow::anyhow!("Invalid output format: {}. Use 'text', 'json', 'ndjson', 'parquet', 'summary' or 'binary'", s)),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// Where a per-node result in this format asked for at `output_path` ends up: see
+    /// [`node_output_path`]. JSON documents and summaries are never sharded, and Parquet and binary
+    /// files are neither sharded nor compressed, so they can be read back directly.
+    pub fn output_path(self, path: &str) -> String {
+        match self {
+            OutputFormat::Text | OutputFormat::Ndjson => node_output_path(path),
+            OutputFormat::Json | OutputFormat::Summary => output_path(path),
+            OutputFormat::Parquet | OutputFormat::Binary => path.to_string(),
+        }
+    }
+}
+
+/// Path of an auxiliary text file next to `output_path`: `out.txt` + `_stats` gives `out_stats.txt`
+/// (and `out.txt.gz` gives `out_stats.txt.gz`).
+pub fn derived_path(output_path: &str, suffix: &str) -> String {
+    derived_path_with_extension(output_path, suffix, "txt")
+}
+
+/// [`derived_path`] with another extension, e.g. `csv`.
+pub fn derived_path_with_extension(output_path: &str, suffix: &str, extension: &str) -> String {
+    let (base, compressed) = split_compression(output_path);
+    let path = Path::new(base);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or(base);
+    path.with_file_name(format!("{}{}.{}{}", stem, suffix, extension, compressed))
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Path of shard `index` of a per-node result split by `--shards`: `out.txt` gives
+/// `out.part-000.txt` (and `out.txt.gz` gives `out.part-000.txt.gz`).
+pub fn shard_path(output_path: &str, index: usize) -> String {
+    let (base, compressed) = split_compression(output_path);
+    let path = Path::new(base);
+    let name = match (path.file_stem().and_then(|s| s.to_str()), path.extension().and_then(|s| s.to_str())) {
+        (Some(stem), Some(ext)) => format!("{}.part-{:03}.{}{}", stem, index, ext, compressed),
+        _ => format!("{}.part-{:03}{}", path.file_name().and_then(|s| s.to_str()).unwrap_or(base), index, compressed),
+    };
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+/// `path` without a `.gz`/`.zst` extension, and that extension (empty if there is none).
+fn split_compression(path: &str) -> (&str, &str) {
+    match Compression::from_path(path).and_then(Compression::extension) {
+        Some(ext) => path.split_at(path.len() - ext.len()),
+        None => (path, ""),
+    }
+}
+
+/// Path a result file asked for at `output_path` is written to: unchanged if it already ends in
+/// `.gz` or `.zst`, otherwise with the extension of the configured [`Compression`] (`--compress`)
+/// appended.
+pub fn output_path(output_path: &str) -> String {
+    match Config::global().compress.extension() {
+        Some(ext) if Compression::from_path(output_path).is_none() => format!("{}{}", output_path, ext),
+        _ => output_path.to_string(),
+    }
+}
+
+/// Where a per-node result asked for at `path` ends up: its [`output_path`], or the first and last of
+/// the shard files when `--shards` splits it.
+pub fn node_output_path(path: &str) -> String {
+    match Config::global().shards {
+        0 | 1 => output_path(path),
+        shards => format!(
+            "{} .. {} ({} shards)",
+            output_path(&shard_path(path, 0)),
+            output_path(&shard_path(path, shards - 1)),
+            shards
+        ),
+    }
+}
+
+/// Writes the `header` line (if any) and `line(writer, node)` for every node in `0..num_nodes` to
+/// `output_path`, or with `shards > 1` to that many files of consecutive node ranges (see
+/// [`shard_path`]), each starting with the header. Shards are written in parallel, so the
+/// formatting and compression of a large result are no longer bound to one thread.
+fn write_node_lines(
+    output_path: &str,
+    header: Option<&str>,
+    num_nodes: usize,
+    shards: usize,
+    line: impl Fn(&mut OutputWriter, usize) -> Result<()> + Sync,
+) -> Result<()> {
+    let write = |path: &str, nodes: std::ops::Range<usize>| {
+        let mut writer = create_output(path)?;
+        if let Some(header) = header {
+            writeln!(writer, "{}", header)?;
+        }
+        for node in nodes {
+            line(&mut writer, node)?;
+        }
+        writer.finish()
+    };
+    if shards <= 1 {
+        return write(output_path, 0..num_nodes);
+    }
+    let write_shard = |i: usize| write(&shard_path(output_path, i), i * num_nodes / shards..(i + 1) * num_nodes / shards);
+    #[cfg(feature = "parallel")]
+    {
+        get_thread_pool(Config::global().threads.min(shards)).install(|| (0..shards).into_par_iter().try_for_each(write_shard))
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        (0..shards).try_for_each(write_shard)
+    }
+}
+
+/// Creates the result file for `output_path` (see [`output_path`]), compressing it while writing if
+/// its name ends in `.gz` or `.zst`. Call [`OutputWriter::finish`] to see errors from the last write.
+pub fn create_output(path: &str) -> Result<OutputWriter> {
+    let path = output_path(path);
+    let file = File::create(&path)?;
+    let sink = match Compression::from_path(&path) {
+        None => Sink::Plain(file),
+        #[cfg(feature = "compression")]
+        Some(Compression::Gz) => Sink::Gzip(flate2::write::GzEncoder::new(file, flate2::Compression::default())),
+        #[cfg(feature = "compression")]
+        Some(_) => Sink::Zstd(zstd::Encoder::new(file, 0)?),
+        #[cfg(not(feature = "compression"))]
+        Some(_) => {
+            drop(file);
+            let _ = std::fs::remove_file(&path);
+            anyhow::bail!("writing {} requires building with `--features compression`", path)
+        }
+    };
+    Ok(OutputWriter { inner: Some(BufWriter::new(sink)) })
+}
+
+/// Buffered writer of one result file, compressed as it is written; see [`create_output`].
+pub struct OutputWriter {
+    inner: Option<BufWriter<Sink>>,
+}
+
+enum Sink {
+    Plain(File),
+    #[cfg(feature = "compression")]
+    Gzip(flate2::write::GzEncoder<File>),
+    #[cfg(feature = "compression")]
+    Zstd(zstd::Encoder<'static, File>),
+}
+
+impl Sink {
+    fn finish(self) -> std::io::Result<()> {
+        match self {
+            Sink::Plain(mut file) => file.flush(),
+            #[cfg(feature = "compression")]
+            Sink::Gzip(encoder) => encoder.finish().map(drop),
+            #[cfg(feature = "compression")]
+            Sink::Zstd(encoder) => encoder.finish().map(drop),
+        }
+    }
+}
+
+impl Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Sink::Plain(file) => file.write(buf),
+            #[cfg(feature = "compression")]
+            Sink::Gzip(encoder) => encoder.write(buf),
+            #[cfg(feature = "compression")]
+            Sink::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Sink::Plain(file) => file.flush(),
+            #[cfg(feature = "compression")]
+            Sink::Gzip(encoder) => encoder.flush(),
+            #[cfg(feature = "compression")]
+            Sink::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+impl OutputWriter {
+    /// Flushes the buffer and writes the end of the compressed stream.
+    pub fn finish(mut self) -> Result<()> {
+        if let Some(writer) = self.inner.take() {
+            writer.into_inner().map_err(|e| e.into_error())?.finish()?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for OutputWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.as_mut().expect("writer used after finish").write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.as_mut().expect("writer used after finish").flush()
+    }
+}
+
+impl Drop for OutputWriter {
+    /// Completes the stream of a writer dropped without [`OutputWriter::finish`], ignoring errors.
+    fn drop(&mut self) {
+        if let Some(writer) = self.inner.take()
+            && let Ok(sink) = writer.into_inner()
+        {
+            let _ = sink.finish();
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct BfsReport<'a> {
+    algorithm: &'static str,
+    source: usize,
+    reachable: usize,
+    levels: usize,
+    elapsed_ms: f64,
+    distances: &'a [i32],
+}
+
+#[derive(Serialize)]
+struct SsspReport<'a> {
+    algorithm: &'static str,
+    source: usize,
+    reachable: usize,
+    /// Unreachable nodes serialize as `null`.
+    distances: &'a [f64],
+    predecessors: Vec<i64>,
+}
+
+#[derive(Serialize)]
+struct SsspRow {
+    node: usize,
+    distance: Option<f64>,
+    predecessor: i64,
+}
+
+/// Predecessors with -1 for the source and unreachable nodes (`usize::MAX` in [`SsspResult`]).
+fn sssp_predecessors(result: &SsspResult) -> impl Iterator<Item = i64> + '_ {
+    (0..result.predecessors.len()).map(|node| sssp_predecessor(result, node))
+}
+
+fn sssp_predecessor(result: &SsspResult, node: usize) -> i64 {
+    match result.predecessors[node] {
+        usize::MAX => -1,
+        p => p as i64,
+    }
+}
+
+#[derive(Serialize)]
+struct WccReport<'a> {
+    algorithm: &'static str,
+    num_components: usize,
+    largest_component: usize,
+    smallest_component: usize,
+    components: &'a [usize],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    component_sizes: Option<Vec<usize>>,
+}
+
+/// Optional columns of WCC label files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WccColumns {
+    /// Component ids `0..k`, largest component first (see [`dense_component_ids`]), instead of each
+    /// component's root node id.
+    pub dense_ids: bool,
+    /// The size of the node's component after its id.
+    pub sizes: bool,
+}
+
+impl WccColumns {
+    /// Labels to write and, if [`WccColumns::sizes`], the component size of every node.
+    fn apply(&self, components: &[usize]) -> (Vec<usize>, Option<Vec<usize>>) {
+        if self.dense_ids {
+            let (labels, sizes) = dense_component_ids(components);
+            let per_node = self.sizes.then(|| labels.iter().map(|&c| sizes[c]).collect());
+            return (labels, per_node);
+        }
+        let per_node = self.sizes.then(|| {
+            let sizes = wcc_stats(components).component_sizes;
+            components.iter().map(|c| sizes[c]).collect()
+        });
+        (components.to_vec(), per_node)
+    }
+}
+
+#[derive(Serialize)]
+struct PageRankReport<'a> {
+    algorithm: &'static str,
+    iterations: usize,
+    converged: bool,
+    final_delta: f64,
+    history: &'a [f64],
+    ranks: &'a [f64],
+}
+
+#[derive(Serialize)]
+struct OutputReport<'a, T: Serialize> {
+    algorithm: &'a str,
+    values: &'a [T],
+}
+
+#[derive(Serialize)]
+struct CentralityRow {
+    node: usize,
+    in_degree: usize,
+    out_degree: usize,
+    pagerank: f64,
+    betweenness: f64,
+    closeness: f64,
+}
+
+#[derive(Serialize)]
+struct CentralityReportJson {
+    algorithm: &'static str,
+    samples: usize,
+    exact: bool,
+    nodes: Vec<CentralityRow>,
+}
+
+#[derive(Serialize)]
+struct NodeValue<'a, T: Serialize> {
+    node: usize,
+    #[serde(flatten)]
+    value: Named<'a, T>,
+}
+
+/// Serializes as `{ name: value }`, flattened into [`NodeValue`].
+struct Named<'a, T>(&'a str, &'a T);
+
+impl<T: Serialize> Serialize for Named<'_, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(self.0, self.1)?;
+        map.end()
+    }
+}
+
+fn write_json<T: Serialize>(report: &T, output_path: &str) -> Result<()> {
+    let mut writer = create_output(output_path)?;
+    serde_json::to_writer(&mut writer, report)?;
+    writeln!(writer)?;
+    writer.finish()
+}
+
+fn write_ndjson<T: Serialize + Sync>(values: &[T], name: &str, output_path: &str) -> Result<()> {
+    write_node_lines(output_path, None, values.len(), Config::global().shards, |writer, node| {
+        serde_json::to_writer(&mut *writer, &NodeValue { node, value: Named(name, &values[node]) })?;
+        writeln!(writer)?;
+        Ok(())
+    })
+}
+
+/// Writes a BFS result in the requested format.
+pub fn write_bfs_result_as(result: &BfsResult, source: usize, output_path: &str, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Text => write_bfs_result(&result.distances, output_path),
+        OutputFormat::Json => write_json(
+            &BfsReport {
+                algorithm: "bfs",
+                source,
+                reachable: result.reachable,
+                levels: result.levels,
+                elapsed_ms: result.elapsed.as_secs_f64() * 1000.0,
+                distances: &result.distances,
+            },
+            output_path,
+        ),
+        OutputFormat::Ndjson => write_ndjson(&result.distances, "distance", output_path),
+        OutputFormat::Parquet => write_bfs_result_parquet(&result.distances, output_path),
+        OutputFormat::Summary => write_distance_summary(&result.distances, output_path),
+        OutputFormat::Binary => Ok(write_result(result, output_path)?),
+    }
+}
+
+/// Writes WCC labels in the requested format, with the optional `columns`.
+pub fn write_wcc_result_as(result: &WccResult, output_path: &str, format: OutputFormat, columns: WccColumns) -> Result<()> {
+    let (labels, sizes) = columns.apply(&result.labels);
+    match format {
+        OutputFormat::Text => write_wcc_text(&labels, sizes.as_deref(), output_path),
+        OutputFormat::Json => write_json(
+            &WccReport {
+                algorithm: "wcc",
+                num_components: result.stats.num_components,
+                largest_component: result.stats.largest_component,
+                smallest_component: result.stats.smallest_component,
+                components: &labels,
+                component_sizes: sizes,
+            },
+            output_path,
+        ),
+        OutputFormat::Ndjson => match sizes {
+            Some(sizes) => write_node_lines(output_path, None, labels.len(), Config::global().shards, |writer, node| {
+                let row = serde_json::json!({ "node": node, "component": labels[node], "component_size": sizes[node] });
+                serde_json::to_writer(&mut *writer, &row)?;
+                writeln!(writer)?;
+                Ok(())
+            }),
+            None => write_ndjson(&labels, "component", output_path),
+        },
+        OutputFormat::Parquet if sizes.is_some() => anyhow::bail!("component sizes are not supported in Parquet output"),
+        OutputFormat::Parquet => write_wcc_result_parquet(&labels, output_path),
+        OutputFormat::Summary => Err(labels_not_summarized()),
+        OutputFormat::Binary if sizes.is_some() => anyhow::bail!("component sizes are not supported in binary output"),
+        OutputFormat::Binary if columns.dense_ids => Ok(write_result(&WccResult::from_labels(labels), output_path)?),
+        OutputFormat::Binary => Ok(write_result(result, output_path)?),
+    }
+}
+
+/// Writes a weighted shortest-path result in the requested format.
+pub fn write_sssp_result_as(result: &SsspResult, output_path: &str, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Text => write_sssp_result(result, output_path),
+        OutputFormat::Json => write_json(
+            &SsspReport {
+                algorithm: "sssp",
+                source: result.source,
+                reachable: result.reachable(),
+                distances: &result.distances,
+                predecessors: sssp_predecessors(result).collect(),
+            },
+            output_path,
+        ),
+        OutputFormat::Ndjson => write_node_lines(output_path, None, result.distances.len(), Config::global().shards, |writer, node| {
+            let distance = result.distances[node];
+            let row = SsspRow { node, distance: distance.is_finite().then_some(distance), predecessor: sssp_predecessor(result, node) };
+            serde_json::to_writer(&mut *writer, &row)?;
+            writeln!(writer)?;
+            Ok(())
+        }),
+        OutputFormat::Parquet => write_sssp_result_parquet(result, output_path),
+        OutputFormat::Summary => write_summary(&summarize(&result.distances, &Config::global().summary), "cost", output_path),
+        OutputFormat::Binary => Err(no_binary_format("shortest-path")),
+    }
+}
+
+/// Writes the output of a registered algorithm in the requested format; text and Parquet match the
+/// dedicated BFS and WCC writers, and scores go through [`write_node_values`] under the algorithm
+/// name.
+pub fn write_output_as(output: &Output, algorithm: &str, output_path: &str, format: OutputFormat) -> Result<()> {
+    match (output, format) {
+        (Output::Distances(d), OutputFormat::Text) => write_bfs_result(d, output_path),
+        (Output::Labels(l), OutputFormat::Text) => write_wcc_result(l, output_path, WccColumns::default()),
+        (Output::Scores(s), OutputFormat::Text) => write_node_values(s, output_path, Some(algorithm)),
+        (Output::Distances(d), OutputFormat::Json) => write_json(&OutputReport { algorithm, values: d }, output_path),
+        (Output::Labels(l), OutputFormat::Json) => write_json(&OutputReport { algorithm, values: l }, output_path),
+        (Output::Scores(s), OutputFormat::Json) => write_json(&OutputReport { algorithm, values: s }, output_path),
+        (Output::Distances(d), OutputFormat::Ndjson) => write_ndjson(d, "distance", output_path),
+        (Output::Labels(l), OutputFormat::Ndjson) => write_ndjson(l, "label", output_path),
+        (Output::Scores(s), OutputFormat::Ndjson) => write_ndjson(s, "score", output_path),
+        (Output::Distances(d), OutputFormat::Parquet) => write_bfs_result_parquet(d, output_path),
+        (Output::Labels(l), OutputFormat::Parquet) => write_wcc_result_parquet(l, output_path),
+        (Output::Scores(s), OutputFormat::Parquet) => write_pagerank_result_parquet(s, output_path),
+        (Output::Distances(d), OutputFormat::Summary) => write_distance_summary(d, output_path),
+        (Output::Labels(_), OutputFormat::Summary) => Err(labels_not_summarized()),
+        (Output::Scores(s), OutputFormat::Summary) => write_summary(&summarize(s, &Config::global().summary), algorithm, output_path),
+        (_, OutputFormat::Binary) => Err(no_binary_format(algorithm)),
+    }
+}
+
+/// Writes a combined centrality table in the requested format.
+pub fn write_centrality_report_as(report: &CentralityReport, output_path: &str, format: OutputFormat) -> Result<()> {
+    let row = |node| CentralityRow {
+        node,
+        in_degree: report.in_degree[node],
+        out_degree: report.out_degree[node],
+        pagerank: report.pagerank[node],
+        betweenness: report.betweenness[node],
+        closeness: report.closeness[node],
+    };
+    let (num_nodes, shards) = (report.num_nodes(), Config::global().shards);
+    match format {
+        OutputFormat::Text => {
+            let header = "# Node InDegree OutDegree PageRank Betweenness Closeness";
+            write_node_lines(output_path, Some(header), num_nodes, shards, |writer, node| {
+                let row = row(node);
+                writeln!(
+                    writer,
+                    "{} {} {} {:.10e} {:.6e} {:.6}",
+                    row.node, row.in_degree, row.out_degree, row.pagerank, row.betweenness, row.closeness
+                )?;
+                Ok(())
+            })
+        }
+        OutputFormat::Json => write_json(
+            &CentralityReportJson {
+                algorithm: "centrality",
+                samples: report.samples,
+                exact: report.exact,
+                nodes: (0..num_nodes).map(row).collect(),
+            },
+            output_path,
+        ),
+        OutputFormat::Ndjson => write_node_lines(output_path, None, num_nodes, shards, |writer, node| {
+            serde_json::to_writer(&mut *writer, &row(node))?;
+            writeln!(writer)?;
+            Ok(())
+        }),
+        OutputFormat::Parquet => write_centrality_report_parquet(report, output_path),
+        OutputFormat::Summary => anyhow::bail!("the centrality report has several columns; summarize one measure with `run <measure> --output-format summary`"),
+        OutputFormat::Binary => Err(no_binary_format("centrality")),
+    }
+}
+
+/// Writes PageRank scores in the requested format.
+pub fn write_pagerank_result_as(result: &PageRankResult, output_path: &str, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Text => write_pagerank_result(&result.ranks, output_path),
+        OutputFormat::Json => write_json(
+            &PageRankReport {
+                algorithm: "pagerank",
+                iterations: result.iterations,
+                converged: result.converged,
+                final_delta: result.final_delta,
+                history: &result.history,
+                ranks: &result.ranks,
+            },
+            output_path,
+        ),
+        OutputFormat::Ndjson => write_ndjson(&result.ranks, "rank", output_path),
+        OutputFormat::Parquet => write_pagerank_result_parquet(&result.ranks, output_path),
+        OutputFormat::Summary => write_summary(&summarize(&result.ranks, &Config::global().summary), "rank", output_path),
+        OutputFormat::Binary => Ok(write_result(result, output_path)?),
+    }
+}
+
+#[cfg(feature = "parquet")]
+mod parquet_writer {
+    use std::fs::File;
+    use std::ops::Range;
+    use std::sync::Arc;
+    use anyhow::Result;
+    use arrow_array::{ArrayRef, RecordBatch, UInt64Array};
+    use arrow_schema::{DataType, Field, Schema};
+    use parquet::arrow::ArrowWriter;
+    use parquet::basic::Compression;
+    use parquet::file::properties::WriterProperties;
+
+    /// Rows per record batch, so large results are never materialized as one Arrow array.
+    const BATCH_ROWS: usize = 1 << 20;
+
+    /// Writes `len` rows of (`node`, `value_field`), building each batch's value column with `values`.
+    pub(super) fn write<F>(output_path: &str, value_field: Field, len: usize, values: F) -> Result<()>
+    where
+        F: Fn(Range<usize>) -> ArrayRef,
+    {
+        write_columns(output_path, vec![value_field], len, |range| vec![values(range)])
+    }
+
+    /// Writes `len` rows of `node` followed by `fields`, building each batch's columns with `columns`.
+    pub(super) fn write_columns<F>(output_path: &str, fields: Vec<Field>, len: usize, columns: F) -> Result<()>
+    where
+        F: Fn(Range<usize>) -> Vec<ArrayRef>,
+    {
+        let schema = Arc::new(Schema::new(
+            std::iter::once(Field::new("node", DataType::UInt64, false)).chain(fields).collect::<Vec<_>>(),
+        ));
+        let props = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let mut writer = ArrowWriter::try_new(File::create(output_path)?, schema.clone(), Some(props))?;
+
+        for start in (0..len).step_by(BATCH_ROWS) {
+            let end = (start + BATCH_ROWS).min(len);
+            let nodes: ArrayRef = Arc::new(UInt64Array::from_iter_values((start..end).map(|v| v as u64)));
+            let mut batch_columns = vec![nodes];
+            batch_columns.extend(columns(start..end));
+            let batch = RecordBatch::try_new(schema.clone(), batch_columns)?;
+            writer.write(&batch)?;
+        }
+
+        writer.close()?;
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "parquet"))]
+fn parquet_unavailable() -> anyhow::Error {
+    anyhow::anyhow!("Parquet output requires building with `--features parquet`")
+}
+
+/// Writes BFS distances as Parquet columns `node` (u64) and `distance` (i32, -1 = unreachable).
+#[cfg(feature = "parquet")]
+pub fn write_bfs_result_parquet(dist: &[i32], output_path: &str) -> Result<()> {
+    use arrow_array::Int32Array;
+    use arrow_schema::{DataType, Field};
+    parquet_writer::write(
+        output_path,
+        Field::new("distance", DataType::Int32, false),
+        dist.len(),
+        |range| std::sync::Arc::new(Int32Array::from(dist[range].to_vec())),
+    )
+}
+
+/// Writes WCC labels as Parquet columns `node` (u64) and `component` (u64).
+#[cfg(feature = "parquet")]
+pub fn write_wcc_result_parquet(components: &[usize], output_path: &str) -> Result<()> {
+    use arrow_array::UInt64Array;
+    use arrow_schema::{DataType, Field};
+    parquet_writer::write(
+        output_path,
+        Field::new("component", DataType::UInt64, false),
+        components.len(),
+        |range| std::sync::Arc::new(UInt64Array::from_iter_values(components[range].iter().map(|&c| c as u64))),
+    )
+}
+
+/// Writes PageRank scores as Parquet columns `node` (u64) and `rank` (f64).
+#[cfg(feature = "parquet")]
+pub fn write_pagerank_result_parquet(ranks: &[f64], output_path: &str) -> Result<()> {
+    use arrow_array::Float64Array;
+    use arrow_schema::{DataType, Field};
+    parquet_writer::write(
+        output_path,
+        Field::new("rank", DataType::Float64, false),
+        ranks.len(),
+        |range| std::sync::Arc::new(Float64Array::from(ranks[range].to_vec())),
+    )
+}
+
+/// Writes shortest-path results as Parquet columns `node` (u64), `distance` (f64, infinite if
+/// unreachable) and `predecessor` (i64, -1 for none).
+#[cfg(feature = "parquet")]
+pub fn write_sssp_result_parquet(result: &SsspResult, output_path: &str) -> Result<()> {
+    use arrow_array::{ArrayRef, Float64Array, Int64Array};
+    use arrow_schema::{DataType, Field};
+    use std::sync::Arc;
+    let predecessors: Vec<i64> = sssp_predecessors(result).collect();
+    parquet_writer::write_columns(
+        output_path,
+        vec![Field::new("distance", DataType::Float64, false), Field::new("predecessor", DataType::Int64, false)],
+        result.distances.len(),
+        |range| {
+            vec![
+                Arc::new(Float64Array::from(result.distances[range.clone()].to_vec())) as ArrayRef,
+                Arc::new(Int64Array::from(predecessors[range].to_vec())) as ArrayRef,
+            ]
+        },
+    )
+}
+
+/// Writes a centrality report as Parquet columns `node` plus one column per score.
+#[cfg(feature = "parquet")]
+pub fn write_centrality_report_parquet(report: &CentralityReport, output_path: &str) -> Result<()> {
+    use arrow_array::{ArrayRef, Float64Array, UInt64Array};
+    use arrow_schema::{DataType, Field};
+    use std::sync::Arc;
+    let counts = |values: &[usize]| -> ArrayRef { Arc::new(UInt64Array::from_iter_values(values.iter().map(|&d| d as u64))) };
+    let scores = |values: &[f64]| -> ArrayRef { Arc::new(Float64Array::from(values.to_vec())) };
+    parquet_writer::write_columns(
+        output_path,
+        vec![
+            Field::new("in_degree", DataType::UInt64, false),
+            Field::new("out_degree", DataType::UInt64, false),
+            Field::new("pagerank", DataType::Float64, false),
+            Field::new("betweenness", DataType::Float64, false),
+            Field::new("closeness", DataType::Float64, false),
+        ],
+        report.num_nodes(),
+        |range| {
+            vec![
+                counts(&report.in_degree[range.clone()]),
+                counts(&report.out_degree[range.clone()]),
+                scores(&report.pagerank[range.clone()]),
+                scores(&report.betweenness[range.clone()]),
+                scores(&report.closeness[range]),
+            ]
+        },
+    )
+}
+
+#[cfg(not(feature = "parquet"))]
+pub fn write_bfs_result_parquet(_dist: &[i32], _output_path: &str) -> Result<()> {
+    Err(parquet_unavailable())
+}
+
+#[cfg(not(feature = "parquet"))]
+pub fn write_wcc_result_parquet(_components: &[usize], _output_path: &str) -> Result<()> {
+    Err(parquet_unavailable())
+}
+
+#[cfg(not(feature = "parquet"))]
+pub fn write_pagerank_result_parquet(_ranks: &[f64], _output_path: &str) -> Result<()> {
+    Err(parquet_unavailable())
+}
+
+#[cfg(not(feature = "parquet"))]
+pub fn write_sssp_result_parquet(_result: &SsspResult, _output_path: &str) -> Result<()> {
+    Err(parquet_unavailable())
+}
+
+#[cfg(not(feature = "parquet"))]
+pub fn write_centrality_report_parquet(_report: &CentralityReport, _output_path: &str) -> Result<()> {
+    Err(parquet_unavailable())
+}
+
+/// Writes one value per node (node, value), one pair per line, after a `# Node <header>` line if
+/// `header` is given. Per-node results without a dedicated writer should use this one; like them it
+/// is split into `--shards` files.
+pub fn write_node_values<T: Display + Sync>(values: &[T], output_path: &str, header: Option<&str>) -> Result<()> {
+    write_node_values_sharded(values, output_path, header, Config::global().shards)
+}
+
+/// [`write_node_values`] split into `shards` files of consecutive nodes, written in parallel (one
+/// file if `shards` is 0 or 1); see [`shard_path`] for the file names.
+pub fn write_node_values_sharded<T: Display + Sync>(values: &[T], output_path: &str, header: Option<&str>, shards: usize) -> Result<()> {
+    let header = header.map(|h| format!("# Node {}", h));
+    write_node_lines(output_path, header.as_deref(), values.len(), shards, |writer, node| {
+        writeln!(writer, "{} {}", node, values[node])?;
+        Ok(())
+    })
+}
+
+/// Writes a [`Summary`] of the per-node `metric`: a commented header with the counts and the mean,
+/// then `quantile value`, top-K `node value` and bottom-K `node value` lines, each block after a
+/// `#` line naming it.
+pub fn write_summary(summary: &Summary, metric: &str, output_path: &str) -> Result<()> {
+    if let Some(&(q, _)) = summary.quantiles.iter().find(|&&(q, _)| !(0.0..=1.0).contains(&q)) {
+        anyhow::bail!("quantile {} is outside [0, 1]", q);
+    }
+    let mut writer = create_output(output_path)?;
+
+    writeln!(writer, "# Summary of {}: {} nodes with a value, {} without", metric, summary.count, summary.missing)?;
+    writeln!(writer, "# Mean {}", summary.mean)?;
+    writeln!(writer, "# Quantile {}", metric)?;
+    for &(q, value) in &summary.quantiles {
+        writeln!(writer, "{} {}", q, value)?;
+    }
+    writeln!(writer, "# Top {}: Node {}", summary.top.len(), metric)?;
+    for &(node, value) in &summary.top {
+        writeln!(writer, "{} {}", node, value)?;
+    }
+    writeln!(writer, "# Bottom {}: Node {}", summary.bottom.len(), metric)?;
+    for &(node, value) in &summary.bottom {
+        writeln!(writer, "{} {}", node, value)?;
     }
     
-    Ok(())
+    writer.finish()
 }
 
-/// Writes WCC results (node, component_id) to a file, one pair per line.
-pub fn write_wcc_result(components: &[usize], output_path: &str) -> Result<()> {
-    let file = File::create(output_path)?;
-    let mut writer = BufWriter::new(file);
+/// Summary of hop distances, leaving out unreachable nodes (-1).
+fn write_distance_summary(dist: &[i32], output_path: &str) -> Result<()> {
+    let values: Vec<f64> = dist.iter().map(|&d| if d < 0 { f64::INFINITY } else { f64::from(d) }).collect();
+    write_summary(&summarize(&values, &Config::global().summary), "distance", output_path)
+}
+
+fn labels_not_summarized() -> anyhow::Error {
+    anyhow::anyhow!("component labels have no order to summarize; the component sizes are in the _stats file")
+}
+
+fn no_binary_format(what: &str) -> anyhow::Error {
+    anyhow::anyhow!("binary output is only available for bfs, wcc and pagerank results, not {}", what)
+}
+
+/// Writes BFS results (node, distance) to a file, one pair per line.
+pub fn write_bfs_result(dist: &[i32], output_path: &str) -> Result<()> {
+    write_node_values(dist, output_path, None)
+}
+
+/// Writes weighted shortest-path results (node, cost, predecessor) to a file, one node per line;
+/// unreachable nodes have cost `inf`, and they and the source have predecessor -1.
+pub fn write_sssp_result(result: &SsspResult, output_path: &str) -> Result<()> {
+    let header = format!("# Node Cost Predecessor (source {})", result.source);
+    write_node_lines(output_path, Some(&header), result.distances.len(), Config::global().shards, |writer, node| {
+        writeln!(writer, "{} {} {}", node, result.distances[node], sssp_predecessor(result, node))?;
+        Ok(())
+    })
+}
+
+/// Writes one centrality score per node (node, score) under a `# Node <measure>` header, e.g. the
+/// output of `approximate_closeness` or `approximate_betweenness`.
+pub fn write_centrality_result(scores: &[f64], measure: &str, output_path: &str) -> Result<()> {
+    write_node_values(scores, output_path, Some(measure))
+}
+
+/// Writes per-edge scores (e.g. `approximate_edge_betweenness`) as an edge list `src dst score` in
+/// input-file ids, one line per CSR edge, so loading it gives the same graph weighted by the scores.
+pub fn write_edge_scores(graph: &Graph, scores: &[f64], measure: &str, output_path: &str) -> Result<()> {
+    let mut writer = create_output(output_path)?;
+
+    writeln!(writer, "# Src Dst {}", measure)?;
     
-    for (node, &comp) in components.iter().enumerate() {
-        writeln!(writer, "{} {}", node, comp)?;
+    for ((u, v), score) in graph.edges().zip(scores) {
+        writeln!(writer, "{} {} {}", graph.original_id(u), graph.original_id(v), score)?;
     }
     
-    Ok(())
+    writer.finish()
+}
+
+/// Writes WCC results (node, component_id) to a file, one pair per line; `columns` can renumber the
+/// components densely and add the component size as a third column.
+pub fn write_wcc_result(components: &[usize], output_path: &str, columns: WccColumns) -> Result<()> {
+    let (labels, sizes) = columns.apply(components);
+    write_wcc_text(&labels, sizes.as_deref(), output_path)
+}
+
+fn write_wcc_text(labels: &[usize], sizes: Option<&[usize]>, output_path: &str) -> Result<()> {
+    write_node_lines(output_path, None, labels.len(), Config::global().shards, |writer, node| {
+        match sizes {
+            Some(sizes) => writeln!(writer, "{} {} {}", node, labels[node], sizes[node])?,
+            None => writeln!(writer, "{} {}", node, labels[node])?,
+        }
+        Ok(())
+    })
 }
 
 /// Writes WCC statistics (component counts and sizes) to a file.
@@ -33,8 +875,7 @@ pub fn write_wcc_stats(
 ) -> Result<()> {
     use std::collections::HashMap;
     
-    let file = File::create(stats_path)?;
-    let mut writer = BufWriter::new(file);
+    let mut writer = create_output(stats_path)?;
     
     let mut comp_sizes: HashMap<usize, usize> = HashMap::new();
     for &comp in components {
@@ -57,31 +898,303 @@ pub fn write_wcc_stats(
         writeln!(writer, "{} {}", comp_id, size)?;
     }
     
+    writer.finish()
+}
+
+/// Writes a snapshot diff as one change per line: `+node v`, `-node v`, `+edge u v`, `-edge u v`,
+/// `~weight u v old new` and `~degree v old_out new_out old_in new_in`.
+pub fn write_graph_diff(diff: &GraphDiff, output_path: &str) -> Result<()> {
+    let mut writer = create_output(output_path)?;
+
+    for &v in &diff.added_nodes {
+        writeln!(writer, "+node {}", v)?;
+    }
+    for &v in &diff.removed_nodes {
+        writeln!(writer, "-node {}", v)?;
+    }
+    for &(u, v) in &diff.added_edges {
+        writeln!(writer, "+edge {} {}", u, v)?;
+    }
+    for &(u, v) in &diff.removed_edges {
+        writeln!(writer, "-edge {} {}", u, v)?;
+    }
+    for &(u, v, old, new) in &diff.weight_changes {
+        writeln!(writer, "~weight {} {} {} {}", u, v, old, new)?;
+    }
+    for c in &diff.degree_changes {
+        writeln!(writer, "~degree {} {} {} {} {}", c.node, c.old_out, c.new_out, c.old_in, c.new_in)?;
+    }
+    
+    writer.finish()
+}
+
+/// Writes a validation report as one JSON document (counts plus the listed structural errors).
+pub fn write_validation_report(report: &ValidationReport, output_path: &str) -> Result<()> {
+    write_json(report, output_path)
+}
+
+/// Appends walks to `writer`, one per line as space-separated node ids.
+pub fn write_walk_corpus<W: Write>(walks: &[Vec<usize>], writer: &mut W) -> Result<()> {
+    for walk in walks {
+        let mut nodes = walk.iter();
+        if let Some(first) = nodes.next() {
+            write!(writer, "{}", first)?;
+        }
+        for node in nodes {
+            write!(writer, " {}", node)?;
+        }
+        writeln!(writer)?;
+    }
     Ok(())
 }
 
-/// Writes PageRank results (node, rank) to a file, one pair per line.
-pub fn write_pagerank_result(ranks: &[f64], output_path: &str) -> Result<()> {
+/// Writes a partition assignment (node, part) to a file, one pair per line.
+pub fn write_partition(parts: &[usize], output_path: &str) -> Result<()> {
+    write_node_values(parts, output_path, Some("Part"))
+}
+
+/// Writes `graph` as an edge list readable by `load_graph_from_file`: `src dst`, plus the weight if the
+/// graph is weighted and the `"type"` edge attribute if present.
+pub fn write_edge_list(graph: &Graph, output_path: &str) -> Result<()> {
     let file = File::create(output_path)?;
     let mut writer = BufWriter::new(file);
+    let types = graph.edge_attr::<Categorical>("type");
 
-    writeln!(writer, "# Node PageRank")?;
-    
-    for (node, &rank) in ranks.iter().enumerate() {
-        writeln!(writer, "{} {:.10e}", node, rank)?;
+    for u in 0..graph.num_nodes {
+        for e in graph.edge_range(u) {
+            write!(writer, "{} {}", u, graph.neighbors[e])?;
+            // A type column needs a weight column in front of it
+            if graph.weights.is_some() || types.is_some() {
+                write!(writer, " {}", graph.weights.as_ref().map_or(1.0, |w| w[e]))?;
+            }
+            if let Some(types) = types {
+                write!(writer, " {}", types.get(e).unwrap_or_default())?;
+            }
+            writeln!(writer)?;
+        }
     }
     
+    writer.flush()?;
     Ok(())
 }
 
+/// Writes a node id mapping (node, original id), one pair per line.
+pub fn write_id_map(original_ids: &[usize], output_path: &str) -> Result<()> {
+    write_node_values(original_ids, output_path, Some("OriginalId"))
+}
+
+/// Writes bridges and cut vertices as one JSON document, most critical first.
+pub fn write_bridges(result: &BridgeResult, output_path: &str) -> Result<()> {
+    write_json(result, output_path)
+}
+
+/// Writes a robustness curve as CSV, one row per removal batch starting with the intact graph.
+pub fn write_robustness_curve(curve: &RobustnessCurve, output_path: &str) -> Result<()> {
+    let mut writer = create_output(output_path)?;
+
+    writeln!(writer, "removed,removed_fraction,giant_fraction,components")?;
+    
+    for p in &curve.points {
+        writeln!(writer, "{},{},{},{}", p.removed, p.removed_fraction, p.giant_fraction, p.components)?;
+    }
+    
+    writer.finish()
+}
+
+/// Writes minimum-cut edges (from, to, capacity) to a file, one edge per line.
+pub fn write_min_cut(edges: &[(usize, usize, f64)], output_path: &str) -> Result<()> {
+    let mut writer = create_output(output_path)?;
+
+    writeln!(writer, "# From To Capacity")?;
+    
+    for &(from, to, capacity) in edges {
+        writeln!(writer, "{} {} {}", from, to, capacity)?;
+    }
+    
+    writer.finish()
+}
+
+/// Writes the nodes of an isochrone (node, cost) in order of increasing cost.
+pub fn write_isochrone(iso: &Isochrone, output_path: &str) -> Result<()> {
+    let mut writer = create_output(output_path)?;
+
+    writeln!(writer, "# Node Cost (source {}, max cost {})", iso.source, iso.max_cost)?;
+    
+    for &(node, cost) in &iso.nodes {
+        writeln!(writer, "{} {}", node, cost)?;
+    }
+    
+    writer.finish()
+}
+
+/// Writes an isochrone as a GeoJSON FeatureCollection: the convex hull of the reachable nodes as a
+/// Polygon, one Point per reachable node (with `node` and `cost`) and one LineString per boundary edge.
+/// Nodes without coordinates are left out.
+pub fn write_isochrone_geojson(iso: &Isochrone, coords: &[Option<[f64; 2]>], output_path: &str) -> Result<()> {
+    let at = |v: usize| coords.get(v).copied().flatten();
+    let mut features = Vec::new();
+
+    let points: Vec<[f64; 2]> = iso.nodes.iter().filter_map(|&(v, _)| at(v)).collect();
+    let mut hull = convex_hull(points);
+    if hull.len() >= 3 {
+        hull.push(hull[0]);
+        features.push(serde_json::json!({
+            "type": "Feature",
+            "geometry": { "type": "Polygon", "coordinates": [hull] },
+            "properties": { "source": iso.source, "max_cost": iso.max_cost },
+        }));
+    }
+    for &(node, cost) in &iso.nodes {
+        if let Some(point) = at(node) {
+            features.push(serde_json::json!({
+                "type": "Feature",
+                "geometry": { "type": "Point", "coordinates": point },
+                "properties": { "node": node, "cost": cost },
+            }));
+        }
+    }
+    for &(u, v) in &iso.boundary_edges {
+        if let (Some(a), Some(b)) = (at(u), at(v)) {
+            features.push(serde_json::json!({
+                "type": "Feature",
+                "geometry": { "type": "LineString", "coordinates": [a, b] },
+                "properties": { "from": u, "to": v, "boundary": true },
+            }));
+        }
+    }
+
+    write_json(&serde_json::json!({ "type": "FeatureCollection", "features": features }), output_path)
+}
+
+/// Convex hull (Andrew's monotone chain), counter-clockwise without the closing point.
+fn convex_hull(mut points: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+    points.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+    let cross = |o: [f64; 2], a: [f64; 2], b: [f64; 2]| (a[0] - o[0]) * (b[1] - o[1]) - (a[1] - o[1]) * (b[0] - o[0]);
+    let mut hull: Vec<[f64; 2]> = Vec::with_capacity(2 * points.len());
+    for pass in 0..2 {
+        let start = hull.len();
+        let ordered: Box<dyn Iterator<Item = &[f64; 2]>> =
+            if pass == 0 { Box::new(points.iter()) } else { Box::new(points.iter().rev()) };
+        for &p in ordered {
+            while hull.len() >= start + 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0 {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        hull.pop();
+    }
+    hull
+}
+
+/// Writes a histogram as JSON if `output_path` ends in `.json` (before any compression extension),
+/// otherwise as CSV.
+pub fn write_histogram(histogram: &Histogram, output_path: &str) -> Result<()> {
+    if split_compression(output_path).0.ends_with(".json") {
+        write_histogram_json(histogram, output_path)
+    } else {
+        write_histogram_csv(histogram, output_path)
+    }
+}
+
+/// Writes a histogram as CSV (`start,end,count,fraction`), one row per bin; values outside the bins
+/// are not in the fractions.
+pub fn write_histogram_csv(histogram: &Histogram, output_path: &str) -> Result<()> {
+    let mut writer = create_output(output_path)?;
+    let total = histogram.total().max(1) as f64;
+
+    writeln!(writer, "start,end,count,fraction")?;
+    for (start, end, count) in histogram.bins() {
+        writeln!(writer, "{},{},{},{:.6}", start, end, count, count as f64 / total)?;
+    }
+    
+    writer.finish()
+}
+
+/// Writes a histogram as one JSON document: `scale`, `excluded` and the `bins` with `start`, `end`
+/// and `count`.
+pub fn write_histogram_json(histogram: &Histogram, output_path: &str) -> Result<()> {
+    let bins: Vec<_> = histogram
+        .bins()
+        .map(|(start, end, count)| serde_json::json!({ "start": start, "end": end, "count": count }))
+        .collect();
+    let scale = match histogram.scale {
+        BinScale::Linear => "linear",
+        BinScale::Log => "log",
+    };
+    write_json(&serde_json::json!({ "scale": scale, "excluded": histogram.excluded, "bins": bins }), output_path)
+}
+
+/// Writes PageRank results (node, rank) to a file, one pair per line.
+pub fn write_pagerank_result(ranks: &[f64], output_path: &str) -> Result<()> {
+    write_node_lines(output_path, Some("# Node PageRank"), ranks.len(), Config::global().shards, |writer, node| {
+        writeln!(writer, "{} {:.10e}", node, ranks[node])?;
+        Ok(())
+    })
+}
+
+/// Writes per-level BFS statistics as CSV (`level,frontier,edges,parallel,time_ms,split_hubs`).
+pub fn write_bfs_levels_csv(levels: &[BfsLevelStats], output_path: &str) -> Result<()> {
+    let mut writer = create_output(output_path)?;
+
+    writeln!(writer, "level,frontier,edges,parallel,time_ms,split_hubs")?;
+    
+    for level in levels {
+        writeln!(
+            writer,
+            "{},{},{},{},{:.6},{}",
+            level.level,
+            level.frontier,
+            level.edges,
+            level.parallel,
+            level.elapsed.as_secs_f64() * 1000.0,
+            level.split_hubs
+        )?;
+    }
+    
+    writer.finish()
+}
+
+/// Writes the distance distribution of a neighbourhood function as CSV, one row per hop count
+/// (distance 0 counts every node with itself).
+pub fn write_neighborhood_function_csv(function: &NeighborhoodFunction, output_path: &str) -> Result<()> {
+    let mut writer = create_output(output_path)?;
+
+    writeln!(writer, "distance,pairs,cumulative_pairs,cumulative_fraction")?;
+    
+    let total = function.pairs.last().copied().unwrap_or(0.0);
+    for (distance, (pairs, cumulative)) in function.distance_distribution().iter().zip(&function.pairs).enumerate() {
+        let fraction = if total > 0.0 { cumulative / total } else { 0.0 };
+        writeln!(writer, "{},{:.1},{:.1},{:.6}", distance, pairs, cumulative, fraction)?;
+    }
+    
+    writer.finish()
+}
+
+/// Writes the PageRank convergence history as CSV (`iteration,delta`), one row per iteration of the run.
+pub fn write_convergence_csv(result: &PageRankResult, output_path: &str) -> Result<()> {
+    let mut writer = create_output(output_path)?;
+
+    writeln!(writer, "iteration,delta")?;
+    
+    let first = result.iterations - result.history.len() + 1;
+    for (i, delta) in result.history.iter().enumerate() {
+        writeln!(writer, "{},{:e}", first + i, delta)?;
+    }
+    
+    writer.finish()
+}
+
 /// Writes top N nodes by PageRank to a file (rank position, node id, score).
 pub fn write_pagerank_top_nodes(
     ranks: &[f64],
     output_path: &str,
     top_n: usize,
 ) -> Result<()> {
-    let file = File::create(output_path)?;
-    let mut writer = BufWriter::new(file);
+    let mut writer = create_output(output_path)?;
 
     let mut indexed_ranks: Vec<(usize, f64)> = ranks
         .iter()
@@ -96,13 +1209,12 @@ pub fn write_pagerank_top_nodes(
         writeln!(writer, "{} {} {:.10e}", rank_position + 1, node, rank)?;
     }
     
-    Ok(())
+    writer.finish()
 }
 
 /// Writes PageRank statistics (sum, min, max, mean, node count) to a file.
 pub fn write_pagerank_stats(ranks: &[f64], stats_path: &str) -> Result<()> {
-    let file = File::create(stats_path)?;
-    let mut writer = BufWriter::new(file);
+    let mut writer = create_output(stats_path)?;
     
     let sum: f64 = ranks.iter().sum();
     let min = ranks.iter().cloned().fold(f64::INFINITY, f64::min);
@@ -116,5 +1228,5 @@ pub fn write_pagerank_stats(ranks: &[f64], stats_path: &str) -> Result<()> {
     writeln!(writer, "mean: {:.10e}", mean)?;
     writeln!(writer, "nodes: {}", ranks.len())?;
     
-    Ok(())
+    writer.finish()
 }
\ No newline at end of file
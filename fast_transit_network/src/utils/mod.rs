@@ -1,2 +1,9 @@
+#[cfg(feature = "fs")]
 pub mod io;
-pub mod benchmark;
\ No newline at end of file
+#[cfg(feature = "fs")]
+pub mod benchmark;
+pub mod memory;
+pub mod rng;
+pub mod simd;
+pub mod stats;
+pub mod summary;
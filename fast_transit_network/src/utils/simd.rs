@@ -0,0 +1,104 @@
+//! Dense `f64` vector kernels with an AVX2 path picked at runtime and a portable fallback.
+//!
+//! The fallback keeps several independent accumulators over fixed-size chunks, which the
+//! compiler turns into SSE2/NEON code; AVX2 doubles the lane count on CPUs that have it.
+
+/// Elements per unrolled step (two 256-bit registers of `f64`).
+const LANES: usize = 8;
+
+/// Name of the kernel set in use on this CPU (`"avx2"` or `"portable"`).
+pub fn active_backend() -> &'static str {
+    if has_avx2() { "avx2" } else { "portable" }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn has_avx2() -> bool {
+    std::arch::is_x86_feature_detected!("avx2")
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn has_avx2() -> bool {
+    false
+}
+
+/// Sum of `|a[i] - b[i]|` (the PageRank convergence delta). Panics if the lengths differ.
+pub fn l1_distance(a: &[f64], b: &[f64]) -> f64 {
+    assert_eq!(a.len(), b.len(), "l1_distance on slices of different length");
+    #[cfg(target_arch = "x86_64")]
+    if has_avx2() {
+        // SAFETY: AVX2 support was just checked.
+        return unsafe { avx2::l1_distance(a, b) };
+    }
+    l1_distance_portable(a, b)
+}
+
+/// `dst[i] = src[i] + offset` (adding the teleport and dangling share to every rank). Panics if the lengths differ.
+pub fn add_offset(dst: &mut [f64], src: &[f64], offset: f64) {
+    assert_eq!(dst.len(), src.len(), "add_offset on slices of different length");
+    #[cfg(target_arch = "x86_64")]
+    if has_avx2() {
+        // SAFETY: AVX2 support was just checked.
+        return unsafe { avx2::add_offset(dst, src, offset) };
+    }
+    add_offset_portable(dst, src, offset)
+}
+
+fn l1_distance_portable(a: &[f64], b: &[f64]) -> f64 {
+    let mut acc = [0.0f64; LANES];
+    let (a_chunks, b_chunks) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+    let tail: f64 = a_chunks.remainder().iter().zip(b_chunks.remainder()).map(|(x, y)| (x - y).abs()).sum();
+    for (x, y) in a_chunks.zip(b_chunks) {
+        for i in 0..LANES {
+            acc[i] += (x[i] - y[i]).abs();
+        }
+    }
+    acc.iter().sum::<f64>() + tail
+}
+
+fn add_offset_portable(dst: &mut [f64], src: &[f64], offset: f64) {
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = s + offset;
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use super::LANES;
+    use std::arch::x86_64::*;
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn l1_distance(a: &[f64], b: &[f64]) -> f64 {
+        let n = a.len() / LANES * LANES;
+        let mut lanes = [0.0f64; 4];
+        // SAFETY: every load reads 4 doubles starting below `n <= a.len() == b.len()`.
+        unsafe {
+            let sign = _mm256_set1_pd(-0.0);
+            let (mut acc0, mut acc1) = (_mm256_setzero_pd(), _mm256_setzero_pd());
+            for i in (0..n).step_by(LANES) {
+                let d0 = _mm256_sub_pd(_mm256_loadu_pd(a.as_ptr().add(i)), _mm256_loadu_pd(b.as_ptr().add(i)));
+                let d1 = _mm256_sub_pd(_mm256_loadu_pd(a.as_ptr().add(i + 4)), _mm256_loadu_pd(b.as_ptr().add(i + 4)));
+                acc0 = _mm256_add_pd(acc0, _mm256_andnot_pd(sign, d0));
+                acc1 = _mm256_add_pd(acc1, _mm256_andnot_pd(sign, d1));
+            }
+            _mm256_storeu_pd(lanes.as_mut_ptr(), _mm256_add_pd(acc0, acc1));
+        }
+        let tail: f64 = a[n..].iter().zip(&b[n..]).map(|(x, y)| (x - y).abs()).sum();
+        lanes.iter().sum::<f64>() + tail
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn add_offset(dst: &mut [f64], src: &[f64], offset: f64) {
+        let n = dst.len() / LANES * LANES;
+        // SAFETY: every load/store touches 4 doubles starting below `n <= dst.len() == src.len()`.
+        unsafe {
+            let off = _mm256_set1_pd(offset);
+            for i in (0..n).step_by(LANES) {
+                _mm256_storeu_pd(dst.as_mut_ptr().add(i), _mm256_add_pd(_mm256_loadu_pd(src.as_ptr().add(i)), off));
+                _mm256_storeu_pd(dst.as_mut_ptr().add(i + 4), _mm256_add_pd(_mm256_loadu_pd(src.as_ptr().add(i + 4)), off));
+            }
+        }
+        for (d, &s) in dst[n..].iter_mut().zip(&src[n..]) {
+            *d = s + offset;
+        }
+    }
+}
@@ -0,0 +1,104 @@
+/// Spacing of histogram bin edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BinScale {
+    /// Equal widths between the smallest and the largest value.
+    #[default]
+    Linear,
+    /// Equal ratios between the smallest positive and the largest value, for heavy-tailed values
+    /// such as degrees and ranks. Values `<= 0` are left out.
+    Log,
+}
+
+impl BinScale {
+    /// Position of `v` on the scale, where the edges are equally spaced.
+    fn position(self, v: f64) -> f64 {
+        match self {
+            BinScale::Linear => v,
+            BinScale::Log => v.ln(),
+        }
+    }
+
+    fn value(self, position: f64) -> f64 {
+        match self {
+            BinScale::Linear => position,
+            BinScale::Log => position.exp(),
+        }
+    }
+}
+
+/// Number and spacing of the bins of a [`histogram`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bins {
+    pub count: usize,
+    pub scale: BinScale,
+}
+
+impl Bins {
+    pub fn linear(count: usize) -> Self {
+        Self { count, scale: BinScale::Linear }
+    }
+
+    pub fn log(count: usize) -> Self {
+        Self { count, scale: BinScale::Log }
+    }
+}
+
+/// Output of [`histogram`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub scale: BinScale,
+    /// `counts.len() + 1` ascending edges; bin `i` is `[edges[i], edges[i + 1])`, the last bin also
+    /// holds the largest value.
+    pub edges: Vec<f64>,
+    pub counts: Vec<u64>,
+    /// Values left out: NaN or infinite (e.g. unreachable), or `<= 0` on a log scale.
+    pub excluded: u64,
+}
+
+impl Histogram {
+    /// Values counted in the bins.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// `(start, end, count)` of every bin.
+    pub fn bins(&self) -> impl Iterator<Item = (f64, f64, u64)> + '_ {
+        self.counts.iter().enumerate().map(|(i, &count)| (self.edges[i], self.edges[i + 1], count))
+    }
+}
+
+/// Histogram of `values` with the edges spread over their range (at least one bin). Used for the
+/// degree, distance and rank distributions the commands write with `--histogram`.
+pub fn histogram(values: &[f64], bins: Bins) -> Histogram {
+    let count = bins.count.max(1);
+    let scale = |v| bins.scale.position(v);
+    let unscale = |x| bins.scale.value(x);
+    let included = |v: f64| v.is_finite() && (bins.scale == BinScale::Linear || v > 0.0);
+
+    let (min, max) = values
+        .iter()
+        .copied()
+        .filter(|&v| included(v))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    let (lo, hi) = if min <= max { (scale(min), scale(max)) } else { (scale(1.0), scale(1.0)) };
+    // All values equal (or none): unit-wide bins starting at the value
+    let width = if hi > lo { (hi - lo) / count as f64 } else { 1.0 };
+    let mut edges: Vec<f64> = (0..=count).map(|i| unscale(lo + i as f64 * width)).collect();
+    if hi > lo {
+        // Exact ends, without the rounding of the logarithm
+        edges[0] = min;
+        edges[count] = max;
+    }
+
+    let mut counts = vec![0u64; count];
+    let mut excluded = 0;
+    for &v in values {
+        if !included(v) {
+            excluded += 1;
+            continue;
+        }
+        let bin = (((scale(v) - lo) / width) as usize).min(count - 1);
+        counts[bin] += 1;
+    }
+    Histogram { scale: bins.scale, edges, counts, excluded }
+}
@@ -0,0 +1,85 @@
+use std::cmp::Ordering;
+
+/// What [`summarize`] keeps of a per-node metric.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SummaryConfig {
+    /// Nodes listed at each end.
+    pub top_k: usize,
+    /// Quantiles to report, each in `[0, 1]` (0 is the minimum, 1 the maximum).
+    pub quantiles: Vec<f64>,
+}
+
+impl Default for SummaryConfig {
+    fn default() -> Self {
+        Self {
+            top_k: 10,
+            quantiles: vec![0.0, 0.25, 0.5, 0.75, 0.9, 0.99, 1.0],
+        }
+    }
+}
+
+/// Top-K, bottom-K and quantiles of a per-node metric, in place of the full per-node dump.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    /// Nodes with a value.
+    pub count: usize,
+    /// Nodes without one (NaN or infinite, e.g. unreachable).
+    pub missing: usize,
+    /// Mean over the nodes with a value (NaN if there are none).
+    pub mean: f64,
+    /// `(q, value)` for every requested quantile, interpolated linearly between the two nearest ranks.
+    pub quantiles: Vec<(f64, f64)>,
+    /// `(node, value)` of the largest values, largest first (ties by node id).
+    pub top: Vec<(usize, f64)>,
+    /// `(node, value)` of the smallest values, smallest first (ties by node id).
+    pub bottom: Vec<(usize, f64)>,
+}
+
+/// Summarizes one value per node; NaN and infinite values (unreachable nodes) count as missing.
+///
+/// The extremes are selected in linear time; the quantiles sort a copy of the values.
+pub fn summarize(values: &[f64], config: &SummaryConfig) -> Summary {
+    let mut present: Vec<(usize, f64)> = values.iter().copied().enumerate().filter(|(_, v)| v.is_finite()).collect();
+    let count = present.len();
+    let mean = present.iter().map(|&(_, v)| v).sum::<f64>() / count as f64;
+
+    let mut sorted: Vec<f64> = present.iter().map(|&(_, v)| v).collect();
+    sorted.sort_unstable_by(f64::total_cmp);
+    let quantiles = config
+        .quantiles
+        .iter()
+        .map(|&q| {
+            let value = match sorted.len() {
+                0 => f64::NAN,
+                n => {
+                    let position = q.clamp(0.0, 1.0) * (n - 1) as f64;
+                    let (lo, hi) = (position.floor() as usize, position.ceil() as usize);
+                    sorted[lo] + (sorted[hi] - sorted[lo]) * (position - lo as f64)
+                }
+            };
+            (q, value)
+        })
+        .collect();
+
+    let top = extremes(&mut present, config.top_k, |a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    let bottom = extremes(&mut present, config.top_k, |a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+    Summary { count, missing: values.len() - count, mean, quantiles, top, bottom }
+}
+
+/// The first `k` entries under `order`, in that order.
+fn extremes(
+    entries: &mut [(usize, f64)],
+    k: usize,
+    order: impl Fn(&(usize, f64), &(usize, f64)) -> Ordering,
+) -> Vec<(usize, f64)> {
+    let k = k.min(entries.len());
+    if k == 0 {
+        return Vec::new();
+    }
+    if k < entries.len() {
+        entries.select_nth_unstable_by(k - 1, &order);
+    }
+    let mut head = entries[..k].to_vec();
+    head.sort_unstable_by(order);
+    head
+}
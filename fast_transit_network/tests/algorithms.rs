@@ -1,11 +1,81 @@
-use fast_transit_network::algorithms::bfs::{bfs_sequential, bfs_parallel};
-use fast_transit_network::algorithms::wcc::{wcc_sequential, wcc_parallel, wcc_stats};
+use fast_transit_network::algorithms::bfs::{
+    shortest_path, BfsResult, bfs_sequential, bfs_sequential_with_progress, bfs_parallel, bfs_parallel_with_policy, bfs_parallel_with_progress,
+    bfs_parallel_with_level_stats, bfs_sequential_with_level_stats, bfs_to_targets, StopAt,
+};
+use fast_transit_network::algorithms::bridges::{find_bridges, Bridge, CutVertex};
+use fast_transit_network::algorithms::atomic_union_find::AtomicUnionFind;
+use fast_transit_network::algorithms::robustness::{robustness, RemovalStrategy, RobustnessConfig};
+use fast_transit_network::algorithms::dfs::{dfs, dfs_forest, EdgeKind};
+use fast_transit_network::algorithms::threadpool::get_thread_pool;
+use fast_transit_network::algorithms::wcc::{canonicalize_labels, dense_component_ids, wcc_label_propagation, WccMethod, IncrementalWcc, WccResult, wcc_sequential, wcc_parallel, wcc_parallel_with_policy, wcc_stats};
 use fast_transit_network::algorithms::pagerank::{
-    pagerank_sequential, pagerank_parallel, pagerank_parallel_optimized, PageRankConfig,
+    pagerank_largest_component, pagerank_sequential, run_pagerank_and_save, pagerank_parallel, pagerank_parallel_optimized, pagerank_parallel_with_policy, pagerank_with_initial,
+    Checkpointing, PageRankCheckpoint, PageRankConfig, PageRankResult,
+};
+use fast_transit_network::algorithms::sssp::{bellman_ford, dijkstra, dijkstra_to_targets};
+use fast_transit_network::algorithms::scc::strongly_connected_components;
+use fast_transit_network::algorithms::reachability::{build_reachability_index, ReachabilityIndex};
+use fast_transit_network::algorithms::maxflow::dinic;
+use fast_transit_network::algorithms::alt::{alt_shortest_path, build_landmarks, Landmarks};
+use fast_transit_network::algorithms::isochrone::isochrone;
+use fast_transit_network::algorithms::route::{constrained_shortest_path, RouteConstraints};
+use fast_transit_network::algorithms::coloring::{edge_coloring, greedy_coloring, is_proper_coloring, jones_plassmann, maximal_independent_set};
+use fast_transit_network::algorithms::diameter::approx_diameter;
+use fast_transit_network::algorithms::triangles::{count_triangles, count_triangles_ordered, count_triangles_parallel};
+use fast_transit_network::algorithms::descendants::{count_descendants, DescendantConfig, DescendantMethod};
+use fast_transit_network::algorithms::anf::{approximate_neighborhood_function, AnfConfig, NeighborhoodFunction};
+use fast_transit_network::algorithms::centrality::{approximate_betweenness, approximate_closeness, approximate_edge_betweenness, centrality_report, CentralityConfig};
+use fast_transit_network::algorithms::random_walk::{node2vec_walks, random_walks, run_node2vec_and_save, Node2VecConfig, RandomWalkConfig};
+use fast_transit_network::algorithms::pagerank_incremental::{pagerank_incremental, EdgeChange};
+use fast_transit_network::algorithms::chunking::{edge_balanced_ranges, frontier_ranges, hybrid_frontier_chunks, FrontierChunk};
+use fast_transit_network::algorithms::apsp::{apsp, floyd_warshall, ApspMethod, DistanceMatrix};
+#[cfg(feature = "parallel")]
+use fast_transit_network::graph::graph::build_csr_parallel;
+use fast_transit_network::graph::graph::{build_csr, build_csr_undirected, build_csr_weighted, load_graph_from_file, load_graph_from_file_with_progress, load_graph_from_files_with_options, load_graph_from_reader_with_options, load_graph_from_str, Graph, GraphLike, LoadOptions};
+use fast_transit_network::graph::formats::{
+    read_binary_info, write_binary, write_binary_to, write_binary_with_metadata_to, BinaryGraphMetadata, GraphFormat, SourceFile,
+};
+use fast_transit_network::graph::view::GraphView;
+use fast_transit_network::graph::expand::{expand_modes, TransferCost, TransferRule, TransferRules};
+use fast_transit_network::graph::attributes::{load_node_attributes, load_node_coordinates, Categorical, EdgeAttr, NodeAttr};
+use fast_transit_network::graph::stats::GraphStats;
+use fast_transit_network::graph::diff::diff;
+use fast_transit_network::graph::compact::{CompactGraph, GraphStorage};
+use fast_transit_network::graph::validate::ValidationIssue;
+use fast_transit_network::graph::reorder::{reorder, Permutation, ReorderStrategy};
+use fast_transit_network::cli::{self, Cli, SuiteArgs};
+use fast_transit_network::cache::{Cached, ResultCache};
+use fast_transit_network::run_config::RunConfig;
+use fast_transit_network::pipeline::{run_pipeline, PipelineConfig, PipelineStep};
+use fast_transit_network::analysis::Analysis;
+use fast_transit_network::sample::{induced_subgraph, sample, SampleConfig, SampleMethod};
+use fast_transit_network::partition::{edge_cut, partition, PartitionMethod};
+use fast_transit_network::query::{run_interactive, Query};
+use fast_transit_network::error::Error;
+use fast_transit_network::config::{available_threads, Config, Mode, ParallelismPolicy, Threads, Workload, BFS_AUTO_MAX_THREADS};
+use fast_transit_network::progress::{NoProgress, ProgressEvent};
+use fast_transit_network::commands;
+use fast_transit_network::registry::{Algorithm, Output, Params, Registry};
+use fast_transit_network::utils::memory::{self, TrackingAllocator};
+use fast_transit_network::utils::simd;
+use fast_transit_network::utils::benchmark::{
+    compare_to_baseline, expand_graph_paths, measure, SyntheticGraph, BenchmarkAlgorithm, BenchmarkInput, BenchmarkLogger, BenchmarkResult, GraphMetadata, TimingStats,
+};
+use fast_transit_network::utils::io::{
+    derived_path, derived_path_with_extension, shard_path, write_bfs_levels_csv, write_histogram, write_bfs_result_as, write_centrality_report_as, write_convergence_csv, write_edge_list, write_edge_scores, write_isochrone_geojson, write_node_values, write_node_values_sharded, write_output_as, write_pagerank_result_as,
+    write_sssp_result, write_sssp_result_as, write_wcc_result_as, OutputFormat, WccColumns,
 };
-use fast_transit_network::graph::graph::{build_csr, load_graph_from_file, Graph};
+use fast_transit_network::graph::testing;
+use fast_transit_network::suite::Suite;
+use fast_transit_network::utils::stats::{histogram, Bins};
+use fast_transit_network::utils::summary::{summarize, SummaryConfig};
+use clap::Parser;
+use std::sync::{Arc, Mutex};
 use std::io::Write;
 
+#[global_allocator]
+static ALLOC: TrackingAllocator = TrackingAllocator;
+
 fn graph_3_node_path() -> (Graph, Vec<(usize, usize)>) {
     let edges = vec![(0, 1), (1, 2)];
     let g = build_csr(3, edges.clone());
@@ -82,7 +152,7 @@ fn large_chain_graph() -> Graph {
 #[test]
 fn bfs_small_path_distances() {
     let (graph, _) = graph_3_node_path();
-    let dist = bfs_sequential(&graph, 0);
+    let dist = bfs_sequential(&graph, 0).unwrap().distances;
     assert_eq!(dist.len(), 3);
     assert_eq!(dist[0], 0);
     assert_eq!(dist[1], 1);
@@ -92,7 +162,7 @@ fn bfs_small_path_distances() {
 #[test]
 fn bfs_small_path_from_middle() {
     let (graph, _) = graph_3_node_path();
-    let dist = bfs_sequential(&graph, 1);
+    let dist = bfs_sequential(&graph, 1).unwrap().distances;
     assert_eq!(dist[0], -1);
     assert_eq!(dist[1], 0);
     assert_eq!(dist[2], 1);
@@ -101,7 +171,7 @@ fn bfs_small_path_from_middle() {
 #[test]
 fn bfs_diamond_distances() {
     let graph = graph_4_node_diamond();
-    let dist = bfs_sequential(&graph, 0);
+    let dist = bfs_sequential(&graph, 0).unwrap().distances;
     assert_eq!(dist[0], 0);
     assert_eq!(dist[1], 1);
     assert_eq!(dist[2], 1);
@@ -111,7 +181,7 @@ fn bfs_diamond_distances() {
 #[test]
 fn bfs_single_node() {
     let graph = graph_single_node();
-    let dist = bfs_sequential(&graph, 0);
+    let dist = bfs_sequential(&graph, 0).unwrap().distances;
     assert_eq!(dist.len(), 1);
     assert_eq!(dist[0], 0);
 }
@@ -119,22 +189,21 @@ fn bfs_single_node() {
 #[test]
 fn bfs_invalid_source() {
     let (graph, _) = graph_3_node_path();
-    let dist = bfs_sequential(&graph, 99);
-    assert_eq!(dist.len(), 3);
-    assert!(dist.iter().all(|&d| d == -1));
+    let err = bfs_sequential(&graph, 99).unwrap_err();
+    assert!(matches!(err, Error::InvalidNode { node: 99, num_nodes: 3 }));
+    assert!(matches!(bfs_parallel(&graph, 99, 2), Err(Error::InvalidNode { .. })));
 }
 
 #[test]
 fn bfs_empty_graph() {
     let graph = graph_empty();
-    let dist = bfs_sequential(&graph, 0);
-    assert_eq!(dist.len(), 0);
+    assert!(matches!(bfs_sequential(&graph, 0), Err(Error::InvalidNode { node: 0, num_nodes: 0 })));
 }
 
 #[test]
 fn bfs_two_nodes_one_edge() {
     let graph = graph_two_nodes_one_edge();
-    let dist = bfs_sequential(&graph, 0);
+    let dist = bfs_sequential(&graph, 0).unwrap().distances;
     assert_eq!(dist[0], 0);
     assert_eq!(dist[1], 1);
 }
@@ -142,7 +211,7 @@ fn bfs_two_nodes_one_edge() {
 #[test]
 fn bfs_two_nodes_from_sink() {
     let graph = graph_two_nodes_one_edge();
-    let dist = bfs_sequential(&graph, 1);
+    let dist = bfs_sequential(&graph, 1).unwrap().distances;
     assert_eq!(dist[0], -1);
     assert_eq!(dist[1], 0);
 }
@@ -150,7 +219,7 @@ fn bfs_two_nodes_from_sink() {
 #[test]
 fn bfs_two_node_cycle() {
     let graph = graph_two_node_cycle();
-    let dist = bfs_sequential(&graph, 0);
+    let dist = bfs_sequential(&graph, 0).unwrap().distances;
     assert_eq!(dist[0], 0);
     assert_eq!(dist[1], 1);
 }
@@ -158,16 +227,16 @@ fn bfs_two_node_cycle() {
 #[test]
 fn bfs_5_node_path() {
     let graph = graph_5_node_path();
-    let dist = bfs_sequential(&graph, 0);
-    for i in 0..5 {
-        assert_eq!(dist[i], i as i32);
+    let dist = bfs_sequential(&graph, 0).unwrap().distances;
+    for (i, &d) in dist.iter().enumerate() {
+        assert_eq!(d, i as i32);
     }
 }
 
 #[test]
 fn bfs_star_from_center() {
     let graph = graph_star_4();
-    let dist = bfs_sequential(&graph, 0);
+    let dist = bfs_sequential(&graph, 0).unwrap().distances;
     assert_eq!(dist[0], 0);
     assert_eq!(dist[1], 1);
     assert_eq!(dist[2], 1);
@@ -177,7 +246,7 @@ fn bfs_star_from_center() {
 #[test]
 fn bfs_star_from_leaf() {
     let graph = graph_star_4();
-    let dist = bfs_sequential(&graph, 1);
+    let dist = bfs_sequential(&graph, 1).unwrap().distances;
     assert_eq!(dist[0], -1);
     assert_eq!(dist[1], 0);
     assert_eq!(dist[2], -1);
@@ -187,7 +256,7 @@ fn bfs_star_from_leaf() {
 #[test]
 fn bfs_isolated_plus_path() {
     let graph = graph_isolated_plus_path();
-    let dist = bfs_sequential(&graph, 1);
+    let dist = bfs_sequential(&graph, 1).unwrap().distances;
     assert_eq!(dist[0], -1);
     assert_eq!(dist[1], 0);
     assert_eq!(dist[2], 1);
@@ -197,8 +266,8 @@ fn bfs_isolated_plus_path() {
 #[test]
 fn bfs_deterministic_same_twice() {
     let (graph, _) = graph_3_node_path();
-    let a = bfs_sequential(&graph, 0);
-    let b = bfs_sequential(&graph, 0);
+    let a = bfs_sequential(&graph, 0).unwrap().distances;
+    let b = bfs_sequential(&graph, 0).unwrap().distances;
     assert_eq!(a, b);
 }
 
@@ -244,7 +313,7 @@ fn graph_is_valid_node() {
 #[test]
 fn bfs_from_sink_only_self() {
     let graph = graph_with_sink();
-    let dist = bfs_sequential(&graph, 2);
+    let dist = bfs_sequential(&graph, 2).unwrap().distances;
     assert_eq!(dist[0], -1);
     assert_eq!(dist[1], -1);
     assert_eq!(dist[2], 0);
@@ -253,7 +322,7 @@ fn bfs_from_sink_only_self() {
 #[test]
 fn wcc_small_path_one_component() {
     let (graph, _) = graph_3_node_path();
-    let comp = wcc_sequential(&graph);
+    let comp = wcc_sequential(&graph).labels;
     assert_eq!(comp.len(), 3);
     assert_eq!(comp[0], comp[1]);
     assert_eq!(comp[1], comp[2]);
@@ -265,7 +334,7 @@ fn wcc_small_path_one_component() {
 #[test]
 fn wcc_two_components() {
     let graph = graph_two_components();
-    let comp = wcc_sequential(&graph);
+    let comp = wcc_sequential(&graph).labels;
     assert_eq!(comp.len(), 4);
     assert_eq!(comp[0], comp[1]);
     assert_eq!(comp[2], comp[3]);
@@ -277,7 +346,7 @@ fn wcc_two_components() {
 #[test]
 fn wcc_single_node() {
     let graph = graph_single_node();
-    let comp = wcc_sequential(&graph);
+    let comp = wcc_sequential(&graph).labels;
     assert_eq!(comp.len(), 1);
     let stats = wcc_stats(&comp);
     assert_eq!(stats.num_components, 1);
@@ -286,14 +355,14 @@ fn wcc_single_node() {
 #[test]
 fn wcc_empty_graph() {
     let graph = graph_empty();
-    let comp = wcc_sequential(&graph);
+    let comp = wcc_sequential(&graph).labels;
     assert_eq!(comp.len(), 0);
 }
 
 #[test]
 fn wcc_two_node_cycle_one_component() {
     let graph = graph_two_node_cycle();
-    let comp = wcc_sequential(&graph);
+    let comp = wcc_sequential(&graph).labels;
     assert_eq!(comp[0], comp[1]);
     assert_eq!(wcc_stats(&comp).num_components, 1);
 }
@@ -301,7 +370,7 @@ fn wcc_two_node_cycle_one_component() {
 #[test]
 fn wcc_three_node_cycle_one_component() {
     let graph = graph_three_node_cycle();
-    let comp = wcc_sequential(&graph);
+    let comp = wcc_sequential(&graph).labels;
     assert_eq!(comp[0], comp[1]);
     assert_eq!(comp[1], comp[2]);
     assert_eq!(wcc_stats(&comp).num_components, 1);
@@ -310,7 +379,7 @@ fn wcc_three_node_cycle_one_component() {
 #[test]
 fn wcc_diamond_one_component() {
     let graph = graph_4_node_diamond();
-    let comp = wcc_sequential(&graph);
+    let comp = wcc_sequential(&graph).labels;
     let stats = wcc_stats(&comp);
     assert_eq!(stats.num_components, 1);
     assert_eq!(stats.largest_component, 4);
@@ -319,7 +388,7 @@ fn wcc_diamond_one_component() {
 #[test]
 fn wcc_four_isolated_four_components() {
     let graph = graph_four_isolated();
-    let comp = wcc_sequential(&graph);
+    let comp = wcc_sequential(&graph).labels;
     let stats = wcc_stats(&comp);
     assert_eq!(stats.num_components, 4);
     assert_eq!(stats.largest_component, 1);
@@ -328,28 +397,57 @@ fn wcc_four_isolated_four_components() {
 #[test]
 fn wcc_star_one_component() {
     let graph = graph_star_4();
-    let comp = wcc_sequential(&graph);
+    let comp = wcc_sequential(&graph).labels;
     assert_eq!(wcc_stats(&comp).num_components, 1);
 }
 
 #[test]
 fn wcc_5_node_path_one_component() {
     let graph = graph_5_node_path();
-    let comp = wcc_sequential(&graph);
+    let comp = wcc_sequential(&graph).labels;
     assert_eq!(wcc_stats(&comp).num_components, 1);
 }
 
 #[test]
 fn wcc_isolated_plus_path_two_components() {
     let graph = graph_isolated_plus_path();
-    let comp = wcc_sequential(&graph);
+    let comp = wcc_sequential(&graph).labels;
     assert_eq!(wcc_stats(&comp).num_components, 2);
 }
 
+#[test]
+fn atomic_union_find_tracks_sizes_and_counts_components() {
+    let uf = AtomicUnionFind::new(6);
+    assert!(uf.union(0, 1));
+    assert!(uf.union(2, 1));
+    assert!(!uf.union(0, 2));
+    assert!(uf.union(4, 5));
+    assert_eq!(uf.count_components(), 3);
+    assert_eq!(uf.component_size(2), 3);
+    assert_eq!(uf.component_size(5), 2);
+    assert_eq!(uf.component_size(3), 1);
+    let labels = uf.get_components();
+    assert_eq!(labels[0], labels[2]);
+    assert_ne!(labels[0], labels[4]);
+    
+    // A long chain unioned from many threads still ends as one component of the right size
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        let n = 100_000;
+        let uf = AtomicUnionFind::new(n);
+        (1..n).into_par_iter().for_each(|i| {
+            uf.union(i - 1, i);
+        });
+        assert_eq!(uf.count_components(), 1);
+        assert_eq!(uf.component_size(n - 1), n);
+    }
+}
+
 #[test]
 fn wcc_component_sizes_sum_to_nodes() {
     let graph = graph_two_components();
-    let comp = wcc_sequential(&graph);
+    let comp = wcc_sequential(&graph).labels;
     let stats = wcc_stats(&comp);
     let sum: usize = stats.component_sizes.values().sum();
     assert_eq!(sum, 4);
@@ -358,7 +456,7 @@ fn wcc_component_sizes_sum_to_nodes() {
 #[test]
 fn wcc_stats_smallest_largest() {
     let graph = graph_two_components();
-    let comp = wcc_sequential(&graph);
+    let comp = wcc_sequential(&graph).labels;
     let stats = wcc_stats(&comp);
     assert_eq!(stats.smallest_component, 2);
     assert_eq!(stats.largest_component, 2);
@@ -371,8 +469,9 @@ fn pagerank_small_path_sum_one() {
         alpha: 0.85,
         max_iterations: 100,
         tolerance: 1e-8,
+        ..PageRankConfig::default()
     };
-    let ranks = pagerank_sequential(&graph, &config);
+    let ranks = pagerank_sequential(&graph, &config).ranks;
     assert_eq!(ranks.len(), 3);
     let sum: f64 = ranks.iter().sum();
     assert!((sum - 1.0).abs() < 1e-6, "PageRank sum should be ~1, got {}", sum);
@@ -385,8 +484,9 @@ fn pagerank_single_node() {
         alpha: 0.85,
         max_iterations: 10,
         tolerance: 1e-10,
+        ..PageRankConfig::default()
     };
-    let ranks = pagerank_sequential(&graph, &config);
+    let ranks = pagerank_sequential(&graph, &config).ranks;
     assert_eq!(ranks.len(), 1);
     assert!((ranks[0] - 1.0).abs() < 1e-10);
 }
@@ -398,8 +498,9 @@ fn pagerank_diamond_nonzero_all_nodes() {
         alpha: 0.85,
         max_iterations: 100,
         tolerance: 1e-8,
+        ..PageRankConfig::default()
     };
-    let ranks = pagerank_sequential(&graph, &config);
+    let ranks = pagerank_sequential(&graph, &config).ranks;
     assert_eq!(ranks.len(), 4);
     let sum: f64 = ranks.iter().sum();
     assert!((sum - 1.0).abs() < 1e-5);
@@ -412,7 +513,7 @@ fn pagerank_diamond_nonzero_all_nodes() {
 fn pagerank_empty_graph() {
     let graph = graph_empty();
     let config = PageRankConfig::default();
-    let ranks = pagerank_sequential(&graph, &config);
+    let ranks = pagerank_sequential(&graph, &config).ranks;
     assert!(ranks.is_empty());
 }
 
@@ -423,8 +524,9 @@ fn pagerank_two_nodes_one_edge() {
         alpha: 0.85,
         max_iterations: 100,
         tolerance: 1e-8,
+        ..PageRankConfig::default()
     };
-    let ranks = pagerank_sequential(&graph, &config);
+    let ranks = pagerank_sequential(&graph, &config).ranks;
     assert_eq!(ranks.len(), 2);
     let sum: f64 = ranks.iter().sum();
     assert!((sum - 1.0).abs() < 1e-5);
@@ -437,8 +539,9 @@ fn pagerank_two_node_cycle() {
         alpha: 0.85,
         max_iterations: 100,
         tolerance: 1e-8,
+        ..PageRankConfig::default()
     };
-    let ranks = pagerank_sequential(&graph, &config);
+    let ranks = pagerank_sequential(&graph, &config).ranks;
     assert_eq!(ranks.len(), 2);
     assert!((ranks[0] - 0.5).abs() < 1e-5);
     assert!((ranks[1] - 0.5).abs() < 1e-5);
@@ -451,8 +554,9 @@ fn pagerank_alpha_half() {
         alpha: 0.5,
         max_iterations: 100,
         tolerance: 1e-8,
+        ..PageRankConfig::default()
     };
-    let ranks = pagerank_sequential(&graph, &config);
+    let ranks = pagerank_sequential(&graph, &config).ranks;
     let sum: f64 = ranks.iter().sum();
     assert!((sum - 1.0).abs() < 1e-5);
 }
@@ -464,8 +568,9 @@ fn pagerank_max_iterations_respected() {
         alpha: 0.85,
         max_iterations: 1,
         tolerance: 1e-15,
+        ..PageRankConfig::default()
     };
-    let ranks = pagerank_sequential(&graph, &config);
+    let ranks = pagerank_sequential(&graph, &config).ranks;
     assert_eq!(ranks.len(), 3);
     let sum: f64 = ranks.iter().sum();
     assert!((sum - 1.0).abs() < 1e-5);
@@ -478,8 +583,9 @@ fn pagerank_high_tolerance_converges_quickly() {
         alpha: 0.85,
         max_iterations: 100,
         tolerance: 0.1,
+        ..PageRankConfig::default()
     };
-    let ranks = pagerank_sequential(&graph, &config);
+    let ranks = pagerank_sequential(&graph, &config).ranks;
     assert_eq!(ranks.len(), 3);
 }
 
@@ -490,8 +596,9 @@ fn pagerank_with_sink() {
         alpha: 0.85,
         max_iterations: 100,
         tolerance: 1e-8,
+        ..PageRankConfig::default()
     };
-    let ranks = pagerank_sequential(&graph, &config);
+    let ranks = pagerank_sequential(&graph, &config).ranks;
     assert_eq!(ranks.len(), 3);
     let sum: f64 = ranks.iter().sum();
     assert!((sum - 1.0).abs() < 1e-5);
@@ -504,7 +611,7 @@ fn pagerank_with_sink() {
 fn pagerank_5_node_path_sum_one() {
     let graph = graph_5_node_path();
     let config = PageRankConfig::default();
-    let ranks = pagerank_sequential(&graph, &config);
+    let ranks = pagerank_sequential(&graph, &config).ranks;
     assert_eq!(ranks.len(), 5);
     let sum: f64 = ranks.iter().sum();
     assert!((sum - 1.0).abs() < 1e-5);
@@ -514,8 +621,8 @@ fn pagerank_5_node_path_sum_one() {
 fn pagerank_deterministic_same_twice() {
     let (graph, _) = graph_3_node_path();
     let config = PageRankConfig::default();
-    let a = pagerank_sequential(&graph, &config);
-    let b = pagerank_sequential(&graph, &config);
+    let a = pagerank_sequential(&graph, &config).ranks;
+    let b = pagerank_sequential(&graph, &config).ranks;
     assert_eq!(a.len(), b.len());
     for (x, y) in a.iter().zip(b.iter()) {
         assert!((x - y).abs() < 1e-12);
@@ -525,7 +632,7 @@ fn pagerank_deterministic_same_twice() {
 #[test]
 fn pagerank_default_config() {
     let graph = graph_single_node();
-    let ranks = pagerank_sequential(&graph, &PageRankConfig::default());
+    let ranks = pagerank_sequential(&graph, &PageRankConfig::default()).ranks;
     assert_eq!(ranks.len(), 1);
     assert!((ranks[0] - 1.0).abs() < 1e-10);
 }
@@ -533,7 +640,7 @@ fn pagerank_default_config() {
 #[test]
 fn pagerank_path_all_positive() {
     let graph = graph_5_node_path();
-    let ranks = pagerank_sequential(&graph, &PageRankConfig::default());
+    let ranks = pagerank_sequential(&graph, &PageRankConfig::default()).ranks;
     for (i, &r) in ranks.iter().enumerate() {
         assert!(r > 0.0, "node {} rank {}", i, r);
     }
@@ -556,6 +663,79 @@ fn load_graph_from_file_valid() {
     let _ = std::fs::remove_file(&path);
 }
 
+#[test]
+fn load_graph_from_file_reports_malformed_line() {
+    let path = std::env::temp_dir().join("ftn_test_graph_malformed.txt");
+    std::fs::write(&path, "# header\n0 1\n1\n").unwrap();
+    let err = load_graph_from_file(path.to_str().unwrap()).unwrap_err();
+    assert!(matches!(err, Error::Parse { line: 3, .. }), "{}", err);
+
+    std::fs::write(&path, "0 1\n1 x\n").unwrap();
+    let err = load_graph_from_file(path.to_str().unwrap()).unwrap_err();
+    assert!(matches!(err, Error::Parse { line: 2, .. }), "{}", err);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn load_graph_from_file_reports_progress() {
+    let path = std::env::temp_dir().join("ftn_test_graph_progress.txt");
+    let contents = "0 1\n1 2\n2 0\n";
+    std::fs::write(&path, contents).unwrap();
+    let events = Mutex::new(Vec::new());
+    let sink = |e: &ProgressEvent| events.lock().unwrap().push(e.clone());
+
+    let graph = load_graph_from_file_with_progress(path.to_str().unwrap(), &sink).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(graph.num_edges, 3);
+    let total = contents.len() as u64;
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![
+            ProgressEvent::LoadProgress { bytes: total, total: Some(total) },
+            ProgressEvent::LoadFinished { nodes: 3, edges: 3 },
+        ]
+    );
+}
+
+#[test]
+fn load_graph_from_files_merges_operators_in_one_id_space() {
+    let dir = std::env::temp_dir();
+    let (bus, rail) = (dir.join("ftn_test_merge_bus.txt"), dir.join("ftn_test_merge_rail.txt"));
+    std::fs::write(&bus, "0 5000000000 2.0 bus\n5000000000 7 1.0 bus\n").unwrap();
+    std::fs::write(&rail, "7 0\n").unwrap();
+    let paths = [bus.to_str().unwrap(), rail.to_str().unwrap()];
+
+    let graph = load_graph_from_files_with_options(&paths, &LoadOptions::default(), &NoProgress).unwrap();
+    assert_eq!(graph.num_nodes, 3);
+    assert_eq!(graph.num_edges, 3);
+    assert_eq!(graph.original_ids(), Some(&[0, 7, 5000000000][..]));
+    // Node 7 of the rail file is node 7 of the bus file
+    assert_eq!(graph.neighbors(1), &[0]);
+    assert_eq!(graph.edge_weights(1), Some(&[1.0][..]));
+    assert_eq!(graph.edge_attr::<Categorical>("type").unwrap().get(graph.edge_range(1).start), Some(""));
+    assert_eq!(bfs_sequential(&graph, 0).unwrap().reachable, 3);
+
+    std::fs::write(&rail, "7 0\n7\n").unwrap();
+    let err = load_graph_from_files_with_options(&paths, &LoadOptions::default(), &NoProgress).unwrap_err();
+    match err {
+        Error::Parse { line: 2, message } => assert!(message.contains("ftn_test_merge_rail"), "{}", message),
+        other => panic!("expected a parse error, got {}", other),
+    }
+    let cli = Cli::parse_from(["tool", "wcc", "-i", paths[0], "--input", paths[1], "-o", "w.txt"]);
+    let Some(cli::Commands::Wcc(args)) = cli.command else { panic!("expected wcc") };
+    assert_eq!(args.input, paths);
+    for path in [bus, rail] {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+#[test]
+fn load_graph_from_missing_file_is_io_error() {
+    let err = load_graph_from_file("/nonexistent/ftn_graph.txt").unwrap_err();
+    assert!(matches!(err, Error::Io(_)));
+    assert!(err.to_string().contains("/nonexistent/ftn_graph.txt"));
+}
+
 #[test]
 fn graph_star_out_degree() {
     let graph = graph_star_4();
@@ -566,7 +746,7 @@ fn graph_star_out_degree() {
 #[test]
 fn wcc_duplicate_edges_still_one_component() {
     let graph = graph_duplicate_edges();
-    let comp = wcc_sequential(&graph);
+    let comp = wcc_sequential(&graph).labels;
     assert_eq!(comp[0], comp[1]);
 }
 
@@ -587,12 +767,3282 @@ fn load_graph_from_file_with_comments() {
     let _ = std::fs::remove_file(&path);
 }
 
+#[test]
+fn graph_view_hides_filtered_node() {
+    let graph = graph_4_node_diamond();
+    let view = GraphView::filtered(&graph, |v| v != 1, |_, _| true);
+    assert_eq!(view.node_count(), 4);
+    assert_eq!(view.num_visible_nodes(), 3);
+    assert_eq!(view.num_visible_edges(), 2);
+    let dist = bfs_sequential(&view, 0).unwrap().distances;
+    assert_eq!(dist, vec![0, -1, 1, 2]);
+}
+
+#[test]
+fn graph_view_edge_filter_splits_components() {
+    let graph = graph_5_node_path();
+    let view = GraphView::filtered(&graph, |_| true, |u, v| !(u == 2 && v == 3));
+    let comp = wcc_sequential(&view).labels;
+    assert_eq!(wcc_stats(&comp).num_components, 2);
+    assert_eq!(comp[0], comp[2]);
+    assert_ne!(comp[2], comp[3]);
+}
+
+#[test]
+fn typed_edge_list_loads_mode_column_and_filters_views() {
+    let graph = load_graph_from_str("1 2 4.0 tram\n0 1 2.0 bus\n0 2 9.0\n2 3 1.0 bus\n").unwrap();
+    let modes = graph.edge_attr::<Categorical>("type").unwrap();
+    assert_eq!(modes.len(), graph.num_edges);
+    // CSR order: 0->1, 0->2, 1->2, 2->3
+    let labels: Vec<_> = (0..graph.num_edges).map(|e| modes.get(e).unwrap()).collect();
+    assert_eq!(labels, vec!["bus", "", "tram", "bus"]);
+    assert_eq!(graph.weights, Some(vec![2.0, 9.0, 4.0, 1.0]));
+
+    let bus = modes.code("bus").unwrap();
+    let view = GraphView::filtered_by_edge(&graph, |_| true, |e| modes.codes()[e] == bus);
+    assert_eq!(view.num_visible_edges(), 2);
+    assert_eq!(bfs_sequential(&view, 0).unwrap().distances, vec![0, 1, -1, -1]);
+}
+
+#[test]
+fn edge_attributes_follow_edges_through_transpose_and_reorder() {
+    let mut graph = graph_4_node_diamond();
+    let time = EdgeAttr::new(vec![10.0, 20.0, 30.0, 40.0]);
+    graph.set_edge_attr("time", time).unwrap();
+    assert!(matches!(
+        graph.set_edge_attr("short", EdgeAttr::new(vec![1u8])),
+        Err(Error::AttributeLength { expected: 4, actual: 1, .. })
+    ));
+    assert!(graph.edge_attr::<EdgeAttr<u8>>("time").is_none());
+
+    let edge_times = |g: &Graph| {
+        let time = g.edge_attr::<EdgeAttr<f64>>("time").unwrap();
+        let mut out: Vec<(usize, usize, f64)> = (0..g.num_nodes)
+            .flat_map(|u| g.neighbors(u).iter().zip(time.for_node(g, u)).map(move |(&v, &t)| (u, v, t)))
+            .collect();
+        out.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        out
+    };
+    let t = graph.transpose();
+    let mut flipped: Vec<_> = edge_times(&graph).into_iter().map(|(u, v, w)| (v, u, w)).collect();
+    flipped.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(edge_times(&t), flipped);
+
+    let (reordered, perm) = reorder(&graph, ReorderStrategy::Degree);
+    let mut mapped: Vec<_> = edge_times(&reordered)
+        .into_iter()
+        .map(|(u, v, w)| (perm.old_id[u], perm.old_id[v], w))
+        .collect();
+    mapped.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(mapped, edge_times(&graph));
+}
+
+#[test]
+fn node_attributes_load_and_hide_closed_stops_from_bfs() {
+    let mut graph = graph_5_node_path();
+    let file = "id,name,zone,type\n0,Central,A,stop\n2,Market,A,closed\n# comment\n3, Harbor ,B,stop\n";
+    load_node_attributes(&mut graph, file.as_bytes()).unwrap();
+    let names = graph.node_attr::<NodeAttr<String>>("name").unwrap();
+    assert_eq!(names.get(3).map(String::as_str), Some("Harbor"));
+    assert_eq!(names.get(1).map(String::as_str), Some(""));
+    let zones = graph.node_attr::<Categorical>("zone").unwrap();
+    assert_eq!(zones.labels(), &["A", "", "B"]);
+
+    let types = graph.node_attr::<Categorical>("type").unwrap();
+    let view = GraphView::nodes_where(&graph, |v| !types.is(v, "closed"));
+    assert_eq!(bfs_sequential(&view, 0).unwrap().distances, vec![0, 1, -1, -1, -1]);
+
+    let (reordered, perm) = reorder(&graph, ReorderStrategy::Rcm);
+    let moved = reordered.node_attr::<NodeAttr<String>>("name").unwrap();
+    assert_eq!(moved.get(perm.new_id[3]).map(String::as_str), Some("Harbor"));
+
+    assert!(matches!(load_node_attributes(&mut graph, "9,Far,C,stop\n".as_bytes()), Err(Error::Parse { line: 1, .. })));
+}
+
+#[test]
+fn graph_view_pagerank_matches_unfiltered_when_everything_kept() {
+    let graph = graph_4_node_diamond();
+    let view = GraphView::filtered(&graph, |_| true, |_, _| true);
+    let config = PageRankConfig::default();
+    let a = pagerank_sequential(&graph, &config).ranks;
+    let b = pagerank_sequential(&view, &config).ranks;
+    for (x, y) in a.iter().zip(b.iter()) {
+        assert!((x - y).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn compact_graph_keeps_neighbor_order() {
+    let graph = build_csr(4, vec![(0, 3), (0, 1), (2, 0), (3, 2)]);
+    let compact = CompactGraph::from_graph(&graph).unwrap();
+    assert!(!compact.is_encoded());
+    assert_eq!(compact.neighbors(0).collect::<Vec<_>>(), vec![3, 1]);
+    assert_eq!(compact.out_degree(0), 2);
+    assert_eq!(compact.neighbors(9).count(), 0);
+}
+
+#[test]
+fn compact_graph_encoded_sorts_and_roundtrips_large_ids() {
+    let edges = vec![(0, 70_000), (0, 5), (0, 300), (0, 5), (1, 0)];
+    let graph = build_csr(70_001, edges);
+    let compact = CompactGraph::from_graph_encoded(&graph).unwrap();
+    assert!(compact.is_encoded());
+    assert_eq!(compact.neighbors(0).collect::<Vec<_>>(), vec![5, 5, 300, 70_000]);
+    assert_eq!(compact.neighbors(1).collect::<Vec<_>>(), vec![0]);
+    assert_eq!(compact.degree(0), 4);
+}
+
+#[test]
+fn graph_storage_runs_algorithms() {
+    let graph = graph_4_node_diamond();
+    let expected = bfs_sequential(&graph, 0).unwrap().distances;
+    let storage = GraphStorage::encoded_if_fits(graph.clone());
+    assert!(storage.is_compact());
+    assert_eq!(bfs_sequential(&storage, 0).unwrap().distances, expected);
+    assert_eq!(wcc_stats(&wcc_sequential(&storage).labels).num_components, 1);
+    let storage = GraphStorage::compact_if_fits(graph);
+    assert!(storage.is_compact());
+    assert_eq!(pagerank_sequential(&storage, &PageRankConfig::default()).ranks.len(), 4);
+
+    // Cache keys and file ids carry over from the wide graph
+    let sparse = load_graph_from_str("10 20\n20 30\n40 4000000000\n").unwrap();
+    let storage = GraphStorage::compact_if_fits(sparse.clone());
+    assert!(storage.is_compact());
+    assert_eq!(storage.fingerprint(), sparse.fingerprint());
+    assert_eq!(storage.original_ids(), Some(&[10, 20, 30, 40, 4_000_000_000][..]));
+}
+
+#[test]
+fn sequential_commands_narrow_the_graph_unless_wide_storage() {
+    let dir = std::env::temp_dir();
+    let input = dir.join("ftn_test_compact_commands.txt");
+    std::fs::write(&input, "10 20\n20 30\n40 4000000000\n").unwrap();
+    let input = input.to_str().unwrap();
+    let out = dir.join("ftn_test_compact_commands_out.txt");
+    let out = out.to_str().unwrap();
+    let args = |cmd: &[&str]| Cli::parse_from(["tool"].iter().chain(cmd).chain(&["-i", input, "-o", out])).command.unwrap();
+    let cli::Commands::Bfs(bfs) = args(&["bfs", "-s", "10", "-m", "seq"]) else { unreachable!() };
+    let cli::Commands::Wcc(wcc) = args(&["wcc", "-m", "seq"]) else { unreachable!() };
+    let cli::Commands::Pagerank(pagerank) = args(&["pagerank", "-m", "seq"]) else { unreachable!() };
+
+    let run = |ctx: &commands::Context| {
+        let distances = commands::bfs::run(&bfs, ctx).unwrap().result.distances;
+        let labels = commands::wcc::run(&wcc, ctx).unwrap().result.labels;
+        let ranks = commands::pagerank::run(&pagerank, ctx).unwrap().result.ranks;
+        assert!(std::fs::exists(derived_path(out, "_ids")).unwrap());
+        (distances, labels, ranks)
+    };
+    let narrow = run(&commands::Context::default());
+    let wide = run(&commands::Context { load: LoadOptions { wide_storage: true, ..LoadOptions::default() }, ..Default::default() });
+    assert_eq!(narrow, wide);
+    assert_eq!(narrow.0, vec![0, 1, 2, -1, -1]);
+    assert_eq!(narrow.1, vec![0, 0, 0, 3, 3]);
+    let _ = std::fs::remove_file(input);
+}
+
+#[test]
+fn reorder_strategies_are_permutations_preserving_bfs() {
+    let graph = build_csr(
+        7,
+        vec![(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (4, 5), (5, 6), (6, 0), (2, 6)],
+    );
+    let expected = bfs_sequential(&graph, 0).unwrap().distances;
+    for strategy in [ReorderStrategy::Degree, ReorderStrategy::Rcm, ReorderStrategy::Gorder] {
+        let (reordered, perm) = reorder(&graph, strategy);
+        let mut seen = perm.old_id.clone();
+        seen.sort();
+        assert_eq!(seen, (0..7).collect::<Vec<_>>(), "{:?}", strategy);
+        assert_eq!(reordered.num_edges, graph.num_edges);
+        let dist = bfs_sequential(&reordered, perm.new_id[0]).unwrap().distances;
+        assert_eq!(perm.to_original(&dist), expected, "{:?}", strategy);
+    }
+}
+
+#[test]
+fn reorder_degree_puts_hub_first() {
+    let (reordered, perm) = reorder(&graph_isolated_plus_path(), ReorderStrategy::Degree);
+    assert_eq!(reordered.out_degree[0], 1);
+    assert_eq!(perm.old_id[0], 1);
+    let graph = graph_star_4();
+    let (_, perm) = reorder(&graph, ReorderStrategy::Degree);
+    assert_eq!(perm.new_id[0], 0);
+}
+
+#[test]
+fn reordering_and_partition_renumbering_preserve_structure() {
+    for graph in testing::arbitrary_graphs(11, 40).take(60) {
+        let mut sorted = graph.clone();
+        sorted.sort_neighbors();
+        assert!(sorted.structural_eq(&graph));
+        for strategy in [ReorderStrategy::Degree, ReorderStrategy::Rcm, ReorderStrategy::Gorder] {
+            let (reordered, perm) = reorder(&graph, strategy);
+            assert!(graph.eq_under_mapping(&reordered, &perm), "{:?}", strategy);
+        }
+        if graph.num_nodes > 0 {
+            // Renumber nodes part by part, as when handing each part to its own worker
+            let parts = partition(&graph, 3, PartitionMethod::Ldg).parts;
+            let mut order: Vec<usize> = (0..graph.num_nodes).collect();
+            order.sort_by_key(|&v| parts[v]);
+            let perm = Permutation::from_order(order);
+            let renumbered = fast_transit_network::graph::reorder::permute(&graph, &perm);
+            assert!(graph.eq_under_mapping(&renumbered, &perm));
+        }
+    }
+
+    let graph = build_csr_weighted(3, vec![(0, 1, 2.0), (0, 1, 2.0), (1, 2, 1.0)]);
+    let shuffled = build_csr_weighted(3, vec![(1, 2, 1.0), (0, 1, 2.0), (0, 1, 2.0)]);
+    assert!(graph.structural_eq(&shuffled));
+    assert!(!graph.structural_eq(&build_csr_weighted(3, vec![(0, 1, 2.0), (0, 1, 3.0), (1, 2, 1.0)])));
+    assert!(!graph.structural_eq(&build_csr_weighted(3, vec![(0, 1, 2.0), (0, 2, 2.0), (1, 2, 1.0)])));
+    assert!(!graph.structural_eq(&build_csr(3, vec![(0, 1), (0, 1), (1, 2)])));
+    assert!(!testing::path(3).structural_eq(&testing::path(4)));
+    let swap = Permutation::from_order(vec![1, 0, 2]);
+    assert!(testing::path(3).eq_under_mapping(&build_csr(3, vec![(1, 0), (0, 2)]), &swap));
+    assert!(!testing::path(3).eq_under_mapping(&testing::path(3), &swap));
+    assert!(!testing::path(3).eq_under_mapping(&testing::path(3), &Permutation::identity(2)));
+}
+
+#[test]
+fn permutation_maps_wcc_labels_back() {
+    let graph = graph_two_components();
+    let perm = Permutation::from_order(vec![3, 2, 1, 0]);
+    let reordered = fast_transit_network::graph::reorder::permute(&graph, &perm);
+    let labels = perm.labels_to_original(&wcc_sequential(&reordered).labels);
+    assert_eq!(labels[0], labels[1]);
+    assert_eq!(labels[2], labels[3]);
+    assert!(labels.iter().all(|&l| l < 4));
+    assert_ne!(labels[0], labels[2]);
+
+    // Saved results are relabeled by the smallest original id
+    let dir = std::env::temp_dir();
+    let (out, stats) = (dir.join("ftn_test_wcc_reordered.txt"), dir.join("ftn_test_wcc_reordered_stats.txt"));
+    let saved = fast_transit_network::algorithms::wcc::run_wcc_and_save(
+        &GraphStorage::Wide(reordered),
+        Mode::Seq,
+        WccMethod::UnionFind,
+        1,
+        out.to_str().unwrap(),
+        stats.to_str().unwrap(),
+        Some(&perm),
+        ParallelismPolicy::Auto,
+        OutputFormat::Text,
+        WccColumns::default(),
+        None,
+    )
+    .unwrap();
+    assert_eq!(saved.labels, vec![0, 0, 2, 2]);
+    let _ = (std::fs::remove_file(out), std::fs::remove_file(stats));
+}
+
+#[test]
+fn thread_pool_reused_per_thread_count() {
+    let a = get_thread_pool(3);
+    let b = get_thread_pool(3);
+    assert!(std::ptr::eq(a, b));
+    assert_eq!(a.current_num_threads(), 3);
+    assert_eq!(get_thread_pool(2).current_num_threads(), 2);
+    assert_eq!(get_thread_pool(0).current_num_threads(), 1);
+}
+
+#[test]
+fn config_cli_flags_override_defaults() {
+    let cli = Cli::parse_from([
+        "tool", "--bfs-min-nodes", "10", "wcc", "-i", "g.txt", "-o", "out.txt", "--chunk-size", "0",
+    ]);
+    let config = cli.apply_to(Config::default());
+    assert_eq!(config.bfs_min_nodes, 10);
+    assert_eq!(config.chunk_size, Some(1));
+    assert_eq!(config.wcc_min_nodes, Config::default().wcc_min_nodes);
+    assert_eq!(config.threads, 4);
+}
+
+#[test]
+fn cli_verbose_and_quiet_select_log_level() {
+    let parse = |args: &[&str]| Cli::parse_from(args.iter().copied());
+    let base = ["tool", "wcc", "-i", "g.txt", "-o", "out.txt"];
+    assert_eq!(parse(&base).log_level(), tracing::Level::INFO);
+    assert_eq!(parse(&[&base[..], &["--verbose"]].concat()).log_level(), tracing::Level::DEBUG);
+    assert_eq!(parse(&[&base[..], &["-q"]].concat()).log_level(), tracing::Level::WARN);
+    assert!(Cli::try_parse_from([&base[..], &["-q", "-v"]].concat()).is_err());
+}
+
+#[test]
+fn threads_auto_resolves_per_workload() {
+    let cpus = available_threads(None);
+    assert!(cpus >= 1);
+    assert_eq!(available_threads(Some(1)), 1);
+    assert_eq!(available_threads(Some(0)), 1);
+    assert_eq!("auto".parse::<Threads>().unwrap(), Threads::Auto);
+    assert_eq!(" 12 ".parse::<Threads>().unwrap(), Threads::Count(12));
+    assert!("all".parse::<Threads>().is_err());
+    assert_eq!(Threads::Count(12).resolve(Workload::Bfs), 12);
+    assert_eq!(Threads::Count(0).resolve(Workload::Wcc), 1);
+    assert_eq!(Threads::Auto.resolve(Workload::Bfs), cpus.min(BFS_AUTO_MAX_THREADS));
+    assert_eq!(Threads::Auto.resolve(Workload::Other), cpus);
+
+    let sweep = Threads::sweep();
+    assert_eq!(*sweep.last().unwrap(), cpus);
+    assert!(sweep.windows(2).all(|w| w[0] < w[1]));
+
+    let cli = Cli::parse_from(["tool", "wcc", "-i", "g.txt", "-o", "out.txt", "--threads", "auto"]);
+    assert!(matches!(cli.command, Some(cli::Commands::Wcc(cli::WccArgs { threads: Some(Threads::Auto), .. }))));
+    assert!(Cli::try_parse_from(["tool", "wcc", "-i", "g.txt", "-o", "out.txt", "--threads", "many"]).is_err());
+}
+
+#[test]
+fn commands_return_structured_reports() {
+    let dir = std::env::temp_dir();
+    let input = dir.join("ftn_test_commands.txt");
+    write_edge_list(&graph_two_components(), input.to_str().unwrap()).unwrap();
+    let input = input.to_str().unwrap();
+    let out = dir.join("ftn_test_commands_out.txt");
+    let out = out.to_str().unwrap();
+    let ctx = commands::Context::default();
+    let args = |cmd: &[&str]| Cli::parse_from(["tool"].iter().chain(cmd).copied()).command.unwrap();
+
+    let cli::Commands::Bfs(bfs) = args(&["bfs", "-i", input, "-s", "0", "-m", "par", "-o", out]) else { unreachable!() };
+    let report = commands::bfs::run(&bfs, &ctx).unwrap();
+    assert_eq!(report.result.distances, vec![0, 1, -1, -1]);
+    assert_eq!(report.num_nodes, 4);
+
+    let cli::Commands::Wcc(wcc) = args(&["wcc", "-i", input, "-o", out]) else { unreachable!() };
+    assert_eq!(commands::wcc::run(&wcc, &ctx).unwrap().result.stats.num_components, 2);
+    
+    let connectivity = |kind: &str| {
+        let cli::Commands::Connectivity(args) = args(&["connectivity", "--kind", kind, "-i", input, "-o", out]) else { unreachable!() };
+        commands::connectivity::run(&args, &ctx).unwrap().result
+    };
+    assert_eq!(connectivity("weak").stats.num_components, 2);
+    let strong = connectivity("strong");
+    assert_eq!(strong.stats.num_components, 3);
+    assert_eq!(strong.labels[0], strong.labels[1]);
+    assert_ne!(strong.labels[2], strong.labels[3]);
+    
+    let cli::Commands::Anf(anf) = args(&["anf", "-i", input, "-o", out]) else { unreachable!() };
+    let report = commands::anf::run(&anf, &ctx).unwrap();
+    assert!(report.function.converged);
+    assert!(std::fs::read_to_string(out).unwrap().starts_with("distance,pairs,cumulative_pairs,cumulative_fraction\n0,"));
+
+    let cli::Commands::Validate(validate) = args(&["validate", "-i", input]) else { unreachable!() };
+    assert!(commands::validate::run(&validate, &ctx).unwrap().validation.is_valid());
+
+    let cli::Commands::Run(run) = args(&["run", "scc", "-i", input, "-o", out]) else { unreachable!() };
+    let Output::Labels(labels) = commands::run::run(&run, &ctx).unwrap().output else { panic!("scc gives labels") };
+    assert_eq!(labels[0], labels[1]);
+    assert_ne!(labels[2], labels[3]);
+
+    let cli::Commands::Bfs(bad) = args(&["bfs", "-i", input, "-s", "9", "-o", out]) else { unreachable!() };
+    assert!(commands::bfs::run(&bad, &ctx).is_err());
+    for path in [input.to_string(), out.to_string(), derived_path(out, "_stats")] {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+#[test]
+fn cli_mode_is_parsed_into_an_enum() {
+    let mode = |args: &[&str]| match Cli::try_parse_from(args.iter().copied()).map(|cli| cli.command) {
+        Ok(Some(cli::Commands::Pagerank(cli::PagerankArgs { mode, .. }) | cli::Commands::Bfs(cli::BfsArgs { mode, .. }))) => Ok(mode),
+        Ok(_) => panic!("unexpected command"),
+        Err(e) => Err(e.kind()),
+    };
+    let pagerank = ["tool", "pagerank", "-i", "g.txt", "-o", "pr.txt"];
+    assert_eq!(mode(&pagerank), Ok(Mode::Seq));
+    assert_eq!(mode(&[&pagerank[..], &["-m", "par-opt"]].concat()), Ok(Mode::ParOpt));
+    assert_eq!(mode(&["tool", "bfs", "-i", "g.txt", "-s", "0", "-o", "d.txt", "--mode", "par"]), Ok(Mode::Par));
+    assert_eq!(mode(&[&pagerank[..], &["-m", "parallel"]].concat()), Err(clap::error::ErrorKind::InvalidValue));
+    assert_eq!(Mode::ParOpt.to_string(), "par-opt");
+    assert!(Mode::ParOpt.is_parallel() && !Mode::Seq.is_parallel());
+}
+
+#[test]
+fn run_config_expands_steps_to_command_lines() {
+    let run = RunConfig::from_toml(
+        r#"
+        output_dir = "results"
+
+        [global]
+        bfs_min_nodes = 0
+
+        [defaults]
+        input = "g.txt"
+        threads = 8
+
+        [[run]]
+        command = "pagerank"
+        mode = "par"
+        alpha = 0.9
+        force_parallel = true
+        out = "pr.txt"
+
+        [[run]]
+        command = "benchmark"
+        threads = [1, 4]
+        algorithms = ["bfs", "wcc"]
+
+        [[run]]
+        command = "diff"
+        old = "a.txt"
+        new = "b.txt"
+        "#,
+    )
+    .unwrap();
+    let out = std::path::Path::new("results").join("pr.txt").to_string_lossy().into_owned();
+    assert_eq!(
+        run.step_args().unwrap(),
+        vec![
+            vec!["--bfs-min-nodes", "0", "pagerank", "--alpha", "0.9", "--force-parallel", "--input", "g.txt", "--mode", "par", "--out", &out, "--threads", "8"],
+            vec!["--bfs-min-nodes", "0", "benchmark", "--algorithms", "bfs,wcc", "--input", "g.txt", "--threads", "1,4"],
+            vec!["--bfs-min-nodes", "0", "diff", "a.txt", "b.txt"],
+        ]
+    );
+
+    let steps = run.steps().unwrap();
+    assert_eq!(steps.len(), 3);
+    assert_eq!(steps[0].cli.apply_to(Config::default()).bfs_min_nodes, 0);
+    assert!(matches!(
+        &steps[0].cli.command,
+        Some(fast_transit_network::cli::Commands::Pagerank(cli::PagerankArgs { threads: Some(Threads::Count(8)), force_parallel: true, alpha, .. })) if *alpha == 0.9
+    ));
+
+    let typo = RunConfig::from_toml("[[run]]\ncommand = \"pagerank\"\ninput = \"g.txt\"\nout = \"pr.txt\"\niterations = 5\n").unwrap();
+    assert!(typo.steps().is_err());
+    assert!(RunConfig::from_toml("[[run]]\nmode = \"par\"\n").unwrap().step_args().is_err());
+    assert!(RunConfig::from_toml("outdir = \"x\"\n").is_err());
+}
+
+#[test]
+fn cli_definition_has_no_conflicting_flags() {
+    use clap::CommandFactory;
+    Cli::command().debug_assert();
+}
+
+#[test]
+fn bfs_result_reports_reachable_and_levels() {
+    let result = bfs_sequential(&graph_isolated_plus_path(), 1).unwrap();
+    assert_eq!(result.distances, vec![-1, 0, 1, 2]);
+    assert_eq!(result.reachable, 3);
+    assert_eq!(result.levels, 3);
+    assert!(result.level_stats.is_empty());
+}
+
+#[test]
+fn bfs_records_per_level_frontier_and_edges() {
+    // Star center 0 plus a path 1 -> 4 -> 5 hanging off leaf 1
+    let graph = build_csr(6, vec![(0, 1), (0, 2), (0, 3), (1, 4), (4, 5), (5, 0)]);
+    let sequential = bfs_sequential_with_level_stats(&graph, 0, &NoProgress).unwrap();
+    let parallel = bfs_parallel_with_level_stats(&graph, 0, 2, ParallelismPolicy::ForceParallel, &NoProgress).unwrap();
+    for result in [&sequential, &parallel] {
+        let shape: Vec<_> = result.level_stats.iter().map(|l| (l.level, l.frontier, l.edges)).collect();
+        assert_eq!(shape, vec![(0, 1, 3), (1, 3, 1), (2, 1, 1), (3, 1, 1)]);
+        assert_eq!(result.level_stats.len(), result.levels);
+    }
+    assert!(sequential.level_stats.iter().all(|l| !l.parallel));
+    #[cfg(feature = "parallel")]
+    assert!(parallel.level_stats.iter().all(|l| l.parallel));
+    assert_eq!(parallel.distances, sequential.distances);
+    
+    let path = std::env::temp_dir().join("ftn_test_bfs_levels.csv");
+    let path = path.to_str().unwrap();
+    write_bfs_levels_csv(&sequential.level_stats, path).unwrap();
+    let csv = std::fs::read_to_string(path).unwrap();
+    assert_eq!(csv.lines().next(), Some("level,frontier,edges,parallel,time_ms,split_hubs"));
+    assert!(csv.lines().nth(2).unwrap().starts_with("1,3,1,false,"));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn pagerank_result_reports_convergence() {
+    let converged = pagerank_sequential(&graph_two_node_cycle(), &PageRankConfig::default());
+    assert!(converged.converged);
+    assert!(converged.final_delta < 1e-6);
+    assert!(converged.iterations >= 1);
+
+    let config = PageRankConfig {
+        max_iterations: 2,
+        tolerance: 0.0,
+        ..PageRankConfig::default()
+    };
+    let capped = pagerank_sequential(&graph_5_node_path(), &config);
+    assert!(!capped.converged);
+    assert_eq!(capped.iterations, 2);
+    assert!(capped.final_delta > 0.0);
+}
+
+#[test]
+fn wcc_result_carries_stats() {
+    let result: WccResult = wcc_sequential(&graph_two_components());
+    assert_eq!(result.labels.len(), 4);
+    assert_eq!(result.stats.num_components, 2);
+    assert_eq!(result.stats.largest_component, 2);
+}
+
+#[test]
+fn force_parallel_small_graphs_match_sequential() {
+    let graph = build_csr(
+        6,
+        vec![(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (5, 4)],
+    );
+    let policy = ParallelismPolicy::ForceParallel;
+    assert_eq!(bfs_parallel_with_policy(&graph, 0, 2, policy).unwrap().distances, bfs_sequential(&graph, 0).unwrap().distances);
+
+    let comp = wcc_parallel_with_policy(&graph, 2, policy).labels;
+    assert_eq!(wcc_stats(&comp).num_components, 1);
+
+    let config = PageRankConfig::default();
+    let seq = pagerank_sequential(&graph, &config).ranks;
+    let par = pagerank_parallel_with_policy(&graph, &config, 2, policy).ranks;
+    for (a, b) in seq.iter().zip(par.iter()) {
+        assert!((a - b).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn parallelism_policy_thresholds() {
+    assert!(!ParallelismPolicy::Auto.use_parallel(10, 100));
+    assert!(ParallelismPolicy::Auto.use_parallel(100, 100));
+    assert!(ParallelismPolicy::ForceParallel.use_parallel(0, 100));
+    assert!(ParallelismPolicy::MinNodes(5).use_parallel(10, 100));
+    assert!(!ParallelismPolicy::MinNodes(50).use_parallel(10, 0));
+}
+
+fn bfs_level_sizes(events: &[ProgressEvent]) -> Vec<usize> {
+    events
+        .iter()
+        .map(|e| match e {
+            ProgressEvent::BfsLevel { frontier, .. } => *frontier,
+            other => panic!("unexpected event {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn bfs_progress_reports_level_sizes() {
+    let graph = graph_4_node_diamond();
+    let events = Mutex::new(Vec::new());
+    let sink = |e: &ProgressEvent| events.lock().unwrap().push(e.clone());
+
+    bfs_sequential_with_progress(&graph, 0, &sink).unwrap();
+    assert_eq!(bfs_level_sizes(&events.lock().unwrap()), vec![1, 2, 1]);
+
+    events.lock().unwrap().clear();
+    bfs_parallel_with_progress(&graph, 0, 2, ParallelismPolicy::ForceParallel, &sink).unwrap();
+    assert_eq!(bfs_level_sizes(&events.lock().unwrap()), vec![1, 2, 1]);
+}
+
+#[test]
+fn pagerank_progress_reports_iterations_and_finish() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink_events = Arc::clone(&events);
+    let config = PageRankConfig {
+        max_iterations: 3,
+        tolerance: 1e-15,
+        progress: Some(Arc::new(move |e: &ProgressEvent| sink_events.lock().unwrap().push(e.clone()))),
+        ..PageRankConfig::default()
+    };
+    pagerank_sequential(&graph_5_node_path(), &config);
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 4);
+    assert!(matches!(events[0], ProgressEvent::PageRankIteration { iteration: 1, .. }));
+    assert!(matches!(
+        events[3],
+        ProgressEvent::PageRankFinished { iterations: 3, converged: false, .. }
+    ));
+}
+
+#[test]
+fn graph_stats_counts_loops_duplicates_and_dangling() {
+    let graph = build_csr(5, vec![(0, 1), (0, 1), (1, 1), (1, 2), (0, 2)]);
+    let stats = GraphStats::compute(&graph);
+    assert_eq!(stats.num_edges, 5);
+    assert_eq!(stats.self_loops, 1);
+    assert_eq!(stats.duplicate_edges, 1);
+    assert_eq!(stats.dangling_nodes, 3);
+    assert_eq!(stats.isolated_nodes, 2);
+    assert_eq!(stats.max_out_degree, 3);
+    assert_eq!(stats.max_in_degree, 3);
+    // out-degrees 3, 2, 0, 0, 0 -> buckets [0], [2, 4)
+    assert_eq!(stats.degree_histogram, vec![3, 0, 2]);
+    assert_eq!(stats.memory_bytes, graph.memory_bytes());
+}
+
+#[test]
+fn graph_stats_empty_graph() {
+    let stats = GraphStats::compute(&graph_empty());
+    assert_eq!(stats.num_nodes, 0);
+    assert_eq!(stats.avg_degree, 0.0);
+    assert!(stats.degree_histogram.is_empty());
+}
+
+#[test]
+fn shortest_path_follows_fewest_hops() {
+    let graph = graph_4_node_diamond();
+    let path = shortest_path(&graph, 0, 3).unwrap().unwrap();
+    assert_eq!(path.len(), 3);
+    assert_eq!((path[0], path[2]), (0, 3));
+    assert_eq!(shortest_path(&graph, 2, 2).unwrap(), Some(vec![2]));
+    assert_eq!(shortest_path(&graph, 3, 0).unwrap(), None);
+    assert!(shortest_path(&graph, 0, 9).is_err());
+}
+
+#[test]
+fn target_searches_stop_early_with_the_full_search_distances() {
+    let graph = grid_graph(20);
+    let full = bfs_sequential(&graph, 0).unwrap().distances;
+    let targets = [21, 5, 21, 0];
+    let all = bfs_to_targets(&graph, 0, &targets, StopAt::All).unwrap();
+    assert_eq!(all.distances, targets.iter().map(|&t| full[t]).collect::<Vec<_>>());
+    assert!(all.settled < graph.num_nodes / 2, "settled {} of {}", all.settled, graph.num_nodes);
+    // The source is a target, so `any` stops at once
+    assert_eq!(bfs_to_targets(&graph, 0, &targets, StopAt::Any).unwrap().settled, 1);
+    let any = bfs_to_targets(&graph, 0, &[399, 5], StopAt::Any).unwrap();
+    assert_eq!(any.nearest(), Some((5, 5)));
+    assert_eq!(any.distances[0], -1, "not reached before the stop");
+    assert!(bfs_to_targets(&graph, 0, &[400], StopAt::All).is_err());
+    
+    let weighted = random_weighted_graph(300, 1500, 7, 0.5);
+    let full = dijkstra(&weighted, 0).unwrap();
+    let targets = [17, 250, 3];
+    let all = dijkstra_to_targets(&weighted, 0, &targets, StopAt::All).unwrap();
+    for (&t, &d) in targets.iter().zip(&all.distances) {
+        assert_eq!(d, full.distances[t], "target {}", t);
+    }
+    let nearest = targets.iter().copied().filter(|&t| full.is_reachable(t)).min_by(|&a, &b| full.distances[a].total_cmp(&full.distances[b]));
+    let any = dijkstra_to_targets(&weighted, 0, &targets, StopAt::Any).unwrap();
+    assert_eq!(any.nearest().map(|(t, _)| t), nearest);
+    assert!(any.settled <= all.settled);
+    
+    let negative = build_csr_weighted(3, vec![(0, 1, 1.0), (1, 2, -1.0)]);
+    assert!(dijkstra_to_targets(&negative, 0, &[1], StopAt::All).is_ok(), "the negative edge is never scanned");
+    assert!(matches!(dijkstra_to_targets(&negative, 0, &[2], StopAt::All), Err(Error::NegativeWeight { from: 1, to: 2 })));
+}
+
+#[test]
+fn query_parse() {
+    assert_eq!("neighbors 3".parse::<Query>().unwrap(), Query::Neighbors(3));
+    assert_eq!("  dist 1   2 ".parse::<Query>().unwrap(), Query::Dist(1, 2));
+    assert_eq!("quit".parse::<Query>().unwrap(), Query::Quit);
+    assert_eq!("dists 0 4 2".parse::<Query>().unwrap(), Query::Dists(0, vec![4, 2]));
+    assert!("nearest 0".parse::<Query>().is_err());
+    assert!("path 1".parse::<Query>().is_err());
+    assert!("degree x".parse::<Query>().is_err());
+    assert!("degree 1 2".parse::<Query>().is_err());
+    assert!("teleport 1".parse::<Query>().is_err());
+}
+
+#[test]
+fn query_session_answers_until_quit() {
+    let input = "neighbors 0\ndegree 3\npath 0 4\ndist 4 0\ndegree 99\ndists 1 4 0 1\nnearest 0 4 3\n\nquit\ndegree 0\n";
+    let mut output = Vec::new();
+    run_interactive(&graph_5_node_path(), input.as_bytes(), &mut output, false).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[..4], ["1", "1", "0 -> 1 -> 2 -> 3 -> 4", "-1"]);
+    assert!(lines[4].starts_with("error:"));
+    assert_eq!(lines[5..7], ["3 -1 0", "3 3"]);
+    assert_eq!(lines.len(), 7, "nothing is answered after quit");
+}
+
+#[test]
+fn query_session_uses_file_ids_of_remapped_graphs() {
+    // Dense ids 0, 1, 2 stand for file ids 0, 7, 4000000000
+    let graph = load_graph_from_str("0 4000000000\n4000000000 7\n").unwrap();
+    let input = "neighbors 4000000000\npath 0 7\ndists 0 7 4000000000\nnearest 0 7\ndegree 1\n";
+    let mut output = Vec::new();
+    run_interactive(&graph, input.as_bytes(), &mut output, false).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[..4], ["7", "0 -> 4000000000 -> 7", "2 1", "7 2"]);
+    assert!(lines[4].starts_with("error:"), "dense id 1 is not a file id");
+}
+
+#[cfg(feature = "serve")]
+fn http_get(addr: std::net::SocketAddr, path: &str) -> (u16, serde_json::Value) {
+    use std::io::Read;
+    let mut stream = std::net::TcpStream::connect(addr).unwrap();
+    write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", path).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status = response[9..12].parse().unwrap();
+    let body = response.split("\r\n\r\n").nth(1).unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+#[cfg(feature = "serve")]
+#[test]
+fn server_answers_route_rank_and_component_requests() {
+    use fast_transit_network::server::{router, ServerState};
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let listener = rt.block_on(tokio::net::TcpListener::bind("127.0.0.1:0")).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = router(Arc::new(ServerState::new(graph_two_components())));
+    rt.spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    assert_eq!(http_get(addr, "/health").1["edges"], 3);
+    assert_eq!(http_get(addr, "/distance?from=0&to=1").1["distance"], 1);
+    assert!(http_get(addr, "/path?from=0&to=3").1["path"].is_null());
+    assert_eq!(http_get(addr, "/component/3").1["component_size"], 2);
+    assert!(http_get(addr, "/pagerank/2").1["rank"].as_f64().unwrap() > 0.0);
+    let (status, body) = http_get(addr, "/pagerank/10");
+    assert_eq!(status, 404);
+    assert!(body["error"].as_str().unwrap().contains("10"));
+
+    let graph = load_graph_from_str("0 4000000000\n4000000000 7\n").unwrap();
+    let listener = rt.block_on(tokio::net::TcpListener::bind("127.0.0.1:0")).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = router(Arc::new(ServerState::new(graph)));
+    rt.spawn(async move { axum::serve(listener, app).await.unwrap() });
+    assert_eq!(http_get(addr, "/path?from=0&to=7").1["path"], serde_json::json!([0, 4000000000u64, 7]));
+    assert_eq!(http_get(addr, "/component/4000000000").1["component_size"], 3);
+    assert!(http_get(addr, "/pagerank/7").1["rank"].as_f64().unwrap() > 0.0);
+    assert_eq!(http_get(addr, "/pagerank/1").0, 404);
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn ffi_roundtrip_bfs_wcc_pagerank() {
+    use fast_transit_network::ffi::*;
+    let src = [0usize, 1, 2];
+    let dst = [1usize, 0, 3];
+    unsafe {
+        let graph = ftn_graph_from_edges(4, src.as_ptr(), dst.as_ptr(), 3);
+        assert!(!graph.is_null());
+        assert_eq!(ftn_graph_num_edges(graph), 3);
+
+        let bfs = ftn_bfs(graph, 0, 0);
+        assert_eq!(std::slice::from_raw_parts(bfs.distances, bfs.len), &[0, 1, -1, -1]);
+        assert_eq!(bfs.reachable, 2);
+        ftn_free_bfs_result(bfs);
+
+        let bad = ftn_bfs(graph, 9, 2);
+        assert!(bad.distances.is_null());
+        assert!(!ftn_last_error().is_null());
+
+        let wcc = ftn_wcc(graph, 2);
+        assert_eq!(wcc.num_components, 2);
+        ftn_free_wcc_result(wcc);
+
+        let pr = ftn_pagerank(graph, 0.85, 100, 1e-8, 0);
+        let sum: f64 = std::slice::from_raw_parts(pr.ranks, pr.len).iter().sum();
+        assert!((sum - 1.0).abs() < 1e-6);
+        ftn_free_pagerank_result(pr);
+
+        ftn_free_graph(graph);
+        assert!(ftn_graph_from_edges(2, src.as_ptr(), dst.as_ptr(), 3).is_null());
+    }
+}
+
+#[test]
+fn load_graph_from_str_matches_file_format() {
+    let graph = load_graph_from_str("# comment\n0 1\n\n1 2\n// another\n2 0\n").unwrap();
+    assert_eq!(graph.num_nodes, 3);
+    assert_eq!(graph.num_edges, 3);
+    assert_eq!(graph.neighbors(2), &[0]);
+    assert!(matches!(load_graph_from_str("0 1\n2\n"), Err(Error::Parse { line: 2, .. })));
+}
+
+#[test]
+fn loader_reports_node_ids_that_would_overflow_the_node_count() {
+    let max = format!("0 1\n{} 0\n", usize::MAX);
+    match load_graph_from_str(&max) {
+        Err(Error::Parse { line: 2, message }) => assert!(message.contains("exceeds"), "{}", message),
+        other => panic!("expected a parse error, got {:?}", other.map(|g| g.num_nodes)),
+    }
+    let too_big = format!("0 {}0\n", u64::MAX);
+    assert!(matches!(load_graph_from_str(&too_big), Err(Error::Parse { line: 1, .. })));
+}
+
+#[test]
+fn loader_reports_self_loops_duplicates_and_malformed_lines() {
+    let text = "0 1 2.0\n1 1\n0 1 5.0\nbad line\n2 x\n1 2\n";
+    assert!(matches!(load_graph_from_str(text), Err(Error::Parse { line: 4, .. })));
+
+    let options = LoadOptions { skip_malformed: true, ..LoadOptions::default() };
+    let graph = load_graph_from_reader_with_options(text.as_bytes(), &options).unwrap();
+    let report = graph.load_report.clone().unwrap();
+    assert_eq!((report.edges_read, report.self_loops, report.duplicate_edges, report.dropped_edges), (4, 1, 1, 0));
+    assert_eq!(report.malformed_lines, 2);
+    assert_eq!(report.first_malformed.map(|(line, _)| line), Some(4));
+    assert_eq!(graph.num_edges, 4);
+
+    let options = LoadOptions { skip_malformed: true, drop_self_loops: true, drop_duplicates: true, ..options };
+    let graph = load_graph_from_reader_with_options(text.as_bytes(), &options).unwrap();
+    assert_eq!(graph.load_report.as_ref().unwrap().dropped_edges, 2);
+    assert_eq!(graph.num_edges, 2);
+    assert_eq!(graph.neighbors(0), &[1]);
+    assert_eq!(graph.neighbors(1), &[2]);
+    assert_eq!(graph.edge_weights(0), Some(&[2.0][..]));
+    assert_eq!(graph.offsets, vec![0, 1, 2, 2]);
+}
+
+#[test]
+fn loader_parses_lines_split_across_buffer_refills() {
+    let text = "# header\r\n0\t1  2.5 bus\r\n+1 2\n  // note\n2 0 1e0\n3 3";
+    // A 3-byte buffer splits almost every line between two refills
+    for capacity in [3, 8, 1 << 16] {
+        let reader = std::io::BufReader::with_capacity(capacity, text.as_bytes());
+        let graph = load_graph_from_reader_with_options(reader, &LoadOptions::default()).unwrap();
+        assert_eq!(graph.num_edges, 4);
+        assert_eq!(graph.neighbors(0), &[1]);
+        assert_eq!(graph.neighbors(1), &[2]);
+        assert_eq!(graph.neighbors(3), &[3]);
+        assert_eq!(graph.edge_weights(0), Some(&[2.5][..]));
+        assert_eq!(graph.edge_attr::<Categorical>("type").unwrap().get(0), Some("bus"));
+    }
+    assert!(matches!(load_graph_from_str("0 1\n18446744073709551616 1\n"), Err(Error::Parse { line: 2, .. })));
+    assert!(matches!(load_graph_from_str("0 -1\n"), Err(Error::Parse { line: 1, .. })));
+    assert!(matches!(load_graph_from_str("0 1 \u{ff}\n"), Err(Error::Parse { line: 1, .. })));
+}
+
+#[test]
+fn loader_detects_metis_matrix_market_and_binary_files() {
+    assert_eq!(GraphFormat::detect(b"0 1\n", Some("g.txt")), GraphFormat::EdgeList);
+    assert_eq!(GraphFormat::detect(b"3 2\n", Some("g.graph")), GraphFormat::Metis);
+    assert_eq!(GraphFormat::detect(b"% metis\n3 2\n", None), GraphFormat::Metis);
+    assert_eq!(GraphFormat::detect(b"%%MatrixMarket matrix", None), GraphFormat::MatrixMarket);
+    
+    // Path 0 - 1 - 2 plus isolated node 3, with edge weights (fmt 001)
+    let metis = "% comment\n4 2 001\n2 1.5\n1 1.5 3 2\n2 2\n\n";
+    let graph = load_graph_from_str(metis).unwrap();
+    assert!(!graph.directed);
+    assert_eq!((graph.num_nodes, graph.num_edges), (4, 4));
+    assert_eq!(graph.neighbors(1), &[0, 2]);
+    assert_eq!(graph.edge_weights(1), Some(&[1.5, 2.0][..]));
+    let options = LoadOptions { format: Some(GraphFormat::Metis), ..LoadOptions::default() };
+    let forced = load_graph_from_reader_with_options("3 1\n2\n1\n\n".as_bytes(), &options).unwrap();
+    assert_eq!(forced.neighbors(1), &[0]);
+    assert!(matches!(load_graph_from_str("% bad\n2 1\n3\n\n"), Err(Error::Parse { line: 3, .. })));
+    
+    let mtx = "%%MatrixMarket matrix coordinate real symmetric\n% comment\n3 3 2\n2 1 0.5\n3 2 4\n";
+    let graph = load_graph_from_str(mtx).unwrap();
+    assert!(!graph.directed);
+    assert_eq!(graph.num_edges, 4);
+    assert_eq!(graph.neighbors(0), &[1]);
+    assert_eq!(graph.edge_weights(0), Some(&[0.5][..]));
+    let general = load_graph_from_str("%%MatrixMarket matrix coordinate pattern general\n4 4 1\n1 2\n").unwrap();
+    assert!(general.directed && general.weights.is_none());
+    assert_eq!(general.num_nodes, 4);
+    assert!(load_graph_from_str("%%MatrixMarket matrix array real general\n2 2\n").is_err());
+    
+    let weighted = build_csr_weighted(3, vec![(0, 1, 2.5), (2, 0, -1.0)]);
+    let mut bytes = Vec::new();
+    write_binary_to(&weighted, &mut bytes).unwrap();
+    assert_eq!(GraphFormat::detect(&bytes, None), GraphFormat::Binary);
+    let loaded = load_graph_from_reader_with_options(bytes.as_slice(), &LoadOptions::default()).unwrap();
+    assert_eq!((loaded.offsets.clone(), loaded.neighbors.clone(), loaded.weights.clone()), (weighted.offsets, weighted.neighbors, weighted.weights));
+    assert!(load_graph_from_reader_with_options(&bytes[..bytes.len() - 1], &LoadOptions::default()).is_err());
+    
+    let path = std::env::temp_dir().join("ftn_test_formats.bin");
+    let path = path.to_str().unwrap();
+    write_binary(&build_csr_undirected(3, vec![(0, 2)]), path).unwrap();
+    let loaded = load_graph_from_file(path).unwrap();
+    assert!(!loaded.directed);
+    assert_eq!(loaded.neighbors(2), &[0]);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn binary_graphs_carry_metadata_for_inspect() {
+    let graph = build_csr_weighted(3, vec![(0, 1, 2.5), (2, 0, -1.0)]);
+    let metadata = BinaryGraphMetadata {
+        name: Some("tram 2024-05".to_string()),
+        created_unix: Some(1_700_000_000),
+        sources: vec![SourceFile::from_reader("tram.txt", "0 1 2.5\n2 0 -1\n".as_bytes()).unwrap()],
+        load_options: LoadOptions { drop_duplicates: true, format: Some(GraphFormat::EdgeList), ..LoadOptions::default() }.flags(),
+        id_map: Some("tram_ids.txt".to_string()),
+        ..BinaryGraphMetadata::new()
+    };
+    assert_eq!(metadata.load_options, ["--drop-duplicates", "--format edge-list"]);
+    assert_eq!(metadata.sources[0].bytes, 15);
+    assert_eq!(metadata.sources[0].hash.len(), 16);
+    let mut bytes = Vec::new();
+    write_binary_with_metadata_to(&graph, &metadata, &mut bytes).unwrap();
+    let info = read_binary_info(bytes.as_slice()).unwrap();
+    assert_eq!((info.version, info.directed, info.weighted, info.nodes, info.edges), (2, true, true, 3, 2));
+    assert_eq!(info.metadata.as_ref(), Some(&metadata));
+    let loaded = load_graph_from_reader_with_options(bytes.as_slice(), &LoadOptions::default()).unwrap();
+    assert_eq!(loaded.fingerprint(), graph.fingerprint());
+
+    // Version 1 files are the same without the metadata block
+    let mut plain = Vec::new();
+    write_binary_to(&graph, &mut plain).unwrap();
+    let block = u64::from_le_bytes(plain[8..16].try_into().unwrap()) as usize;
+    let v1 = [&b"FTNGRPH1"[..], &plain[16 + block..]].concat();
+    assert_eq!(GraphFormat::detect(&v1, None), GraphFormat::Binary);
+    assert_eq!(load_graph_from_reader_with_options(v1.as_slice(), &LoadOptions::default()).unwrap().fingerprint(), graph.fingerprint());
+    let info = read_binary_info(v1.as_slice()).unwrap();
+    assert_eq!((info.version, info.metadata), (1, None));
+    assert!(read_binary_info(&bytes[..20]).is_err());
+    assert!(read_binary_info(b"0 1\n".as_slice()).is_err());
+
+    let path = std::env::temp_dir().join("ftn_test_inspect.ftn");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, &bytes).unwrap();
+    let cli::Commands::Inspect(args) = Cli::parse_from(["tool", "inspect", path]).command.unwrap() else { unreachable!() };
+    let ctx = commands::Context::default();
+    let commands::inspect::Report::Graph(info) = commands::inspect::run(&args, &ctx).unwrap() else { panic!("a binary graph") };
+    assert_eq!(info.metadata.unwrap().name.as_deref(), Some("tram 2024-05"));
+    std::fs::write(path, "0 1\n").unwrap();
+    assert!(matches!(commands::inspect::run(&args, &ctx).unwrap(), commands::inspect::Report::Other(GraphFormat::EdgeList)));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn sorted_neighbors_support_has_edge_and_common_neighbors() {
+    let mut graph = build_csr_weighted(4, vec![(0, 3, 3.0), (0, 1, 1.0), (0, 2, 2.0), (1, 3, 5.0), (1, 2, 4.0), (1, 2, 6.0)]);
+    assert!(graph.has_edge(0, 3));
+    assert_eq!(graph.common_neighbors(0, 1), vec![2, 3]);
+    graph.set_edge_attr("cost", EdgeAttr::new(graph.weights.clone().unwrap())).unwrap();
+
+    graph.sort_neighbors();
+    assert!(graph.neighbors_sorted);
+    assert_eq!(graph.neighbors(0), &[1, 2, 3]);
+    assert_eq!(graph.edge_weights(1), Some(&[4.0, 6.0, 5.0][..]));
+    assert_eq!(graph.edge_attr::<EdgeAttr<f64>>("cost").unwrap().values(), graph.weights.as_deref().unwrap());
+    assert!(graph.has_edge(0, 3) && graph.has_edge(1, 2));
+    assert!(!graph.has_edge(2, 0) && !graph.has_edge(9, 0));
+    assert_eq!(graph.common_neighbors(0, 1), vec![2, 3]);
+    assert!(graph.validate().is_valid());
+
+    graph.neighbors.swap(0, 1);
+    assert_eq!(graph.validate().errors, vec![ValidationIssue::UnsortedNeighbors { node: 0 }]);
+
+    let options = LoadOptions { sort_neighbors: true, ..LoadOptions::default() };
+    let loaded = load_graph_from_reader_with_options("0 2\n0 1\n".as_bytes(), &options).unwrap();
+    assert_eq!(loaded.neighbors(0), &[1, 2]);
+}
+
+#[test]
+fn edges_iterate_in_csr_order() {
+    let graph = build_csr(4, vec![(2, 3), (0, 1), (0, 2), (3, 3)]);
+    let edges: Vec<_> = graph.edges().collect();
+    assert_eq!(edges, vec![(0, 1), (0, 2), (2, 3), (3, 3)]);
+    for (e, (_, v)) in graph.edges().enumerate() {
+        assert_eq!(graph.neighbors[e], v);
+    }
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::iter::ParallelIterator;
+        let mut par: Vec<_> = graph.par_edges().collect();
+        par.sort_unstable();
+        assert_eq!(par, edges);
+    }
+    assert_eq!(graph_empty().edges().count(), 0);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_csr_build_matches_sorted_sequential_build() {
+    // Pseudo-random edges with duplicates, self-loops and isolated nodes
+    let n = 5_000;
+    let edges: Vec<(usize, usize)> = (0..200_000u64)
+        .map(|i| {
+            let h = i.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+            ((h % 4_000) as usize, ((h >> 20) % 4_500) as usize)
+        })
+        .collect();
+    let par = build_csr_parallel(n, edges.clone());
+    let mut seq = build_csr(n, edges);
+    seq.sort_neighbors();
+    assert!(par.neighbors_sorted);
+    assert_eq!(par.num_edges, seq.num_edges);
+    assert_eq!(par.out_degree, seq.out_degree);
+    assert_eq!(par.offsets, seq.offsets);
+    assert_eq!(par.neighbors, seq.neighbors);
+    assert_eq!(build_csr_parallel(0, vec![]).num_nodes, 0);
+}
+
+#[test]
+fn undirected_graphs_store_both_directions() {
+    let graph = build_csr_undirected(4, vec![(0, 1), (1, 2), (3, 3)]);
+    assert!(!graph.directed);
+    assert_eq!(graph.num_edges, 5);
+    assert_eq!(graph.neighbors(1), &[2, 0]);
+    assert_eq!(graph.neighbors(3), &[3]);
+    assert_eq!(graph.transpose().neighbors(1), graph.neighbors(1));
+    assert!(graph_two_components().directed);
+
+    let options = LoadOptions { undirected: true, ..LoadOptions::default() };
+    let loaded = load_graph_from_reader_with_options("0 1 2.5 bus\n1 2\n".as_bytes(), &options).unwrap();
+    assert!(!loaded.directed);
+    assert_eq!(loaded.num_edges, 4);
+    assert_eq!(loaded.neighbors(1), &[2, 0]);
+    assert_eq!(loaded.edge_weights(1), Some(&[1.0, 2.5][..]));
+    let types = loaded.edge_attr::<Categorical>("type").unwrap();
+    assert_eq!(types.get(loaded.edge_range(1).end - 1), Some("bus"));
+    assert_eq!(bfs_sequential(&loaded, 2).unwrap().distances, vec![2, 1, 0]);
+}
+
+#[test]
+fn loader_remaps_sparse_node_ids() {
+    let graph = load_graph_from_str("0 4000000000\n4000000000 7\n").unwrap();
+    assert_eq!(graph.num_nodes, 3);
+    assert_eq!(graph.original_ids(), Some(&[0, 7, 4000000000][..]));
+    assert_eq!(graph.node_for_id(4000000000), Some(2));
+    assert_eq!(graph.node_for_id(5), None);
+    assert_eq!(graph.neighbors(0), &[2]);
+    assert_eq!(graph.neighbors(2), &[1]);
+    assert_eq!(graph.original_id(1), 7);
+
+    // Dense files keep their ids, and reordering keeps the mapping usable
+    assert_eq!(load_graph_from_str("0 1\n3 2\n").unwrap().original_ids(), None);
+    let (reordered, perm) = reorder(&graph, ReorderStrategy::Degree);
+    let v = reordered.node_for_id(7).unwrap();
+    assert_eq!(perm.old_id[v], 1);
+}
+
+#[test]
+fn compact_graph_rejects_out_degrees_beyond_u32() {
+    let mut graph = graph_4_node_diamond();
+    assert!(CompactGraph::fits_graph(&graph));
+    graph.out_degree[0] = u32::MAX as usize + 1;
+    assert!(!CompactGraph::fits_graph(&graph));
+    assert!(CompactGraph::from_graph(&graph).is_none());
+}
+
+#[test]
+fn weighted_csr_keeps_weights_aligned_with_neighbors() {
+    let graph = build_csr_weighted(3, vec![(1, 2, 0.5), (0, 2, 3.0), (0, 1, -1.0)]);
+    assert!(graph.is_weighted());
+    assert_eq!(graph.neighbors(0), &[2, 1]);
+    assert_eq!(graph.edge_weights(0).unwrap(), &[3.0, -1.0]);
+    let t = graph.transpose();
+    assert_eq!(t.weighted_neighbors(2).collect::<Vec<_>>(), vec![(0, 3.0), (1, 0.5)]);
+    assert!(graph_3_node_path().0.edge_weights(0).is_none());
+    assert_eq!(graph_3_node_path().0.weighted_neighbors(0).collect::<Vec<_>>(), vec![(1, 1.0)]);
+}
+
+#[test]
+fn load_weighted_edge_list_defaults_missing_weights_to_one() {
+    let graph = load_graph_from_str("0 1 2.5\n1 2\n").unwrap();
+    assert_eq!(graph.weights, Some(vec![2.5, 1.0]));
+    assert!(!load_graph_from_str("0 1\n1 2\n").unwrap().is_weighted());
+    assert!(matches!(load_graph_from_str("0 1 fast\n"), Err(Error::Parse { line: 1, .. })));
+}
+
+#[test]
+fn reorder_preserves_edge_weights() {
+    let graph = build_csr_weighted(4, vec![(0, 1, 1.5), (1, 2, 2.5), (3, 2, 4.0)]);
+    let (reordered, perm) = reorder(&graph, ReorderStrategy::Degree);
+    let w = reordered
+        .weighted_neighbors(perm.new_id[3])
+        .find(|&(v, _)| v == perm.new_id[2])
+        .unwrap()
+        .1;
+    assert_eq!(w, 4.0);
+}
+
+#[test]
+fn bellman_ford_handles_negative_edges() {
+    // 0 -> 1 (4), 0 -> 2 (5), 2 -> 1 (-3), 1 -> 3 (2)
+    let graph = build_csr_weighted(5, vec![(0, 1, 4.0), (0, 2, 5.0), (2, 1, -3.0), (1, 3, 2.0)]);
+    let result = bellman_ford(&graph, 0).unwrap();
+    assert_eq!(result.distances[..4], [0.0, 2.0, 5.0, 4.0]);
+    assert_eq!(result.distances[4], f64::INFINITY);
+    assert_eq!(result.path_to(3), Some(vec![0, 2, 1, 3]));
+    assert_eq!(result.path_to(4), None);
+    assert_eq!(result.reachable(), 4);
+}
+
+#[test]
+fn bellman_ford_unweighted_matches_bfs() {
+    let graph = graph_4_node_diamond();
+    let sssp = bellman_ford(&graph, 0).unwrap();
+    let bfs = bfs_sequential(&graph, 0).unwrap();
+    for (d, h) in sssp.distances.iter().zip(&bfs.distances) {
+        assert_eq!(*d, *h as f64);
+    }
+}
+
+#[test]
+fn bellman_ford_detects_reachable_negative_cycle_only() {
+    let cycle = build_csr_weighted(3, vec![(0, 1, 1.0), (1, 2, -2.0), (2, 1, 1.0)]);
+    assert!(matches!(bellman_ford(&cycle, 0), Err(Error::NegativeCycle { .. })));
+    // Same cycle, but not reachable from node 0
+    let unreachable = build_csr_weighted(3, vec![(1, 2, -2.0), (2, 1, 1.0)]);
+    assert!(bellman_ford(&unreachable, 0).is_ok());
+    assert!(matches!(bellman_ford(&cycle, 5), Err(Error::InvalidNode { .. })));
+}
+
+fn random_weighted_graph(n: usize, m: usize, seed: u64, min_weight: f64) -> Graph {
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        state >> 33
+    };
+    let edges = (0..m)
+        .map(|_| {
+            let u = next() as usize % n;
+            let v = next() as usize % n;
+            (u, v, min_weight + (next() % 100) as f64 / 10.0)
+        })
+        .collect();
+    build_csr_weighted(n, edges)
+}
+
+#[test]
+fn apsp_floyd_warshall_matches_repeated_dijkstra() {
+    // More than one FW block, so the blocked phases are exercised
+    let graph = random_weighted_graph(150, 600, 7, 0.5);
+    let fw = apsp(&graph, ApspMethod::FloydWarshall, 4).unwrap();
+    let sssp = apsp(&graph, ApspMethod::RepeatedSssp, 4).unwrap();
+    for u in 0..graph.num_nodes {
+        for v in 0..graph.num_nodes {
+            let (a, b) = (fw.get(u, v), sssp.get(u, v));
+            assert!(a == b || (a - b).abs() < 1e-3, "({}, {}): fw {} vs sssp {}", u, v, a, b);
+        }
+    }
+}
+
+#[test]
+fn apsp_unweighted_rows_match_bfs() {
+    let graph = graph_two_components();
+    let matrix = apsp(&graph, ApspMethod::RepeatedSssp, 2).unwrap();
+    assert_eq!(matrix.row(0), &[0.0, 1.0, f32::INFINITY, f32::INFINITY]);
+    assert_eq!(apsp(&graph, ApspMethod::FloydWarshall, 2).unwrap(), matrix);
+}
+
+#[test]
+fn apsp_negative_weights_need_floyd_warshall() {
+    let graph = build_csr_weighted(3, vec![(0, 1, 2.0), (1, 2, -1.0), (0, 2, 5.0)]);
+    assert!(matches!(apsp(&graph, ApspMethod::RepeatedSssp, 2), Err(Error::NegativeWeight { from: 1, to: 2 })));
+    assert_eq!(apsp(&graph, ApspMethod::FloydWarshall, 2).unwrap().get(0, 2), 1.0);
+    let cycle = build_csr_weighted(2, vec![(0, 1, 1.0), (1, 0, -2.0)]);
+    assert!(matches!(floyd_warshall(&cycle, 2), Err(Error::NegativeCycle { .. })));
+}
+
+#[test]
+fn distance_matrix_binary_roundtrip() {
+    let matrix = apsp(&graph_two_components(), ApspMethod::FloydWarshall, 1).unwrap();
+    let path = std::env::temp_dir().join("ftn_test_apsp.bin");
+    matrix.write_binary(path.to_str().unwrap()).unwrap();
+    let back = DistanceMatrix::read_binary(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(back, matrix);
+    assert!(DistanceMatrix::from_bytes(b"FTNAPSP1\x02\0\0\0\0\0\0\0").is_err());
+    assert!(matches!(DistanceMatrix::from_bytes(b"FTNAPSP1\0\0\0\0\0\0\0\x40"), Err(Error::Parse { .. })));
+}
+
+#[test]
+fn dijkstra_matches_bellman_ford_on_non_negative_weights() {
+    let graph = random_weighted_graph(60, 240, 11, 0.0);
+    let d = dijkstra(&graph, 0).unwrap();
+    let b = bellman_ford(&graph, 0).unwrap();
+    for (x, y) in d.distances.iter().zip(&b.distances) {
+        assert!(x == y || (x - y).abs() < 1e-9);
+    }
+    let negative = build_csr_weighted(2, vec![(0, 1, -1.0)]);
+    assert!(matches!(dijkstra(&negative, 0), Err(Error::NegativeWeight { .. })));
+}
+
+#[test]
+fn scc_finds_cycles_in_reverse_topological_order() {
+    // 0 <-> 1 -> 2 -> 3 -> 2, 4 isolated
+    let graph = build_csr(5, vec![(0, 1), (1, 0), (1, 2), (2, 3), (3, 2)]);
+    let scc = strongly_connected_components(&graph);
+    assert_eq!(scc.num_components, 3);
+    assert_eq!(scc.component[0], scc.component[1]);
+    assert_eq!(scc.component[2], scc.component[3]);
+    assert!(scc.component[1] > scc.component[2]);
+    let mut sizes = scc.sizes();
+    sizes.sort();
+    assert_eq!(sizes, vec![1, 2, 2]);
+}
+
+#[test]
+fn reachability_index_matches_bfs() {
+    for (seed, m) in [(1, 150), (2, 300), (3, 600)] {
+        let graph = random_weighted_graph(120, m, seed, 1.0);
+        let index = build_reachability_index(&graph);
+        for u in 0..graph.num_nodes {
+            let bfs = bfs_sequential(&graph, u).unwrap();
+            for v in 0..graph.num_nodes {
+                assert_eq!(index.can_reach(u, v).unwrap(), bfs.distances[v] >= 0, "seed {} {} -> {}", seed, u, v);
+            }
+        }
+    }
+}
+
+#[test]
+fn reachability_index_single_labeling_and_invalid_nodes() {
+    let graph = build_csr(4, vec![(0, 1), (1, 2), (3, 2)]);
+    let index = ReachabilityIndex::with_labelings(&graph, 1);
+    assert_eq!(index.num_components(), 4);
+    assert!(index.can_reach(0, 2).unwrap());
+    assert!(index.can_reach(3, 3).unwrap());
+    assert!(!index.can_reach(0, 3).unwrap());
+    assert!(!index.can_reach(2, 1).unwrap());
+    assert!(matches!(index.can_reach(0, 4), Err(Error::InvalidNode { node: 4, num_nodes: 4 })));
+}
+
+#[test]
+fn dinic_classic_network() {
+    // CLRS flow network, max flow 23
+    let graph = build_csr_weighted(6, vec![
+        (0, 1, 16.0), (0, 2, 13.0), (1, 2, 10.0), (2, 1, 4.0), (1, 3, 12.0),
+        (3, 2, 9.0), (2, 4, 14.0), (4, 3, 7.0), (3, 5, 20.0), (4, 5, 4.0),
+    ]);
+    let result = dinic(&graph, 0, 5).unwrap();
+    assert!((result.value - 23.0).abs() < 1e-9);
+    let cut = result.min_cut_edges(&graph);
+    assert!((cut.iter().map(|&(_, _, c)| c).sum::<f64>() - 23.0).abs() < 1e-9);
+
+    // Conservation at inner nodes and capacity bounds
+    let mut net = [0.0; 6];
+    for u in 0..6 {
+        for (i, (v, c)) in graph.weighted_neighbors(u).enumerate() {
+            let f = result.edge_flows[graph.offsets[u] + i];
+            assert!(f >= -1e-9 && f <= c + 1e-9);
+            net[u] -= f;
+            net[v] += f;
+        }
+    }
+    assert!(net[1..5].iter().all(|x| x.abs() < 1e-9));
+    assert!((net[5] - 23.0).abs() < 1e-9);
+}
+
+#[test]
+fn dinic_unweighted_counts_edge_disjoint_paths() {
+    let graph = build_csr(4, vec![(0, 1), (0, 2), (1, 3), (2, 3), (1, 2)]);
+    assert_eq!(dinic(&graph, 0, 3).unwrap().value, 2.0);
+    let disconnected = dinic(&graph_two_components(), 0, 3).unwrap();
+    assert_eq!(disconnected.value, 0.0);
+    assert!(disconnected.source_side[0] && !disconnected.source_side[3]);
+}
+
+#[test]
+fn dinic_rejects_bad_input() {
+    let graph = build_csr_weighted(2, vec![(0, 1, -1.0)]);
+    assert!(matches!(dinic(&graph, 0, 1), Err(Error::NegativeWeight { from: 0, to: 1 })));
+    assert!(matches!(dinic(&graph, 0, 2), Err(Error::InvalidNode { node: 2, .. })));
+}
+
+#[test]
+fn jones_plassmann_produces_proper_coloring() {
+    let graph = random_weighted_graph(500, 3000, 5, 1.0);
+    let stats = GraphStats::compute(&graph);
+    let max_degree = stats.max_out_degree + stats.max_in_degree;
+    let par = jones_plassmann(&graph, 4);
+    assert!(is_proper_coloring(&graph, &par.colors));
+    assert!(par.num_colors <= max_degree + 1);
+    assert_eq!(jones_plassmann(&graph, 1), par);
+
+    let seq = greedy_coloring(&graph);
+    assert!(is_proper_coloring(&graph, &seq.colors));
+    assert_eq!(seq.classes().iter().map(Vec::len).sum::<usize>(), 500);
+}
+
+#[test]
+fn coloring_odd_cycle_needs_three_colors() {
+    let graph = build_csr(5, vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (2, 2)]);
+    assert_eq!(jones_plassmann(&graph, 2).num_colors, 3);
+    assert!(!is_proper_coloring(&graph, &[0, 1, 0, 1, 0]));
+}
+
+#[test]
+fn maximal_independent_set_is_independent_and_maximal() {
+    let graph = random_weighted_graph(200, 600, 9, 1.0);
+    let mis = maximal_independent_set(&graph, 4);
+    for u in 0..graph.num_nodes {
+        for &v in graph.neighbors(u) {
+            assert!(u == v || !(mis[u] && mis[v]));
+        }
+    }
+    let transposed = graph.transpose();
+    for v in (0..graph.num_nodes).filter(|&v| !mis[v]) {
+        assert!(graph.neighbors(v).iter().chain(transposed.neighbors(v)).any(|&w| mis[w]));
+    }
+}
+
+#[test]
+fn edge_coloring_separates_edges_sharing_a_node() {
+    // Star with a back edge: all four edges touch node 0
+    let graph = build_csr(4, vec![(0, 1), (0, 2), (3, 0), (1, 2)]);
+    let result = edge_coloring(&graph, 2);
+    assert_eq!(result.colors.len(), 4);
+    let (c01, c02, c30, c12) = (result.colors[0], result.colors[1], result.colors[3], result.colors[2]);
+    assert!(c01 != c02 && c01 != c30 && c02 != c30);
+    assert!(c12 != c01 && c12 != c02);
+}
+
+#[test]
+fn exact_betweenness_and_closeness_on_path() {
+    // 0 -> 1 -> 2 -> 3
+    let graph = build_csr(4, vec![(0, 1), (1, 2), (2, 3)]);
+    assert_eq!(approximate_betweenness(&graph, 10, 2), vec![0.0, 2.0, 2.0, 0.0]);
+    let closeness = approximate_closeness(&graph, 10, 2);
+    let expected = [(1.0 + 0.5 + 1.0 / 3.0) / 3.0, 1.5 / 3.0, 1.0 / 3.0, 0.0];
+    for (c, e) in closeness.iter().zip(expected) {
+        assert!((c - e).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn approx_diameter_bounds_contain_the_exact_diameter() {
+    // Path 0 - 1 - ... - 9 (directed edges), plus a star 10 -> {11, 12, 13} and isolated 14
+    let mut edges: Vec<(usize, usize)> = (0..9).map(|i| (i, i + 1)).collect();
+    edges.extend([(10, 11), (10, 12), (10, 13)]);
+    let bounds = approx_diameter(&build_csr(15, edges), 4, 2);
+    assert_eq!((bounds.lower, bounds.upper), (9, 9));
+    assert!(bounds.is_exact());
+    assert!(matches!(bounds.endpoints, (0, 9) | (9, 0)));
+    
+    // Pseudo-random sparse graphs: the bounds must bracket the diameter from BFS at every node
+    for seed in 0..5u64 {
+        let n = 60;
+        let edges: Vec<(usize, usize)> = (0..90u64)
+            .map(|i| {
+                let h = (i + 1000 * seed).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+                ((h % n) as usize, ((h >> 32) % n) as usize)
+            })
+            .collect();
+        let undirected = build_csr_undirected(n as usize, edges.clone());
+        let exact = (0..n as usize)
+            .map(|s| bfs_sequential(&undirected, s).unwrap().distances.into_iter().max().unwrap() as usize)
+            .max()
+            .unwrap();
+        for graph in [build_csr(n as usize, edges), undirected] {
+            let bounds = approx_diameter(&graph, 6, 2);
+            assert!(bounds.lower <= exact && exact <= bounds.upper, "{:?} vs {}", bounds, exact);
+        }
+    }
+    assert_eq!(approx_diameter(&graph_empty(), 4, 2).upper, 0);
+}
+
+#[test]
+fn anf_estimates_the_exact_distance_distribution() {
+    // Exact statistics from an interpolated neighbourhood function match the definitions
+    let exact = NeighborhoodFunction { pairs: vec![4.0, 10.0, 14.0], converged: true };
+    assert_eq!(exact.distance_distribution(), vec![4.0, 6.0, 4.0]);
+    assert!((exact.average_distance() - 1.4).abs() < 1e-12);
+    assert!((exact.effective_diameter(0.9) - 1.75).abs() < 1e-12);
+    
+    // Undirected ring of 300 nodes with chords: compare against BFS from every node
+    let n = 300;
+    let mut edges: Vec<(usize, usize)> = (0..n).map(|i| (i, (i + 1) % n)).collect();
+    edges.extend((0..n).step_by(7).map(|i| (i, (i * 31 + 11) % n)));
+    let graph = build_csr_undirected(n, edges);
+    let mut pairs = vec![0.0f64; n];
+    for s in 0..n {
+        for d in bfs_sequential(&graph, s).unwrap().distances {
+            pairs[d as usize] += 1.0;
+        }
+    }
+    let mut cumulative = 0.0;
+    let mut exact_pairs: Vec<f64> = pairs.iter().map(|p| { cumulative += p; cumulative }).collect();
+    while exact_pairs.len() > 1 && exact_pairs[exact_pairs.len() - 2] == exact_pairs[exact_pairs.len() - 1] {
+        exact_pairs.pop();
+    }
+    let exact = NeighborhoodFunction { pairs: exact_pairs, converged: true };
+    
+    let config = AnfConfig { log2_registers: 10, num_threads: 2, ..AnfConfig::default() };
+    let estimate = approximate_neighborhood_function(&graph, &config);
+    assert!(estimate.converged);
+    assert!(estimate.pairs.windows(2).all(|w| w[0] <= w[1]));
+    let total = (n * n) as f64;
+    assert!((estimate.pairs.last().unwrap() - total).abs() < 0.1 * total, "{:?}", estimate.pairs);
+    assert!((estimate.pairs[0] - n as f64).abs() < 0.1 * n as f64);
+    let (ed, exact_ed) = (estimate.effective_diameter(0.9), exact.effective_diameter(0.9));
+    assert!((ed - exact_ed).abs() < 0.15 * exact_ed, "{} vs {}", ed, exact_ed);
+    let (avg, exact_avg) = (estimate.average_distance(), exact.average_distance());
+    assert!((avg - exact_avg).abs() < 0.1 * exact_avg, "{} vs {}", avg, exact_avg);
+    
+    // Same counters whatever the thread count; a cut-off run is marked as not converged
+    assert_eq!(approximate_neighborhood_function(&graph, &AnfConfig { num_threads: 1, ..config.clone() }), estimate);
+    let truncated = approximate_neighborhood_function(&graph, &AnfConfig { max_distance: 2, ..config });
+    assert!(!truncated.converged);
+    assert_eq!(truncated.pairs.len(), 3);
+    assert_eq!(approximate_neighborhood_function(&graph_empty(), &AnfConfig::default()).pairs, vec![0.0]);
+}
+
+#[test]
+fn triangle_counts_ignore_direction_duplicates_and_self_loops() {
+    // K4 as one-way edges, plus a reversed duplicate, a self-loop and a pendant node 4
+    let graph = build_csr(5, vec![(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3), (3, 2), (1, 1), (3, 4)]);
+    for result in [count_triangles(&graph), count_triangles_ordered(&graph), count_triangles_parallel(&graph, 2)] {
+        assert_eq!(result.total, 4);
+        assert_eq!(result.per_node, vec![3, 3, 3, 3, 0]);
+        // Degrees 3, 3, 3, 4, 1
+        assert_eq!(result.wedges, 3 * 3 + 6);
+        assert!((result.transitivity() - 12.0 / 15.0).abs() < 1e-12);
+    }
+    
+    // Random multigraphs: every variant matches a brute-force count over node triples
+    for seed in 0..4 {
+        let n = 60;
+        let graph = random_weighted_graph(n, 500, seed, 1.0);
+        let mut adjacent = vec![vec![false; n]; n];
+        for (u, v) in graph.edges().filter(|(u, v)| u != v) {
+            adjacent[u][v] = true;
+            adjacent[v][u] = true;
+        }
+        let mut per_node = vec![0u64; n];
+        for u in 0..n {
+            for v in u + 1..n {
+                for w in v + 1..n {
+                    if adjacent[u][v] && adjacent[v][w] && adjacent[u][w] {
+                        per_node[u] += 1;
+                        per_node[v] += 1;
+                        per_node[w] += 1;
+                    }
+                }
+            }
+        }
+        let expected = count_triangles(&graph);
+        assert_eq!(expected.per_node, per_node);
+        assert_eq!(expected.total, per_node.iter().sum::<u64>() / 3);
+        assert_eq!(count_triangles_ordered(&graph), expected);
+        for threads in [1, 3] {
+            assert_eq!(count_triangles_parallel(&graph, threads), expected);
+        }
+        let undirected = build_csr_undirected(n, graph.edges().collect());
+        assert_eq!(count_triangles_parallel(&undirected, 2), expected);
+    }
+    assert_eq!(count_triangles_parallel(&graph_empty(), 2).total, 0);
+}
+
+#[test]
+fn descendant_counts_match_reachability_on_dags() {
+    // Diamond 0 -> {1, 2} -> 3 -> 4 with a duplicate edge and a self-loop, plus isolated 5
+    let graph = build_csr(6, vec![(0, 1), (0, 2), (1, 3), (2, 3), (2, 3), (3, 4), (3, 3)]);
+    let result = count_descendants(&graph, &DescendantConfig::default()).unwrap();
+    assert_eq!(result.counts, vec![4, 2, 2, 1, 0, 0]);
+    assert_eq!((result.method, result.targets), (DescendantMethod::Exact, 6));
+    assert_eq!(result.top(2), vec![(0, 4), (1, 2)]);
+    
+    // Random DAG (edges from lower to higher id): every method against BFS from each node
+    let n = 400;
+    let edges: Vec<(usize, usize)> = random_weighted_graph(n, 1200, 9, 1.0)
+        .edges()
+        .filter(|(u, v)| u != v)
+        .map(|(u, v)| (u.min(v), u.max(v)))
+        .collect();
+    let graph = build_csr(n, edges);
+    let expected: Vec<u64> = (0..n)
+        .map(|s| bfs_sequential(&graph, s).unwrap().distances.iter().filter(|&&d| d > 0).count() as u64)
+        .collect();
+    // One 64-target word per node and block, so the blocked runs take 7 blocks
+    for (method, num_threads) in [(DescendantMethod::Exact, 1), (DescendantMethod::Blocked, 1), (DescendantMethod::Blocked, 3)] {
+        let config = DescendantConfig { method, num_threads, block_bytes: 8 * n, ..DescendantConfig::default() };
+        assert_eq!(count_descendants(&graph, &config).unwrap().counts, expected, "{:?}", method);
+    }
+    // Sampling every node is exact; a quarter of them stays close on the nodes with many descendants
+    let sampled = |samples| {
+        let config = DescendantConfig { method: DescendantMethod::Sampled, samples, num_threads: 2, ..DescendantConfig::default() };
+        count_descendants(&graph, &config).unwrap()
+    };
+    let all = sampled(n);
+    assert!(!all.is_exact());
+    assert_eq!(all.counts, expected);
+    let quarter = sampled(n / 4);
+    assert_eq!(quarter.targets, n / 4);
+    for v in (0..n).filter(|&v| expected[v] >= 100) {
+        assert!((quarter.counts[v] as f64 - expected[v] as f64).abs() < 0.35 * expected[v] as f64, "{}: {} vs {}", v, quarter.counts[v], expected[v]);
+    }
+    
+    let cyclic = build_csr(4, vec![(0, 1), (1, 2), (2, 1), (2, 3)]);
+    assert!(matches!(count_descendants(&cyclic, &DescendantConfig::default()), Err(Error::Cycle { node: 1 | 2 })));
+    assert!(count_descendants(&graph_empty(), &DescendantConfig::default()).unwrap().counts.is_empty());
+}
+
+#[test]
+fn betweenness_splits_over_equal_paths() {
+    // Diamond 0 -> {1, 2} -> 3: each middle node carries half of the 0 -> 3 pair
+    let graph = build_csr(4, vec![(0, 1), (0, 2), (1, 3), (2, 3)]);
+    assert_eq!(approximate_betweenness(&graph, 4, 1), vec![0.0, 0.5, 0.5, 0.0]);
+}
+
+#[test]
+fn largest_component_scores_the_giant_component_only() {
+    // A 5x5 grid (nodes 0..25) plus two isolated pairs, which would each keep 2/29 of the teleport mass
+    let grid = testing::grid(5);
+    let mut edges: Vec<(usize, usize)> = grid.edges().collect();
+    edges.extend([(25, 26), (27, 28)]);
+    let graph = build_csr(29, edges);
+    let config = PageRankConfig { tolerance: 1e-12, ..Default::default() };
+
+    let on_grid = pagerank_sequential(&grid, &config).ranks;
+    let whole = pagerank_sequential(&graph, &config).ranks;
+    assert!(whole[25..].iter().all(|&r| r > 0.0));
+    for threads in [None, Some(4)] {
+        let result = pagerank_largest_component(&graph, &config, threads, ParallelismPolicy::ForceParallel);
+        assert_eq!(result.ranks.len(), 29);
+        assert!(result.ranks[25..].iter().all(|&r| r == 0.0));
+        assert!(result.ranks[..25].iter().zip(&on_grid).all(|(a, b)| (a - b).abs() < 1e-9), "{:?}", threads);
+    }
+
+    let config = CentralityConfig { samples: 64, edge_betweenness: true, largest_component: true, ..Default::default() };
+    let report = centrality_report(&graph, &config);
+    let expected = centrality_report(&grid, &CentralityConfig { largest_component: false, ..config.clone() });
+    assert_eq!(report.num_nodes(), 29);
+    assert!(report.exact);
+    assert_eq!(report.out_degree, graph.out_degree, "degrees are not restricted");
+    assert_eq!(report.in_degree[26], 1);
+    let close = |a: &[f64], b: &[f64]| a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-9);
+    assert!(close(&report.betweenness[..25], &expected.betweenness));
+    assert!(report.closeness[25..].iter().chain(&report.pagerank[25..]).all(|&s| s == 0.0));
+    let edge_scores = report.edge_betweenness.unwrap();
+    assert!(close(&edge_scores[..grid.num_edges], &expected.edge_betweenness.unwrap()));
+    assert_eq!(&edge_scores[grid.num_edges..], &[0.0, 0.0]);
+    let analysed = Analysis::new(&graph).centrality(config.clone()).run().unwrap().centrality.unwrap();
+    assert!(close(&analysed.betweenness, &report.betweenness));
+    assert_eq!(analysed.out_degree, graph.out_degree);
+
+    let dir = std::env::temp_dir();
+    let (input, out) = (dir.join("ftn_test_lcc.txt"), dir.join("ftn_test_lcc_pr.txt"));
+    write_edge_list(&graph, input.to_str().unwrap()).unwrap();
+    let cli = Cli::parse_from(["tool", "pagerank", "-i", input.to_str().unwrap(), "-o", out.to_str().unwrap(), "--eps", "1e-12", "--largest-component"]);
+    let Some(cli::Commands::Pagerank(args)) = cli.command else { unreachable!() };
+    let ranks = commands::pagerank::run(&args, &commands::Context::default()).unwrap().result.ranks;
+    assert!(ranks[25..].iter().all(|&r| r == 0.0));
+    assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    let _ = std::fs::remove_file(&input);
+    for suffix in ["", "_top100", "_stats"] {
+        let _ = std::fs::remove_file(dir.join(format!("ftn_test_lcc_pr{}.txt", suffix)));
+    }
+    let _ = std::fs::remove_file(dir.join("ftn_test_lcc_pr_convergence.csv"));
+}
+
+#[test]
+fn edge_betweenness_is_written_as_a_weighted_edge_list() {
+    // Path 0 -> 1 -> 2 -> 3: the middle edge lies on the four paths crossing it
+    assert_eq!(approximate_edge_betweenness(&testing::path(4), 4, 1), vec![3.0, 4.0, 3.0]);
+    // Diamond: each side carries its own hop and half of 0 -> 3
+    let graph = build_csr(4, vec![(0, 1), (0, 2), (1, 3), (2, 3)]);
+    let scores = approximate_edge_betweenness(&graph, 4, 1);
+    assert_eq!(scores, vec![1.5, 1.5, 1.5, 1.5]);
+
+    let graph = testing::random(200, 800, 9);
+    let exact = approximate_edge_betweenness(&graph, 200, 4);
+    let report = centrality_report(&graph, &CentralityConfig { samples: 200, edge_betweenness: true, ..Default::default() });
+    assert!(report.edge_betweenness.unwrap().iter().zip(&exact).all(|(a, b)| (a - b).abs() < 1e-6));
+    assert!(centrality_report(&graph, &CentralityConfig::default()).edge_betweenness.is_none());
+    // Every shortest path of k hops adds 1 to each of its k edges and to k - 1 inner nodes
+    let (edge_total, node_total) = (exact.iter().sum::<f64>(), approximate_betweenness(&graph, 200, 4).iter().sum::<f64>());
+    let pairs = (0..200).map(|s| bfs_sequential(&graph, s).unwrap().distances.iter().filter(|&&d| d > 0).count()).sum::<usize>();
+    assert!((edge_total - node_total - pairs as f64).abs() < 1e-6 * edge_total);
+
+    let path = std::env::temp_dir().join("ftn_test_edge_betweenness.txt");
+    write_edge_scores(&graph, &exact, "Betweenness", path.to_str().unwrap()).unwrap();
+    let loaded = load_graph_from_file(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let expected = build_csr_weighted(200, graph.edges().zip(&exact).map(|((u, v), &w)| (u, v, w)).collect());
+    assert!(loaded.structural_eq(&expected));
+}
+
+#[test]
+fn centrality_report_combines_scores() {
+    let graph = random_weighted_graph(300, 1200, 4, 1.0);
+    let config = CentralityConfig { samples: 300, num_threads: 4, ..Default::default() };
+    let report = centrality_report(&graph, &config);
+    assert!(report.exact);
+    assert_eq!(report.out_degree, graph.out_degree);
+    assert_eq!(report.in_degree.iter().sum::<usize>(), graph.num_edges);
+    let sequential = approximate_betweenness(&graph, 300, 1);
+    assert!(report.betweenness.iter().zip(&sequential).all(|(a, b)| (a - b).abs() < 1e-6));
+    assert!((report.pagerank.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+
+    let sampled = centrality_report(&graph, &CentralityConfig { samples: 50, ..Default::default() });
+    assert!(!sampled.exact);
+    assert_eq!(sampled.samples, 50);
+
+    let path = std::env::temp_dir().join("ftn_test_centrality.ndjson");
+    write_centrality_report_as(&report, path.to_str().unwrap(), OutputFormat::Ndjson).unwrap();
+    let text = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(text.lines().count(), 300);
+    let first: serde_json::Value = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+    assert_eq!(first["node"], 0);
+    assert!(first["betweenness"].is_number() && first["closeness"].is_number());
+}
+
+#[test]
+fn random_walks_are_reproducible_across_thread_counts() {
+    let graph = random_weighted_graph(200, 800, 12, 1.0);
+    let config = RandomWalkConfig { walk_length: 20, walks_per_node: 3, record_traces: true, ..Default::default() };
+    let one = random_walks(&graph, &RandomWalkConfig { num_threads: 1, ..config.clone() });
+    let many = random_walks(&graph, &RandomWalkConfig { num_threads: 8, ..config.clone() });
+    assert_eq!(one, many);
+    assert_eq!(one.traces.len(), 600);
+    assert!(one.traces.iter().all(|t| t.len() == 21));
+    assert_eq!(one.traces[3][0], 1);
+    assert_eq!(one.total_visits, 600 * 21);
+
+    let counts_only = random_walks(&graph, &RandomWalkConfig { record_traces: false, ..config });
+    assert_eq!(counts_only.visit_counts, one.visit_counts);
+    assert!(counts_only.traces.is_empty());
+}
+
+#[test]
+fn random_walks_follow_edges_and_restart() {
+    let graph = build_csr(3, vec![(0, 1), (1, 2), (2, 0)]);
+    let result = random_walks(&graph, &RandomWalkConfig { walk_length: 5, walks_per_node: 1, record_traces: true, ..Default::default() });
+    assert_eq!(result.traces[0], vec![0, 1, 2, 0, 1, 2]);
+
+    let always_restart = RandomWalkConfig { walk_length: 5, walks_per_node: 1, restart_probability: 1.0, ..Default::default() };
+    assert_eq!(random_walks(&graph, &always_restart).visit_counts, vec![6, 6, 6]);
+}
+
+#[test]
+fn random_walk_visit_frequencies_approximate_pagerank() {
+    let graph = random_weighted_graph(50, 250, 21, 1.0);
+    let walks = random_walks(&graph, &RandomWalkConfig {
+        walk_length: 2000,
+        walks_per_node: 20,
+        restart_probability: 0.15,
+        ..Default::default()
+    });
+    let ranks = pagerank_sequential(&graph, &PageRankConfig { tolerance: 1e-10, ..Default::default() }).ranks;
+    let l1: f64 = walks.visit_frequencies().iter().zip(&ranks).map(|(a, b)| (a - b).abs()).sum();
+    assert!(l1 < 0.05, "L1 distance {}", l1);
+}
+
+#[test]
+fn node2vec_walks_follow_edges_and_are_reproducible() {
+    let graph = random_weighted_graph(100, 500, 30, 1.0);
+    let config = Node2VecConfig { p: 0.5, q: 2.0, walk_length: 15, walks_per_node: 2, ..Default::default() };
+    let walks = node2vec_walks(&graph, &config);
+    assert_eq!(walks.len(), 200);
+    assert_eq!(walks[101][0], 1);
+    for walk in &walks {
+        assert!(walk.len() <= 16);
+        assert!(walk.windows(2).all(|w| graph.neighbors(w[0]).contains(&w[1])));
+        assert!(walk.len() == 16 || graph.neighbors(*walk.last().unwrap()).is_empty());
+    }
+    assert_eq!(node2vec_walks(&graph, &Node2VecConfig { num_threads: 1, ..config }), walks);
+}
+
+#[test]
+fn node2vec_return_parameter_controls_backtracking() {
+    // Undirected star around node 0 plus a ring, so every step has a return option
+    let mut edges = Vec::new();
+    for v in 1..6 {
+        edges.extend([(0, v), (v, 0), (v, v % 5 + 1), (v % 5 + 1, v)]);
+    }
+    let graph = build_csr(6, edges);
+    let backtracks = |p: f64| {
+        let config = Node2VecConfig { p, walk_length: 40, walks_per_node: 20, ..Default::default() };
+        node2vec_walks(&graph, &config)
+            .iter()
+            .map(|w| w.windows(3).filter(|t| t[0] == t[2]).count())
+            .sum::<usize>()
+    };
+    assert!(backtracks(0.1) > 2 * backtracks(10.0));
+}
+
+#[test]
+fn node2vec_corpus_file_has_one_walk_per_line() {
+    let graph = build_csr(3, vec![(0, 1), (1, 2), (2, 0)]);
+    let path = std::env::temp_dir().join("ftn_test_node2vec.txt");
+    let config = Node2VecConfig { walk_length: 3, walks_per_node: 2, ..Default::default() };
+    assert_eq!(run_node2vec_and_save(&graph, &config, path.to_str().unwrap()).unwrap(), 6);
+    let text = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(text.lines().collect::<Vec<_>>(), ["0 1 2 0", "1 2 0 1", "2 0 1 2", "0 1 2 0", "1 2 0 1", "2 0 1 2"]);
+}
+
+#[test]
+fn diff_reports_node_edge_and_weight_changes() {
+    let old = load_graph_from_str("0 1 1.0\n1 2 2.0\n2 3 1.0\n3 4 1.0\n").unwrap();
+    let new = load_graph_from_str("0 1 1.0\n1 2 5.0\n1 2 1.0\n2 3 1.0\n5 0 1.0\n").unwrap();
+    let changes = diff(&old, &new);
+    assert_eq!(changes.added_nodes, vec![5]);
+    assert_eq!(changes.removed_nodes, vec![4]);
+    assert_eq!(changes.added_edges, vec![(5, 0)]);
+    assert_eq!(changes.removed_edges, vec![(3, 4)]);
+    assert_eq!(changes.weight_changes, vec![(1, 2, 2.0, 5.0)]);
+    let nodes: Vec<usize> = changes.degree_changes.iter().map(|c| c.node).collect();
+    assert_eq!(nodes, vec![0, 1, 2, 3, 4, 5]);
+    assert!(!changes.is_empty());
+    assert!(diff(&old, &old).is_empty());
+}
+
+#[test]
+fn diff_detects_component_splits_and_merges() {
+    // Old: {0,1,2} and {3,4}; new: {0,1} and {2,3,4}
+    let old = build_csr(5, vec![(0, 1), (1, 2), (3, 4)]);
+    let new = build_csr(5, vec![(0, 1), (2, 3), (3, 4)]);
+    let c = diff(&old, &new).components;
+    assert_eq!((c.old_components, c.new_components), (2, 2));
+    assert_eq!((c.split, c.merged), (1, 1));
+
+    let joined = build_csr(5, vec![(0, 1), (1, 2), (2, 3), (3, 4)]);
+    let c = diff(&old, &joined).components;
+    assert_eq!((c.split, c.merged, c.new_largest), (0, 1, 5));
+}
+
+#[test]
+fn incremental_wcc_tracks_edge_insertions() {
+    let graph = graph_two_components();
+    let mut wcc = IncrementalWcc::from_result(&wcc_sequential(&graph));
+    assert_eq!(wcc.num_components(), 2);
+    assert!(wcc.same_component(0, 1));
+    assert!(!wcc.same_component(1, 2));
+
+    assert!(wcc.add_edge(1, 2));
+    assert!(!wcc.add_edge(0, 3));
+    assert!(wcc.same_component(0, 3));
+    assert_eq!(wcc.num_components(), 1);
+    assert_eq!(wcc.component_size(2), 4);
+
+    // New stop beyond the original id range
+    assert!(!wcc.same_component(6, 0));
+    assert!(wcc.same_component(6, 6));
+    assert!(wcc.add_edge(6, 0));
+    assert_eq!(wcc.num_nodes(), 7);
+    assert_eq!(wcc.num_components(), 3);
+    assert_eq!(wcc.component(5), Some(5));
+    assert_eq!(wcc.component(9), None);
+}
+
+#[test]
+fn incremental_wcc_matches_full_recompute() {
+    let base = random_weighted_graph(300, 200, 17, 1.0);
+    let extra = random_weighted_graph(300, 150, 18, 1.0);
+    let mut wcc = IncrementalWcc::new(&base);
+    for u in 0..extra.num_nodes {
+        for &v in extra.neighbors(u) {
+            wcc.add_edge(u, v);
+        }
+    }
+    let mut edges: Vec<(usize, usize)> = Vec::new();
+    for g in [&base, &extra] {
+        for u in 0..g.num_nodes {
+            edges.extend(g.neighbors(u).iter().map(|&v| (u, v)));
+        }
+    }
+    let full = wcc_sequential(&build_csr(300, edges));
+    let incremental = wcc.to_result();
+    assert_eq!(incremental.stats.num_components, full.stats.num_components);
+    assert_eq!(wcc.num_components(), full.stats.num_components);
+    for u in 0..300 {
+        for v in [0, 7, 150, 299] {
+            assert_eq!(incremental.labels[u] == incremental.labels[v], full.labels[u] == full.labels[v]);
+        }
+    }
+}
+
+#[test]
+fn incremental_pagerank_matches_full_recompute() {
+    let config = PageRankConfig { tolerance: 1e-12, max_iterations: 500, ..Default::default() };
+    let mut edges: Vec<(usize, usize)> = Vec::new();
+    let base = random_weighted_graph(400, 1600, 23, 1.0);
+    for u in 0..base.num_nodes {
+        edges.extend(base.neighbors(u).iter().map(|&v| (u, v)));
+    }
+    let before = pagerank_sequential(&build_csr(400, edges.clone()), &config).ranks;
+
+    // Remove two edges (one leaves its source dangling if it was the only one), add three
+    let removed = [edges[0], edges[100]];
+    edges.retain(|e| !removed.contains(e));
+    let added = [(5, 17), (17, 5), (399, 0)];
+    edges.extend(added);
+    let after_graph = build_csr(400, edges);
+    let changes: Vec<EdgeChange> = removed
+        .iter()
+        .map(|&(u, v)| EdgeChange::Removed(u, v))
+        .chain(added.iter().map(|&(u, v)| EdgeChange::Added(u, v)))
+        .collect();
+
+    let full = pagerank_sequential(&after_graph, &config).ranks;
+    let incremental = pagerank_incremental(&after_graph, &before, &changes, &config);
+    assert!(incremental.converged);
+    let l1: f64 = full.iter().zip(&incremental.ranks).map(|(a, b)| (a - b).abs()).sum();
+    assert!(l1 < 1e-8, "L1 distance {}", l1);
+}
+
+#[test]
+fn incremental_pagerank_handles_new_dangling_nodes() {
+    let config = PageRankConfig { tolerance: 1e-12, max_iterations: 500, ..Default::default() };
+    let before_graph = build_csr(4, vec![(0, 1), (1, 2), (2, 3), (3, 0)]);
+    let before = pagerank_sequential(&before_graph, &config).ranks;
+    let after_graph = build_csr(4, vec![(0, 1), (1, 2), (3, 0)]);
+    let changes = [EdgeChange::Removed(2, 3)];
+    let full = pagerank_sequential(&after_graph, &config).ranks;
+    let incremental = pagerank_incremental(&after_graph, &before, &changes, &config).ranks;
+    for (a, b) in full.iter().zip(&incremental) {
+        assert!((a - b).abs() < 1e-9);
+    }
+    // No changes: nothing to push
+    assert_eq!(pagerank_incremental(&before_graph, &before, &[], &config).iterations, 0);
+}
+
+fn grid_graph(side: usize) -> Graph {
+    let mut edges = Vec::new();
+    for r in 0..side {
+        for c in 0..side {
+            let v = r * side + c;
+            if c + 1 < side {
+                edges.extend([(v, v + 1), (v + 1, v)]);
+            }
+            if r + 1 < side {
+                edges.extend([(v, v + side), (v + side, v)]);
+            }
+        }
+    }
+    build_csr(side * side, edges)
+}
+
+#[test]
+fn partitioners_are_balanced_and_beat_round_robin() {
+    let graph = grid_graph(20);
+    let round_robin: Vec<usize> = (0..400).map(|v| v % 4).collect();
+    let baseline = edge_cut(&graph, &round_robin);
+    for method in [PartitionMethod::Ldg, PartitionMethod::BfsGrow] {
+        let p = partition(&graph, 4, method);
+        assert_eq!(p.parts.len(), 400);
+        assert!(p.parts.iter().all(|&x| x < 4));
+        assert_eq!(p.part_sizes.iter().sum::<usize>(), 400);
+        assert!(p.imbalance() <= 1.06, "{:?}: imbalance {}", method, p.imbalance());
+        assert_eq!(p.edge_cut, edge_cut(&graph, &p.parts));
+        assert!(p.edge_cut * 2 < baseline, "{:?}: cut {} vs {}", method, p.edge_cut, baseline);
+    }
+}
+
+#[test]
+fn partition_edge_cases() {
+    let graph = graph_two_components();
+    let single = partition(&graph, 0, PartitionMethod::Ldg);
+    assert_eq!((single.num_parts, single.edge_cut), (1, 0));
+    let p = partition(&graph, 2, PartitionMethod::BfsGrow);
+    assert_eq!(p.parts, vec![0, 0, 1, 1]);
+    assert_eq!(p.cut_ratio(&graph), 0.0);
+    assert_eq!("bfs".parse::<PartitionMethod>().unwrap(), PartitionMethod::BfsGrow);
+    assert!("metis".parse::<PartitionMethod>().is_err());
+}
+
+#[test]
+fn edge_balanced_ranges_isolate_hubs() {
+    // Node 0 has 1000 out-edges, the other 999 nodes one each
+    let mut edges: Vec<(usize, usize)> = (1..1000).map(|v| (0, v)).collect();
+    edges.push((0, 0));
+    edges.extend((1..1000).map(|v| (v, (v + 1) % 1000)));
+    let graph = build_csr(1000, edges);
+
+    let ranges = edge_balanced_ranges(&graph.offsets, 4);
+    assert_eq!(ranges[0], 0..1);
+    assert_eq!(ranges.first().unwrap().start, 0);
+    assert_eq!(ranges.last().unwrap().end, 1000);
+    assert!(ranges.windows(2).all(|w| w[0].end == w[1].start && !w[1].is_empty()));
+    // The remaining chunks split the light nodes about evenly
+    let sizes: Vec<usize> = ranges[1..].iter().map(|r| r.len()).collect();
+    assert!(sizes.iter().max().unwrap() - sizes.iter().min().unwrap() <= 2, "{:?}", sizes);
+
+    assert!(edge_balanced_ranges(&[0], 4).is_empty());
+    assert_eq!(edge_balanced_ranges(&graph.offsets, 0), vec![0..1000]);
+    assert_eq!(edge_balanced_ranges(&graph.offsets, 5000).len(), 1000);
+
+    let frontier = [5, 0, 7, 9];
+    let chunks = frontier_ranges(&graph, &frontier, 3);
+    assert!(chunks.contains(&(1..2)));
+    assert_eq!(chunks.iter().map(|r| r.len()).sum::<usize>(), 4);
+}
+
+#[test]
+fn hybrid_frontier_chunks_split_hub_adjacency() {
+    // Hub 0 points at every other node; 1..=9 each point at 10 nodes, the rest have no out-edges
+    let mut edges: Vec<(usize, usize)> = (1..2000).map(|v| (0, v)).collect();
+    edges.extend((1..10).flat_map(|u| (0..10).map(move |i| (u, 100 + 10 * u + i))));
+    let graph = build_csr(2000, edges);
+
+    let frontier: Vec<usize> = (0..20).collect();
+    let chunks = hybrid_frontier_chunks(&graph, &frontier, 8);
+    let mut hub_edges: Vec<usize> = Vec::new();
+    let mut nodes = Vec::new();
+    for chunk in &chunks {
+        match chunk {
+            FrontierChunk::Nodes(range) => nodes.extend(range.clone()),
+            FrontierChunk::Edges { node, edges } => {
+                assert_eq!(*node, 0);
+                hub_edges.extend(edges.clone());
+            }
+        }
+    }
+    // Every edge of the hub exactly once, over several pieces; every other node in one node range
+    assert_eq!(hub_edges, (0..1999).collect::<Vec<_>>());
+    let pieces = chunks.iter().filter(|c| matches!(c, FrontierChunk::Edges { .. })).count();
+    assert!((8..=9).contains(&pieces), "{} pieces", pieces);
+    assert_eq!(nodes, (1..20).collect::<Vec<_>>());
+
+    // Without a dominant hub this is plain node ranges, as frontier_ranges cuts them
+    let light: Vec<usize> = (1..20).collect();
+    let expected: Vec<FrontierChunk> = frontier_ranges(&graph, &light, 4).into_iter().map(FrontierChunk::Nodes).collect();
+    assert_eq!(hybrid_frontier_chunks(&graph, &light, 4), expected);
+    assert!(hybrid_frontier_chunks(&graph, &[], 4).is_empty());
+
+    for threads in [1, 4] {
+        let par = bfs_parallel_with_level_stats(&graph, 0, threads, ParallelismPolicy::ForceParallel, &NoProgress).unwrap();
+        assert_eq!(par.distances, bfs_sequential(&graph, 0).unwrap().distances);
+        #[cfg(feature = "parallel")]
+        assert_eq!(par.level_stats[0].split_hubs, 1);
+    }
+}
+
+#[test]
+fn simd_kernels_match_scalar_code() {
+    assert!(["avx2", "portable"].contains(&simd::active_backend()));
+    for len in [0, 1, 3, 7, 8, 9, 31, 1000] {
+        let a: Vec<f64> = (0..len).map(|i| (i as f64 * 0.37).sin()).collect();
+        let b: Vec<f64> = (0..len).map(|i| (i as f64 * 0.11).cos()).collect();
+        let expected: f64 = a.iter().zip(&b).map(|(x, y)| (x - y).abs()).sum();
+        assert!((simd::l1_distance(&a, &b) - expected).abs() < 1e-9, "len {}", len);
+
+        let mut out = vec![0.0; len];
+        simd::add_offset(&mut out, &a, 0.25);
+        assert!(out.iter().zip(&a).all(|(o, x)| *o == x + 0.25));
+    }
+}
+
+#[test]
+fn parallel_bfs_reuses_buffers_across_varying_levels() {
+    // Levels grow and shrink (wide random part, then a long tail), so chunk counts change between levels
+    let mut edges: Vec<(usize, usize)> = Vec::new();
+    let wide = random_weighted_graph(2000, 8000, 40, 1.0);
+    for u in 0..wide.num_nodes {
+        edges.extend(wide.neighbors(u).iter().map(|&v| (u, v)));
+    }
+    edges.extend((1999..2500).map(|v| (v, v + 1)));
+    let graph = build_csr(2501, edges);
+    for threads in [1, 3, 8] {
+        let par = bfs_parallel_with_policy(&graph, 0, threads, ParallelismPolicy::ForceParallel).unwrap();
+        assert_eq!(par.distances, bfs_sequential(&graph, 0).unwrap().distances);
+    }
+}
+
+#[test]
+fn validate_accepts_loaded_graphs_and_counts_loops_and_duplicates() {
+    let graph = load_graph_from_str("0 1\n0 1\n1 1\n1 2\n").unwrap();
+    let report = graph.validate();
+    assert!(report.is_valid(), "{:?}", report.errors);
+    assert_eq!(report.self_loops, 1);
+    assert_eq!(report.duplicate_edges, 1);
+}
+
+#[test]
+fn validate_reports_corrupted_csr_arrays() {
+    let mut graph = graph_4_node_diamond();
+    graph.num_edges += 1;
+    graph.neighbors[1] = 9;
+    graph.offsets.swap(1, 2);
+    let report = graph.validate();
+    assert!(!report.is_valid());
+    assert_eq!(report.error_count, report.errors.len());
+    assert!(report.errors.contains(&ValidationIssue::EdgeCount { num_edges: 5, neighbors: 4 }));
+    assert!(report.errors.contains(&ValidationIssue::TargetOutOfRange { edge: 1, target: 9 }));
+    assert!(report.errors.iter().any(|e| matches!(e, ValidationIssue::NonMonotonicOffsets { node: 1, .. })));
+    assert!(report.errors.iter().any(|e| matches!(e, ValidationIssue::DegreeMismatch { .. })));
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["errors"][0]["kind"], "non_monotonic_offsets");
+    graph.offsets.pop();
+    assert!(graph.validate().errors.contains(&ValidationIssue::OffsetsLength { expected: 5, actual: 4 }));
+}
+
+#[test]
+fn mode_expansion_prices_transfers_and_forbidden_turns() {
+    let graph = load_graph_from_str("0 1 1 bus\n1 2 1 tram\n0 3 2 bus\n3 2 2 bus\n").unwrap();
+    let run = |rules: &TransferRules| {
+        let expanded = expand_modes(&graph, rules).unwrap();
+        let result = dijkstra(&expanded.graph, expanded.origin_state(0)).unwrap();
+        (expanded.node_distances(&result)[2], expanded.node_path(&result, 2).unwrap())
+    };
+
+    assert_eq!(run(&TransferRules::default()), (2.0, vec![0, 1, 2]));
+    let penalized = TransferRules { default_penalty: 5.0, ..TransferRules::default() };
+    assert_eq!(run(&penalized), (4.0, vec![0, 3, 2]));
+    let forbidden = TransferRules {
+        rules: vec![TransferRule { node: Some(1), from: "bus".into(), to: "tram".into(), cost: TransferCost::Forbidden }],
+        ..TransferRules::default()
+    };
+    assert_eq!(run(&forbidden), (4.0, vec![0, 3, 2]));
+    let cheap_here = TransferRules {
+        rules: vec![TransferRule { node: Some(1), from: "bus".into(), to: "tram".into(), cost: TransferCost::Penalty(0.5) }],
+        ..penalized
+    };
+    assert_eq!(run(&cheap_here).0, 2.5);
+
+    // Without a mode column the expansion is a plain shortest-path problem
+    let plain = graph_5_node_path();
+    let expanded = expand_modes(&plain, &TransferRules::default()).unwrap();
+    let result = dijkstra(&expanded.graph, expanded.origin_state(0)).unwrap();
+    assert_eq!(expanded.node_distances(&result), dijkstra(&plain, 0).unwrap().distances);
+    let bad = TransferRules {
+        rules: vec![TransferRule { node: Some(99), from: "".into(), to: "".into(), cost: TransferCost::Forbidden }],
+        ..TransferRules::default()
+    };
+    assert!(matches!(expand_modes(&plain, &bad), Err(Error::InvalidNode { node: 99, .. })));
+}
+
+#[test]
+fn isochrone_limits_hops_and_weighted_cost() {
+    let graph = graph_5_node_path();
+    let iso = isochrone(&graph, 1, 2.0).unwrap();
+    assert_eq!(iso.nodes, vec![(1, 0.0), (2, 1.0), (3, 2.0)]);
+    assert_eq!(iso.boundary_edges, vec![(3, 4)]);
+    assert!(iso.contains(3) && !iso.contains(0));
+
+    let weighted = build_csr_weighted(4, vec![(0, 1, 2.0), (1, 2, 2.0), (0, 2, 5.0), (2, 3, 0.5)]);
+    let iso = isochrone(&weighted, 0, 4.0).unwrap();
+    assert_eq!(iso.nodes, vec![(0, 0.0), (1, 2.0), (2, 4.0)]);
+    assert_eq!(iso.boundary_edges, vec![(2, 3)]);
+    assert!(isochrone(&weighted, 0, -1.0).unwrap().is_empty());
+    assert!(matches!(isochrone(&weighted, 7, 1.0), Err(Error::InvalidNode { node: 7, .. })));
+}
+
+#[test]
+fn isochrone_geojson_has_hull_points_and_boundary() {
+    let mut graph = build_csr(5, vec![(0, 1), (0, 2), (0, 3), (3, 4)]);
+    load_node_coordinates(&mut graph, "id lon lat\n0 0 0\n1 1,0\n2 0 1\n3,-1,0\n4 -2 0\n".as_bytes()).unwrap();
+    let coords = graph.node_attr::<NodeAttr<Option<[f64; 2]>>>("coords").unwrap();
+    let iso = isochrone(&graph, 0, 1.0).unwrap();
+    let path = std::env::temp_dir().join("ftn_test_isochrone.geojson");
+    write_isochrone_geojson(&iso, coords.values(), path.to_str().unwrap()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let features = json["features"].as_array().unwrap();
+    assert_eq!(json["type"], "FeatureCollection");
+    assert_eq!(features[0]["geometry"]["type"], "Polygon");
+    // Hull of (0,0), (1,0), (0,1), (-1,0) drops the interior origin and closes the ring
+    assert_eq!(features[0]["geometry"]["coordinates"][0].as_array().unwrap().len(), 4);
+    assert_eq!(features.iter().filter(|f| f["geometry"]["type"] == "Point").count(), 4);
+    assert_eq!(features.last().unwrap()["properties"]["to"], 4);
+}
+
+#[test]
+fn constrained_route_trades_cost_for_fewer_transfers_and_hops() {
+    let graph = load_graph_from_str("0 1 1 bus\n1 2 1 tram\n0 3 2 bus\n3 2 2 bus\n0 2 10 walk\n").unwrap();
+    let route = |max_transfers, max_hops| {
+        let constraints = RouteConstraints { max_transfers, max_hops, ..RouteConstraints::default() };
+        constrained_shortest_path(&graph, 0, 2, &constraints).unwrap()
+    };
+
+    let free = route(None, None).unwrap();
+    assert_eq!((free.cost, free.transfers, free.path.clone()), (2.0, 1, vec![0, 1, 2]));
+    assert_eq!(free.modes, vec!["bus", "tram"]);
+    let direct = route(Some(0), None).unwrap();
+    assert_eq!((direct.cost, direct.path), (4.0, vec![0, 3, 2]));
+    let one_hop = route(Some(0), Some(1)).unwrap();
+    assert_eq!((one_hop.cost, one_hop.hops()), (10.0, 1));
+    assert!(route(Some(0), Some(0)).is_none());
+
+    let path = graph_5_node_path();
+    let hops = RouteConstraints { max_hops: Some(3), ..RouteConstraints::default() };
+    assert!(constrained_shortest_path(&path, 0, 4, &hops).unwrap().is_none());
+    assert_eq!(constrained_shortest_path(&path, 0, 3, &hops).unwrap().unwrap().transfers, 0);
+}
+
+#[test]
+fn alt_matches_dijkstra_and_settles_fewer_nodes() {
+    let side = 20;
+    let mut edges = Vec::new();
+    for r in 0..side {
+        for c in 0..side {
+            let v = r * side + c;
+            let w = 1.0 + ((r * 7 + c * 3) % 5) as f64;
+            if c + 1 < side {
+                edges.push((v, v + 1, w));
+                edges.push((v + 1, v, w));
+            }
+            if r + 1 < side {
+                edges.push((v, v + side, w));
+                edges.push((v + side, v, w));
+            }
+        }
+    }
+    let graph = build_csr_weighted(side * side, edges);
+    let landmarks = build_landmarks(&graph, 4).unwrap();
+    assert_eq!(landmarks.landmarks.len(), 4);
+
+    let mut total_settled = 0;
+    for (s, t) in [(0, 399), (21, 378), (190, 9), (399, 0)] {
+        let exact = dijkstra(&graph, s).unwrap();
+        let alt = alt_shortest_path(&graph, &landmarks, s, t).unwrap().unwrap();
+        assert!((alt.cost - exact.distances[t]).abs() < 1e-9);
+        assert_eq!(alt.path.first(), Some(&s));
+        assert_eq!(alt.path.last(), Some(&t));
+        assert!(landmarks.lower_bound(s, t) <= exact.distances[t] + 1e-9);
+        total_settled += alt.settled;
+    }
+    assert!(total_settled < 4 * graph.num_nodes / 2, "settled {}", total_settled);
+
+    let path = std::env::temp_dir().join("ftn_test_landmarks.bin");
+    landmarks.write_binary(path.to_str().unwrap()).unwrap();
+    let reloaded = Landmarks::read_binary(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(reloaded, landmarks);
+    assert!(Landmarks::from_bytes(b"FTNAPSP1").is_err());
+    let overflow = b"FTNALT01\0\0\0\0\0\0\0\x40\x04\0\0\0\0\0\0\0";
+    assert!(matches!(Landmarks::from_bytes(overflow), Err(Error::Parse { .. })));
+    assert!(alt_shortest_path(&graph_5_node_path(), &landmarks, 0, 1).is_err());
+    let unreachable = build_landmarks(&graph_two_components(), 2).unwrap();
+    assert!(alt_shortest_path(&graph_two_components(), &unreachable, 0, 3).unwrap().is_none());
+}
+
+#[test]
+fn every_sample_method_returns_the_requested_size_reproducibly() {
+    let graph = grid_graph(5);
+    for method in [SampleMethod::Node, SampleMethod::Edge, SampleMethod::RandomWalk, SampleMethod::ForestFire] {
+        let config = SampleConfig { method, nodes: 8, seed: 7, ..SampleConfig::default() };
+        let a = sample(&graph, &config);
+        assert_eq!(a.graph.num_nodes, 8, "{:?}", method);
+        assert_eq!(a.original_ids, sample(&graph, &config).original_ids, "{:?}", method);
+        assert!(a.original_ids.windows(2).all(|w| w[0] < w[1]));
+        for u in 0..8 {
+            for &v in a.graph.neighbors(u) {
+                assert!(graph.neighbors(a.original_ids[u]).contains(&a.original_ids[v]));
+            }
+        }
+    }
+    let all = sample(&graph, &SampleConfig { nodes: 100, ..SampleConfig::default() });
+    assert_eq!(all.original_ids.len(), 25);
+    assert_eq!(all.graph.num_edges, graph.num_edges);
+    assert!("walk".parse::<SampleMethod>().is_ok() && "bogus".parse::<SampleMethod>().is_err());
+}
+
+#[test]
+fn induced_subgraph_keeps_weights_and_types_and_round_trips_as_edge_list() {
+    let graph = load_graph_from_str("0 1 1.5 bus\n1 2 2.5 tram\n2 0 3.5 bus\n2 3 4.5 walk\n").unwrap();
+    let sub = induced_subgraph(&graph, &[1, 2, 3]);
+    assert_eq!(sub.num_edges, 2);
+    assert_eq!(sub.weights, Some(vec![2.5, 4.5]));
+    let types = sub.edge_attr::<Categorical>("type").unwrap();
+    assert_eq!((types.get(0), types.get(1)), (Some("tram"), Some("walk")));
+
+    let path = std::env::temp_dir().join("ftn_test_sample_edges.txt");
+    write_edge_list(&sub, path.to_str().unwrap()).unwrap();
+    let reloaded = load_graph_from_file(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(reloaded.neighbors, sub.neighbors);
+    assert_eq!(reloaded.weights, sub.weights);
+    assert_eq!(reloaded.edge_attr::<Categorical>("type").unwrap().get(1), Some("walk"));
+}
+
+#[test]
+fn pagerank_warm_start_converges_faster() {
+    let edges = (0..50).flat_map(|i| [(i, i / 3), (i, (i * 7 + 3) % 50)]).collect();
+    let graph = build_csr(50, edges);
+    let config = |alpha| PageRankConfig { alpha, max_iterations: 200, tolerance: 1e-10, ..PageRankConfig::default() };
+    let previous = pagerank_sequential(&graph, &config(0.85));
+
+    let cold = pagerank_sequential(&graph, &config(0.86));
+    let warm = pagerank_with_initial(&graph, &config(0.86), &previous.ranks, None).unwrap();
+    assert!(warm.converged);
+    assert!(warm.iterations < cold.iterations, "warm {} vs cold {}", warm.iterations, cold.iterations);
+    for (a, b) in warm.ranks.iter().zip(&cold.ranks) {
+        assert!((a - b).abs() < 1e-8);
+    }
+
+    // Unnormalized input is scaled; the fixed point does not depend on the start
+    let scaled: Vec<f64> = previous.ranks.iter().map(|r| r * 10.0).collect();
+    let rescaled = pagerank_with_initial(&graph, &config(0.85), &scaled, Some(2)).unwrap();
+    assert!(rescaled.iterations <= 2);
+    assert!(matches!(pagerank_with_initial(&graph, &config(0.85), &[1.0; 3], None), Err(Error::InvalidInput { .. })));
+}
+
+#[test]
+fn weighted_pagerank_follows_edge_weights() {
+    // Node 0 sends three quarters of its rank to 1 and a quarter to 2; both send everything back
+    let graph = build_csr_weighted(3, vec![(0, 1, 3.0), (0, 2, 1.0), (1, 0, 2.0), (2, 0, 0.5)]);
+    let config = PageRankConfig { tolerance: 1e-12, max_iterations: 500, weighted: true, ..PageRankConfig::default() };
+    let ranks = pagerank_sequential(&graph, &config).ranks;
+    let r0 = 0.9 / 1.85;
+    let expected = [r0, 0.05 + 0.85 * 0.75 * r0, 0.05 + 0.85 * 0.25 * r0];
+    for (r, e) in ranks.iter().zip(expected) {
+        assert!((r - e).abs() < 1e-9, "{:?} vs {:?}", ranks, expected);
+    }
+    let parallel = pagerank_parallel_with_policy(&graph, &config, 2, ParallelismPolicy::ForceParallel).ranks;
+    for (a, b) in parallel.iter().zip(&ranks) {
+        assert!((a - b).abs() < 1e-9);
+    }
+
+    // Uniform weights, an unweighted graph and zero-weight (dangling) nodes match the plain run
+    let plain = PageRankConfig { weighted: false, ..config.clone() };
+    let uniform = build_csr_weighted(3, vec![(0, 1, 2.0), (0, 2, 2.0), (1, 0, 2.0), (2, 0, 2.0)]);
+    let unweighted = build_csr(3, vec![(0, 1), (0, 2), (1, 0), (2, 0)]);
+    let dangling_weight = build_csr_weighted(3, vec![(0, 1, 1.0), (1, 2, 0.0)]);
+    let dangling = build_csr(3, vec![(0, 1)]);
+    for (graph, reference) in [(&uniform, &unweighted), (&unweighted, &unweighted), (&dangling_weight, &dangling)] {
+        let expected = pagerank_sequential(reference, &plain).ranks;
+        let seq = pagerank_sequential(graph, &config).ranks;
+        let par = pagerank_parallel_with_policy(graph, &config, 2, ParallelismPolicy::ForceParallel).ranks;
+        for ((a, b), e) in seq.iter().zip(&par).zip(&expected) {
+            assert!((a - e).abs() < 1e-9 && (b - e).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn pagerank_resumes_from_checkpoint() {
+    let graph = grid_graph(6);
+    let path = std::env::temp_dir().join("ftn_test_pagerank.ckpt");
+    let path = path.to_str().unwrap().to_string();
+    let full = pagerank_sequential(&graph, &PageRankConfig { max_iterations: 9, tolerance: 0.0, ..PageRankConfig::default() });
+
+    let interrupted = PageRankConfig {
+        max_iterations: 5,
+        tolerance: 0.0,
+        checkpoint: Some(Checkpointing { path: path.clone(), every: 2 }),
+        ..PageRankConfig::default()
+    };
+    pagerank_sequential(&graph, &interrupted);
+    let saved = PageRankCheckpoint::read_binary(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(saved.iteration, 4);
+    assert!(saved.check_nodes(graph.num_nodes).is_ok());
+    assert!(saved.check_nodes(3).is_err());
+
+    let resumed = PageRankConfig {
+        max_iterations: 9,
+        tolerance: 0.0,
+        resume: Some(saved),
+        ..PageRankConfig::default()
+    };
+    let result = pagerank_sequential(&graph, &resumed);
+    assert_eq!(result.iterations, 9);
+    assert_eq!(result.ranks, full.ranks);
+    let parallel = pagerank_parallel_with_policy(&graph, &resumed, 2, ParallelismPolicy::ForceParallel);
+    for (a, b) in parallel.ranks.iter().zip(&full.ranks) {
+        assert!((a - b).abs() < 1e-12);
+    }
+    assert!(PageRankCheckpoint::from_bytes(b"FTNPRCK1\x02\0\0\0\0\0\0\0").is_err());
+    let overflow = [b"FTNPRCK1".as_slice(), &[0, 0, 0, 0, 0, 0, 0, 0x40], &[0; 16]].concat();
+    assert!(matches!(PageRankCheckpoint::from_bytes(&overflow), Err(Error::Parse { .. })));
+}
+
+#[test]
+fn pagerank_history_records_every_iteration() {
+    let graph = grid_graph(6);
+    let config = PageRankConfig { tolerance: 1e-9, ..PageRankConfig::default() };
+    let seq = pagerank_sequential(&graph, &config);
+    let par = pagerank_parallel_with_policy(&graph, &config, 2, ParallelismPolicy::ForceParallel);
+    for result in [&seq, &par] {
+        assert_eq!(result.history.len(), result.iterations);
+        assert_eq!(*result.history.last().unwrap(), result.final_delta);
+        assert!(result.history[..result.iterations - 1].iter().all(|&d| d >= config.tolerance));
+    }
+
+    // A resumed run only records its own iterations, numbered from the checkpoint's
+    let ranks = pagerank_sequential(&graph, &PageRankConfig { max_iterations: 3, ..config.clone() }).ranks;
+    let resumed = PageRankConfig {
+        resume: Some(PageRankCheckpoint { iteration: 3, delta: seq.history[2], ranks }),
+        ..config.clone()
+    };
+    let result = pagerank_sequential(&graph, &resumed);
+    assert_eq!(result.history, seq.history[3..]);
+
+    let path = std::env::temp_dir().join("ftn_test_convergence.csv");
+    write_convergence_csv(&result, path.to_str().unwrap()).unwrap();
+    let text = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "iteration,delta");
+    assert_eq!(lines.len(), result.history.len() + 1);
+    assert!(lines[1].starts_with("4,"));
+    assert_eq!(lines.last().unwrap().split(',').next().unwrap(), result.iterations.to_string());
+    assert_eq!(lines[1].split(',').nth(1).unwrap().parse::<f64>().unwrap(), result.history[0]);
+}
+
+#[test]
+fn graph_fingerprint_tracks_structure_and_weights() {
+    let graph = graph_5_node_path();
+    assert_eq!(graph.fingerprint(), graph_5_node_path().fingerprint());
+    assert_eq!(graph.fingerprint(), graph.clone().fingerprint());
+    assert_ne!(graph.fingerprint(), build_csr(5, vec![(0, 1), (1, 2), (2, 3), (4, 3)]).fingerprint());
+    assert_ne!(graph.fingerprint(), build_csr(6, vec![(0, 1), (1, 2), (2, 3), (3, 4)]).fingerprint());
+    let weighted = build_csr_weighted(5, vec![(0, 1, 1.0), (1, 2, 1.0), (2, 3, 1.0), (3, 4, 1.0)]);
+    let heavier = build_csr_weighted(5, vec![(0, 1, 1.0), (1, 2, 2.0), (2, 3, 1.0), (3, 4, 1.0)]);
+    assert_ne!(graph.fingerprint(), weighted.fingerprint());
+    assert_ne!(weighted.fingerprint(), heavier.fingerprint());
+}
+
+#[test]
+fn result_cache_reuses_results_for_same_graph_and_params() {
+    let dir = std::env::temp_dir().join("ftn_test_result_cache");
+    let _ = std::fs::remove_dir_all(&dir);
+    let cache = ResultCache::new(&dir).unwrap();
+    let graph = graph_two_components();
+    let fp = graph.fingerprint();
+
+    let wcc = cache.get_or_insert_with(fp, "mode=seq", || Ok::<_, ()>(wcc_sequential(&graph))).unwrap();
+    let cached: WccResult = cache.get_or_insert_with(fp, "mode=seq", || -> Result<_, ()> { panic!("cache miss") }).unwrap();
+    assert_eq!(cached.labels, wcc.labels);
+    assert_eq!(cached.stats.num_components, wcc.stats.num_components);
+    assert!(cache.get::<WccResult>(fp, "mode=par").is_none());
+    assert!(cache.get::<WccResult>(graph_5_node_path().fingerprint(), "mode=seq").is_none());
+
+    let bfs = bfs_sequential(&graph, 0).unwrap();
+    cache.put(fp, "source=0", &bfs).unwrap();
+    assert_eq!(cache.get::<BfsResult>(fp, "source=0").unwrap().distances, bfs.distances);
+
+    let pr = pagerank_sequential(&graph, &PageRankConfig::default());
+    cache.put(fp, "alpha=0.85", &pr).unwrap();
+    let back = cache.get::<PageRankResult>(fp, "alpha=0.85").unwrap();
+    assert_eq!((back.ranks, back.iterations, back.converged, back.history), (pr.ranks, pr.iterations, pr.converged, pr.history));
+
+    std::fs::write(cache.path::<PageRankResult>(fp, "alpha=0.85"), b"FTNCACH1\x01").unwrap();
+    assert!(cache.get::<PageRankResult>(fp, "alpha=0.85").is_none());
+    assert!(PageRankResult::decode(&[0; 8]).is_none());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn pagerank_cache_skips_checkpointed_and_resumed_runs() {
+    let dir = std::env::temp_dir().join("ftn_test_pagerank_cache");
+    let _ = std::fs::remove_dir_all(&dir);
+    let cache = ResultCache::new(dir.join("cache")).unwrap();
+    let graph = GraphStorage::Wide(grid_graph(4));
+    let out = dir.join("ranks.txt");
+    let out = out.to_str().unwrap();
+    let ckpt = dir.join("ranks.ckpt");
+    let run = |config: &PageRankConfig| {
+        run_pagerank_and_save(&graph, config, Mode::Seq, 1, out, None, ParallelismPolicy::Auto, OutputFormat::Text, Some(&cache), false).unwrap()
+    };
+
+    let config = PageRankConfig { max_iterations: 4, tolerance: 0.0, ..PageRankConfig::default() };
+    let cached = run(&config);
+    let checkpointed = PageRankConfig {
+        checkpoint: Some(Checkpointing { path: ckpt.to_str().unwrap().to_string(), every: 2 }),
+        ..config.clone()
+    };
+    run(&checkpointed);
+    let saved = PageRankCheckpoint::read_binary(ckpt.to_str().unwrap()).unwrap();
+    assert_eq!(saved.iteration, 4);
+
+    let mut skewed = vec![0.0; graph.node_count()];
+    skewed[0] = 1.0;
+    let resume = PageRankConfig { resume: Some(PageRankCheckpoint { iteration: 3, ranks: skewed, ..saved }), ..config };
+    let resumed = run(&resume);
+    assert_eq!(resumed.ranks, pagerank_sequential(&graph, &resume).ranks);
+    assert_ne!(resumed.ranks, cached.ranks);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn binary_results_reload_through_the_output_writers() {
+    use fast_transit_network::results::{read_result, result_kind};
+    let dir = std::env::temp_dir();
+    let path = |name: &str| dir.join(name).to_str().unwrap().to_string();
+    let graph = random_weighted_graph(500, 2000, 3, 1.0);
+
+    let bfs = bfs_sequential(&graph, 0).unwrap();
+    let bfs_path = path("ftn_test_result_bfs.bin");
+    write_bfs_result_as(&bfs, 0, &bfs_path, OutputFormat::Binary).unwrap();
+    assert_eq!(read_result::<BfsResult>(&bfs_path).unwrap(), bfs);
+
+    let wcc = wcc_sequential(&graph);
+    let wcc_path = path("ftn_test_result_wcc.bin");
+    write_wcc_result_as(&wcc, &wcc_path, OutputFormat::Binary, WccColumns::default()).unwrap();
+    let back: WccResult = read_result(&wcc_path).unwrap();
+    assert_eq!((back.labels, back.stats.num_components), (wcc.labels.clone(), wcc.stats.num_components));
+    write_wcc_result_as(&wcc, &wcc_path, OutputFormat::Binary, WccColumns { dense_ids: true, sizes: false }).unwrap();
+    assert_eq!(read_result::<WccResult>(&wcc_path).unwrap().labels, dense_component_ids(&wcc.labels).0);
+    assert!(write_wcc_result_as(&wcc, &wcc_path, OutputFormat::Binary, WccColumns { dense_ids: false, sizes: true }).is_err());
+
+    let pr = pagerank_sequential(&graph, &PageRankConfig::default());
+    let pr_path = path("ftn_test_result_pagerank.bin");
+    write_pagerank_result_as(&pr, &pr_path, OutputFormat::Binary).unwrap();
+    let back: PageRankResult = read_result(&pr_path).unwrap();
+    assert_eq!((back.ranks, back.iterations, back.final_delta, back.history), (pr.ranks, pr.iterations, pr.final_delta, pr.history));
+
+    // The kind is checked, and truncated or foreign files are rejected
+    assert_eq!(result_kind(&std::fs::read(&pr_path).unwrap()).unwrap(), "pagerank");
+    assert!(matches!(read_result::<BfsResult>(&pr_path), Err(Error::Parse { .. })));
+    let bytes = std::fs::read(&bfs_path).unwrap();
+    std::fs::write(&bfs_path, &bytes[..bytes.len() - 1]).unwrap();
+    assert!(matches!(read_result::<BfsResult>(&bfs_path), Err(Error::Parse { .. })));
+    assert!(read_result::<WccResult>(&path("ftn_test_result_missing.bin")).is_err());
+    assert!(matches!(fast_transit_network::results::from_bytes::<WccResult>(b"0 1\n"), Err(Error::Parse { .. })));
+
+    let sssp = dijkstra(&graph, 0).unwrap();
+    assert!(write_sssp_result_as(&sssp, &path("ftn_test_result_sssp.bin"), OutputFormat::Binary).is_err());
+    assert_eq!("bin".parse::<OutputFormat>().unwrap(), OutputFormat::Binary);
+    assert_eq!(OutputFormat::Binary.output_path("ranks.bin"), "ranks.bin");
+    for p in [bfs_path, wcc_path, pr_path] {
+        let _ = std::fs::remove_file(p);
+    }
+}
+
+#[test]
+fn bfs_iter_matches_bfs_distances_and_stops_early() {
+    let graph = grid_graph(5);
+    let expected = bfs_sequential(&graph, 0).unwrap().distances;
+    let visited: Vec<(usize, usize)> = graph.bfs_iter(0).collect();
+    assert_eq!(visited.len(), graph.num_nodes);
+    assert!(visited.windows(2).all(|w| w[0].1 <= w[1].1));
+    for &(v, depth) in &visited {
+        assert_eq!(expected[v], depth as i32);
+    }
+
+    let near: Vec<usize> = graph.bfs_iter(0).take_while(|&(_, d)| d <= 1).map(|(v, _)| v).collect();
+    assert_eq!(near, vec![0, 1, 5]);
+    assert_eq!(graph.bfs_iter(99).count(), 0);
+}
+
+#[test]
+fn dfs_iter_yields_preorder_with_tree_depth() {
+    // 3 is reached through 1 first, so 2 is visited last as a second child of 0
+    let diamond = graph_4_node_diamond();
+    assert_eq!(diamond.dfs_iter(0).collect::<Vec<_>>(), vec![(0, 0), (1, 1), (3, 2), (2, 1)]);
+
+    let path = graph_5_node_path();
+    assert_eq!(path.dfs_iter(0).collect::<Vec<_>>(), vec![(0, 0), (1, 1), (2, 2), (3, 3), (4, 4)]);
+    assert_eq!(path.dfs_iter(3).collect::<Vec<_>>(), vec![(3, 0), (4, 1)]);
+    assert_eq!(graph_two_components().dfs_iter(0).count(), 2);
+    assert_eq!(path.dfs_iter(5).count(), 0);
+}
+
+#[test]
+fn dfs_classifies_tree_back_forward_and_cross_edges() {
+    // 0 -> 1 -> 2 -> 0 (back), 0 -> 2 (forward), 3 -> 1 (cross, from a later tree)
+    let graph = build_csr(4, vec![(0, 1), (0, 2), (1, 2), (2, 0), (3, 1)]);
+    let result = dfs_forest(&graph);
+    let kinds: Vec<EdgeKind> = result.edge_kinds.iter().map(|k| k.unwrap()).collect();
+    assert_eq!(kinds, vec![EdgeKind::Tree, EdgeKind::Forward, EdgeKind::Tree, EdgeKind::Back, EdgeKind::Cross]);
+    assert_eq!(result.edge_counts(), (2, 1, 1, 1));
+    assert_eq!(result.preorder, vec![0, 1, 2, 3]);
+    assert_eq!(result.postorder, vec![2, 1, 0, 3]);
+    assert_eq!((result.discovery[0], result.finish[0]), (0, 5));
+    assert!(result.is_ancestor(0, 2) && !result.is_ancestor(3, 1));
+    assert_eq!(result.parent[2], 1);
+    assert!(result.has_cycle());
+    assert!(result.topological_order().is_none());
+
+    let from_one = dfs(&graph, 1).unwrap();
+    assert!(!from_one.is_visited(3));
+    assert_eq!(from_one.edge_kinds[4], None);
+    assert!(dfs(&graph, 4).is_err());
+}
+
+#[test]
+fn dfs_topological_order_on_dag() {
+    let graph = graph_4_node_diamond();
+    let result = dfs_forest(&graph);
+    assert!(!result.has_cycle());
+    let order = result.topological_order().unwrap();
+    let mut position = [0; 4];
+    for (i, &v) in order.iter().enumerate() {
+        position[v] = i;
+    }
+    for u in 0..4 {
+        for &v in graph.neighbors(u) {
+            assert!(position[u] < position[v]);
+        }
+    }
+}
+
+#[test]
+fn bridges_and_cut_vertices_of_two_triangles_joined_by_a_link() {
+    // Triangles 0-1-2 and 3-4-5 (both directions), linked by 2 <-> 3, plus a pendant 6 hanging off 5
+    let mut edges = Vec::new();
+    for (u, v) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3), (5, 6)] {
+        edges.push((u, v));
+        edges.push((v, u));
+    }
+    let graph = build_csr(7, edges);
+    let result = find_bridges(&graph);
+    assert_eq!(result.bridges, vec![Bridge { u: 2, v: 3, stranded: 3 }, Bridge { u: 5, v: 6, stranded: 1 }]);
+    assert_eq!(
+        result.cut_vertices,
+        vec![
+            CutVertex { node: 3, pieces: 2, stranded: 3 },
+            CutVertex { node: 2, pieces: 2, stranded: 2 },
+            CutVertex { node: 5, pieces: 2, stranded: 1 },
+        ]
+    );
+
+    // One-way edges count as links too; a cycle has no bridges
+    let path = find_bridges(&graph_5_node_path());
+    assert_eq!(path.bridges.len(), 4);
+    assert_eq!(path.cut_vertices.iter().map(|c| c.node).collect::<Vec<_>>(), vec![2, 1, 3]);
+    let ring = build_csr(4, vec![(0, 1), (1, 2), (2, 3), (3, 0)]);
+    assert_eq!(find_bridges(&ring), Default::default());
+}
+
+#[test]
+fn robustness_curve_degrades_from_intact_graph() {
+    let graph = grid_graph(6);
+    for strategy in [RemovalStrategy::Degree, RemovalStrategy::PageRank, RemovalStrategy::Betweenness, RemovalStrategy::Random] {
+        let config = RobustnessConfig { strategy, steps: 4, max_fraction: 0.5, samples: 36, ..RobustnessConfig::default() };
+        let curve = robustness(&graph, &config);
+        assert_eq!(curve.points.len(), 5);
+        assert_eq!(curve.points[0].giant_fraction, 1.0);
+        assert_eq!(curve.points[0].components, 1);
+        assert_eq!(curve.points.last().unwrap().removed, 18);
+        assert!(curve.points.windows(2).all(|w| w[1].giant_fraction <= w[0].giant_fraction));
+        assert!(curve.r_index() > 0.0 && curve.r_index() <= 1.0);
+    }
+
+    // Cutting the hub of a star leaves only singletons
+    let star = build_csr(5, vec![(0, 1), (0, 2), (0, 3), (0, 4)]);
+    let curve = robustness(&star, &RobustnessConfig { steps: 5, ..RobustnessConfig::default() });
+    assert_eq!(curve.points[1].removed, 1);
+    assert_eq!(curve.points[1].giant_fraction, 0.2);
+    assert_eq!(curve.points[1].components, 4);
+    assert_eq!(curve.points.last().unwrap().giant_fraction, 0.0);
+    assert!("closeness".parse::<RemovalStrategy>().is_err());
+}
+
+#[test]
+fn pipeline_runs_steps_on_largest_component() {
+    let steps = PipelineStep::parse_list("wcc, lcc, bfs:3, pagerank, export").unwrap();
+    assert_eq!(steps[2], PipelineStep::Bfs { source: 3 });
+    assert_eq!(steps.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(","), "wcc,largest-component,bfs:3,pagerank,export");
+    assert!(PipelineStep::parse_list("wcc,bfs:x").is_err());
+    assert!(PipelineStep::parse_list("wcc,hits").is_err());
+
+    let dir = std::env::temp_dir().join("ftn_test_pipeline");
+    let config = PipelineConfig {
+        out_dir: dir.clone(),
+        mode: Mode::Seq,
+        num_threads: 2,
+        policy: ParallelismPolicy::Auto,
+        format: OutputFormat::Text,
+        pagerank: PageRankConfig::default(),
+    };
+    // Components {0, 1}, {2, 3, 4} and {5}
+    let graph = build_csr(6, vec![(0, 1), (2, 3), (3, 4), (4, 2), (5, 5)]);
+    let written = run_pipeline(graph, &steps, &config).unwrap();
+    let names: Vec<_> = written.iter().map(|p| p.file_name().unwrap().to_str().unwrap().to_string()).collect();
+    assert_eq!(names, ["wcc.txt", "wcc_stats.txt", "largest_component_ids.txt", "bfs_3.txt", "pagerank.txt", "graph.txt"]);
+
+    let exported = load_graph_from_file(dir.join("graph.txt").to_str().unwrap()).unwrap();
+    assert_eq!((exported.num_nodes, exported.num_edges), (3, 3));
+    let ids = std::fs::read_to_string(dir.join("largest_component_ids.txt")).unwrap();
+    assert_eq!(ids.lines().skip(1).collect::<Vec<_>>(), ["0 2", "1 3", "2 4"]);
+
+    let missing = PipelineStep::parse_list("lcc,bfs:0").unwrap();
+    let graph = build_csr(6, vec![(0, 1), (2, 3), (3, 4), (4, 2)]);
+    assert!(run_pipeline(graph, &missing, &config).is_err());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn prelude_and_flat_paths_name_the_module_items() {
+    use fast_transit_network::prelude::*;
+
+    let graph: fast_transit_network::Graph = build_csr(4, vec![(0, 1), (1, 2), (3, 3)]);
+    let nested: &fast_transit_network::graph::graph::Graph = &graph;
+    let bfs: fast_transit_network::BfsResult = bfs_sequential(nested, 0).unwrap();
+    assert_eq!(bfs.distances, [0, 1, 2, -1]);
+    assert_eq!(wcc_sequential(&graph).stats.num_components, 2);
+    let ranks = fast_transit_network::pagerank_sequential(&graph, &PageRankConfig::default()).ranks;
+    assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    let failed: fast_transit_network::Result<BfsResult> = bfs_sequential(&graph, 9);
+    assert!(matches!(failed, Err(Error::InvalidNode { node: 9, num_nodes: 4 })));
+    // The prelude leaves std's two-parameter Result alone
+    let parsed: Result<usize, std::num::ParseIntError> = "3".parse();
+    assert_eq!(parsed, Ok(3));
+}
+
+#[test]
+fn analysis_builder_shares_results_across_steps() {
+    // Components {0, 1}, {2, 3, 4} and {5}
+    let graph = build_csr(6, vec![(0, 1), (2, 3), (3, 4), (4, 2), (5, 5)]);
+    let results = Analysis::new(&graph)
+        .sequential()
+        .stats()
+        .wcc()
+        .largest_component()
+        .bfs(3)
+        .pagerank(PageRankConfig::default())
+        .closeness(10)
+        .run()
+        .unwrap();
+
+    assert_eq!(results.stats.unwrap().num_nodes, 6);
+    assert_eq!(results.wcc.unwrap().stats.num_components, 3);
+    let component = results.component.unwrap();
+    assert_eq!(component.original_ids, vec![2, 3, 4]);
+    assert_eq!(results.bfs.len(), 1);
+    assert_eq!(results.bfs[0].0, 3);
+    assert_eq!(results.bfs[0].1.distances, vec![2, 0, 1]);
+    assert_eq!(results.pagerank.unwrap().ranks.len(), 3);
+    assert_eq!(results.closeness.unwrap(), approximate_closeness(&component.graph, 10, 1));
+    assert!(results.bridges.is_none());
+
+    let err = Analysis::new(&graph).threads(2).largest_component().bfs(0).run().err().unwrap();
+    assert!(matches!(err, Error::InvalidNode { node: 0, .. }));
+}
+
+#[test]
+fn output_format_parse() {
+    assert_eq!("text".parse::<OutputFormat>().unwrap(), OutputFormat::Text);
+    assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+    assert_eq!("jsonl".parse::<OutputFormat>().unwrap(), OutputFormat::Ndjson);
+    assert!("csv".parse::<OutputFormat>().is_err());
+}
+
+#[test]
+fn derived_path_keeps_directory_and_uses_txt() {
+    assert_eq!(derived_path("out/pr.txt", "_stats"), "out/pr_stats.txt");
+    assert_eq!(derived_path("pr.json", "_top100"), "pr_top100.txt");
+    assert_eq!(derived_path("pr", "_stats"), "pr_stats.txt");
+}
+
+#[test]
+fn bfs_json_output_has_metadata_and_distances() {
+    let result = bfs_sequential(&graph_two_components(), 0).unwrap();
+    let path = std::env::temp_dir().join("ftn_test_bfs_output.json");
+    write_bfs_result_as(&result, 0, path.to_str().unwrap(), OutputFormat::Json).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(json["algorithm"], "bfs");
+    assert_eq!(json["source"], 0);
+    assert_eq!(json["reachable"], 2);
+    assert_eq!(json["distances"], serde_json::json!([0, 1, -1, -1]));
+}
+
+#[test]
+fn node_value_and_sssp_writers_share_the_node_value_layout() {
+    let dir = std::env::temp_dir();
+    let path = dir.join("ftn_test_node_values.txt");
+    let path = path.to_str().unwrap();
+    let read = || std::fs::read_to_string(path).unwrap();
+    
+    write_node_values(&[1.5, 0.25], path, Some("Score")).unwrap();
+    assert_eq!(read(), "# Node Score\n0 1.5\n1 0.25\n");
+    write_node_values(&[7u64, 8], path, None).unwrap();
+    assert_eq!(read(), "0 7\n1 8\n");
+    write_output_as(&Output::Scores(vec![2.0, 0.5]), "closeness", path, OutputFormat::Text).unwrap();
+    assert_eq!(read(), "# Node closeness\n0 2\n1 0.5\n");
+    
+    // 0 -(2)-> 1 -(0.5)-> 2, node 3 unreachable
+    let result = dijkstra(&build_csr_weighted(4, vec![(0, 1, 2.0), (1, 2, 0.5)]), 0).unwrap();
+    write_sssp_result(&result, path).unwrap();
+    assert_eq!(read(), "# Node Cost Predecessor (source 0)\n0 0 -1\n1 2 0\n2 2.5 1\n3 inf -1\n");
+    write_sssp_result_as(&result, path, OutputFormat::Json).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&read()).unwrap();
+    assert_eq!(json["reachable"], 3);
+    assert_eq!(json["distances"], serde_json::json!([0.0, 2.0, 2.5, null]));
+    assert_eq!(json["predecessors"], serde_json::json!([-1, 0, 1, -1]));
+    write_sssp_result_as(&result, path, OutputFormat::Ndjson).unwrap();
+    let last: serde_json::Value = serde_json::from_str(read().lines().last().unwrap()).unwrap();
+    assert_eq!(last, serde_json::json!({ "node": 3, "distance": null, "predecessor": -1 }));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn summaries_keep_extremes_and_interpolated_quantiles() {
+    // Node 5 is unreachable; nodes 1 and 3 tie
+    let values = [4.0, 1.0, 7.0, 1.0, 10.0, f64::INFINITY];
+    let config = SummaryConfig { top_k: 2, quantiles: vec![0.0, 0.5, 0.9, 1.0] };
+    let summary = summarize(&values, &config);
+    assert_eq!((summary.count, summary.missing), (5, 1));
+    assert!((summary.mean - 4.6).abs() < 1e-12);
+    assert_eq!(summary.top, vec![(4, 10.0), (2, 7.0)]);
+    assert_eq!(summary.bottom, vec![(1, 1.0), (3, 1.0)]);
+    let quantiles: Vec<f64> = summary.quantiles.iter().map(|&(_, v)| v).collect();
+    assert_eq!(quantiles[0], 1.0);
+    assert_eq!(quantiles[1], 4.0);
+    assert!((quantiles[2] - 8.8).abs() < 1e-12, "interpolated between 7 and 10");
+    assert_eq!(quantiles[3], 10.0);
+    
+    let empty = summarize(&[f64::NAN], &config);
+    assert!(empty.top.is_empty() && empty.quantiles[1].1.is_nan());
+    
+    let path = std::env::temp_dir().join("ftn_test_summary.txt");
+    let path = path.to_str().unwrap();
+    write_output_as(&Output::Distances(vec![0, 1, -1, 2]), "bfs", path, OutputFormat::Summary).unwrap();
+    let content = std::fs::read_to_string(path).unwrap();
+    let _ = std::fs::remove_file(path);
+    assert!(content.starts_with("# Summary of distance: 3 nodes with a value, 1 without\n"));
+    assert!(content.contains("# Top 3: Node distance\n3 2\n1 1\n0 0\n"));
+    assert!(write_output_as(&Output::Labels(vec![0, 0]), "wcc", path, OutputFormat::Summary).is_err());
+    assert!("summary".parse::<OutputFormat>().is_ok());
+}
+
+#[test]
+fn histograms_bin_linearly_or_logarithmically() {
+    let values = [0.0, 1.0, 2.0, 3.0, 4.0, 10.0, 100.0, 1000.0, f64::NAN];
+    let linear = histogram(&values, Bins::linear(4));
+    assert_eq!(linear.edges, vec![0.0, 250.0, 500.0, 750.0, 1000.0]);
+    assert_eq!(linear.counts, vec![7, 0, 0, 1]);
+    assert_eq!(linear.excluded, 1);
+    
+    // Decades from 1 to 1000; 0 has no logarithm
+    let log = histogram(&values, Bins::log(3));
+    assert_eq!(log.counts, vec![4, 1, 2]);
+    assert_eq!(log.excluded, 2);
+    assert_eq!((log.edges[0], log.edges[3]), (1.0, 1000.0));
+    assert!((log.edges[1] - 10.0).abs() < 1e-9 && (log.edges[2] - 100.0).abs() < 1e-9);
+    
+    let constant = histogram(&[5.0, 5.0], Bins::linear(2));
+    assert_eq!(constant.counts, vec![2, 0]);
+    
+    let dir = std::env::temp_dir();
+    let csv = dir.join("ftn_test_histogram.csv");
+    write_histogram(&linear, csv.to_str().unwrap()).unwrap();
+    let content = std::fs::read_to_string(&csv).unwrap();
+    let _ = std::fs::remove_file(&csv);
+    assert_eq!(content.lines().next(), Some("start,end,count,fraction"));
+    assert_eq!(content.lines().nth(1), Some("0,250,7,0.875000"));
+    let json = dir.join("ftn_test_histogram.json");
+    write_histogram(&log, json.to_str().unwrap()).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&json).unwrap()).unwrap();
+    let _ = std::fs::remove_file(&json);
+    assert_eq!(parsed["scale"], "log");
+    assert_eq!(parsed["bins"][2]["count"], 2);
+}
+
+#[test]
+fn wcc_ndjson_output_one_object_per_node() {
+    let result = wcc_sequential(&graph_two_components());
+    let path = std::env::temp_dir().join("ftn_test_wcc_output.ndjson");
+    write_wcc_result_as(&result, path.to_str().unwrap(), OutputFormat::Ndjson, WccColumns::default()).unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let lines: Vec<serde_json::Value> = content.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[3], serde_json::json!({ "node": 3, "component": result.labels[3] }));
+}
+
+#[test]
+fn wcc_output_can_use_dense_ids_and_component_sizes() {
+    // Components {0, 1, 2} (root label 2 or 0) and {3, 4}, {5}
+    let labels = vec![2, 2, 2, 4, 4, 5];
+    let (dense, sizes) = dense_component_ids(&labels);
+    assert_eq!(dense, vec![0, 0, 0, 1, 1, 2]);
+    assert_eq!(sizes, vec![3, 2, 1]);
+
+    let path = std::env::temp_dir().join("ftn_test_wcc_dense.txt");
+    let path = path.to_str().unwrap();
+    let columns = WccColumns { dense_ids: true, sizes: true };
+    write_wcc_result_as(&WccResult::from_labels(labels.clone()), path, OutputFormat::Text, columns).unwrap();
+    let text = std::fs::read_to_string(path).unwrap();
+    assert_eq!(text.lines().collect::<Vec<_>>(), vec!["0 0 3", "1 0 3", "2 0 3", "3 1 2", "4 1 2", "5 2 1"]);
+
+    write_wcc_result_as(&WccResult::from_labels(labels.clone()), path, OutputFormat::Json, WccColumns { dense_ids: false, sizes: true }).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    assert_eq!(json["components"], serde_json::json!(labels));
+    assert_eq!(json["component_sizes"], serde_json::json!([3, 3, 3, 2, 2, 1]));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn wcc_labels_are_the_smallest_node_id_in_every_mode() {
+    // Sparse enough to leave many components of different sizes
+    let graph = random_weighted_graph(3000, 1800, 11, 1.0);
+    let sequential = wcc_sequential(&graph);
+    assert!(sequential.stats.num_components > 100);
+    for (v, &label) in sequential.labels.iter().enumerate() {
+        assert!(label <= v && sequential.labels[label] == label, "node {} label {}", v, label);
+    }
+    let text = |result: &WccResult, name: &str| {
+        let path = std::env::temp_dir().join(name);
+        write_wcc_result_as(result, path.to_str().unwrap(), OutputFormat::Text, WccColumns::default()).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        bytes
+    };
+    let expected = text(&sequential, "ftn_test_wcc_canonical_seq.txt");
+    for threads in [1, 2, 8] {
+        let parallel = wcc_parallel_with_policy(&graph, threads, ParallelismPolicy::ForceParallel);
+        assert_eq!(parallel.labels, sequential.labels, "{} threads", threads);
+        assert!(text(&parallel, "ftn_test_wcc_canonical_par.txt") == expected);
+    }
+    assert_eq!(IncrementalWcc::new(&graph).to_result().labels, sequential.labels);
+
+    let mut labels = vec![7, 3, 7, 100, 3, 100];
+    canonicalize_labels(&mut labels);
+    assert_eq!(labels, vec![0, 1, 0, 3, 1, 3]);
+}
+
+#[test]
+fn label_propagation_wcc_matches_union_find() {
+    // Edges only point from higher to lower ids in the chain, so labels must also travel against them
+    let mut edges: Vec<(usize, usize)> = (1..50).map(|v| (v, v - 1)).collect();
+    edges.push((60, 55));
+    let graphs = [grid_graph(40), random_weighted_graph(3000, 1800, 11, 1.0), build_csr(70, edges), graph_empty()];
+    for graph in &graphs {
+        let expected = wcc_sequential(graph).labels;
+        for threads in [1, 2, 8] {
+            let result = wcc_label_propagation(graph, threads);
+            assert_eq!(result.labels, expected, "{} nodes, {} threads", graph.num_nodes, threads);
+            assert_eq!(result.stats.num_components, wcc_stats(&expected).num_components);
+        }
+    }
+    assert_eq!("lp".parse::<WccMethod>().unwrap(), WccMethod::LabelPropagation);
+    assert_eq!("union-find".parse::<WccMethod>().unwrap(), WccMethod::UnionFind);
+    assert!("bfs".parse::<WccMethod>().is_err());
+}
+
+#[test]
+fn pagerank_json_output_reports_convergence() {
+    let result = pagerank_sequential(&graph_three_node_cycle(), &PageRankConfig::default());
+    let path = std::env::temp_dir().join("ftn_test_pagerank_output.json");
+    write_pagerank_result_as(&result, path.to_str().unwrap(), OutputFormat::Json).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(json["algorithm"], "pagerank");
+    assert_eq!(json["converged"], true);
+    assert_eq!(json["ranks"].as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn benchmark_algorithm_list_parse() {
+    assert_eq!(
+        BenchmarkAlgorithm::parse_list("bfs, PageRank,bfs").unwrap(),
+        vec![BenchmarkAlgorithm::Bfs, BenchmarkAlgorithm::PageRank]
+    );
+    assert!(BenchmarkAlgorithm::parse_list("bfs,sssp").is_err());
+    assert!(BenchmarkAlgorithm::parse_list("").is_err());
+}
+
+#[test]
+fn benchmark_variants_agree_with_their_reference() {
+    let graph = grid_graph(8);
+    let input = BenchmarkInput {
+        graph: &graph,
+        bfs_source: 0,
+        policy: ParallelismPolicy::ForceParallel,
+        pagerank: PageRankConfig::default(),
+    };
+    for algorithm in BenchmarkAlgorithm::ALL {
+        let variants = algorithm.variants();
+        assert!(!variants[0].is_parallel(), "{} reference must be sequential", algorithm.name());
+        let mut modes: Vec<_> = variants.iter().map(|v| v.mode()).collect();
+        modes.sort_unstable();
+        modes.dedup();
+        assert_eq!(modes.len(), variants.len());
+
+        let reference = variants[0].run(&input, 1).unwrap();
+        for variant in &variants[1..] {
+            assert!(variant.run(&input, 2).unwrap().matches(&reference), "{} {}", algorithm.name(), variant.mode());
+        }
+    }
+    let pagerank: Vec<_> = BenchmarkAlgorithm::PageRank.variants().iter().map(|v| v.mode()).collect();
+    assert_eq!(pagerank, ["seq", "par", "par-opt"]);
+}
+
+struct OutDegree;
+
+impl Algorithm for OutDegree {
+    fn name(&self) -> &'static str {
+        "out-degree"
+    }
+
+    fn about(&self) -> &'static str {
+        "Out-degree per node"
+    }
+
+    fn params(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn parallel(&self) -> bool {
+        false
+    }
+
+    fn run(&self, graph: &Graph, _params: &Params) -> anyhow::Result<Output> {
+        Ok(Output::Scores((0..graph.num_nodes).map(|v| graph.neighbors(v).len() as f64).collect()))
+    }
+}
+
+#[test]
+fn registry_runs_builtin_and_registered_algorithms() {
+    let graph = grid_graph(6);
+    let mut registry = Registry::builtin();
+    let names: Vec<_> = registry.iter().map(|a| a.name()).collect();
+    assert_eq!(names, ["bfs", "wcc", "scc", "pagerank", "closeness", "betweenness", "triangles", "descendants"]);
+
+    // The grid has cycles, which descendants rejects
+    assert!(registry.run("descendants", &graph, &Params::new()).is_err());
+    for algorithm in registry.iter().filter(|a| a.parallel() && a.name() != "descendants") {
+        let seq = registry.run(algorithm.name(), &graph, &Params::new()).unwrap();
+        let par = registry.run(algorithm.name(), &graph, &Params::new().set("mode", "par").set("threads", 2)).unwrap();
+        assert_eq!(seq.len(), graph.num_nodes);
+        assert!(par.matches(&seq), "{}", algorithm.name());
+    }
+    let params = Params::parse(&["source=5".to_string(), "mode=par".to_string()]).unwrap();
+    let Output::Distances(distances) = registry.run("bfs", &graph, &params).unwrap() else { panic!("bfs gives distances") };
+    assert_eq!(distances[5], 0);
+
+    assert!(registry.run("bfs", &graph, &Params::new().set("alpha", 0.5)).is_err());
+    assert!(registry.run("bfs", &graph, &Params::new().set("mode", "fast")).is_err());
+    assert!(registry.run("sssp", &graph, &Params::new()).is_err());
+    assert!(Params::parse(&["source".to_string()]).is_err());
+
+    registry.register(OutDegree);
+    assert!(registry.run("out-degree", &graph, &Params::new()).unwrap().matches(&Output::Scores(
+        (0..graph.num_nodes).map(|v| graph.neighbors(v).len() as f64).collect()
+    )));
+    assert!(Output::Labels(vec![7, 7, 3]).matches(&Output::Labels(vec![0, 0, 1])));
+    assert!(!Output::Labels(vec![7, 3, 3]).matches(&Output::Labels(vec![0, 0, 1])));
+
+    assert_eq!(BenchmarkAlgorithm::parse_list("scc").unwrap(), [BenchmarkAlgorithm::Registered("scc")]);
+    let variants: Vec<_> = BenchmarkAlgorithm::Registered("wcc").variants().iter().map(|v| v.mode()).collect();
+    assert_eq!(variants, ["seq", "par"]);
+}
+
+#[test]
+fn timing_stats_min_mean_stddev() {
+    let stats = TimingStats::from_samples(&[2.0, 4.0, 6.0]);
+    assert_eq!(stats.runs, 3);
+    assert_eq!(stats.min_ms, 2.0);
+    assert!((stats.mean_ms - 4.0).abs() < 1e-12);
+    assert!((stats.stddev_ms - 2.0).abs() < 1e-12);
+    assert_eq!(TimingStats::from_samples(&[5.0]).stddev_ms, 0.0);
+}
+
+#[test]
+fn measure_runs_warmup_and_repeats() {
+    let mut calls = 0;
+    let (last, stats) = measure(2, 3, || {
+        calls += 1;
+        calls
+    });
+    assert_eq!(calls, 5);
+    assert_eq!(last, 5);
+    assert_eq!(stats.runs, 3);
+}
+
+#[test]
+fn measure_reports_peak_heap_with_tracking_allocator() {
+    let (len, stats) = measure(0, 1, || vec![1u8; 16 << 20].len());
+    assert_eq!(len, 16 << 20);
+    assert!(memory::is_tracking());
+    assert!(stats.peak_memory_mb >= 16.0, "peak {}MB", stats.peak_memory_mb);
+}
+
+#[test]
+fn benchmark_json_report_has_schema_environment_and_results() {
+    let graph = graph_4_node_diamond();
+    let mut logger = BenchmarkLogger::new();
+    logger.add_graph(GraphMetadata {
+        name: "diamond".to_string(),
+        nodes: graph.num_nodes as u64,
+        edges: graph.num_edges as u64,
+        memory_bytes: graph.memory_bytes() as u64,
+        reorder: None,
+    });
+    let timing = TimingStats::from_samples(&[1.0, 3.0]);
+    logger.add_result(BenchmarkResult {
+        algorithm: "BFS".to_string(),
+        graph_name: "diamond".to_string(),
+        graph_nodes: graph.num_nodes as u64,
+        graph_edges: graph.num_edges as u64,
+        mode: "seq".to_string(),
+        threads: 1,
+        time_ms: timing.mean_ms,
+        min_ms: timing.min_ms,
+        stddev_ms: timing.stddev_ms,
+        runs: timing.runs,
+        peak_memory_mb: timing.peak_memory_mb,
+        speedup: 1.0,
+        correct: true,
+    });
+    let path = std::env::temp_dir().join("ftn_test_benchmark_report.json");
+    logger.save_to_json(path.to_str().unwrap()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(json["schema_version"], 1);
+    assert!(json["environment"]["available_parallelism"].as_u64().unwrap() >= 1);
+    for key in ["git_commit", "rustc_version", "hostname", "cpu_model"] {
+        assert!(json["environment"].get(key).is_some(), "environment records {}", key);
+    }
+    assert_eq!(json["graphs"][0]["edges"], 4);
+    assert_eq!(json["results"][0]["runs"], 2);
+    assert!(json["results"][0]["peak_memory_mb"].is_number());
+    assert_eq!(json["results"][0]["time_ms"], 2.0);
+}
+
+fn bench_result(mode: &str, threads: usize, time_ms: f64) -> BenchmarkResult {
+    BenchmarkResult {
+        algorithm: "BFS".to_string(),
+        graph_name: "diamond".to_string(),
+        graph_nodes: 4,
+        graph_edges: 4,
+        mode: mode.to_string(),
+        threads,
+        time_ms,
+        min_ms: time_ms,
+        stddev_ms: 0.0,
+        runs: 1,
+        peak_memory_mb: 0.0,
+        speedup: 1.0,
+        correct: true,
+    }
+}
+
+#[test]
+fn benchmark_csv_roundtrip_and_baseline_regressions() {
+    let mut logger = BenchmarkLogger::new();
+    logger.add_result(bench_result("seq", 1, 10.0));
+    logger.add_result(bench_result("par", 4, 5.0));
+    let path = std::env::temp_dir().join("ftn_test_benchmark_baseline.csv");
+    logger.save_to_csv(path.to_str().unwrap()).unwrap();
+    let baseline = BenchmarkLogger::load_csv(path.to_str().unwrap()).unwrap();
+    let environment = BenchmarkLogger::load_csv_environment(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let keys: Vec<&str> = environment.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(keys[..5], ["crate_version", "git_commit", "rustc_version", "hostname", "cpu_model"]);
+    assert_eq!(environment[0].1, env!("CARGO_PKG_VERSION"));
+    assert_eq!(baseline.len(), 2);
+    assert_eq!(baseline[1].threads, 4);
+    assert_eq!(baseline[1].time_ms, 5.0);
+
+    let current = vec![bench_result("seq", 1, 10.5), bench_result("par", 4, 6.0), bench_result("par", 8, 1.0)];
+    let comparisons = compare_to_baseline(&baseline, &current, 0.1);
+    assert_eq!(comparisons.len(), 2, "runs missing from the baseline are skipped");
+    assert!(!comparisons[0].regression);
+    assert!(comparisons[1].regression);
+    assert!((comparisons[1].change - 0.2).abs() < 1e-9);
+}
+
+#[test]
+fn benchmark_load_csv_accepts_original_columns() {
+    let path = std::env::temp_dir().join("ftn_test_benchmark_old.csv");
+    std::fs::write(
+        &path,
+        "algorithm,graph_name,nodes,edges,mode,threads,time_ms,speedup,correct\nWCC,g,10,9,par,2,3.5,1.2,true\n",
+    )
+    .unwrap();
+    let results = BenchmarkLogger::load_csv(path.to_str().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(results[0].algorithm, "WCC");
+    assert_eq!(results[0].min_ms, 3.5);
+    assert_eq!(results[0].runs, 1);
+}
+
+#[test]
+fn testing_builders_have_their_documented_shape() {
+    assert_eq!(testing::path(4).neighbors, [1, 2, 3]);
+    assert_eq!(testing::cycle(3).neighbors(2), [0]);
+    assert_eq!(testing::star(5).out_degree, [4, 0, 0, 0, 0]);
+    let grid = testing::grid(3);
+    assert_eq!((grid.num_edges, grid.directed), (24, false));
+    assert_eq!(grid.neighbors(4), [1, 3, 5, 7]);
+    assert_eq!(testing::random(50, 200, 1).neighbors, testing::random(50, 200, 1).neighbors);
+    assert_ne!(testing::random(50, 200, 1).neighbors, testing::random(50, 200, 2).neighbors);
+    assert_eq!(testing::random(0, 10, 1).num_edges, 0);
+    let weighted = testing::random_weighted(20, 80, 3, 1.0);
+    assert!(weighted.weights.unwrap().iter().all(|&w| (1.0..11.0).contains(&w)));
+    assert_eq!(testing::power_law(0, 3, 1).num_nodes, 0);
+}
+
+#[test]
+fn arbitrary_graphs_agree_across_implementations() {
+    let cases: Vec<Graph> = testing::arbitrary_graphs(42, 40).take(60).collect();
+    assert!(cases.iter().any(|g| g.num_nodes == 0) && cases.iter().any(|g| g.weights.is_some()));
+    assert_eq!(testing::arbitrary_graphs(42, 40).nth(17).unwrap().neighbors, cases[17].neighbors, "a case is reproducible from its index");
+    for (case, graph) in cases.iter().enumerate() {
+        let labels = wcc_sequential(graph).labels;
+        assert_eq!(wcc_parallel_with_policy(graph, 4, ParallelismPolicy::ForceParallel).labels, labels, "case {}", case);
+        assert_eq!(wcc_label_propagation(graph, 4).labels, labels, "case {}", case);
+        if graph.num_nodes > 0 {
+            let dist = bfs_sequential(graph, 0).unwrap().distances;
+            assert_eq!(bfs_parallel_with_policy(graph, 0, 4, ParallelismPolicy::ForceParallel).unwrap().distances, dist, "case {}", case);
+        }
+    }
+}
+
+#[test]
+fn synthetic_benchmark_graphs_are_fixed() {
+    let grid = SyntheticGraph::Grid { side: 10 }.build();
+    assert_eq!((grid.num_nodes, grid.num_edges, grid.directed), (100, 2 * 2 * 10 * 9, false));
+    assert_eq!(bfs_sequential(&grid, 0).unwrap().distances[99], 18);
+
+    let uniform = SyntheticGraph::Uniform { nodes: 1000, degree: 4 };
+    assert_eq!(uniform.build().num_edges, 4000);
+    assert_eq!(uniform.build().neighbors, uniform.build().neighbors, "same seed, same graph");
+    assert_eq!(uniform.name(), "uniform_1000x4");
+
+    let power_law = SyntheticGraph::PowerLaw { nodes: 2000, degree: 3 }.build();
+    assert_eq!(power_law.num_edges, 2 * 3 * 1999);
+    let max_degree = power_law.out_degree.iter().copied().max().unwrap();
+    assert!(max_degree > 20 * 6, "preferential attachment grows hubs, max degree {}", max_degree);
+}
+
+#[test]
+fn benchmark_iterates_a_directory_of_graphs_into_one_csv() {
+    let dir = std::env::temp_dir().join("ftn_test_benchmark_graphs");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("nested")).unwrap();
+    for (name, content) in [("path.txt", "0 1\n1 2\n2 3\n"), ("pair.el", "0 1\n"), ("star.txt", "0 1\n0 2\n0 3\n"), ("notes.csv", "a,b\n"), (".hidden.txt", "0 1\n")] {
+        std::fs::write(dir.join(name), content).unwrap();
+    }
+    let dir_arg = dir.to_str().unwrap();
+    let names = |paths: Vec<String>| -> Vec<String> {
+        paths.iter().map(|p| std::path::Path::new(p).file_name().unwrap().to_string_lossy().into_owned()).collect()
+    };
+    assert_eq!(names(expand_graph_paths(dir_arg).unwrap()), ["pair.el", "path.txt", "star.txt"]);
+    let glob = dir.join("*a?.txt");
+    assert_eq!(names(expand_graph_paths(glob.to_str().unwrap()).unwrap()), ["star.txt"]);
+    assert_eq!(expand_graph_paths("no/such/file.txt").unwrap(), ["no/such/file.txt"]);
+    assert!(expand_graph_paths(dir.join("*.mtx").to_str().unwrap()).is_err());
+    assert!(expand_graph_paths(dir.join("nested").to_str().unwrap()).is_err());
+
+    let out = dir.join("nested").join("results.csv");
+    let cli::Commands::Benchmark(args) = Cli::parse_from([
+        "tool", "benchmark", "--graphs", dir_arg, "--algorithms", "bfs,wcc", "--threads", "1,2", "--graph-jobs", "2", "--small-graph-edges", "3",
+        "--out", out.to_str().unwrap(),
+    ])
+    .command
+    .unwrap() else { unreachable!() };
+    let report = commands::benchmark::run(&args, &commands::Context::default()).unwrap();
+    let graphs: Vec<&str> = report.results.iter().map(|r| r.graph_name.as_str()).collect();
+    let per_graph = graphs.len() / 3;
+    assert_eq!(graphs.len() % 3, 0);
+    assert!(graphs[..per_graph].iter().all(|&g| g == "pair.el"), "rows follow the file order: {:?}", graphs);
+    assert!(graphs[per_graph..2 * per_graph].iter().all(|&g| g == "path"));
+    assert!(graphs[2 * per_graph..].iter().all(|&g| g == "star"));
+    assert!(report.results.iter().all(|r| r.correct));
+    assert_eq!(BenchmarkLogger::load_csv(out.to_str().unwrap()).unwrap().len(), report.results.len());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn suite_files_list_graphs_sources_algorithms_and_threads() {
+    let dir = std::env::temp_dir().join("ftn_test_suite");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("data")).unwrap();
+    std::fs::write(dir.join("data").join("path.txt"), "0 1\n1 2\n2 3\n").unwrap();
+    std::fs::write(dir.join("data").join("sparse.txt"), "10 20\n20 30\n").unwrap();
+    let toml = r#"
+        algorithms = ["bfs", "wcc"]
+        threads = [1, "auto"]
+
+        [[graph]]
+        path = "data/path.txt"
+
+        [[graph]]
+        path = "data/sparse.txt"
+        source = 20
+        algorithms = ["bfs"]
+        threads = [2]
+    "#;
+    std::fs::write(dir.join("nightly.toml"), toml).unwrap();
+    let suite = Suite::load(dir.join("nightly.toml").to_str().unwrap()).unwrap();
+    let steps = suite.benchmark_args().unwrap();
+    assert_eq!(steps.len(), 2);
+    assert_eq!((steps[0].algorithms.as_str(), steps[0].threads.as_str(), steps[0].source), ("bfs,wcc", "1,auto", None));
+    assert_eq!((steps[1].algorithms.as_str(), steps[1].threads.as_str(), steps[1].source), ("bfs", "2", Some(20)));
+    assert_eq!(steps[1].graphs, [dir.join("data/sparse.txt").to_string_lossy()]);
+    assert_eq!(suite.out_path(), dir.join("nightly_results.csv").to_string_lossy());
+
+    let json = r#"{"algorithms": ["bfs"], "graph": [{"path": "data/path.txt", "source": 3}]}"#;
+    assert_eq!(Suite::from_json(json).unwrap().benchmark_args().unwrap()[0].source, Some(3));
+    assert!(Suite::from_toml("algorithms = [\"bfs\"]\ngraph = []").unwrap().benchmark_args().is_err());
+    assert!(Suite::from_toml("[[graph]]\npath = \"g.txt\"\nsorce = 1").is_err(), "unknown keys are rejected");
+
+    let file = dir.join("nightly.toml");
+    let args = SuiteArgs { file: file.to_string_lossy().into_owned(), out: None, baseline: None };
+    let report = commands::suite::run(&args, &commands::Context::default()).unwrap();
+    let graphs: std::collections::BTreeSet<&str> = report.results.iter().map(|r| r.graph_name.as_str()).collect();
+    assert_eq!(graphs.into_iter().collect::<Vec<_>>(), ["path", "sparse"]);
+    assert!(report.results.iter().filter(|r| r.graph_name == "sparse").all(|r| r.algorithm == "BFS" && r.correct));
+    assert_eq!(BenchmarkLogger::load_csv(&suite.out_path()).unwrap().len(), report.results.len());
+
+    // A bad entry fails before any graph is benchmarked
+    std::fs::write(&file, toml.replace("[\"bfs\"]\n        threads", "[\"bsf\"]\n        threads")).unwrap();
+    let _ = std::fs::remove_file(suite.out_path());
+    assert!(commands::suite::run(&args, &commands::Context::default()).is_err());
+    assert!(!std::path::Path::new(&suite.out_path()).exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[cfg(not(feature = "parquet"))]
+#[test]
+fn parquet_output_requires_feature() {
+    use fast_transit_network::utils::io::write_pagerank_result_parquet;
+    let path = std::env::temp_dir().join("ftn_test_no_parquet.parquet");
+    assert!(write_pagerank_result_parquet(&[0.5, 0.5], path.to_str().unwrap()).is_err());
+    assert!(!path.exists());
+}
+
+#[test]
+fn derived_paths_keep_the_compression_extension() {
+    assert_eq!(derived_path("out/pr.txt", "_stats"), "out/pr_stats.txt");
+    assert_eq!(derived_path("out/pr.txt.zst", "_stats"), "out/pr_stats.txt.zst");
+    assert_eq!(derived_path_with_extension("pr.txt.gz", "_convergence", "csv"), "pr_convergence.csv.gz");
+    assert_eq!(OutputFormat::Parquet.output_path("pr.parquet"), "pr.parquet");
+}
+
+#[test]
+fn sharded_node_values_concatenate_to_the_single_file() {
+    assert_eq!(shard_path("out/pr.txt", 3), "out/pr.part-003.txt");
+    assert_eq!(shard_path("pr.txt.zst", 0), "pr.part-000.txt.zst");
+    assert_eq!(shard_path("ranks", 12), "ranks.part-012");
+    
+    let values: Vec<u64> = (0..10).map(|v| v * v).collect();
+    let dir = std::env::temp_dir();
+    let single = dir.join("ftn_test_shards.txt");
+    let single = single.to_str().unwrap();
+    write_node_values_sharded(&values, single, Some("Square"), 1).unwrap();
+    let expected = std::fs::read_to_string(single).unwrap();
+    let _ = std::fs::remove_file(single);
+    
+    write_node_values_sharded(&values, single, Some("Square"), 4).unwrap();
+    let mut lines = Vec::new();
+    for i in 0..4 {
+        let path = shard_path(single, i);
+        let content = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let mut shard = content.lines();
+        assert_eq!(shard.next(), Some("# Node Square"), "every shard starts with the header");
+        lines.extend(shard.map(str::to_string));
+    }
+    assert!(!std::path::Path::new(single).exists());
+    assert_eq!(format!("# Node Square\n{}\n", lines.join("\n")), expected);
+}
+
+#[cfg(not(feature = "compression"))]
+#[test]
+fn compressed_output_requires_feature() {
+    let path = std::env::temp_dir().join("ftn_test_no_compression.txt.gz");
+    assert!(write_node_values(&[1, 2], path.to_str().unwrap(), None).is_err());
+    assert!(!path.exists());
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn compressed_outputs_decode_to_the_text_format() {
+    use std::io::Read;
+    let result = pagerank_sequential(&graph_two_components(), &PageRankConfig::default());
+    let plain = std::env::temp_dir().join("ftn_test_compressed_plain.txt");
+    write_pagerank_result_as(&result, plain.to_str().unwrap(), OutputFormat::Text).unwrap();
+    let expected = std::fs::read_to_string(&plain).unwrap();
+    let _ = std::fs::remove_file(&plain);
+
+    for name in ["ftn_test_compressed.txt.gz", "ftn_test_compressed.txt.zst"] {
+        let path = std::env::temp_dir().join(name);
+        write_pagerank_result_as(&result, path.to_str().unwrap(), OutputFormat::Text).unwrap();
+        let file = std::fs::File::open(&path).unwrap();
+        let mut decoded = String::new();
+        if name.ends_with(".gz") {
+            flate2::read::GzDecoder::new(file).read_to_string(&mut decoded).unwrap();
+        } else {
+            zstd::Decoder::new(file).unwrap().read_to_string(&mut decoded).unwrap();
+        }
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(decoded, expected, "{}", name);
+    }
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn wcc_parquet_output_roundtrip() {
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    let result = wcc_sequential(&graph_two_components());
+    let path = std::env::temp_dir().join("ftn_test_wcc_output.parquet");
+    write_wcc_result_as(&result, path.to_str().unwrap(), OutputFormat::Parquet, WccColumns::default()).unwrap();
+    let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap())
+        .unwrap()
+        .build()
+        .unwrap();
+    let batches: Vec<_> = reader.map(|b| b.unwrap()).collect();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 4);
+    let schema = batches[0].schema();
+    assert_eq!(schema.field(0).name(), "node");
+    assert_eq!(schema.field(1).name(), "component");
+}
+
 #[test]
 #[ignore = "large graph ~100k nodes; use --include-ignored for full run"]
 fn bfs_seq_par_same_distances() {
     let graph = large_chain_graph();
-    let dist_seq = bfs_sequential(&graph, 0);
-    let dist_par = bfs_parallel(&graph, 0, 4);
+    let dist_seq = bfs_sequential(&graph, 0).unwrap().distances;
+    let dist_par = bfs_parallel(&graph, 0, 4).unwrap().distances;
     assert_eq!(dist_seq.len(), dist_par.len());
     assert_eq!(dist_seq, dist_par, "BFS sequential and parallel must produce the same distances");
 }
@@ -601,8 +4051,8 @@ fn bfs_seq_par_same_distances() {
 #[ignore = "large graph ~100k nodes; use --include-ignored for full run"]
 fn wcc_seq_par_same_partition() {
     let graph = large_chain_graph();
-    let comp_seq = wcc_sequential(&graph);
-    let comp_par = wcc_parallel(&graph, 4);
+    let comp_seq = wcc_sequential(&graph).labels;
+    let comp_par = wcc_parallel(&graph, 4).labels;
     assert_eq!(comp_seq.len(), comp_par.len());
 
     let stats_seq = wcc_stats(&comp_seq);
@@ -624,9 +4074,10 @@ fn pagerank_seq_par_agree() {
         alpha: 0.85,
         max_iterations: 50,
         tolerance: 1e-6,
+        ..PageRankConfig::default()
     };
-    let ranks_seq = pagerank_sequential(&graph, &config);
-    let ranks_par = pagerank_parallel(&graph, &config, 4);
+    let ranks_seq = pagerank_sequential(&graph, &config).ranks;
+    let ranks_par = pagerank_parallel(&graph, &config, 4).ranks;
     assert_eq!(ranks_seq.len(), ranks_par.len());
     let max_diff: f64 = ranks_seq
         .iter()
@@ -644,9 +4095,10 @@ fn pagerank_seq_par_opt_agree() {
         alpha: 0.85,
         max_iterations: 50,
         tolerance: 1e-6,
+        ..PageRankConfig::default()
     };
-    let ranks_seq = pagerank_sequential(&graph, &config);
-    let ranks_par_opt = pagerank_parallel_optimized(&graph, &config, 4);
+    let ranks_seq = pagerank_sequential(&graph, &config).ranks;
+    let ranks_par_opt = pagerank_parallel_optimized(&graph, &config, 4).ranks;
     assert_eq!(ranks_seq.len(), ranks_par_opt.len());
     let max_diff: f64 = ranks_seq
         .iter()